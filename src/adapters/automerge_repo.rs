@@ -1,19 +1,38 @@
 use crate::traits::BookmarkRepository;
-use crate::types::{Bookmark, BookmarkResult, BookmarkError, BookmarkFilters, SortBy, SortDirection};
+use crate::types::{Bookmark, BookmarkResult, BookmarkError, BookmarkFilters, SortBy, SortDirection, TrashedBookmark, BookmarkBatch, SavedSearch, LinkStatus, normalize_tag, tag_matches};
 use async_trait::async_trait;
 use automerge::{AutoCommit, ObjType, ReadDoc, ROOT};
 use automerge::transaction::Transactable;
 use automerge::sync::{self, SyncDoc, State as SyncState};
+use automerge::{ChangeHash, Prop};
 use std::path::PathBuf;
 use std::fs;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::OnceLock;
 use chrono::{DateTime, Utc};
+use super::content_store::ContentStore;
+use super::search_index::{boost_phrases, SearchIndex};
+
+/// File name of the full-text search index, stored next to the bookmark document
+const SEARCH_INDEX_FILE: &str = "search_index.json";
+
+/// Directory for captured page text, stored next to the bookmark document
+const CONTENT_DIR: &str = "content";
+
+/// Directory for offline page archives, stored next to the bookmark document
+const ARCHIVE_DIR: &str = "archives";
 
 pub struct AutomergeBookmarkRepository {
     doc: AutoCommit,
     bookmarks_map: automerge::ObjId,
     file_path: PathBuf,
     sync_states: HashMap<String, SyncState>,
+    /// Document heads as of the last load or save
+    heads: Vec<ChangeHash>,
+    /// Loaded on first text search or write
+    search_index: OnceLock<SearchIndex>,
+    content_store: ContentStore,
+    archive_store: ContentStore,
 }
 
 impl AutomergeBookmarkRepository {
@@ -24,13 +43,20 @@ impl AutomergeBookmarkRepository {
                 .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to create directory: {}", e)))?;
         }
 
-        let (doc, bookmarks_map) = Self::load_from_file(&file_path)?;
+        let (mut doc, bookmarks_map) = Self::load_from_file(&file_path)?;
+        let heads = doc.get_heads();
+        let content_store = ContentStore::new(file_path.with_file_name(CONTENT_DIR));
+        let archive_store = ContentStore::with_extension(file_path.with_file_name(ARCHIVE_DIR), "html");
 
         Ok(Self { 
             doc, 
             bookmarks_map, 
             file_path,
             sync_states: HashMap::new(),
+            heads,
+            search_index: OnceLock::new(),
+            content_store,
+            archive_store,
         })
     }
 
@@ -75,18 +101,27 @@ impl AutomergeBookmarkRepository {
             AutoCommit::new()
         };
 
-        // Ensure we have the map structure
-        let bookmarks_map = match doc.get(ROOT, "bookmarks")
-            .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to get bookmarks: {}", e)))? {
-            Some((_, obj_id)) => obj_id,
+        // Ensure we have the map structure. The trash and saved searches maps are created
+        // here rather than on first use: two devices creating the same root map
+        // independently would conflict, and one device's entries would be hidden.
+        let bookmarks_map = Self::ensure_root_map(&mut doc, "bookmarks")?;
+        Self::ensure_root_map(&mut doc, "trash")?;
+        Self::ensure_root_map(&mut doc, "saved_searches")?;
+
+        Ok((doc, bookmarks_map))
+    }
+
+    /// Get a map under the document root, creating it if the document has none
+    fn ensure_root_map(doc: &mut AutoCommit, name: &str) -> BookmarkResult<automerge::ObjId> {
+        match doc.get(ROOT, name)
+            .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to get {}: {}", name, e)))? {
+            Some((_, obj_id)) => Ok(obj_id),
             None => {
                 // Create new map structure
-                doc.put_object(ROOT, "bookmarks", ObjType::Map)
-                    .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to create bookmarks map: {}", e)))?
+                doc.put_object(ROOT, name, ObjType::Map)
+                    .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to create {} map: {}", name, e)))
             }
-        };
-
-        Ok((doc, bookmarks_map))
+        }
     }
 
 
@@ -94,9 +129,111 @@ impl AutomergeBookmarkRepository {
         let bytes = self.doc.save();
         fs::write(&self.file_path, bytes)
             .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to write file: {}", e)))?;
+
+        let heads = self.doc.get_heads();
+        self.update_search_index(&heads);
+        self.heads = heads;
         Ok(())
     }
 
+    fn search_index_path(&self) -> PathBuf {
+        self.file_path.with_file_name(SEARCH_INDEX_FILE)
+    }
+
+    fn heads_as_strings(heads: &[ChangeHash]) -> Vec<String> {
+        heads.iter().map(|hash| hash.to_string()).collect()
+    }
+
+    /// The search index for the current document, loading it from disk or
+    /// rebuilding it from `bookmarks` if it is missing or out of date
+    fn search_index(&self, bookmarks: &[Bookmark]) -> &SearchIndex {
+        self.search_index.get_or_init(|| {
+            let path = self.search_index_path();
+            let heads = Self::heads_as_strings(&self.heads);
+            match SearchIndex::load(&path) {
+                Some(index) if index.heads() == heads.as_slice() => index,
+                _ => {
+                    let index = SearchIndex::build(bookmarks, heads, |id| self.content_store.load(id).ok().flatten());
+                    // The index is a cache; failing to persist it only costs a rebuild next time
+                    let _ = index.save(&path);
+                    index
+                }
+            }
+        })
+    }
+
+    /// The search index for the last saved heads, if it is loaded or current on disk
+    ///
+    /// Used for incremental updates; an index that is out of date is dropped
+    /// and rebuilt on the next search instead.
+    fn current_search_index(&mut self) -> Option<&mut SearchIndex> {
+        let heads = Self::heads_as_strings(&self.heads);
+        if self.search_index.get().is_none() {
+            let index = SearchIndex::load(&self.search_index_path())
+                .filter(|index| index.heads() == heads.as_slice())?;
+            let _ = self.search_index.set(index);
+        }
+        if self.search_index.get().is_some_and(|index| index.heads() != heads.as_slice()) {
+            self.search_index = OnceLock::new();
+            return None;
+        }
+        self.search_index.get_mut()
+    }
+
+    /// Bring the search index up to date with changes between the last saved heads and `new_heads`
+    ///
+    /// Only bookmarks touched by those changes are re-indexed.
+    fn update_search_index(&mut self, new_heads: &[ChangeHash]) {
+        if new_heads == self.heads.as_slice() || self.current_search_index().is_none() {
+            return;
+        }
+
+        let changed: Vec<(String, Option<Bookmark>, Option<String>)> = self.changed_bookmark_ids(new_heads)
+            .into_iter()
+            .map(|id| {
+                let bookmark = match self.doc.get(&self.bookmarks_map, &id) {
+                    Ok(Some((_, obj_id))) => self.bookmark_from_automerge(&obj_id).ok(),
+                    _ => None,
+                };
+                let content = self.content_store.load(&id).ok().flatten();
+                (id, bookmark, content)
+            })
+            .collect();
+
+        let path = self.search_index_path();
+        if let Some(index) = self.search_index.get_mut() {
+            for (id, bookmark, content) in changed {
+                match bookmark {
+                    Some(bookmark) => index.upsert(&bookmark, content.as_deref()),
+                    None => index.remove(&id),
+                }
+            }
+            index.set_heads(Self::heads_as_strings(new_heads));
+            let _ = index.save(&path);
+        }
+    }
+
+    /// IDs of bookmarks added, changed or removed between the last saved heads and `new_heads`
+    fn changed_bookmark_ids(&mut self, new_heads: &[ChangeHash]) -> HashSet<String> {
+        let old_heads = self.heads.clone();
+        let patches = self.doc.diff(&old_heads, new_heads);
+
+        patches.into_iter()
+            .filter_map(|patch| {
+                if patch.obj == self.bookmarks_map {
+                    return match patch.action {
+                        automerge::PatchAction::PutMap { key, .. } | automerge::PatchAction::DeleteMap { key } => Some(key),
+                        _ => None,
+                    };
+                }
+                patch.path.into_iter().find_map(|(obj, prop)| match prop {
+                    Prop::Map(key) if obj == self.bookmarks_map => Some(key),
+                    _ => None,
+                })
+            })
+            .collect()
+    }
+
     fn bookmark_from_automerge(&self, obj_id: &automerge::ObjId) -> BookmarkResult<Bookmark> {
         // Extract basic fields
         let id = self.get_string_field(obj_id, "id")?;
@@ -127,6 +264,19 @@ impl AutomergeBookmarkRepository {
         let priority_rating = self.get_optional_string_field(obj_id, "priority_rating")
             .and_then(|priority_str| priority_str.parse::<u8>().ok());
 
+        let started_at = self.get_optional_date_field(obj_id, "started_at");
+        let completed_at = self.get_optional_date_field(obj_id, "completed_at");
+
+        let archive_path = self.get_optional_string_field(obj_id, "archive_path");
+        let archived_at = self.get_optional_string_field(obj_id, "archived_at")
+            .and_then(|date_str| DateTime::parse_from_rfc3339(&date_str).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+
+        let link_status = self.link_status_from_automerge(obj_id);
+        let metadata_pending = self.doc.get(obj_id, "metadata_pending").ok().flatten()
+            .and_then(|(value, _)| value.to_bool())
+            .unwrap_or(false);
+
         // Extract tags from list
         let tags = self.get_tags_from_list(obj_id)?;
         
@@ -144,6 +294,19 @@ impl AutomergeBookmarkRepository {
             notes,
             reading_status,
             priority_rating,
+            started_at,
+            completed_at,
+            description: self.get_optional_string_field(obj_id, "description"),
+            site_name: self.get_optional_string_field(obj_id, "site_name"),
+            canonical_url: self.get_optional_string_field(obj_id, "canonical_url"),
+            image_url: self.get_optional_string_field(obj_id, "image_url"),
+            language: self.get_optional_string_field(obj_id, "language"),
+            favicon_url: self.get_optional_string_field(obj_id, "favicon_url"),
+            site_details: self.site_details_from_automerge(obj_id),
+            archive_path,
+            archived_at,
+            link_status,
+            metadata_pending,
         })
     }
 
@@ -160,6 +323,12 @@ impl AutomergeBookmarkRepository {
             .and_then(|(value, _)| value.to_str().map(|s| s.to_string()))
     }
 
+    fn get_optional_date_field(&self, obj_id: &automerge::ObjId, field: &str) -> Option<DateTime<Utc>> {
+        self.get_optional_string_field(obj_id, field)
+            .and_then(|date_str| DateTime::parse_from_rfc3339(&date_str).ok())
+            .map(|dt| dt.with_timezone(&Utc))
+    }
+
     fn get_tags_from_list(&self, obj_id: &automerge::ObjId) -> BookmarkResult<Vec<String>> {
         let tags_list = match self.doc.get(obj_id, "tags")
             .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to get tags: {}", e)))? {
@@ -202,6 +371,83 @@ impl AutomergeBookmarkRepository {
         Ok(notes)
     }
 
+    fn link_status_from_automerge(&self, obj_id: &automerge::ObjId) -> Option<LinkStatus> {
+        let (_, status_obj) = self.doc.get(obj_id, "link_status").ok()??;
+        let checked_at = self.get_optional_string_field(&status_obj, "checked_at")
+            .and_then(|date_str| DateTime::parse_from_rfc3339(&date_str).ok())?
+            .with_timezone(&Utc);
+
+        Some(LinkStatus {
+            checked_at,
+            status_code: self.get_optional_string_field(&status_obj, "status_code")
+                .and_then(|code| code.parse().ok()),
+            redirected_to: self.get_optional_string_field(&status_obj, "redirected_to"),
+            permanent_redirect: self.get_optional_string_field(&status_obj, "permanent_redirect")
+                .is_some_and(|value| value == "true"),
+            error: self.get_optional_string_field(&status_obj, "error"),
+        })
+    }
+
+    /// Replace the link status of a bookmark object
+    fn put_link_status(&mut self, obj_id: &automerge::ObjId, status: Option<&LinkStatus>) -> BookmarkResult<()> {
+        let Some(status) = status else {
+            let _ = self.doc.delete(obj_id, "link_status");
+            return Ok(());
+        };
+
+        let status_obj = self.doc.put_object(obj_id, "link_status", ObjType::Map)
+            .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to create link_status: {}", e)))?;
+        let mut fields = vec![
+            ("checked_at", status.checked_at.to_rfc3339()),
+            ("permanent_redirect", status.permanent_redirect.to_string()),
+        ];
+        fields.extend(status.status_code.map(|code| ("status_code", code.to_string())));
+        fields.extend(status.redirected_to.clone().map(|target| ("redirected_to", target)));
+        fields.extend(status.error.clone().map(|error| ("error", error)));
+        for (field, value) in fields {
+            self.doc.put(&status_obj, field, value)
+                .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to set link_status {}: {}", field, e)))?;
+        }
+        Ok(())
+    }
+
+    fn site_details_from_automerge(&self, obj_id: &automerge::ObjId) -> BTreeMap<String, String> {
+        let Some((_, details_obj)) = self.doc.get(obj_id, "site_details").ok().flatten() else {
+            return BTreeMap::new();
+        };
+        self.doc.keys(&details_obj)
+            .filter_map(|key| {
+                let value = self.get_optional_string_field(&details_obj, &key)?;
+                Some((key, value))
+            })
+            .collect()
+    }
+
+    /// Bring the site details of a bookmark object in line, touching only keys that changed
+    fn put_site_details(&mut self, obj_id: &automerge::ObjId, details: &BTreeMap<String, String>) -> BookmarkResult<()> {
+        if details.is_empty() {
+            let _ = self.doc.delete(obj_id, "site_details");
+            return Ok(());
+        }
+
+        let existing = self.site_details_from_automerge(obj_id);
+        let details_obj = match self.doc.get(obj_id, "site_details").ok().flatten() {
+            Some((_, details_obj)) => details_obj,
+            None => self.doc.put_object(obj_id, "site_details", ObjType::Map)
+                .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to create site_details: {}", e)))?,
+        };
+        for key in existing.keys().filter(|key| !details.contains_key(*key)) {
+            let _ = self.doc.delete(&details_obj, key.as_str());
+        }
+        for (key, value) in details {
+            if existing.get(key) != Some(value) {
+                self.doc.put(&details_obj, key.as_str(), value.clone())
+                    .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to set site detail {}: {}", key, e)))?;
+            }
+        }
+        Ok(())
+    }
+
     fn note_from_automerge(&self, obj_id: &automerge::ObjId) -> BookmarkResult<crate::types::Note> {
         let id = self.get_string_field(obj_id, "id")?;
         let content = self.get_string_field(obj_id, "content")?;
@@ -219,8 +465,14 @@ impl AutomergeBookmarkRepository {
     }
 
     fn add_bookmark_to_automerge(&mut self, bookmark: &Bookmark) -> BookmarkResult<()> {
+        let bookmarks_map = self.bookmarks_map.clone();
+        self.write_bookmark_object(&bookmarks_map, bookmark)?;
+        Ok(())
+    }
+
+    fn write_bookmark_object(&mut self, parent_map: &automerge::ObjId, bookmark: &Bookmark) -> BookmarkResult<automerge::ObjId> {
         // Create a new bookmark object in the map using the bookmark ID as key
-        let bookmark_obj = self.doc.put_object(&self.bookmarks_map, &bookmark.id, ObjType::Map)
+        let bookmark_obj = self.doc.put_object(parent_map, &bookmark.id, ObjType::Map)
             .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to create bookmark object: {}", e)))?;
 
         // Set basic bookmark properties
@@ -262,6 +514,46 @@ impl AutomergeBookmarkRepository {
                 .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to set priority_rating: {}", e)))?;
         }
 
+        // Set reading timestamps
+        for (field, value) in reading_dates(bookmark) {
+            if let Some(value) = value {
+                self.doc.put(&bookmark_obj, field, value.to_rfc3339())
+                    .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to set {}: {}", field, e)))?;
+            }
+        }
+
+        // Set page metadata
+        for (field, value) in page_metadata_fields(bookmark) {
+            if let Some(value) = value {
+                self.doc.put(&bookmark_obj, field, value.clone())
+                    .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to set {}: {}", field, e)))?;
+            }
+        }
+
+        if !bookmark.site_details.is_empty() {
+            self.put_site_details(&bookmark_obj, &bookmark.site_details)?;
+        }
+
+        // Set archive location
+        if let Some(ref archive_path) = bookmark.archive_path {
+            self.doc.put(&bookmark_obj, "archive_path", archive_path.clone())
+                .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to set archive_path: {}", e)))?;
+        }
+
+        if let Some(ref archived_at) = bookmark.archived_at {
+            self.doc.put(&bookmark_obj, "archived_at", archived_at.to_rfc3339())
+                .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to set archived_at: {}", e)))?;
+        }
+
+        if bookmark.link_status.is_some() {
+            self.put_link_status(&bookmark_obj, bookmark.link_status.as_ref())?;
+        }
+
+        if bookmark.metadata_pending {
+            self.doc.put(&bookmark_obj, "metadata_pending", true)
+                .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to set metadata_pending: {}", e)))?;
+        }
+
         // Add tags as a list
         if !bookmark.tags.is_empty() {
             let tags_list = self.doc.put_object(&bookmark_obj, "tags", ObjType::List)
@@ -283,7 +575,7 @@ impl AutomergeBookmarkRepository {
             }
         }
 
-        Ok(())
+        Ok(bookmark_obj)
     }
 
     fn add_note_to_list(&mut self, notes_list: &automerge::ObjId, note: &crate::types::Note) -> BookmarkResult<()> {
@@ -305,25 +597,70 @@ impl AutomergeBookmarkRepository {
     fn bookmark_exists(&self, id: &str) -> bool {
         matches!(self.doc.get(&self.bookmarks_map, id), Ok(Some(_)))
     }
+
+    /// The trash map, created with the document
+    fn trash_map(&self) -> Option<automerge::ObjId> {
+        match self.doc.get(ROOT, "trash") {
+            Ok(Some((_, obj_id))) => Some(obj_id),
+            _ => None,
+        }
+    }
+
+    /// The saved searches map, created with the document
+    fn saved_searches_map(&self) -> Option<automerge::ObjId> {
+        match self.doc.get(ROOT, "saved_searches") {
+            Ok(Some((_, obj_id))) => Some(obj_id),
+            _ => None,
+        }
+    }
+
+    fn trashed_bookmark_from_automerge(&self, obj_id: &automerge::ObjId) -> BookmarkResult<TrashedBookmark> {
+        let bookmark = self.bookmark_from_automerge(obj_id)?;
+        let deleted_at_str = self.get_string_field(obj_id, "deleted_at")?;
+        let deleted_at = DateTime::parse_from_rfc3339(&deleted_at_str)
+            .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to parse deleted_at: {}", e)))?
+            .with_timezone(&Utc);
+
+        Ok(TrashedBookmark { bookmark, deleted_at })
+    }
     
     fn apply_filters(&self, mut bookmarks: Vec<Bookmark>, filters: &BookmarkFilters) -> Vec<Bookmark> {
-        // Apply text query filter
+        // Apply text query filter through the search index, keeping BM25 scores for ranking
+        let mut relevance: HashMap<String, f64> = HashMap::new();
         if let Some(ref query) = filters.text_query {
-            let query_lower = query.to_lowercase();
-            bookmarks.retain(|bookmark| {
-                bookmark.title.to_lowercase().contains(&query_lower) ||
-                bookmark.url.to_lowercase().contains(&query_lower) ||
-                bookmark.author.as_ref().is_some_and(|author| author.to_lowercase().contains(&query_lower)) ||
-                bookmark.notes.iter().any(|note| note.content.to_lowercase().contains(&query_lower))
-            });
+            match self.search_index(&bookmarks).search(query) {
+                Some(mut scores) => {
+                    bookmarks.retain(|bookmark| scores.contains_key(&bookmark.id));
+                    boost_phrases(&mut scores, &bookmarks, query);
+                    relevance = scores;
+                }
+                None => {
+                    // Nothing indexable (e.g. only stop words or punctuation), fall back to substring matching
+                    let query_lower = query.replace('"', "").to_lowercase();
+                    bookmarks.retain(|bookmark| {
+                        bookmark.title.to_lowercase().contains(&query_lower) ||
+                        bookmark.url.to_lowercase().contains(&query_lower) ||
+                        bookmark.author.as_ref().is_some_and(|author| author.to_lowercase().contains(&query_lower)) ||
+                        bookmark.description.as_ref().is_some_and(|description| description.to_lowercase().contains(&query_lower)) ||
+                        bookmark.notes.iter().any(|note| note.content.to_lowercase().contains(&query_lower))
+                    });
+                }
+            }
+        }
+        
+        // Apply query language expression
+        if let Some(ref query) = filters.query {
+            bookmarks.retain(|bookmark| query.matches(bookmark));
         }
         
         // Apply tags filter (AND logic - must contain ALL tags)
         if let Some(ref filter_tags) = filters.tags {
-            let tags_lower: Vec<String> = filter_tags.iter().map(|tag| tag.to_lowercase()).collect();
+            let filter_tags: Vec<String> = filter_tags.iter().map(|tag| normalize_tag(tag)).collect();
             bookmarks.retain(|bookmark| {
-                tags_lower.iter().all(|tag| {
-                    bookmark.tags.iter().any(|bookmark_tag| bookmark_tag.to_lowercase() == *tag)
+                filter_tags.iter().all(|tag| {
+                    bookmark.tags.iter().any(|bookmark_tag| {
+                        tag_matches(tag, &normalize_tag(bookmark_tag), filters.include_descendant_tags)
+                    })
                 })
             });
         }
@@ -333,6 +670,11 @@ impl AutomergeBookmarkRepository {
             bookmarks.retain(|bookmark| bookmark.reading_status == *status);
         }
         
+        // Apply dead link filter
+        if filters.dead_links {
+            bookmarks.retain(|bookmark| bookmark.link_status.as_ref().is_some_and(LinkStatus::is_dead));
+        }
+        
         // Apply priority range filter
         if let Some((min_priority, max_priority)) = filters.priority_range {
             bookmarks.retain(|bookmark| {
@@ -364,8 +706,10 @@ impl AutomergeBookmarkRepository {
             });
         }
         
-        // Apply sorting
-        if let Some(ref sort_by) = filters.sort_by {
+        // Apply sorting; text searches are ranked by relevance unless another order is requested
+        let sort_by = filters.sort_by.clone()
+            .or_else(|| filters.text_query.as_ref().map(|_| SortBy::Relevance));
+        if let Some(ref sort_by) = sort_by {
             let sort_order = filters.sort_order.as_ref().unwrap_or(&SortDirection::Descending);
             
             match sort_by {
@@ -401,6 +745,15 @@ impl AutomergeBookmarkRepository {
                         }
                     });
                 }
+                SortBy::Relevance => {
+                    let score = |bookmark: &Bookmark| relevance.get(&bookmark.id).copied().unwrap_or(0.0);
+                    bookmarks.sort_by(|a, b| {
+                        match sort_order {
+                            SortDirection::Ascending => score(a).total_cmp(&score(b)),
+                            SortDirection::Descending => score(b).total_cmp(&score(a)),
+                        }
+                    });
+                }
             }
         }
         
@@ -463,20 +816,11 @@ impl BookmarkRepository for AutomergeBookmarkRepository {
 
     
     async fn search_by_text(&self, query: &str) -> BookmarkResult<Vec<Bookmark>> {
-        let all_bookmarks = self.find_all(None).await?;
-        let query_lower = query.to_lowercase();
-        
-        let results = all_bookmarks
-            .into_iter()
-            .filter(|bookmark| {
-                bookmark.title.to_lowercase().contains(&query_lower) ||
-                bookmark.url.to_lowercase().contains(&query_lower) ||
-                bookmark.author.as_ref().is_some_and(|author| author.to_lowercase().contains(&query_lower)) ||
-                bookmark.notes.iter().any(|note| note.content.to_lowercase().contains(&query_lower))
-            })
-            .collect();
-            
-        Ok(results)
+        let filters = BookmarkFilters {
+            text_query: Some(query.to_string()),
+            ..Default::default()
+        };
+        self.find_all(Some(filters)).await
     }
     
     async fn find_by_tags(&self, tags: &[String]) -> BookmarkResult<Vec<Bookmark>> {
@@ -589,8 +933,8 @@ impl BookmarkRepository for AutomergeBookmarkRepository {
             .receive_sync_message(sync_state, sync_message)
             .map_err(|e| BookmarkError::SyncError(format!("Failed to apply sync message: {}", e)))?;
         
-        // Check if document has changed
-        let has_changes = self.doc.get_last_local_change().is_some();
+        // Check if document has changed since it was last saved
+        let has_changes = self.doc.get_heads() != self.heads;
         
         if has_changes {
             self.save()?;
@@ -604,17 +948,210 @@ impl BookmarkRepository for AutomergeBookmarkRepository {
         if !self.bookmark_exists(id) {
             return Err(BookmarkError::NotFound(id.to_string()));
         }
-            
-        self.doc.delete(&self.bookmarks_map, id)
-            .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to delete bookmark: {}", e)))?;
-            
+        
+        self.move_to_trash(id)?;
         self.save()?;
         Ok(())
     }
+    
+    async fn find_trash(&self) -> BookmarkResult<Vec<TrashedBookmark>> {
+        let trash_map = match self.trash_map() {
+            Some(obj_id) => obj_id,
+            None => return Ok(Vec::new()),
+        };
+        
+        let mut trashed = Vec::new();
+        let keys: Vec<String> = self.doc.keys(&trash_map).collect();
+        for bookmark_id in keys {
+            if let Ok(Some((_, obj_id))) = self.doc.get(&trash_map, &bookmark_id) {
+                match self.trashed_bookmark_from_automerge(&obj_id) {
+                    Ok(item) => trashed.push(item),
+                    Err(_) => continue, // Skip corrupted entries
+                }
+            }
+        }
+        
+        Ok(trashed)
+    }
+    
+    async fn restore(&mut self, id: &str) -> BookmarkResult<Bookmark> {
+        let trash_map = self.trash_map()
+            .ok_or_else(|| BookmarkError::NotFound(id.to_string()))?;
+        
+        let obj_id = match self.doc.get(&trash_map, id)
+            .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to get trashed bookmark: {}", e)))? {
+            Some((_, obj_id)) => obj_id,
+            None => return Err(BookmarkError::NotFound(id.to_string())),
+        };
+        
+        let bookmark = self.bookmark_from_automerge(&obj_id)?;
+        self.add_bookmark_to_automerge(&bookmark)?;
+        self.doc.delete(&trash_map, id)
+            .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to remove bookmark from trash: {}", e)))?;
+        
+        self.save()?;
+        Ok(bookmark)
+    }
+    
+    async fn purge_trash(&mut self, deleted_before: Option<DateTime<Utc>>) -> BookmarkResult<Vec<TrashedBookmark>> {
+        let trash_map = match self.trash_map() {
+            Some(obj_id) => obj_id,
+            None => return Ok(Vec::new()),
+        };
+        
+        let purged: Vec<TrashedBookmark> = self.find_trash().await?
+            .into_iter()
+            .filter(|trashed| deleted_before.is_none_or(|cutoff| trashed.deleted_at < cutoff))
+            .collect();
+        
+        if purged.is_empty() {
+            return Ok(purged);
+        }
+        
+        for trashed in &purged {
+            self.doc.delete(&trash_map, &trashed.bookmark.id)
+                .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to purge bookmark: {}", e)))?;
+        }
+        
+        self.save()?;
+        for trashed in &purged {
+            self.content_store.remove(&trashed.bookmark.id)?;
+            self.archive_store.remove(&trashed.bookmark.id)?;
+        }
+        Ok(purged)
+    }
+    
+    async fn apply_batch(&mut self, batch: BookmarkBatch) -> BookmarkResult<()> {
+        // Validate up front so a missing bookmark leaves the document untouched
+        let ids = batch.updates.iter().map(|bookmark| &bookmark.id).chain(batch.deletes.iter());
+        for id in ids {
+            if !self.bookmark_exists(id) {
+                return Err(BookmarkError::NotFound(id.clone()));
+            }
+        }
+        
+        if batch.is_empty() {
+            return Ok(());
+        }
+        
+        for bookmark in &batch.updates {
+            let obj_id = match self.doc.get(&self.bookmarks_map, &bookmark.id)
+                .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to get bookmark for update: {}", e)))? {
+                Some((_, obj_id)) => obj_id,
+                None => return Err(BookmarkError::NotFound(bookmark.id.clone())),
+            };
+            self.update_bookmark_fields(&obj_id, bookmark)?;
+        }
+        
+        for id in &batch.deletes {
+            self.move_to_trash(id)?;
+        }
+        
+        // A single save commits every pending operation as one change
+        self.save()?;
+        Ok(())
+    }
+    
+    async fn save_search(&mut self, search: SavedSearch) -> BookmarkResult<()> {
+        // Filters are stored as a JSON string so the whole search is replaced atomically
+        let filters_json = serde_json::to_string(&search.filters)
+            .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to serialize saved search: {}", e)))?;
+        let saved_searches = self.saved_searches_map()
+            .ok_or_else(|| BookmarkError::InvalidUrl("Missing saved searches map".to_string()))?;
+        self.doc.put(&saved_searches, search.name.as_str(), filters_json)
+            .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to save search: {}", e)))?;
+        self.save()?;
+        Ok(())
+    }
+    
+    async fn find_saved_searches(&self) -> BookmarkResult<Vec<SavedSearch>> {
+        let saved_searches = match self.saved_searches_map() {
+            Some(obj_id) => obj_id,
+            None => return Ok(Vec::new()),
+        };
+        
+        let mut searches = Vec::new();
+        for name in self.doc.keys(&saved_searches) {
+            if let Some(filters_json) = self.get_optional_string_field(&saved_searches, &name) {
+                match serde_json::from_str(&filters_json) {
+                    Ok(filters) => searches.push(SavedSearch { name, filters }),
+                    Err(_) => continue, // Skip corrupted entries
+                }
+            }
+        }
+        
+        // Keys are returned in sorted order
+        Ok(searches)
+    }
+    
+    async fn delete_saved_search(&mut self, name: &str) -> BookmarkResult<()> {
+        let saved_searches = self.saved_searches_map()
+            .ok_or_else(|| BookmarkError::NotFound(name.to_string()))?;
+        if !matches!(self.doc.get(&saved_searches, name), Ok(Some(_))) {
+            return Err(BookmarkError::NotFound(name.to_string()));
+        }
+        
+        self.doc.delete(&saved_searches, name)
+            .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to delete saved search: {}", e)))?;
+        self.save()?;
+        Ok(())
+    }
+    
+    async fn save_content(&mut self, bookmark_id: &str, content: &str) -> BookmarkResult<()> {
+        let bookmark = self.find_by_id(bookmark_id).await?;
+        self.content_store.save(bookmark_id, content)?;
+        
+        // The document is unchanged, so re-index this bookmark in place
+        let path = self.search_index_path();
+        if let Some(index) = self.current_search_index() {
+            index.upsert(&bookmark, Some(content));
+            let _ = index.save(&path);
+        }
+        Ok(())
+    }
+    
+    async fn find_content(&self, bookmark_id: &str) -> BookmarkResult<Option<String>> {
+        self.content_store.load(bookmark_id)
+    }
+    
+    async fn save_archive(&mut self, bookmark_id: &str, html: &str) -> BookmarkResult<Bookmark> {
+        let mut bookmark = self.find_by_id(bookmark_id).await?;
+        self.archive_store.save(bookmark_id, html)?;
+        
+        bookmark.archive_path = Some(format!("{}/{}.html", ARCHIVE_DIR, bookmark_id));
+        bookmark.archived_at = Some(Utc::now());
+        self.update(bookmark).await
+    }
+    
+    async fn find_archive(&self, bookmark_id: &str) -> BookmarkResult<Option<PathBuf>> {
+        let path = self.archive_store.path_for(bookmark_id)?;
+        Ok(path.exists().then_some(path))
+    }
 }
 
 // Additional helper methods for CRDT operations
 impl AutomergeBookmarkRepository {
+    /// Copy a bookmark into the trash map and remove it from the bookmarks map
+    fn move_to_trash(&mut self, id: &str) -> BookmarkResult<()> {
+        let obj_id = match self.doc.get(&self.bookmarks_map, id)
+            .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to get bookmark for delete: {}", e)))? {
+            Some((_, obj_id)) => obj_id,
+            None => return Err(BookmarkError::NotFound(id.to_string())),
+        };
+        
+        let bookmark = self.bookmark_from_automerge(&obj_id)?;
+        let trash_map = self.trash_map()
+            .ok_or_else(|| BookmarkError::InvalidUrl("Missing trash map".to_string()))?;
+        let trashed_obj = self.write_bookmark_object(&trash_map, &bookmark)?;
+        self.doc.put(&trashed_obj, "deleted_at", Utc::now().to_rfc3339())
+            .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to set deleted_at: {}", e)))?;
+        
+        self.doc.delete(&self.bookmarks_map, id)
+            .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to delete bookmark: {}", e)))?;
+        
+        Ok(())
+    }
+
     fn update_bookmark_fields(&mut self, obj_id: &automerge::ObjId, bookmark: &Bookmark) -> BookmarkResult<()> {
         // Update basic fields (last-writer-wins semantics)
         self.doc.put(obj_id, "url", bookmark.url.clone())
@@ -659,6 +1196,55 @@ impl AutomergeBookmarkRepository {
             let _ = self.doc.delete(obj_id, "priority_rating");
         }
 
+        // Update reading timestamps
+        for (field, value) in reading_dates(bookmark) {
+            if let Some(value) = value {
+                self.doc.put(obj_id, field, value.to_rfc3339())
+                    .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to update {}: {}", field, e)))?;
+            } else {
+                let _ = self.doc.delete(obj_id, field);
+            }
+        }
+
+        // Update page metadata
+        for (field, value) in page_metadata_fields(bookmark) {
+            if let Some(value) = value {
+                self.doc.put(obj_id, field, value.clone())
+                    .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to update {}: {}", field, e)))?;
+            } else {
+                let _ = self.doc.delete(obj_id, field);
+            }
+        }
+
+        self.put_site_details(obj_id, &bookmark.site_details)?;
+
+        // Update archive location
+        if let Some(ref archive_path) = bookmark.archive_path {
+            self.doc.put(obj_id, "archive_path", archive_path.clone())
+                .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to update archive_path: {}", e)))?;
+        } else {
+            let _ = self.doc.delete(obj_id, "archive_path");
+        }
+
+        if let Some(ref archived_at) = bookmark.archived_at {
+            self.doc.put(obj_id, "archived_at", archived_at.to_rfc3339())
+                .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to update archived_at: {}", e)))?;
+        } else {
+            let _ = self.doc.delete(obj_id, "archived_at");
+        }
+
+        // Update link check result, unless it is unchanged
+        if bookmark.link_status != self.link_status_from_automerge(obj_id) {
+            self.put_link_status(obj_id, bookmark.link_status.as_ref())?;
+        }
+
+        if bookmark.metadata_pending {
+            self.doc.put(obj_id, "metadata_pending", true)
+                .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to update metadata_pending: {}", e)))?;
+        } else {
+            let _ = self.doc.delete(obj_id, "metadata_pending");
+        }
+
         // Update tags with set union semantics
         self.update_tags_list(obj_id, &bookmark.tags)?;
         
@@ -704,6 +1290,25 @@ impl AutomergeBookmarkRepository {
     }
 }
 
+/// Optional page metadata stored as plain string fields, keyed by field name
+fn page_metadata_fields(bookmark: &Bookmark) -> [(&'static str, Option<&String>); 6] {
+    [
+        ("description", bookmark.description.as_ref()),
+        ("site_name", bookmark.site_name.as_ref()),
+        ("canonical_url", bookmark.canonical_url.as_ref()),
+        ("image_url", bookmark.image_url.as_ref()),
+        ("language", bookmark.language.as_ref()),
+        ("favicon_url", bookmark.favicon_url.as_ref()),
+    ]
+}
+
+fn reading_dates(bookmark: &Bookmark) -> [(&'static str, Option<DateTime<Utc>>); 2] {
+    [
+        ("started_at", bookmark.started_at),
+        ("completed_at", bookmark.completed_at),
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -865,6 +1470,177 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_delete_moves_to_trash_and_restore() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("trash_test.automerge");
+        
+        let mut bookmark = Bookmark::new("https://example.com", "Example").unwrap()
+            .with_tags(vec!["rust".to_string()]);
+        bookmark.add_note("Keep this note");
+        let bookmark_id = bookmark.id.clone();
+        
+        {
+            let mut repo = AutomergeBookmarkRepository::new(file_path.clone()).unwrap();
+            repo.create(bookmark).await.unwrap();
+            repo.delete(&bookmark_id).await.unwrap();
+        }
+        
+        // Trash persists across instances
+        let mut repo = AutomergeBookmarkRepository::new(file_path).unwrap();
+        assert!(repo.find_all(None).await.unwrap().is_empty());
+        let trash = repo.find_trash().await.unwrap();
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].bookmark.id, bookmark_id);
+        assert!(trash[0].deleted_at <= Utc::now());
+        
+        // Restore brings back all fields
+        let restored = repo.restore(&bookmark_id).await.unwrap();
+        assert_eq!(restored.tags, vec!["rust"]);
+        assert_eq!(restored.notes.len(), 1);
+        assert_eq!(repo.find_by_id(&bookmark_id).await.unwrap().title, "Example");
+        assert!(repo.find_trash().await.unwrap().is_empty());
+        
+        // Restoring again fails
+        assert!(matches!(repo.restore(&bookmark_id).await, Err(BookmarkError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_apply_batch_single_commit() {
+        let (mut repo, _temp_dir) = create_test_repo();
+        let mut ids = Vec::new();
+        for i in 0..3 {
+            let bookmark = Bookmark::new(&format!("https://example{}.com", i), &format!("Example {}", i)).unwrap();
+            ids.push(bookmark.id.clone());
+            repo.create(bookmark).await.unwrap();
+        }
+        let changes_before = repo.doc.get_changes(&[]).len();
+        
+        let mut first = repo.find_by_id(&ids[0]).await.unwrap();
+        let mut second = repo.find_by_id(&ids[1]).await.unwrap();
+        first.tags.push("batch".to_string());
+        second.priority_rating = Some(5);
+        repo.apply_batch(BookmarkBatch {
+            updates: vec![first, second],
+            deletes: vec![ids[2].clone()],
+        }).await.unwrap();
+        
+        assert_eq!(repo.doc.get_changes(&[]).len(), changes_before + 1);
+        assert_eq!(repo.find_by_id(&ids[0]).await.unwrap().tags, vec!["batch"]);
+        assert_eq!(repo.find_by_id(&ids[1]).await.unwrap().priority_rating, Some(5));
+        assert!(matches!(repo.find_by_id(&ids[2]).await, Err(BookmarkError::NotFound(_))));
+        assert_eq!(repo.find_trash().await.unwrap().len(), 1);
+        
+        // A missing bookmark rejects the whole batch
+        let result = repo.apply_batch(BookmarkBatch {
+            updates: vec![],
+            deletes: vec![ids[0].clone(), "missing".to_string()],
+        }).await;
+        assert!(matches!(result, Err(BookmarkError::NotFound(_))));
+        assert!(repo.find_by_id(&ids[0]).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_saved_searches_persist() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("test.automerge");
+        let filters = BookmarkFilters {
+            tags: Some(vec!["rust".to_string()]),
+            priority_range: Some((4, 5)),
+            sort_by: Some(SortBy::Priority),
+            ..Default::default()
+        };
+        
+        {
+            let mut repo = AutomergeBookmarkRepository::new(file_path.clone()).unwrap();
+            assert!(repo.find_saved_searches().await.unwrap().is_empty());
+            repo.save_search(SavedSearch { name: "rust-todo".to_string(), filters: filters.clone() }).await.unwrap();
+            repo.save_search(SavedSearch { name: "all".to_string(), filters: BookmarkFilters::default() }).await.unwrap();
+        }
+        
+        let mut repo = AutomergeBookmarkRepository::new(file_path).unwrap();
+        let searches = repo.find_saved_searches().await.unwrap();
+        assert_eq!(searches.len(), 2);
+        assert_eq!(searches[0].name, "all");
+        assert_eq!(searches[1].name, "rust-todo");
+        assert_eq!(searches[1].filters.tags, filters.tags);
+        assert_eq!(searches[1].filters.priority_range, Some((4, 5)));
+        assert_eq!(searches[1].filters.sort_by, Some(SortBy::Priority));
+        
+        repo.delete_saved_search("all").await.unwrap();
+        assert_eq!(repo.find_saved_searches().await.unwrap().len(), 1);
+        assert!(matches!(repo.delete_saved_search("all").await, Err(BookmarkError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_purge_trash() {
+        let (mut repo, _temp_dir) = create_test_repo();
+        
+        let bookmark1 = Bookmark::new("https://example.com", "Example").unwrap();
+        let bookmark2 = Bookmark::new("https://test.com", "Test").unwrap();
+        let id1 = bookmark1.id.clone();
+        let id2 = bookmark2.id.clone();
+        repo.create(bookmark1).await.unwrap();
+        repo.create(bookmark2).await.unwrap();
+        repo.delete(&id1).await.unwrap();
+        repo.delete(&id2).await.unwrap();
+        
+        // Nothing was deleted more than a day ago
+        let purged = repo.purge_trash(Some(Utc::now() - chrono::Duration::days(1))).await.unwrap();
+        assert!(purged.is_empty());
+        assert_eq!(repo.find_trash().await.unwrap().len(), 2);
+        
+        let purged = repo.purge_trash(None).await.unwrap();
+        assert_eq!(purged.len(), 2);
+        assert!(repo.find_trash().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_trash_merges_deletes_from_two_devices() {
+        let (mut laptop, temp_dir) = create_test_repo();
+        let bookmark1 = Bookmark::new("https://example.com", "Example").unwrap();
+        let bookmark2 = Bookmark::new("https://test.com", "Test").unwrap();
+        let id1 = bookmark1.id.clone();
+        let id2 = bookmark2.id.clone();
+        laptop.create(bookmark1).await.unwrap();
+        laptop.create(bookmark2).await.unwrap();
+        
+        // A second device starts from a copy of the document, then both delete offline
+        let phone_path = temp_dir.path().join("phone").join("bookmarks.automerge");
+        fs::create_dir_all(phone_path.parent().unwrap()).unwrap();
+        fs::copy(&laptop.file_path, &phone_path).unwrap();
+        let mut phone = AutomergeBookmarkRepository::new(phone_path).unwrap();
+        laptop.delete(&id1).await.unwrap();
+        phone.delete(&id2).await.unwrap();
+        
+        laptop.doc.merge(&mut phone.doc).unwrap();
+        assert!(laptop.find_all(None).await.unwrap().is_empty());
+        let mut trashed: Vec<String> = laptop.find_trash().await.unwrap().into_iter().map(|t| t.bookmark.id).collect();
+        trashed.sort();
+        let mut expected = vec![id1, id2.clone()];
+        expected.sort();
+        assert_eq!(trashed, expected);
+        assert_eq!(laptop.restore(&id2).await.unwrap().title, "Test");
+    }
+
+    #[tokio::test]
+    async fn test_saved_searches_merge_from_two_devices() {
+        let (mut laptop, temp_dir) = create_test_repo();
+        laptop.save().unwrap();
+        let phone_path = temp_dir.path().join("phone").join("bookmarks.automerge");
+        fs::create_dir_all(phone_path.parent().unwrap()).unwrap();
+        fs::copy(&laptop.file_path, &phone_path).unwrap();
+        let mut phone = AutomergeBookmarkRepository::new(phone_path).unwrap();
+        
+        // Both devices save a search before they first sync
+        laptop.save_search(SavedSearch { name: "work".to_string(), filters: BookmarkFilters::default() }).await.unwrap();
+        phone.save_search(SavedSearch { name: "reading".to_string(), filters: BookmarkFilters::default() }).await.unwrap();
+        
+        laptop.doc.merge(&mut phone.doc).unwrap();
+        let names: Vec<String> = laptop.find_saved_searches().await.unwrap().into_iter().map(|search| search.name).collect();
+        assert_eq!(names, vec!["reading", "work"]);
+    }
+
     #[tokio::test]
     async fn test_automerge_document_structure() {
         let (mut repo, _temp_dir) = create_test_repo();
@@ -946,6 +1722,235 @@ mod tests {
         assert_eq!(retrieved.notes[1].id, note_id2);
     }
 
+    #[tokio::test]
+    async fn test_search_index_kept_up_to_date() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("bookmarks.automerge");
+        let mut repo = AutomergeBookmarkRepository::new(file_path.clone()).unwrap();
+
+        let rust = Bookmark::new("https://rust-lang.org", "Rust Programming Language").unwrap();
+        let rust_id = rust.id.clone();
+        repo.create(rust).await.unwrap();
+        assert_eq!(repo.search_by_text("rust").await.unwrap().len(), 1);
+        assert!(temp_dir.path().join(SEARCH_INDEX_FILE).exists());
+
+        // Updates and deletes after the index is loaded are applied incrementally
+        let mut updated = repo.find_by_id(&rust_id).await.unwrap();
+        updated.title = "Ferris Handbook".to_string();
+        repo.update(updated).await.unwrap();
+        assert_eq!(repo.search_by_text("ferris").await.unwrap().len(), 1);
+        assert!(repo.search_by_text("programming").await.unwrap().is_empty());
+
+        let python = Bookmark::new("https://python.org", "Python Handbook").unwrap();
+        let python_id = python.id.clone();
+        repo.create(python).await.unwrap();
+        repo.delete(&rust_id).await.unwrap();
+        drop(repo);
+
+        // A fresh instance picks up the saved index
+        let repo = AutomergeBookmarkRepository::new(file_path.clone()).unwrap();
+        let results = repo.search_by_text("handbook").await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, python_id);
+
+        // Changes made while the index is not loaded are still applied to it
+        let mut repo2 = AutomergeBookmarkRepository::new(file_path.clone()).unwrap();
+        repo2.restore(&rust_id).await.unwrap();
+        drop(repo2);
+        let repo = AutomergeBookmarkRepository::new(file_path).unwrap();
+        assert_eq!(repo.search_by_text("handbook").await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_search_index_rebuilt_when_stale() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("bookmarks.automerge");
+        let mut repo = AutomergeBookmarkRepository::new(file_path.clone()).unwrap();
+        repo.create(Bookmark::new("https://tokio.rs", "Tokio").unwrap()).await.unwrap();
+        drop(repo);
+
+        fs::write(temp_dir.path().join(SEARCH_INDEX_FILE), "garbage").unwrap();
+        let repo = AutomergeBookmarkRepository::new(file_path).unwrap();
+        assert_eq!(repo.search_by_text("tokio").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_captured_content_searchable_and_purged() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("bookmarks.automerge");
+        let mut repo = AutomergeBookmarkRepository::new(file_path.clone()).unwrap();
+        let bookmark = Bookmark::new("https://example.com/post", "Weekly Notes").unwrap();
+        let id = bookmark.id.clone();
+        repo.create(bookmark).await.unwrap();
+        assert!(repo.search_by_text("lifetimes").await.unwrap().is_empty());
+
+        repo.save_content(&id, "An article about lifetimes and borrowing").await.unwrap();
+        assert_eq!(repo.search_by_text("lifetimes").await.unwrap().len(), 1);
+        assert!(temp_dir.path().join(CONTENT_DIR).join(format!("{}.txt", id)).exists());
+        assert!(matches!(repo.save_content("missing", "text").await, Err(BookmarkError::NotFound(_))));
+
+        // Content survives the trash and is removed when purged
+        repo.delete(&id).await.unwrap();
+        repo.restore(&id).await.unwrap();
+        let repo2 = AutomergeBookmarkRepository::new(file_path).unwrap();
+        assert_eq!(repo2.search_by_text("borrowing").await.unwrap().len(), 1);
+
+        repo.delete(&id).await.unwrap();
+        repo.purge_trash(None).await.unwrap();
+        assert_eq!(repo.find_content(&id).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_archive_recorded_and_purged() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("bookmarks.automerge");
+        let mut repo = AutomergeBookmarkRepository::new(file_path.clone()).unwrap();
+        let bookmark = Bookmark::new("https://example.com/post", "Post").unwrap();
+        let id = bookmark.id.clone();
+        repo.create(bookmark).await.unwrap();
+        assert_eq!(repo.find_archive(&id).await.unwrap(), None);
+
+        let archived = repo.save_archive(&id, "<html>copy</html>").await.unwrap();
+        assert_eq!(archived.archive_path, Some(format!("archives/{}.html", id)));
+        let path = repo.find_archive(&id).await.unwrap().unwrap();
+        assert_eq!(path, temp_dir.path().join(ARCHIVE_DIR).join(format!("{}.html", id)));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "<html>copy</html>");
+
+        // Archive details are stored in the document
+        let reloaded = AutomergeBookmarkRepository::new(file_path).unwrap();
+        let bookmark = reloaded.find_by_id(&id).await.unwrap();
+        assert_eq!(bookmark.archive_path, archived.archive_path);
+        assert_eq!(bookmark.archived_at.map(|at| at.timestamp()), archived.archived_at.map(|at| at.timestamp()));
+
+        repo.delete(&id).await.unwrap();
+        assert_eq!(repo.find_trash().await.unwrap()[0].bookmark.archive_path, archived.archive_path);
+        repo.purge_trash(None).await.unwrap();
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_link_status_round_trip_and_dead_filter() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("bookmarks.automerge");
+        let mut repo = AutomergeBookmarkRepository::new(file_path.clone()).unwrap();
+        let mut dead = Bookmark::new("https://gone.example.com", "Gone").unwrap();
+        let mut moved = Bookmark::new("https://old.example.com", "Moved").unwrap();
+        repo.create(dead.clone()).await.unwrap();
+        repo.create(moved.clone()).await.unwrap();
+        repo.create(Bookmark::new("https://unchecked.example.com", "Unchecked").unwrap()).await.unwrap();
+
+        dead.link_status = Some(LinkStatus {
+            checked_at: Utc::now(),
+            status_code: None,
+            redirected_to: None,
+            permanent_redirect: false,
+            error: Some("dns error".to_string()),
+        });
+        moved.link_status = Some(LinkStatus {
+            checked_at: Utc::now(),
+            status_code: Some(200),
+            redirected_to: Some("https://new.example.com/".to_string()),
+            permanent_redirect: true,
+            error: None,
+        });
+        repo.apply_batch(BookmarkBatch { updates: vec![dead.clone(), moved.clone()], deletes: vec![] }).await.unwrap();
+
+        let reloaded = AutomergeBookmarkRepository::new(file_path).unwrap();
+        let status = reloaded.find_by_id(&moved.id).await.unwrap().link_status.unwrap();
+        assert_eq!(status.redirected_to, Some("https://new.example.com/".to_string()));
+        assert!(status.moved_permanently());
+        assert_eq!(status.checked_at.timestamp(), moved.link_status.as_ref().unwrap().checked_at.timestamp());
+
+        let filters = BookmarkFilters { dead_links: true, ..Default::default() };
+        let results = reloaded.find_all(Some(filters)).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Gone");
+        assert_eq!(results[0].link_status.as_ref().unwrap().error, Some("dns error".to_string()));
+
+        // Clearing the status removes it from the document
+        dead.link_status = None;
+        repo.update(dead.clone()).await.unwrap();
+        assert_eq!(repo.find_by_id(&dead.id).await.unwrap().link_status, None);
+    }
+
+    #[tokio::test]
+    async fn test_metadata_pending_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("bookmarks.automerge");
+        let mut repo = AutomergeBookmarkRepository::new(file_path.clone()).unwrap();
+        let mut pending = Bookmark::new("https://offline.example.com", "offline.example.com").unwrap();
+        pending.metadata_pending = true;
+        repo.create(pending.clone()).await.unwrap();
+        repo.create(Bookmark::new("https://fetched.example.com", "Fetched").unwrap()).await.unwrap();
+
+        let reloaded = AutomergeBookmarkRepository::new(file_path).unwrap();
+        let bookmarks = reloaded.find_all(None).await.unwrap();
+        assert_eq!(bookmarks.iter().filter(|bookmark| bookmark.metadata_pending).count(), 1);
+        assert!(reloaded.find_by_id(&pending.id).await.unwrap().metadata_pending);
+
+        pending.metadata_pending = false;
+        repo.update(pending.clone()).await.unwrap();
+        assert!(!repo.find_by_id(&pending.id).await.unwrap().metadata_pending);
+    }
+
+    #[tokio::test]
+    async fn test_reading_dates_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("bookmarks.automerge");
+        let mut repo = AutomergeBookmarkRepository::new(file_path.clone()).unwrap();
+        let mut bookmark = repo.create(Bookmark::new("https://example.com", "Example").unwrap()).await.unwrap();
+
+        bookmark.set_reading_status(crate::types::ReadingStatus::Reading);
+        bookmark.set_reading_status(crate::types::ReadingStatus::Completed);
+        repo.update(bookmark.clone()).await.unwrap();
+
+        let reloaded = AutomergeBookmarkRepository::new(file_path).unwrap();
+        let stored = reloaded.find_by_id(&bookmark.id).await.unwrap();
+        assert_eq!(stored.started_at.map(|at| at.timestamp()), bookmark.started_at.map(|at| at.timestamp()));
+        assert_eq!(stored.completed_at.map(|at| at.timestamp()), bookmark.completed_at.map(|at| at.timestamp()));
+
+        // Going back to unread clears them
+        bookmark.set_reading_status(crate::types::ReadingStatus::Unread);
+        repo.update(bookmark.clone()).await.unwrap();
+        let stored = repo.find_by_id(&bookmark.id).await.unwrap();
+        assert_eq!((stored.started_at, stored.completed_at), (None, None));
+    }
+
+    #[tokio::test]
+    async fn test_text_search_sorted_by_relevance() {
+        let (mut repo, _temp_dir) = create_test_repo();
+        let mut passing = Bookmark::new("https://example.com/a", "Systems Languages").unwrap();
+        passing.add_note("Touches on async rust");
+        repo.create(passing).await.unwrap();
+        repo.create(Bookmark::new("https://example.com/b", "Async Rust in Depth").unwrap()).await.unwrap();
+
+        let results = repo.search_by_text("async rust").await.unwrap();
+        let titles: Vec<&str> = results.iter().map(|b| b.title.as_str()).collect();
+        assert_eq!(titles, vec!["Async Rust in Depth", "Systems Languages"]);
+
+        let filters = BookmarkFilters {
+            text_query: Some("async rust".to_string()),
+            sort_by: Some(SortBy::Relevance),
+            sort_order: Some(SortDirection::Ascending),
+            ..Default::default()
+        };
+        let results = repo.find_all(Some(filters)).await.unwrap();
+        assert_eq!(results[0].title, "Systems Languages");
+    }
+
+    #[tokio::test]
+    async fn test_quoted_phrase_ranks_contiguous_match_first() {
+        let (mut repo, _temp_dir) = create_test_repo();
+        repo.create(Bookmark::new("https://example.com/a", "Learning machine shop").unwrap()).await.unwrap();
+        repo.create(Bookmark::new("https://example.com/b", "A gentle guide to machine learning for working programmers").unwrap())
+            .await.unwrap();
+
+        let filters = BookmarkFilters::default().with_search_input("\"machine learning\"").unwrap();
+        let results = repo.find_all(Some(filters)).await.unwrap();
+        let titles: Vec<&str> = results.iter().map(|b| b.title.as_str()).collect();
+        assert_eq!(titles, vec!["A gentle guide to machine learning for working programmers", "Learning machine shop"]);
+    }
+
     #[tokio::test]
     async fn test_search_by_text_functionality() {
         let (mut repo, _temp_dir) = create_test_repo();
@@ -1090,4 +2095,62 @@ mod tests {
         assert_eq!(retrieved.url, "https://example.com");
         assert_eq!(retrieved.id, bookmark_id);
     }
+
+    #[tokio::test]
+    async fn test_page_metadata_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("bookmarks.automerge");
+        let mut repo = AutomergeBookmarkRepository::new(file_path.clone()).unwrap();
+        let mut bookmark = Bookmark::new("https://example.com/post?utm_source=feed", "Post").unwrap();
+        bookmark.description = Some("A post about things".to_string());
+        bookmark.site_name = Some("Example Blog".to_string());
+        bookmark.canonical_url = Some("https://example.com/post".to_string());
+        bookmark.image_url = Some("https://example.com/cover.png".to_string());
+        bookmark.language = Some("en-US".to_string());
+        bookmark.favicon_url = Some("https://example.com/favicon.png".to_string());
+        repo.create(bookmark.clone()).await.unwrap();
+
+        let reloaded = AutomergeBookmarkRepository::new(file_path.clone()).unwrap();
+        let stored = reloaded.find_by_id(&bookmark.id).await.unwrap();
+        assert_eq!(stored.description, bookmark.description);
+        assert_eq!(stored.site_name, bookmark.site_name);
+        assert_eq!(stored.canonical_url, bookmark.canonical_url);
+        assert_eq!(stored.image_url, bookmark.image_url);
+        assert_eq!(stored.language, bookmark.language);
+        assert_eq!(stored.favicon_url, bookmark.favicon_url);
+
+        bookmark.description = Some("Updated summary".to_string());
+        bookmark.image_url = None;
+        repo.update(bookmark.clone()).await.unwrap();
+        let reloaded = AutomergeBookmarkRepository::new(file_path).unwrap();
+        let stored = reloaded.find_by_id(&bookmark.id).await.unwrap();
+        assert_eq!(stored.description, Some("Updated summary".to_string()));
+        assert_eq!(stored.image_url, None);
+        assert_eq!(stored.site_name, Some("Example Blog".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_site_details_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("bookmarks.automerge");
+        let mut repo = AutomergeBookmarkRepository::new(file_path.clone()).unwrap();
+        let mut bookmark = Bookmark::new("https://github.com/rust-lang/rust", "rust-lang/rust").unwrap();
+        bookmark.site_details.insert("stars".to_string(), "98000".to_string());
+        bookmark.site_details.insert("language".to_string(), "Rust".to_string());
+        repo.create(bookmark.clone()).await.unwrap();
+
+        let reloaded = AutomergeBookmarkRepository::new(file_path.clone()).unwrap();
+        assert_eq!(reloaded.find_by_id(&bookmark.id).await.unwrap().site_details, bookmark.site_details);
+
+        bookmark.site_details.insert("stars".to_string(), "99000".to_string());
+        bookmark.site_details.remove("language");
+        repo.update(bookmark.clone()).await.unwrap();
+        let reloaded = AutomergeBookmarkRepository::new(file_path.clone()).unwrap();
+        assert_eq!(reloaded.find_by_id(&bookmark.id).await.unwrap().site_details, bookmark.site_details);
+
+        bookmark.site_details.clear();
+        repo.update(bookmark.clone()).await.unwrap();
+        let reloaded = AutomergeBookmarkRepository::new(file_path).unwrap();
+        assert!(reloaded.find_by_id(&bookmark.id).await.unwrap().site_details.is_empty());
+    }
 }
\ No newline at end of file
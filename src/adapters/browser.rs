@@ -0,0 +1,40 @@
+use std::process::Command;
+
+/// Open a URL or local file in the default browser
+pub fn open_url(url: &str) -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(target_os = "macos")]
+    {
+        Command::new("open").arg(url).spawn()?;
+        Ok(())
+    }
+    #[cfg(target_os = "linux")]
+    {
+        Command::new("xdg-open").arg(url).spawn()?;
+        Ok(())
+    }
+    #[cfg(target_os = "windows")]
+    {
+        Command::new("cmd").args(["/C", "start", "", url]).spawn()?;
+        Ok(())
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+    {
+        Err(format!("Opening URLs is not supported on this platform").into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore]
+    fn test_open_url_function() {
+        // Test that open_url function doesn't panic with valid URLs
+        // We can't actually test browser opening in CI, but we can test the function exists
+        let result = open_url("https://example.com");
+        // The function should complete without panicking
+        // Result may be Ok or Err depending on the environment, which is fine
+        let _ = result;
+    }
+}
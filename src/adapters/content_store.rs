@@ -0,0 +1,98 @@
+use crate::types::{BookmarkError, BookmarkResult};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+/// Sidecar store for per-bookmark files such as captured page text or page archives
+///
+/// These can be large, so they are kept out of the Automerge document and
+/// do not sync; each device captures its own copy.
+pub struct ContentStore {
+    dir: PathBuf,
+    extension: &'static str,
+}
+
+impl ContentStore {
+    /// A store of plain text files
+    pub fn new(dir: PathBuf) -> Self {
+        Self::with_extension(dir, "txt")
+    }
+
+    pub fn with_extension(dir: PathBuf, extension: &'static str) -> Self {
+        Self { dir, extension }
+    }
+
+    /// Where the file for a bookmark is (or would be) stored
+    pub fn path_for(&self, id: &str) -> BookmarkResult<PathBuf> {
+        // IDs are UUIDs; refuse anything that could escape the directory
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(BookmarkError::InvalidId(format!("Invalid bookmark ID for content: {}", id)));
+        }
+        Ok(self.dir.join(format!("{}.{}", id, self.extension)))
+    }
+
+    /// Store the text for a bookmark, replacing any previous capture
+    pub fn save(&self, id: &str, content: &str) -> BookmarkResult<()> {
+        let path = self.path_for(id)?;
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to create content directory: {}", e)))?;
+        fs::write(path, content)
+            .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to write content: {}", e)))
+    }
+
+    /// Load the text for a bookmark, if any has been captured
+    pub fn load(&self, id: &str) -> BookmarkResult<Option<String>> {
+        match fs::read_to_string(self.path_for(id)?) {
+            Ok(content) => Ok(Some(content)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(BookmarkError::InvalidUrl(format!("Failed to read content: {}", e))),
+        }
+    }
+
+    /// Remove the text for a bookmark; missing content is not an error
+    pub fn remove(&self, id: &str) -> BookmarkResult<()> {
+        match fs::remove_file(self.path_for(id)?) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(BookmarkError::InvalidUrl(format!("Failed to remove content: {}", e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_save_load_remove() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ContentStore::new(temp_dir.path().join("content"));
+        let id = "123e4567-e89b-12d3-a456-426614174000";
+
+        assert_eq!(store.load(id).unwrap(), None);
+        store.save(id, "Article body").unwrap();
+        assert_eq!(store.load(id).unwrap(), Some("Article body".to_string()));
+
+        store.remove(id).unwrap();
+        assert_eq!(store.load(id).unwrap(), None);
+        store.remove(id).unwrap();
+    }
+
+    #[test]
+    fn test_with_extension() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ContentStore::with_extension(temp_dir.path().join("archives"), "html");
+        store.save("abc-123", "<html></html>").unwrap();
+        assert_eq!(store.path_for("abc-123").unwrap(), temp_dir.path().join("archives").join("abc-123.html"));
+        assert!(store.path_for("abc-123").unwrap().exists());
+    }
+
+    #[test]
+    fn test_rejects_path_traversal() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ContentStore::new(temp_dir.path().to_path_buf());
+        assert!(matches!(store.save("../escape", "x"), Err(BookmarkError::InvalidId(_))));
+        assert!(matches!(store.load("a/b"), Err(BookmarkError::InvalidId(_))));
+    }
+}
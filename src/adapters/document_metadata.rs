@@ -0,0 +1,228 @@
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use lopdf::{decode_text_string, Dictionary, Document};
+use url::Url;
+
+use super::web_extractor::{clean_text, truncate_content};
+use crate::types::ExtractedMetadata;
+
+/// Pages of a PDF whose text is captured; enough for search without parsing whole books
+const MAX_PDF_TEXT_PAGES: u32 = 10;
+
+/// Longest first line of text that is still taken as a title
+const MAX_TITLE_CHARS: usize = 200;
+
+/// Metadata for a PDF: the document info dictionary, falling back to the first page's text
+///
+/// - title: info `Title`, first line of text on the first page, file name
+/// - author: info `Author`
+/// - description: info `Subject`
+/// - publish date: info `CreationDate`
+///
+/// The page count is recorded in `site_details`, and the text of the first pages
+/// becomes the captured content. Encrypted or damaged files only get a title from
+/// their file name.
+pub(crate) fn pdf_metadata(bytes: &[u8], url: &Url) -> ExtractedMetadata {
+    let mut metadata = resource_metadata("application/pdf");
+
+    if let Ok(document) = Document::load_mem(bytes) {
+        if let Some(info) = info_dictionary(&document).filter(|_| !document.is_encrypted()) {
+            metadata.title = info_string(&document, info, b"Title")
+                .filter(|title| !title.eq_ignore_ascii_case("untitled"));
+            metadata.author = info_string(&document, info, b"Author");
+            metadata.description = info_string(&document, info, b"Subject");
+            metadata.publish_date = info_string(&document, info, b"CreationDate")
+                .and_then(|date| parse_pdf_date(&date));
+        }
+
+        let pages = document.get_pages();
+        metadata.site_details.insert("pages".to_string(), pages.len().to_string());
+        if !document.is_encrypted() {
+            let page_numbers: Vec<u32> = pages.keys().copied().take(MAX_PDF_TEXT_PAGES as usize).collect();
+            metadata.content = document.extract_text(&page_numbers).ok()
+                .map(|text| normalize_lines(&text))
+                .filter(|text| !text.is_empty())
+                .map(truncate_content);
+        }
+    }
+
+    metadata.title = metadata.title
+        .or_else(|| metadata.content.as_deref().and_then(first_line))
+        .or_else(|| file_name(url));
+    metadata
+}
+
+/// Metadata for an image: its file name as the title, and its dimensions when the format is recognized
+pub(crate) fn image_metadata(bytes: &[u8], media_type: &str, url: &Url) -> ExtractedMetadata {
+    let mut metadata = resource_metadata(media_type);
+    metadata.title = file_name(url);
+    metadata.image_url = Some(url.to_string());
+    if let Ok(size) = imagesize::blob_size(bytes) {
+        metadata.site_details.insert("dimensions".to_string(), format!("{}x{}", size.width, size.height));
+    }
+    metadata
+}
+
+/// Metadata for a plain text or Markdown file: the first line as the title, and the text as content
+pub(crate) fn text_metadata(text: &str, url: &Url) -> ExtractedMetadata {
+    let mut metadata = resource_metadata("text/plain");
+    let text = normalize_lines(text);
+    metadata.title = first_line(text.trim_start_matches('#')).or_else(|| file_name(url));
+    metadata.content = Some(text).filter(|text| !text.is_empty()).map(truncate_content);
+    metadata
+}
+
+fn resource_metadata(media_type: &str) -> ExtractedMetadata {
+    let mut metadata = ExtractedMetadata::default();
+    metadata.site_details.insert("media_type".to_string(), media_type.to_string());
+    metadata
+}
+
+/// The document info dictionary named by the trailer, if any
+fn info_dictionary(document: &Document) -> Option<&Dictionary> {
+    let info = document.trailer.get(b"Info").ok()?;
+    let (_, info) = document.dereference(info).ok()?;
+    info.as_dict().ok()
+}
+
+/// A text string from the info dictionary, in either PDFDocEncoding or UTF-16
+fn info_string(document: &Document, info: &Dictionary, key: &[u8]) -> Option<String> {
+    let (_, value) = document.dereference(info.get(key).ok()?).ok()?;
+    clean_text(&decode_text_string(value).ok()?)
+}
+
+/// Parse a PDF date such as `D:20190402153000+02'00'`; everything after the year is optional
+fn parse_pdf_date(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    let value = value.strip_prefix("D:").unwrap_or(value);
+    let digits_end = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    let digits = &value[..digits_end];
+
+    let year: i32 = digits.get(..4)?.parse().ok()?;
+    let field = |start: usize, default: u32| -> Option<u32> {
+        match digits.get(start..start + 2) {
+            Some(field) => field.parse().ok(),
+            None => Some(default),
+        }
+    };
+    let date = NaiveDate::from_ymd_opt(year, field(4, 1)?, field(6, 1)?)?
+        .and_hms_opt(field(8, 0)?, field(10, 0)?, field(12, 0)?)?;
+
+    // The offset is `Z`, or `+HH'mm'` / `-HH'mm'` with optional minutes
+    let offset = &value[digits_end..];
+    let offset_minutes = match offset.chars().next() {
+        Some(sign @ ('+' | '-')) => {
+            let mut parts = offset[1..].split('\'').filter(|part| !part.is_empty());
+            let hours: i64 = parts.next()?.parse().ok()?;
+            let minutes: i64 = parts.next().map_or(Some(0), |minutes| minutes.parse().ok())?;
+            let total = hours * 60 + minutes;
+            if sign == '-' { -total } else { total }
+        }
+        _ => 0,
+    };
+    Some(Utc.from_utc_datetime(&(date - Duration::minutes(offset_minutes))))
+}
+
+/// Collapse whitespace within each line and drop blank lines
+fn normalize_lines(text: &str) -> String {
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The first non-blank line of some text, if it is short enough to be a title
+fn first_line(text: &str) -> Option<String> {
+    text.lines()
+        .find_map(clean_text)
+        .filter(|line| line.chars().count() <= MAX_TITLE_CHARS)
+}
+
+/// The last path segment of a URL, decoded, without its extension
+fn file_name(url: &Url) -> Option<String> {
+    let segment = url.path_segments()?.rev().find(|segment| !segment.is_empty())?;
+    let name = percent_encoding::percent_decode_str(segment).decode_utf8_lossy();
+    let stem = match name.rsplit_once('.') {
+        Some((stem, _)) if !stem.is_empty() => stem,
+        _ => &name,
+    };
+    clean_text(stem)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAPER: &[u8] = include_bytes!("fixtures/paper.pdf");
+    const UNTITLED: &[u8] = include_bytes!("fixtures/untitled.pdf");
+    const PIXEL: &[u8] = include_bytes!("fixtures/pixel.png");
+
+    #[test]
+    fn test_pdf_document_info() {
+        let url = Url::parse("https://www.inkandswitch.com/local-first.pdf").unwrap();
+        let metadata = pdf_metadata(PAPER, &url);
+
+        assert_eq!(metadata.title, Some("Local-First Software".to_string()));
+        assert_eq!(metadata.author, Some("Martin Kleppmann".to_string()));
+        assert_eq!(metadata.description, Some("You own your data, in spite of the cloud".to_string()));
+        assert_eq!(metadata.publish_date, Some(Utc.with_ymd_and_hms(2019, 4, 2, 13, 30, 0).unwrap()));
+        assert_eq!(metadata.site_details.get("pages"), Some(&"1".to_string()));
+        assert_eq!(metadata.site_details.get("media_type"), Some(&"application/pdf".to_string()));
+        assert!(metadata.content.unwrap().contains("Martin Kleppmann et al."));
+    }
+
+    #[test]
+    fn test_pdf_title_falls_back_to_first_page() {
+        let url = Url::parse("https://example.com/papers/crdts.pdf").unwrap();
+        let metadata = pdf_metadata(UNTITLED, &url);
+
+        assert_eq!(metadata.title, Some("CRDTs for Everyone".to_string()));
+        assert_eq!(metadata.author, None);
+        assert_eq!(metadata.publish_date, None);
+    }
+
+    #[test]
+    fn test_damaged_pdf_uses_file_name() {
+        let url = Url::parse("https://example.com/papers/Attention%20Is%20All%20You%20Need.pdf").unwrap();
+        let metadata = pdf_metadata(b"%PDF-1.4 truncated", &url);
+
+        assert_eq!(metadata.title, Some("Attention Is All You Need".to_string()));
+        assert_eq!(metadata.content, None);
+    }
+
+    #[test]
+    fn test_image_dimensions() {
+        let url = Url::parse("https://example.com/images/diagram.png?size=large").unwrap();
+        let metadata = image_metadata(PIXEL, "image/png", &url);
+
+        assert_eq!(metadata.title, Some("diagram".to_string()));
+        assert_eq!(metadata.image_url, Some(url.to_string()));
+        assert_eq!(metadata.site_details.get("dimensions"), Some(&"3x2".to_string()));
+        assert_eq!(metadata.site_details.get("media_type"), Some(&"image/png".to_string()));
+    }
+
+    #[test]
+    fn test_text_metadata() {
+        let url = Url::parse("https://example.com/notes/README.md").unwrap();
+        let metadata = text_metadata("\n\n# Sync   Protocol\n\nPeers exchange   changes.\n", &url);
+
+        assert_eq!(metadata.title, Some("Sync Protocol".to_string()));
+        assert_eq!(metadata.content, Some("# Sync Protocol\nPeers exchange changes.".to_string()));
+
+        let empty = text_metadata("   \n", &url);
+        assert_eq!(empty.title, Some("README".to_string()));
+        assert_eq!(empty.content, None);
+    }
+
+    #[test]
+    fn test_parse_pdf_date() {
+        let date = |value: &str| parse_pdf_date(value);
+
+        assert_eq!(date("D:20190402153000Z"), Some(Utc.with_ymd_and_hms(2019, 4, 2, 15, 30, 0).unwrap()));
+        assert_eq!(date("D:20190402153000-05'30'"), Some(Utc.with_ymd_and_hms(2019, 4, 2, 21, 0, 0).unwrap()));
+        assert_eq!(date("D:2019"), Some(Utc.with_ymd_and_hms(2019, 1, 1, 0, 0, 0).unwrap()));
+        assert_eq!(date("20190402"), Some(Utc.with_ymd_and_hms(2019, 4, 2, 0, 0, 0).unwrap()));
+        assert_eq!(date("D:20191399"), None);
+        assert_eq!(date("yesterday"), None);
+    }
+}
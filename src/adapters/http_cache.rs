@@ -0,0 +1,437 @@
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, HeaderValue, CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+use crate::types::Config;
+
+/// Directory under the data directory holding cached responses
+const CACHE_DIR: &str = "http-cache";
+
+/// On-disk cache of successful GET responses, shared by everything that downloads pages
+///
+/// Responses are kept only if they can be reused: `no-store` responses are
+/// skipped, as are responses with neither a `max-age` nor an `ETag` or
+/// `Last-Modified` validator. A response is served without a request while it
+/// is younger than its `max-age` (and not `no-cache`); after that it is
+/// revalidated with `If-None-Match`/`If-Modified-Since`. Once the cache grows
+/// past its size limit, the entries stored or revalidated longest ago are removed.
+pub struct HttpCache {
+    dir: PathBuf,
+    max_bytes: u64,
+    /// Serializes writes and eviction within this process
+    write_lock: Mutex<()>,
+}
+
+/// A response read back from the cache
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    /// Where the response was served from after redirects
+    pub url: Url,
+    pub content_type: Option<String>,
+    pub body: Vec<u8>,
+    entry: CacheEntry,
+}
+
+/// What is stored next to each cached body
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    /// The requested URL, checked on lookup in case two URLs share a file name
+    url: String,
+    final_url: String,
+    content_type: Option<String>,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    stored_at: DateTime<Utc>,
+    max_age_secs: Option<u64>,
+    no_cache: bool,
+}
+
+/// The caching directives of a response's `Cache-Control` header that we act on
+#[derive(Debug, Default, PartialEq)]
+struct CacheControl {
+    no_store: bool,
+    no_cache: bool,
+    max_age_secs: Option<u64>,
+}
+
+/// Entries found and bytes used or removed by the cache
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CacheUsage {
+    pub entries: usize,
+    pub bytes: u64,
+}
+
+impl HttpCache {
+    pub fn new(dir: PathBuf, max_bytes: u64) -> Self {
+        Self { dir, max_bytes, write_lock: Mutex::new(()) }
+    }
+
+    /// The cache configured for this data directory, or `None` if caching is off
+    pub fn for_config(config: &Config) -> Option<Arc<Self>> {
+        if !config.cache.enabled {
+            return None;
+        }
+        let dir = Self::dir_for(config).ok()?;
+        Some(Arc::new(Self::new(dir, config.cache.max_bytes)))
+    }
+
+    /// Where cached responses live for a configuration
+    pub fn dir_for(config: &Config) -> io::Result<PathBuf> {
+        config.data_dir_path()
+            .map(|data_dir| data_dir.join(CACHE_DIR))
+            .map_err(|e| io::Error::new(ErrorKind::InvalidInput, e.to_string()))
+    }
+
+    /// The stored response for a URL, fresh or not
+    pub fn lookup(&self, url: &Url) -> Option<CachedResponse> {
+        let (entry_path, body_path) = self.paths_for(url);
+        let entry: CacheEntry = serde_json::from_slice(&fs::read(entry_path).ok()?).ok()?;
+        if entry.url != url.as_str() {
+            return None;
+        }
+        let body = fs::read(body_path).ok()?;
+        Some(CachedResponse {
+            url: Url::parse(&entry.final_url).ok()?,
+            content_type: entry.content_type.clone(),
+            body,
+            entry,
+        })
+    }
+
+    /// Store a 200 response for `url` if its headers allow reusing it
+    ///
+    /// Failing to write is not an error; the response is simply not cached.
+    pub fn store(&self, url: &Url, final_url: &Url, headers: &HeaderMap, body: &[u8]) {
+        let control = CacheControl::parse(headers);
+        let etag = header_string(headers, ETAG);
+        let last_modified = header_string(headers, LAST_MODIFIED);
+        let reusable = control.max_age_secs.is_some_and(|max_age| max_age > 0)
+            || etag.is_some()
+            || last_modified.is_some();
+        if control.no_store || !reusable || body.len() as u64 > self.max_bytes {
+            return;
+        }
+
+        let entry = CacheEntry {
+            url: url.to_string(),
+            final_url: final_url.to_string(),
+            content_type: header_string(headers, CONTENT_TYPE),
+            etag,
+            last_modified,
+            stored_at: Utc::now(),
+            max_age_secs: control.max_age_secs,
+            no_cache: control.no_cache,
+        };
+        let _guard = self.write_lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let (entry_path, body_path) = self.paths_for(url);
+        let written = fs::create_dir_all(&self.dir)
+            .and_then(|_| write_atomically(&body_path, body))
+            .and_then(|_| write_entry(&entry_path, &entry));
+        if written.is_ok() {
+            let _ = self.evict_to_fit();
+        }
+    }
+
+    /// Record that the server confirmed a cached response is unchanged (304 Not Modified)
+    pub fn revalidated(&self, cached: CachedResponse, headers: &HeaderMap) -> CachedResponse {
+        let mut entry = cached.entry;
+        entry.stored_at = Utc::now();
+        if headers.contains_key(CACHE_CONTROL) {
+            let control = CacheControl::parse(headers);
+            entry.max_age_secs = control.max_age_secs;
+            entry.no_cache = control.no_cache;
+        }
+        entry.etag = header_string(headers, ETAG).or(entry.etag);
+        entry.last_modified = header_string(headers, LAST_MODIFIED).or(entry.last_modified);
+
+        if let Ok(url) = Url::parse(&entry.url) {
+            let _guard = self.write_lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            let _ = write_entry(&self.paths_for(&url).0, &entry);
+        }
+        CachedResponse { entry, ..cached }
+    }
+
+    /// Entries and bytes currently stored
+    pub fn usage(&self) -> io::Result<CacheUsage> {
+        let entries = self.entries()?;
+        Ok(CacheUsage {
+            entries: entries.len(),
+            bytes: entries.iter().map(|entry| entry.bytes).sum(),
+        })
+    }
+
+    /// Remove every cached response, returning what was removed
+    pub fn clear(&self) -> io::Result<CacheUsage> {
+        let _guard = self.write_lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let usage = self.usage()?;
+        match fs::remove_dir_all(&self.dir) {
+            Ok(()) => Ok(usage),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(usage),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Remove the least recently validated entries until the cache fits its limit
+    fn evict_to_fit(&self) -> io::Result<()> {
+        let mut entries = self.entries()?;
+        let mut total: u64 = entries.iter().map(|entry| entry.bytes).sum();
+        entries.sort_by_key(|entry| entry.validated);
+        for entry in entries {
+            if total <= self.max_bytes {
+                break;
+            }
+            for path in [entry.path.with_extension("json"), entry.path.with_extension("body")] {
+                let _ = fs::remove_file(path);
+            }
+            total -= entry.bytes;
+        }
+        Ok(())
+    }
+
+    /// Each stored entry with its combined size and when it was last stored or revalidated
+    fn entries(&self) -> io::Result<Vec<StoredEntry>> {
+        let read_dir = match fs::read_dir(&self.dir) {
+            Ok(read_dir) => read_dir,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut entries = Vec::new();
+        for file in read_dir.flatten() {
+            let path = file.path();
+            if path.extension().is_none_or(|extension| extension != "json") {
+                continue;
+            }
+            let (Ok(entry_file), Ok(body_file)) = (file.metadata(), fs::metadata(path.with_extension("body"))) else {
+                continue;
+            };
+            // Unreadable entries sort first and are the first to go
+            let validated = fs::read(&path).ok()
+                .and_then(|json| serde_json::from_slice::<CacheEntry>(&json).ok())
+                .map(|entry| entry.stored_at);
+            entries.push(StoredEntry {
+                bytes: entry_file.len() + body_file.len(),
+                validated,
+                path,
+            });
+        }
+        Ok(entries)
+    }
+
+    /// The metadata and body files for a URL
+    fn paths_for(&self, url: &Url) -> (PathBuf, PathBuf) {
+        let name = format!("{:016x}", fnv1a(url.as_str().as_bytes()));
+        (self.dir.join(format!("{}.json", name)), self.dir.join(format!("{}.body", name)))
+    }
+}
+
+impl CachedResponse {
+    /// Whether the response may be used without asking the server
+    pub fn is_fresh(&self) -> bool {
+        let age = Utc::now().signed_duration_since(self.entry.stored_at).num_seconds();
+        !self.entry.no_cache && self.entry.max_age_secs.is_some_and(|max_age| age >= 0 && (age as u64) < max_age)
+    }
+
+    /// Conditional request headers asking the server whether the response changed
+    pub fn validators(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        let etag = self.entry.etag.as_deref().and_then(|etag| HeaderValue::from_str(etag).ok());
+        if let Some(etag) = etag {
+            headers.insert(IF_NONE_MATCH, etag);
+        }
+        let last_modified = self.entry.last_modified.as_deref().and_then(|date| HeaderValue::from_str(date).ok());
+        if let Some(last_modified) = last_modified {
+            headers.insert(IF_MODIFIED_SINCE, last_modified);
+        }
+        headers
+    }
+}
+
+impl CacheControl {
+    fn parse(headers: &HeaderMap) -> Self {
+        let mut control = Self::default();
+        let directives = headers.get_all(CACHE_CONTROL).iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','));
+        for directive in directives {
+            let directive = directive.trim().to_ascii_lowercase();
+            match directive.split_once('=') {
+                Some(("max-age", seconds)) => control.max_age_secs = seconds.trim_matches('"').parse().ok(),
+                _ if directive == "no-store" => control.no_store = true,
+                _ if directive == "no-cache" => control.no_cache = true,
+                _ => {}
+            }
+        }
+        control
+    }
+}
+
+/// An entry found on disk while measuring the cache
+struct StoredEntry {
+    /// The entry's `.json` file; the body sits next to it
+    path: PathBuf,
+    bytes: u64,
+    validated: Option<DateTime<Utc>>,
+}
+
+fn header_string(headers: &HeaderMap, name: reqwest::header::HeaderName) -> Option<String> {
+    headers.get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn write_entry(path: &Path, entry: &CacheEntry) -> io::Result<()> {
+    let json = serde_json::to_vec(entry).map_err(io::Error::other)?;
+    write_atomically(path, &json)
+}
+
+/// Write through a temporary file so readers never see half a file
+///
+/// Each write gets its own temporary name, so processes and threads storing
+/// the same URL at once never write into each other's file.
+fn write_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+    static NEXT_TEMP: AtomicU64 = AtomicU64::new(0);
+    let extension = path.extension().and_then(|extension| extension.to_str()).unwrap_or_default();
+    let temp_path = path.with_extension(format!(
+        "{}.{}-{}.tmp",
+        extension,
+        std::process::id(),
+        NEXT_TEMP.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp_path);
+    })
+}
+
+/// 64-bit FNV-1a, used for file names that stay the same across builds
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x100000001b3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(*name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    fn url(path: &str) -> Url {
+        Url::parse(&format!("https://example.com{}", path)).unwrap()
+    }
+
+    #[test]
+    fn test_cache_control_parsing() {
+        let control = CacheControl::parse(&headers(&[("cache-control", "public, Max-Age=600"), ("cache-control", "no-cache")]));
+        assert_eq!(control, CacheControl { no_store: false, no_cache: true, max_age_secs: Some(600) });
+        assert!(CacheControl::parse(&headers(&[("cache-control", "private, no-store")])).no_store);
+        assert_eq!(CacheControl::parse(&HeaderMap::new()), CacheControl::default());
+    }
+
+    #[test]
+    fn test_store_and_lookup() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = HttpCache::new(temp_dir.path().join("cache"), 1024 * 1024);
+
+        let fresh = url("/fresh");
+        cache.store(&fresh, &url("/fresh/"), &headers(&[("cache-control", "max-age=600"), ("content-type", "text/html")]), b"<p>hi</p>");
+        let cached = cache.lookup(&fresh).unwrap();
+        assert!(cached.is_fresh());
+        assert_eq!(cached.url, url("/fresh/"));
+        assert_eq!(cached.content_type.as_deref(), Some("text/html"));
+        assert_eq!(cached.body, b"<p>hi</p>");
+
+        // Validators make a response worth keeping even when it must be revalidated
+        let tagged = url("/tagged");
+        cache.store(&tagged, &tagged, &headers(&[("etag", "\"v1\""), ("cache-control", "no-cache, max-age=600")]), b"v1");
+        let cached = cache.lookup(&tagged).unwrap();
+        assert!(!cached.is_fresh());
+        assert_eq!(cached.validators().get(IF_NONE_MATCH).unwrap(), "\"v1\"");
+
+        // Nothing to reuse, or not allowed to
+        cache.store(&url("/plain"), &url("/plain"), &HeaderMap::new(), b"plain");
+        cache.store(&url("/secret"), &url("/secret"), &headers(&[("cache-control", "no-store, max-age=600")]), b"secret");
+        assert!(cache.lookup(&url("/plain")).is_none());
+        assert!(cache.lookup(&url("/secret")).is_none());
+        assert_eq!(cache.usage().unwrap().entries, 2);
+    }
+
+    #[test]
+    fn test_revalidated_refreshes_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = HttpCache::new(temp_dir.path().to_path_buf(), 1024 * 1024);
+        let page = url("/page");
+        cache.store(&page, &page, &headers(&[("last-modified", "Wed, 21 Oct 2015 07:28:00 GMT")]), b"body");
+
+        let cached = cache.lookup(&page).unwrap();
+        assert!(!cached.is_fresh());
+        assert!(cached.validators().contains_key(IF_MODIFIED_SINCE));
+
+        let cached = cache.revalidated(cached, &headers(&[("cache-control", "max-age=60")]));
+        assert!(cached.is_fresh());
+        assert!(cache.lookup(&page).unwrap().is_fresh());
+    }
+
+    #[test]
+    fn test_eviction_and_clear() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = HttpCache::new(temp_dir.path().join("cache"), 1000);
+        let cacheable = headers(&[("cache-control", "max-age=600")]);
+
+        // Too large to ever fit
+        cache.store(&url("/huge"), &url("/huge"), &cacheable, &[b'x'; 2000]);
+        assert!(cache.lookup(&url("/huge")).is_none());
+
+        for index in 0..4 {
+            let page = url(&format!("/page{}", index));
+            cache.store(&page, &page, &cacheable, &[b'x'; 300]);
+        }
+        let usage = cache.usage().unwrap();
+        assert!(usage.bytes <= 1000);
+        assert!(usage.entries < 4);
+        assert!(cache.lookup(&url("/page3")).is_some());
+        assert!(cache.lookup(&url("/page0")).is_none());
+
+        assert_eq!(cache.clear().unwrap(), usage);
+        assert_eq!(cache.usage().unwrap(), CacheUsage::default());
+        assert_eq!(cache.clear().unwrap(), CacheUsage::default());
+    }
+
+    #[test]
+    fn test_eviction_follows_revalidation_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = HttpCache::new(temp_dir.path().to_path_buf(), 1200);
+        let cacheable = headers(&[("cache-control", "max-age=600")]);
+        for path in ["/a", "/b"] {
+            cache.store(&url(path), &url(path), &cacheable, &[b'x'; 300]);
+        }
+
+        // Revalidating `/a` makes `/b` the stalest entry
+        let cached = cache.lookup(&url("/a")).unwrap();
+        cache.revalidated(cached, &cacheable);
+        cache.store(&url("/c"), &url("/c"), &cacheable, &[b'x'; 300]);
+
+        assert!(cache.lookup(&url("/a")).is_some());
+        assert!(cache.lookup(&url("/b")).is_none());
+        assert!(cache.lookup(&url("/c")).is_some());
+
+        // Every write was renamed into place
+        let leftovers = fs::read_dir(temp_dir.path()).unwrap()
+            .flatten()
+            .filter(|file| file.path().extension().is_some_and(|extension| extension == "tmp"))
+            .count();
+        assert_eq!(leftovers, 0);
+    }
+}
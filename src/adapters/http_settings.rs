@@ -0,0 +1,238 @@
+use chrono::Utc;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, COOKIE};
+use reqwest::{Certificate, ClientBuilder, Proxy};
+use std::fs;
+use url::Url;
+
+use crate::types::config::expand_path;
+use crate::types::{ExtractorError, MetadataConfig};
+
+/// Network settings shared by every HTTP client: user agent, proxy, extra CA
+/// certificates, per-domain headers and cookies
+///
+/// Files are read once, when the settings are built, so a bad proxy URL, CA
+/// bundle or cookie file is reported before any request is made.
+#[derive(Clone)]
+pub struct HttpSettings {
+    user_agent: String,
+    proxy: Option<Proxy>,
+    certificates: Vec<Certificate>,
+    /// Headers by domain, applied to the domain and its subdomains
+    headers: Vec<(String, HeaderMap)>,
+    cookies: Vec<Cookie>,
+}
+
+/// One line of a Netscape `cookies.txt` file
+#[derive(Debug, Clone, PartialEq)]
+struct Cookie {
+    domain: String,
+    include_subdomains: bool,
+    path: String,
+    secure: bool,
+    /// Unix time after which the cookie is dropped; 0 for session cookies
+    expires: i64,
+    name: String,
+    value: String,
+}
+
+impl HttpSettings {
+    pub fn from_config(config: &MetadataConfig) -> Result<Self, ExtractorError> {
+        let proxy = config.proxy.as_deref()
+            .map(|proxy| Proxy::all(proxy).map_err(|e| invalid(format!("proxy {}: {}", proxy, e))))
+            .transpose()?;
+
+        let certificates = match config.ca_bundle.as_deref() {
+            Some(path) => {
+                let pem = fs::read(expand(path)?).map_err(|e| invalid(format!("CA bundle {}: {}", path, e)))?;
+                Certificate::from_pem_bundle(&pem).map_err(|e| invalid(format!("CA bundle {}: {}", path, e)))?
+            }
+            None => Vec::new(),
+        };
+
+        let mut headers = Vec::new();
+        for (domain, values) in &config.headers {
+            let mut map = HeaderMap::new();
+            for (name, value) in values {
+                let name = HeaderName::from_bytes(name.as_bytes())
+                    .map_err(|_| invalid(format!("header name {:?} for {}", name, domain)))?;
+                let value = HeaderValue::from_str(value)
+                    .map_err(|_| invalid(format!("value of header {} for {}", name, domain)))?;
+                map.insert(name, value);
+            }
+            headers.push((domain.trim_start_matches('.').to_ascii_lowercase(), map));
+        }
+
+        let mut cookies = Vec::new();
+        for path in &config.cookie_files {
+            let contents = fs::read_to_string(expand(path)?)
+                .map_err(|e| invalid(format!("cookie file {}: {}", path, e)))?;
+            cookies.extend(parse_cookies_txt(&contents));
+        }
+
+        Ok(Self { user_agent: config.user_agent.clone(), proxy, certificates, headers, cookies })
+    }
+
+    /// A client builder with the user agent, proxy and certificates applied
+    pub fn client_builder(&self) -> ClientBuilder {
+        let mut builder = reqwest::Client::builder().user_agent(self.user_agent.as_str());
+        if let Some(ref proxy) = self.proxy {
+            builder = builder.proxy(proxy.clone());
+        }
+        for certificate in &self.certificates {
+            builder = builder.add_root_certificate(certificate.clone());
+        }
+        builder
+    }
+
+    /// Whether requests go through a proxy, which then resolves host names
+    pub fn has_proxy(&self) -> bool {
+        self.proxy.is_some()
+    }
+
+    /// Headers and cookies configured for the site serving `url`
+    pub fn headers_for(&self, url: &Url) -> HeaderMap {
+        let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+        let mut headers = HeaderMap::new();
+        for (domain, values) in &self.headers {
+            if host_within(&host, domain) {
+                headers.extend(values.clone());
+            }
+        }
+
+        let now = Utc::now().timestamp();
+        let cookies: Vec<String> = self.cookies.iter()
+            .filter(|cookie| cookie.matches(url, &host, now))
+            .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+            .collect();
+        if let Ok(value) = HeaderValue::from_str(&cookies.join("; ")) {
+            if !cookies.is_empty() {
+                headers.insert(COOKIE, value);
+            }
+        }
+        headers
+    }
+}
+
+impl Cookie {
+    fn matches(&self, url: &Url, host: &str, now: i64) -> bool {
+        let domain_matches = host == self.domain || (self.include_subdomains && host_within(host, &self.domain));
+        let path_matches = url.path().starts_with(&self.path);
+        domain_matches && path_matches && (!self.secure || url.scheme() == "https") && (self.expires == 0 || self.expires > now)
+    }
+}
+
+/// Whether a lowercase host is `domain` or one of its subdomains
+pub(super) fn host_within(host: &str, domain: &str) -> bool {
+    host == domain || host.strip_suffix(domain).is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+/// Parse the tab-separated lines of a Netscape `cookies.txt`, skipping comments and malformed lines
+fn parse_cookies_txt(contents: &str) -> Vec<Cookie> {
+    contents.lines()
+        .filter_map(|line| {
+            // curl marks HttpOnly cookies with a prefix on an otherwise commented-out line
+            let line = line.strip_prefix("#HttpOnly_").unwrap_or(line);
+            if line.starts_with('#') || line.trim().is_empty() {
+                return None;
+            }
+            let fields: Vec<&str> = line.trim_end_matches(['\r', '\n']).split('\t').collect();
+            let [domain, include_subdomains, path, secure, expires, name, value] = fields[..] else {
+                return None;
+            };
+            Some(Cookie {
+                include_subdomains: include_subdomains.eq_ignore_ascii_case("TRUE") || domain.starts_with('.'),
+                domain: domain.trim_start_matches('.').to_ascii_lowercase(),
+                path: path.to_string(),
+                secure: secure.eq_ignore_ascii_case("TRUE"),
+                expires: expires.parse().ok()?,
+                name: name.to_string(),
+                value: value.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn expand(path: &str) -> Result<std::path::PathBuf, ExtractorError> {
+    expand_path(path).map_err(|e| invalid(e.to_string()))
+}
+
+fn invalid(message: String) -> ExtractorError {
+    ExtractorError::InvalidSettings(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use tempfile::TempDir;
+
+    const COOKIES_TXT: &str = "# Netscape HTTP Cookie File\n\
+        wiki.example.com\tFALSE\t/\tTRUE\t0\tsession\tabc123\n\
+        #HttpOnly_.example.com\tTRUE\t/docs\tFALSE\t0\ttheme\tdark\n\
+        example.org\tFALSE\t/\tFALSE\t1\texpired\tgone\n\
+        not enough fields\n";
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).unwrap()
+    }
+
+    #[test]
+    fn test_parse_cookies_txt() {
+        let cookies = parse_cookies_txt(COOKIES_TXT);
+        assert_eq!(cookies.len(), 3);
+        assert_eq!(cookies[1], Cookie {
+            domain: "example.com".to_string(),
+            include_subdomains: true,
+            path: "/docs".to_string(),
+            secure: false,
+            expires: 0,
+            name: "theme".to_string(),
+            value: "dark".to_string(),
+        });
+    }
+
+    #[test]
+    fn test_headers_for_matching_sites() {
+        let temp_dir = TempDir::new().unwrap();
+        let cookie_file = temp_dir.path().join("cookies.txt");
+        fs::write(&cookie_file, COOKIES_TXT).unwrap();
+        let config = MetadataConfig {
+            cookie_files: vec![cookie_file.to_string_lossy().to_string()],
+            headers: BTreeMap::from([
+                ("example.com".to_string(), BTreeMap::from([("X-Team".to_string(), "docs".to_string())])),
+            ]),
+            ..MetadataConfig::default()
+        };
+        let settings = HttpSettings::from_config(&config).unwrap();
+
+        let headers = settings.headers_for(&url("https://wiki.example.com/docs/page"));
+        assert_eq!(headers.get("x-team").unwrap(), "docs");
+        assert_eq!(headers.get(COOKIE).unwrap(), "session=abc123; theme=dark");
+
+        // Secure cookies need HTTPS; path-limited cookies need a matching path
+        let headers = settings.headers_for(&url("http://wiki.example.com/"));
+        assert!(headers.get(COOKIE).is_none());
+
+        let headers = settings.headers_for(&url("https://example.org/"));
+        assert!(headers.is_empty());
+        assert!(settings.headers_for(&url("https://notexample.com/docs")).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_settings_are_reported() {
+        let config = |update: fn(&mut MetadataConfig)| {
+            let mut config = MetadataConfig::default();
+            update(&mut config);
+            HttpSettings::from_config(&config).err()
+        };
+        assert!(config(|_| {}).is_none());
+        assert!(config(|c| c.proxy = Some("http://proxy.example.com:8080".to_string())).is_none());
+        assert!(matches!(config(|c| c.proxy = Some("not a proxy".to_string())), Some(ExtractorError::InvalidSettings(_))));
+        assert!(matches!(config(|c| c.ca_bundle = Some("/nonexistent/ca.pem".to_string())), Some(ExtractorError::InvalidSettings(_))));
+        assert!(matches!(config(|c| c.cookie_files = vec!["/nonexistent/cookies.txt".to_string()]), Some(ExtractorError::InvalidSettings(_))));
+        assert!(matches!(
+            config(|c| { c.headers.insert("example.com".to_string(), BTreeMap::from([("Bad Header".to_string(), "x".to_string())])); }),
+            Some(ExtractorError::InvalidSettings(_))
+        ));
+    }
+}
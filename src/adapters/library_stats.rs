@@ -0,0 +1,243 @@
+use crate::types::{normalize_tag, Bookmark, ReadingStatus};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use url::Url;
+
+/// Reading statistics and a breakdown of the library
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LibraryStats {
+    pub total: usize,
+    pub unread: usize,
+    pub reading: usize,
+    pub completed: usize,
+    /// Share of bookmarks completed, from 0 to 1
+    pub completion_rate: f64,
+    /// Mean days from saving a bookmark to completing it, over bookmarks with a completion time
+    pub average_days_to_complete: Option<f64>,
+    /// Bookmarks per priority from 5 down to 1, then unrated
+    pub by_priority: Vec<PriorityCount>,
+    /// Most used tags, most bookmarks first
+    pub top_tags: Vec<NamedCount>,
+    /// Most bookmarked domains, most bookmarks first
+    pub top_domains: Vec<NamedCount>,
+    /// Bookmarks added and completed in each recent week, oldest first
+    pub weeks: Vec<WeekCount>,
+    /// Unread bookmarks that have waited longest, oldest first
+    pub oldest_unread: Vec<WaitingBookmark>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriorityCount {
+    /// None for bookmarks without a rating
+    pub priority: Option<u8>,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamedCount {
+    pub name: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeekCount {
+    /// Monday the week starts on
+    pub week_start: NaiveDate,
+    pub added: usize,
+    pub completed: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WaitingBookmark {
+    pub id: String,
+    pub title: String,
+    pub bookmarked_date: DateTime<Utc>,
+    pub age_days: i64,
+}
+
+impl LibraryStats {
+    /// Compute statistics over `bookmarks` as of `now`
+    ///
+    /// `top` limits the tag, domain and oldest unread lists; `weeks` is how many
+    /// weeks, including the current one, get added and completed counts.
+    pub fn compute(bookmarks: &[Bookmark], now: DateTime<Utc>, top: usize, weeks: usize) -> Self {
+        let count_status = |status: ReadingStatus| {
+            bookmarks.iter().filter(|bookmark| bookmark.reading_status == status).count()
+        };
+        let completed = count_status(ReadingStatus::Completed);
+
+        let completion_days: Vec<f64> = bookmarks.iter()
+            .filter(|bookmark| bookmark.reading_status == ReadingStatus::Completed)
+            .filter_map(|bookmark| bookmark.completed_at.map(|at| at - bookmark.bookmarked_date))
+            .map(|elapsed| elapsed.num_seconds().max(0) as f64 / Duration::days(1).num_seconds() as f64)
+            .collect();
+        let average_days_to_complete = if completion_days.is_empty() {
+            None
+        } else {
+            Some(completion_days.iter().sum::<f64>() / completion_days.len() as f64)
+        };
+
+        let by_priority = (1..=5).rev().map(Some).chain([None])
+            .map(|priority| PriorityCount {
+                priority,
+                count: bookmarks.iter().filter(|bookmark| bookmark.priority_rating == priority).count(),
+            })
+            .collect();
+
+        let mut tags: HashMap<String, usize> = HashMap::new();
+        let mut domains: HashMap<String, usize> = HashMap::new();
+        for bookmark in bookmarks {
+            let mut seen = Vec::new();
+            for tag in bookmark.tags.iter().map(|tag| normalize_tag(tag)) {
+                if !tag.is_empty() && !seen.contains(&tag) {
+                    *tags.entry(tag.clone()).or_default() += 1;
+                    seen.push(tag);
+                }
+            }
+            if let Some(domain) = domain(&bookmark.url) {
+                *domains.entry(domain).or_default() += 1;
+            }
+        }
+
+        let mut unread: Vec<&Bookmark> = bookmarks.iter()
+            .filter(|bookmark| bookmark.reading_status == ReadingStatus::Unread)
+            .collect();
+        unread.sort_by_key(|bookmark| bookmark.bookmarked_date);
+        let oldest_unread = unread.into_iter().take(top)
+            .map(|bookmark| WaitingBookmark {
+                id: bookmark.id.clone(),
+                title: bookmark.title.clone(),
+                bookmarked_date: bookmark.bookmarked_date,
+                age_days: (now - bookmark.bookmarked_date).num_days(),
+            })
+            .collect();
+
+        LibraryStats {
+            total: bookmarks.len(),
+            unread: count_status(ReadingStatus::Unread),
+            reading: count_status(ReadingStatus::Reading),
+            completed,
+            completion_rate: if bookmarks.is_empty() { 0.0 } else { completed as f64 / bookmarks.len() as f64 },
+            average_days_to_complete,
+            by_priority,
+            top_tags: ranked(tags, top),
+            top_domains: ranked(domains, top),
+            weeks: week_counts(bookmarks, now, weeks),
+            oldest_unread,
+        }
+    }
+}
+
+/// Host of a URL without a leading `www.`
+fn domain(url: &str) -> Option<String> {
+    Url::parse(url).ok()
+        .and_then(|url| url.host_str().map(|host| host.trim_start_matches("www.").to_lowercase()))
+}
+
+/// The `limit` largest counts, ties broken by name
+fn ranked(counts: HashMap<String, usize>, limit: usize) -> Vec<NamedCount> {
+    let mut ranked: Vec<NamedCount> = counts.into_iter()
+        .map(|(name, count)| NamedCount { name, count })
+        .collect();
+    ranked.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    ranked.truncate(limit);
+    ranked
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+fn week_counts(bookmarks: &[Bookmark], now: DateTime<Utc>, weeks: usize) -> Vec<WeekCount> {
+    let current = week_start(now.date_naive());
+    let mut counts: Vec<WeekCount> = (0..weeks).rev()
+        .map(|weeks_ago| WeekCount {
+            week_start: current - Duration::weeks(weeks_ago as i64),
+            added: 0,
+            completed: 0,
+        })
+        .collect();
+
+    let mut count = |date: DateTime<Utc>, completed: bool| {
+        let start = week_start(date.date_naive());
+        if let Some(week) = counts.iter_mut().find(|week| week.week_start == start) {
+            if completed {
+                week.completed += 1;
+            } else {
+                week.added += 1;
+            }
+        }
+    };
+    for bookmark in bookmarks {
+        count(bookmark.bookmarked_date, false);
+        if let Some(completed_at) = bookmark.completed_at.filter(|_| bookmark.reading_status == ReadingStatus::Completed) {
+            count(completed_at, true);
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn bookmark(url: &str, tags: &[&str], days_ago: i64, now: DateTime<Utc>) -> Bookmark {
+        let mut bookmark = Bookmark::new(url, url)
+            .unwrap()
+            .with_tags(tags.iter().map(|tag| tag.to_string()).collect());
+        bookmark.bookmarked_date = now - Duration::days(days_ago);
+        bookmark
+    }
+
+    #[test]
+    fn test_compute_breakdowns() {
+        // A Wednesday
+        let now = Utc.with_ymd_and_hms(2024, 5, 15, 12, 0, 0).unwrap();
+        let mut read = bookmark("https://www.rust-lang.org/learn", &["rust"], 10, now);
+        read.priority_rating = Some(5);
+        read.reading_status = ReadingStatus::Completed;
+        read.completed_at = Some(now - Duration::days(6));
+        let mut reading = bookmark("https://rust-lang.org/tools", &["rust", "tools"], 1, now);
+        reading.reading_status = ReadingStatus::Reading;
+        let bookmarks = vec![
+            read,
+            reading,
+            bookmark("https://example.com/old", &[], 40, now),
+            bookmark("https://example.com/new", &["Rust"], 0, now),
+        ];
+
+        let stats = LibraryStats::compute(&bookmarks, now, 2, 3);
+        assert_eq!((stats.total, stats.unread, stats.reading, stats.completed), (4, 2, 1, 1));
+        assert_eq!(stats.completion_rate, 0.25);
+        assert_eq!(stats.average_days_to_complete, Some(4.0));
+        assert_eq!(stats.by_priority[0], PriorityCount { priority: Some(5), count: 1 });
+        assert_eq!(stats.by_priority[5], PriorityCount { priority: None, count: 3 });
+        assert_eq!(stats.top_tags, vec![
+            NamedCount { name: "rust".to_string(), count: 3 },
+            NamedCount { name: "tools".to_string(), count: 1 },
+        ]);
+        assert_eq!(stats.top_domains[0], NamedCount { name: "example.com".to_string(), count: 2 });
+        assert_eq!(stats.oldest_unread[0].title, "https://example.com/old");
+        assert_eq!(stats.oldest_unread[0].age_days, 40);
+
+        let weeks: Vec<(String, usize, usize)> = stats.weeks.iter()
+            .map(|week| (week.week_start.to_string(), week.added, week.completed))
+            .collect();
+        assert_eq!(weeks, vec![
+            ("2024-04-29".to_string(), 1, 0),
+            ("2024-05-06".to_string(), 0, 1),
+            ("2024-05-13".to_string(), 2, 0),
+        ]);
+    }
+
+    #[test]
+    fn test_compute_empty_library() {
+        let stats = LibraryStats::compute(&[], Utc::now(), 5, 4);
+        assert_eq!(stats.total, 0);
+        assert_eq!(stats.completion_rate, 0.0);
+        assert_eq!(stats.average_days_to_complete, None);
+        assert_eq!(stats.weeks.len(), 4);
+    }
+}
@@ -0,0 +1,206 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use reqwest::header::LOCATION;
+use reqwest::redirect::Policy;
+use reqwest::{Client, Method, Response};
+use std::time::Duration;
+use url::Url;
+
+use super::http_settings::HttpSettings;
+use crate::traits::LinkChecker;
+use crate::types::{ExtractorError, LinkStatus, MetadataConfig};
+
+/// Redirect chains longer than this are reported as broken
+const MAX_REDIRECTS: usize = 10;
+
+/// Checks links over HTTP, following redirects itself so it can tell where they went
+pub struct HttpLinkChecker {
+    client: Client,
+    settings: HttpSettings,
+}
+
+impl HttpLinkChecker {
+    /// A checker using the network settings in `config`, which fails if they are invalid
+    pub fn new(config: &MetadataConfig) -> Result<Self, ExtractorError> {
+        let settings = HttpSettings::from_config(config)?;
+        let client = settings.client_builder()
+            .redirect(Policy::none())
+            .build()
+            .map_err(|e| ExtractorError::InvalidSettings(e.to_string()))?;
+        Ok(Self { client, settings })
+    }
+
+    /// Send a HEAD request, falling back to GET for servers that reject or mishandle HEAD
+    async fn probe(&self, url: &Url, timeout: Duration) -> Result<Response, reqwest::Error> {
+        let response = self.request(Method::HEAD, url, timeout).await?;
+        if response.status().is_client_error() || response.status().is_server_error() {
+            return self.request(Method::GET, url, timeout).await;
+        }
+        Ok(response)
+    }
+
+    async fn request(&self, method: Method, url: &Url, timeout: Duration) -> Result<Response, reqwest::Error> {
+        self.client.request(method, url.clone())
+            .headers(self.settings.headers_for(url))
+            .timeout(timeout)
+            .send()
+            .await
+    }
+}
+
+#[async_trait]
+impl LinkChecker for HttpLinkChecker {
+    async fn check_link(&self, url: &str, timeout: Duration) -> LinkStatus {
+        let mut status = LinkStatus {
+            checked_at: Utc::now(),
+            status_code: None,
+            redirected_to: None,
+            permanent_redirect: false,
+            error: None,
+        };
+        let mut current = match Url::parse(url) {
+            Ok(url) => url,
+            Err(e) => {
+                status.error = Some(format!("invalid URL: {}", e));
+                return status;
+            }
+        };
+        let mut all_permanent = true;
+
+        for _ in 0..=MAX_REDIRECTS {
+            let response = match self.probe(&current, timeout).await {
+                Ok(response) => response,
+                Err(e) => {
+                    status.error = Some(describe_error(&e));
+                    return status;
+                }
+            };
+
+            let next = response.headers()
+                .get(LOCATION)
+                .and_then(|location| location.to_str().ok())
+                .and_then(|location| current.join(location).ok())
+                .filter(|_| response.status().is_redirection());
+            match next {
+                Some(next) => {
+                    all_permanent &= matches!(response.status().as_u16(), 301 | 308);
+                    status.redirected_to = Some(next.to_string());
+                    status.permanent_redirect = all_permanent;
+                    current = next;
+                }
+                None => {
+                    status.status_code = Some(response.status().as_u16());
+                    return status;
+                }
+            }
+        }
+
+        status.error = Some(format!("more than {} redirects", MAX_REDIRECTS));
+        status
+    }
+}
+
+/// The innermost cause of a request failure, e.g. the DNS lookup error
+fn describe_error(error: &reqwest::Error) -> String {
+    if error.is_timeout() {
+        return "timed out".to_string();
+    }
+    let mut source: &dyn std::error::Error = error;
+    while let Some(inner) = source.source() {
+        source = inner;
+    }
+    source.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve canned responses by path: /ok, /gone, /moved, /temp, /loop and /no-head
+    async fn start_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = match listener.accept().await {
+                    Ok(connection) => connection,
+                    Err(_) => return,
+                };
+                tokio::spawn(async move {
+                    let mut buffer = vec![0; 4096];
+                    let read = socket.read(&mut buffer).await.unwrap_or(0);
+                    let request = String::from_utf8_lossy(&buffer[..read]).to_string();
+                    let mut parts = request.split_whitespace();
+                    let method = parts.next().unwrap_or("");
+                    let path = parts.next().unwrap_or("");
+                    let (status, location) = match (method, path) {
+                        (_, "/ok") => ("200 OK", None),
+                        (_, "/moved") => ("301 Moved Permanently", Some("/ok")),
+                        (_, "/temp") => ("302 Found", Some("/ok")),
+                        (_, "/moved-then-temp") => ("308 Permanent Redirect", Some("/temp")),
+                        (_, "/loop") => ("301 Moved Permanently", Some("/loop")),
+                        ("HEAD", "/no-head") => ("405 Method Not Allowed", None),
+                        ("GET", "/no-head") => ("200 OK", None),
+                        _ => ("404 Not Found", None),
+                    };
+                    let location = location.map(|location| format!("Location: {}\r\n", location)).unwrap_or_default();
+                    let response = format!("HTTP/1.1 {}\r\n{}Content-Length: 0\r\nConnection: close\r\n\r\n", status, location);
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        address
+    }
+
+    #[tokio::test]
+    async fn test_check_link_statuses_and_redirects() {
+        let server = start_server().await;
+        let checker = HttpLinkChecker::new(&MetadataConfig { user_agent: "automark-test".to_string(), ..MetadataConfig::default() }).unwrap();
+        let timeout = Duration::from_secs(5);
+
+        let ok = checker.check_link(&format!("{}/ok", server), timeout).await;
+        assert_eq!(ok.status_code, Some(200));
+        assert!(!ok.is_dead());
+        assert_eq!(ok.redirected_to, None);
+
+        let gone = checker.check_link(&format!("{}/gone", server), timeout).await;
+        assert_eq!(gone.status_code, Some(404));
+        assert!(gone.is_dead());
+
+        let moved = checker.check_link(&format!("{}/moved", server), timeout).await;
+        assert_eq!(moved.status_code, Some(200));
+        assert_eq!(moved.redirected_to, Some(format!("{}/ok", server)));
+        assert!(moved.moved_permanently());
+
+        let temp = checker.check_link(&format!("{}/temp", server), timeout).await;
+        assert_eq!(temp.redirected_to, Some(format!("{}/ok", server)));
+        assert!(!temp.permanent_redirect);
+
+        let mixed = checker.check_link(&format!("{}/moved-then-temp", server), timeout).await;
+        assert!(!mixed.permanent_redirect);
+
+        let no_head = checker.check_link(&format!("{}/no-head", server), timeout).await;
+        assert_eq!(no_head.status_code, Some(200));
+
+        let looping = checker.check_link(&format!("{}/loop", server), timeout).await;
+        assert!(looping.is_dead());
+        assert!(looping.error.unwrap().contains("redirects"));
+    }
+
+    #[tokio::test]
+    async fn test_check_link_connection_failure() {
+        // Bind and drop a listener to find a port nothing is listening on
+        let port = TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap().port();
+        let checker = HttpLinkChecker::new(&MetadataConfig { user_agent: "automark-test".to_string(), ..MetadataConfig::default() }).unwrap();
+
+        let status = checker.check_link(&format!("http://127.0.0.1:{}/", port), Duration::from_secs(5)).await;
+        assert!(status.is_dead());
+        assert_eq!(status.status_code, None);
+        assert!(status.error.is_some());
+
+        let invalid = checker.check_link("not a url", Duration::from_secs(5)).await;
+        assert!(invalid.error.unwrap().starts_with("invalid URL"));
+    }
+}
@@ -1,7 +1,30 @@
 pub mod automerge_repo;
+pub mod browser;
+pub mod content_store;
+pub mod document_metadata;
+pub mod http_cache;
+pub mod http_settings;
+pub mod library_stats;
+pub mod link_checker;
+pub mod page_fetcher;
+pub mod rate_limiter;
+pub mod robots;
+pub mod web_archiver;
 pub mod web_extractor;
 pub mod file_storage;
+pub mod search_index;
+pub mod similarity;
+pub mod site_extractors;
+pub mod tag_suggester;
+pub mod text_analysis;
 
+pub use http_cache::HttpCache;
+pub use link_checker::HttpLinkChecker;
+pub use web_archiver::WebArchiver;
 pub use web_extractor::WebExtractor;
+pub use site_extractors::ExtractorRegistry;
+pub use similarity::{LibraryText, SimilarityIndex};
+pub use library_stats::LibraryStats;
+pub use tag_suggester::TagSuggester;
 pub use automerge_repo::AutomergeBookmarkRepository;
 pub use file_storage::FileStorageManager;
\ No newline at end of file
@@ -0,0 +1,881 @@
+use encoding_rs::{Encoding, UTF_8};
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use reqwest::header::{HeaderMap, CONTENT_TYPE, LOCATION};
+use reqwest::redirect::Policy;
+use reqwest::{Client, Response, StatusCode};
+use std::error::Error;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use url::{Host, Url};
+
+use super::http_cache::{CachedResponse, HttpCache};
+use super::http_settings::{host_within, HttpSettings};
+use super::rate_limiter::HostRateLimiter;
+use super::robots::RobotsCache;
+use crate::types::{ExtractorError, MetadataConfig};
+
+/// How much of a page to scan for a `<meta charset>` declaration
+const CHARSET_SCAN_BYTES: usize = 1024;
+
+/// Downloads pages for metadata extraction politely and defensively
+///
+/// Requests carry the configured user agent, skip paths robots.txt disallows
+/// (unless `respect_robots_txt` is off), and share one per-host rate limiter, so
+/// every extractor holding the same fetcher counts against the same budget.
+///
+/// Redirects are followed by hand so every hop is rate limited and carries only
+/// its own site's headers. With `block_private_addresses`, host names are
+/// resolved by [`PublicResolver`], so the addresses checked against loopback,
+/// private and link-local ranges are the ones connected to, and address literals
+/// are checked before sending. Through a proxy, which resolves names itself,
+/// only literals are checked. Hosts in `allow_private_hosts` are exempt. Only
+/// HTML, plain text, PDFs and images are accepted; pages and text are streamed
+/// up to `max_page_bytes` and PDFs and images up to `max_document_bytes`, and
+/// text is decoded using its declared charset.
+///
+/// With an [`HttpCache`], fresh responses are served from disk without a
+/// request and stale ones are revalidated with a conditional request.
+pub struct PageFetcher {
+    client: Client,
+    settings: HttpSettings,
+    cache: Option<Arc<HttpCache>>,
+    robots: Option<RobotsCache>,
+    limiter: HostRateLimiter,
+    max_page_bytes: u64,
+    max_document_bytes: u64,
+    max_redirects: usize,
+    block_private_addresses: bool,
+    allow_private_hosts: Arc<Vec<String>>,
+}
+
+impl PageFetcher {
+    /// A fetcher using the network settings in `config`, which fails if they are invalid
+    pub fn new(config: &MetadataConfig) -> Result<Self, ExtractorError> {
+        let settings = HttpSettings::from_config(config)?;
+        let allow_private_hosts: Arc<Vec<String>> = Arc::new(config.allow_private_hosts.iter()
+            .map(|host| host.trim_start_matches('.').to_ascii_lowercase())
+            .collect());
+        let mut builder = settings.client_builder().redirect(Policy::none());
+        // A proxy's own address may well be private, and it is the only name the client resolves
+        if config.block_private_addresses && !settings.has_proxy() {
+            builder = builder.dns_resolver(Arc::new(PublicResolver { allowed_hosts: allow_private_hosts.clone() }));
+        }
+        let client = builder
+            .build()
+            .map_err(|e| ExtractorError::InvalidSettings(e.to_string()))?;
+        Ok(Self {
+            client,
+            settings,
+            cache: None,
+            robots: config.respect_robots_txt.then(|| RobotsCache::new(&config.user_agent)),
+            limiter: HostRateLimiter::new(config.per_host_requests_per_sec, config.per_host_burst),
+            max_page_bytes: config.max_page_bytes,
+            max_document_bytes: config.max_document_bytes,
+            max_redirects: config.max_redirects,
+            block_private_addresses: config.block_private_addresses,
+            allow_private_hosts,
+        })
+    }
+
+    /// Reuse responses stored in `cache`, and store new ones there
+    pub fn with_cache(mut self, cache: Option<Arc<HttpCache>>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Download a page, returning its HTML and the URL it was served from after redirects
+    pub async fn fetch(&self, url: &str, timeout: Duration) -> Result<(String, Url), ExtractorError> {
+        match self.fetch_resource(url, timeout).await? {
+            (Resource::Html(html), page_url) => Ok((html, page_url)),
+            (resource, _) => Err(ExtractorError::UnsupportedContentType(resource.media_type().to_string())),
+        }
+    }
+
+    /// Download whatever a URL serves, returning it with the URL it was served from after redirects
+    pub async fn fetch_resource(&self, url: &str, timeout: Duration) -> Result<(Resource, Url), ExtractorError> {
+        // Validate URL
+        let parsed_url = Url::parse(url)
+            .map_err(|_| ExtractorError::InvalidUrl(url.to_string()))?;
+
+        // Refuse unsupported types before downloading them; undeclared bodies are sniffed
+        // and may turn out to be documents, so they get the larger limit until then
+        let download = self.download(&parsed_url, timeout, |content_type| {
+            Ok(match declared_kind(content_type)? {
+                Some(ResourceKind::Html | ResourceKind::Text) => self.max_page_bytes,
+                Some(ResourceKind::Pdf | ResourceKind::Image) | None => self.max_document_bytes,
+            })
+        }).await?;
+
+        let kind = declared_kind(download.content_type.as_deref())?.or_else(|| ResourceKind::sniff(&download.body));
+        if matches!(kind, Some(ResourceKind::Html | ResourceKind::Text)) && download.body.len() as u64 > self.max_page_bytes {
+            return Err(ExtractorError::TooLarge(self.max_page_bytes));
+        }
+        Ok((resource_from(download.body, download.content_type.as_deref())?, download.url))
+    }
+
+    /// Download a URL's body whatever its type, from the cache when possible
+    ///
+    /// `limit_for` gets the Content-Type header and returns the largest body to
+    /// accept, or an error to refuse the response before reading it.
+    pub(super) async fn download(
+        &self,
+        url: &Url,
+        timeout: Duration,
+        limit_for: impl FnOnce(Option<&str>) -> Result<u64, ExtractorError>,
+    ) -> Result<Download, ExtractorError> {
+        if let Some(ref robots) = self.robots {
+            let rules = robots.rules_for(url, self, timeout).await;
+            if !rules.is_allowed(url) {
+                return Err(ExtractorError::BlockedByRobots(url.to_string()));
+            }
+        }
+
+        let cached = self.cache.as_ref().and_then(|cache| cache.lookup(url));
+        if let Some(cached) = cached.as_ref().filter(|cached| cached.is_fresh()) {
+            return Ok(Download::from(cached.clone()));
+        }
+
+        let validators = cached.as_ref().map(|cached| cached.validators()).unwrap_or_default();
+        let response = self.send_with_headers(url.clone(), timeout, &validators).await?;
+        if let (Some(cache), Some(cached)) = (&self.cache, cached) {
+            if response.status() == StatusCode::NOT_MODIFIED {
+                return Ok(Download::from(cache.revalidated(cached, response.headers())));
+            }
+        }
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ExtractorError::HttpStatus(status.as_u16()));
+        }
+
+        // Relative links in the page resolve against where redirects ended up
+        let final_url = response.url().clone();
+        let headers = response.headers().clone();
+        let content_type = headers.get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+
+        let limit = limit_for(content_type.as_deref())?;
+        let body = self.read_body_up_to(response, limit).await?;
+        if let Some(ref cache) = self.cache {
+            if status == StatusCode::OK {
+                cache.store(url, &final_url, &headers, &body);
+            }
+        }
+        Ok(Download { url: final_url, content_type, body })
+    }
+
+    /// Send a GET request, following redirects up to the configured limit
+    pub(super) async fn send(&self, url: Url, timeout: Duration) -> Result<Response, ExtractorError> {
+        self.send_with_headers(url, timeout, &HeaderMap::new()).await
+    }
+
+    /// Send a GET request with extra headers on every hop, following redirects up to the configured limit
+    ///
+    /// Each hop also carries the headers and cookies configured for its site.
+    async fn send_with_headers(&self, url: Url, timeout: Duration, headers: &HeaderMap) -> Result<Response, ExtractorError> {
+        let mut url = url;
+        for _ in 0..=self.max_redirects {
+            if !matches!(url.scheme(), "http" | "https") {
+                return Err(ExtractorError::InvalidUrl(url.to_string()));
+            }
+            if self.block_private_addresses {
+                ensure_public_host(&url, &self.allow_private_hosts)?;
+            }
+            self.limiter.wait(url.host_str().unwrap_or_default()).await;
+
+            // Make HTTP request
+            let response = self
+                .client
+                .get(url.clone())
+                .headers(self.settings.headers_for(&url))
+                .headers(headers.clone())
+                .timeout(timeout)
+                .send()
+                .await
+                .map_err(|e| {
+                    if e.is_timeout() {
+                        ExtractorError::Timeout
+                    } else if let Some(host) = refused_host(&e) {
+                        ExtractorError::PrivateAddress(host)
+                    } else {
+                        ExtractorError::NetworkError(e.to_string())
+                    }
+                })?;
+
+            let location = response.headers().get(LOCATION).and_then(|value| value.to_str().ok());
+            match location {
+                Some(location) if response.status().is_redirection() => {
+                    url = url.join(location)
+                        .map_err(|_| ExtractorError::InvalidUrl(location.to_string()))?;
+                }
+                _ => return Ok(response),
+            }
+        }
+        Err(ExtractorError::TooManyRedirects(self.max_redirects))
+    }
+
+    /// Read a response body, giving up once it passes `max_page_bytes`
+    pub(super) async fn read_body(&self, response: Response) -> Result<Vec<u8>, ExtractorError> {
+        self.read_body_up_to(response, self.max_page_bytes).await
+    }
+
+    /// Read a response body, giving up once it passes `limit` bytes
+    pub(super) async fn read_body_up_to(&self, mut response: Response, limit: u64) -> Result<Vec<u8>, ExtractorError> {
+        if response.content_length().is_some_and(|length| length > limit) {
+            return Err(ExtractorError::TooLarge(limit));
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(|e| {
+            if e.is_timeout() {
+                ExtractorError::Timeout
+            } else {
+                ExtractorError::NetworkError(e.to_string())
+            }
+        })? {
+            if (body.len() + chunk.len()) as u64 > limit {
+                return Err(ExtractorError::TooLarge(limit));
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body)
+    }
+}
+
+/// A downloaded body, before it is interpreted
+pub(super) struct Download {
+    /// Where the body was served from after redirects
+    pub url: Url,
+    /// The Content-Type header, including any charset
+    pub content_type: Option<String>,
+    pub body: Vec<u8>,
+}
+
+impl From<CachedResponse> for Download {
+    fn from(cached: CachedResponse) -> Self {
+        Self { url: cached.url, content_type: cached.content_type, body: cached.body }
+    }
+}
+
+/// What a URL served, as far as metadata extraction is concerned
+pub enum Resource {
+    Html(String),
+    Pdf(Vec<u8>),
+    Image { media_type: String, bytes: Vec<u8> },
+    Text(String),
+}
+
+impl Resource {
+    pub fn media_type(&self) -> &str {
+        match self {
+            Self::Html(_) => "text/html",
+            Self::Pdf(_) => "application/pdf",
+            Self::Image { media_type, .. } => media_type,
+            Self::Text(_) => "text/plain",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ResourceKind {
+    Html,
+    Pdf,
+    Image,
+    Text,
+}
+
+impl ResourceKind {
+    /// The kind of a declared media type, or `None` if it can't be handled
+    fn for_media_type(media_type: &str) -> Option<Self> {
+        match media_type {
+            "text/html" | "application/xhtml+xml" => Some(Self::Html),
+            "application/pdf" | "application/x-pdf" => Some(Self::Pdf),
+            "text/plain" | "text/markdown" | "text/x-markdown" => Some(Self::Text),
+            _ if media_type.starts_with("image/") => Some(Self::Image),
+            _ => None,
+        }
+    }
+
+    /// Guess the kind of an undeclared body from its first bytes
+    fn sniff(body: &[u8]) -> Option<Self> {
+        let start = &body[..body.len().min(CHARSET_SCAN_BYTES)];
+        if start.starts_with(b"%PDF-") {
+            Some(Self::Pdf)
+        } else if looks_like_html(body) {
+            Some(Self::Html)
+        } else if looks_like_text(start) {
+            Some(Self::Text)
+        } else if imagesize::image_type(body).is_ok() {
+            Some(Self::Image)
+        } else {
+            None
+        }
+    }
+}
+
+/// The kind a Content-Type header declares, `None` if it declares nothing useful, or an error if it can't be handled
+fn declared_kind(content_type: Option<&str>) -> Result<Option<ResourceKind>, ExtractorError> {
+    match content_type.map(media_type).filter(|media_type| media_type != "application/octet-stream") {
+        Some(media_type) => ResourceKind::for_media_type(&media_type)
+            .map(Some)
+            .ok_or(ExtractorError::UnsupportedContentType(media_type)),
+        None => Ok(None),
+    }
+}
+
+/// Interpret a downloaded body by its declared type, sniffing it if the server didn't say
+fn resource_from(body: Vec<u8>, content_type: Option<&str>) -> Result<Resource, ExtractorError> {
+    let kind = declared_kind(content_type)?.or_else(|| ResourceKind::sniff(&body))
+        .ok_or_else(|| ExtractorError::UnsupportedContentType("unknown".to_string()))?;
+
+    Ok(match kind {
+        ResourceKind::Html => Resource::Html(decode_text(&body, content_type)),
+        ResourceKind::Text => Resource::Text(decode_text(&body, content_type)),
+        ResourceKind::Pdf => Resource::Pdf(body),
+        ResourceKind::Image => {
+            let media_type = content_type.map(media_type)
+                .filter(|media_type| media_type.starts_with("image/"))
+                .or_else(|| imagesize::image_type(&body).ok()
+                    .map(|image_type| format!("image/{:?}", image_type).to_ascii_lowercase()))
+                .unwrap_or_else(|| "image".to_string());
+            Resource::Image { media_type, bytes: body }
+        }
+    })
+}
+
+/// The lowercased `type/subtype` of a Content-Type header, without parameters
+fn media_type(content_type: &str) -> String {
+    content_type.split(';').next().unwrap_or("").trim().to_ascii_lowercase()
+}
+
+/// Whether an undeclared body is markup rather than binary data
+fn looks_like_html(body: &[u8]) -> bool {
+    let start = &body[..body.len().min(CHARSET_SCAN_BYTES)];
+    let start = start.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(start);
+    !start.contains(&0) && start.iter().find(|byte| !byte.is_ascii_whitespace()) == Some(&b'<')
+}
+
+/// Whether the start of a body is text: no NUL bytes, and valid UTF-8 apart from a character cut off at the end
+fn looks_like_text(start: &[u8]) -> bool {
+    !start.contains(&0) && std::str::from_utf8(start).map_or_else(|e| e.error_len().is_none(), |_| true)
+}
+
+/// Decode a page using its byte order mark, the Content-Type charset, or a `<meta>` declaration, defaulting to UTF-8
+pub(super) fn decode_text(body: &[u8], content_type: Option<&str>) -> String {
+    let declared = content_type
+        .and_then(charset_parameter)
+        .or_else(|| charset_parameter(&String::from_utf8_lossy(&body[..body.len().min(CHARSET_SCAN_BYTES)])))
+        .and_then(|label| Encoding::for_label(label.as_bytes()));
+    // decode() prefers a byte order mark over the declared encoding
+    let (text, _, _) = declared.unwrap_or(UTF_8).decode(body);
+    text.into_owned()
+}
+
+/// The value after the first `charset=`, as in `text/html; charset=utf-8` or `<meta charset="utf-8">`
+fn charset_parameter(text: &str) -> Option<String> {
+    let lower = text.to_ascii_lowercase();
+    let start = lower.find("charset=")? + "charset=".len();
+    let value = lower[start..].trim_start_matches(['"', '\'', ' ']);
+    let end = value.find(|c: char| c == '"' || c == '\'' || c == ';' || c == '>' || c == '/' || c.is_whitespace())
+        .unwrap_or(value.len());
+    Some(value[..end].to_string()).filter(|label| !label.is_empty())
+}
+
+/// Refuse URLs whose host is an address on the local machine or network, unless it is allowed
+///
+/// Host names are left to [`PublicResolver`], which checks the addresses the
+/// request actually connects to.
+fn ensure_public_host(url: &Url, allowed_hosts: &[String]) -> Result<(), ExtractorError> {
+    let ip = match url.host() {
+        Some(Host::Ipv4(ip)) => IpAddr::V4(ip),
+        Some(Host::Ipv6(ip)) => IpAddr::V6(ip),
+        Some(Host::Domain(_)) => return Ok(()),
+        None => return Err(ExtractorError::InvalidUrl(url.to_string())),
+    };
+
+    let host = url.host_str().unwrap_or_default();
+    if is_private_address(ip) && !is_allowed_host(host, allowed_hosts) {
+        return Err(ExtractorError::PrivateAddress(host.to_string()));
+    }
+    Ok(())
+}
+
+fn is_allowed_host(host: &str, allowed_hosts: &[String]) -> bool {
+    let host = host.trim_start_matches('[').trim_end_matches(']').to_ascii_lowercase();
+    allowed_hosts.iter().any(|allowed| host_within(&host, allowed.trim_start_matches('[').trim_end_matches(']')))
+}
+
+/// Resolves host names for the HTTP client, failing for names with any private address
+///
+/// Checking at connection time rather than in a lookup of its own means a host
+/// can't pass the check and then resolve somewhere else when the request is sent.
+struct PublicResolver {
+    allowed_hosts: Arc<Vec<String>>,
+}
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let allowed = is_allowed_host(name.as_str(), &self.allowed_hosts);
+        Box::pin(async move {
+            // The connector fills in the port
+            let addresses: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0)).await?.collect();
+            if !allowed && addresses.iter().any(|address| is_private_address(address.ip())) {
+                return Err(ExtractorError::PrivateAddress(name.as_str().to_string()).into());
+            }
+            Ok(Box::new(addresses.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// The host [`PublicResolver`] refused, if that is why a request failed
+fn refused_host(error: &reqwest::Error) -> Option<String> {
+    let mut source = error.source();
+    while let Some(error) = source {
+        if let Some(ExtractorError::PrivateAddress(host)) = error.downcast_ref::<ExtractorError>() {
+            return Some(host.clone());
+        }
+        source = error.source();
+    }
+    None
+}
+
+/// Loopback, private, link-local (including cloud metadata endpoints) and other non-public addresses
+fn is_private_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_private_ipv4(ip),
+        IpAddr::V6(ip) => {
+            let segments = ip.segments();
+            let first = segments[0];
+            ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                || (first & 0xfe00) == 0xfc00 // unique local
+                || (first & 0xffc0) == 0xfe80 // link-local
+                || (first & 0xffc0) == 0xfec0 // deprecated site-local
+                // IPv4-mapped ::ffff:a.b.c.d and IPv4-compatible ::a.b.c.d
+                || ip.to_ipv4().is_some_and(is_private_ipv4)
+                // NAT64 64:ff9b::a.b.c.d
+                || (segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] && is_private_ipv4(embedded_ipv4(segments[6], segments[7])))
+                // 6to4 2002:aabb:ccdd::
+                || (first == 0x2002 && is_private_ipv4(embedded_ipv4(segments[1], segments[2])))
+        }
+    }
+}
+
+/// The IPv4 address carried in two 16-bit segments of an IPv6 address
+fn embedded_ipv4(high: u16, low: u16) -> Ipv4Addr {
+    Ipv4Addr::from(((high as u32) << 16) | low as u32)
+}
+
+fn is_private_ipv4(ip: Ipv4Addr) -> bool {
+    let [first, second, ..] = ip.octets();
+    ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || first == 0
+        || (first == 100 && (64..128).contains(&second)) // carrier-grade NAT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve a robots.txt disallowing /private, a few special pages, and a page everywhere else; counts robots.txt requests
+    async fn start_server() -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let port = listener.local_addr().unwrap().port();
+        let robots_requests = Arc::new(AtomicUsize::new(0));
+        let counter = robots_requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = match listener.accept().await {
+                    Ok(connection) => connection,
+                    Err(_) => return,
+                };
+                let counter = counter.clone();
+                tokio::spawn(async move {
+                    let mut buffer = vec![0; 4096];
+                    let read = socket.read(&mut buffer).await.unwrap_or(0);
+                    let request = String::from_utf8_lossy(&buffer[..read]).to_string();
+                    let path = request.split_whitespace().nth(1).unwrap_or("").to_string();
+                    let elsewhere = format!("Location: http://localhost:{}/echo\r\n", port);
+                    let (status, headers, body): (&str, &str, Vec<u8>) = match path.as_str() {
+                        "/robots.txt" => {
+                            counter.fetch_add(1, Ordering::SeqCst);
+                            ("200 OK", "", b"User-agent: *\nDisallow: /private\n".to_vec())
+                        }
+                        "/large" => ("200 OK", "Content-Type: text/html\r\n", vec![b'a'; 4096]),
+                        "/large-undeclared" => ("200 OK", "", vec![b'a'; 2048]),
+                        "/large-image" => ("200 OK", "Content-Type: image/png\r\n", [&[0x89, b'P', b'N', b'G'][..], &[0; 2044]].concat()),
+                        "/huge-image" => ("200 OK", "Content-Type: image/png\r\n", vec![0; 8192]),
+                        "/image" => ("200 OK", "Content-Type: image/png\r\n", vec![0x89, b'P', b'N', b'G', 0]),
+                        "/paper" => ("200 OK", "Content-Type: application/octet-stream\r\n", include_bytes!("fixtures/paper.pdf").to_vec()),
+                        "/notes.txt" => ("200 OK", "Content-Type: text/plain; charset=utf-8\r\n", "Notes \u{2014} draft".as_bytes().to_vec()),
+                        "/echo" => ("200 OK", "Content-Type: text/plain\r\n", request.clone().into_bytes()),
+                        "/archive.zip" => ("200 OK", "Content-Type: application/zip\r\n", b"PK\x03\x04".to_vec()),
+                        "/loop" => ("302 Found", "Location: /loop\r\n", Vec::new()),
+                        "/missing" => ("404 Not Found", "Content-Type: text/html\r\n", b"<title>Not Found</title>".to_vec()),
+                        "/busy" => ("503 Service Unavailable", "Content-Type: text/html\r\n", b"<title>Try later</title>".to_vec()),
+                        "/moved" => ("301 Moved Permanently", "Location: /public\r\n", Vec::new()),
+                        "/elsewhere" => ("302 Found", elsewhere.as_str(), Vec::new()),
+                        "/latin1" => ("200 OK", "Content-Type: text/html; charset=ISO-8859-1\r\n", b"<title>Caf\xe9</title>".to_vec()),
+                        "/meta-charset" => ("200 OK", "Content-Type: text/html\r\n", b"<meta charset=\"windows-1252\"><title>\x93Quoted\x94</title>".to_vec()),
+                        _ => ("200 OK", "", format!("<html><head><title>{}</title></head></html>", path).into_bytes()),
+                    };
+                    let head = format!("HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n", status, headers, body.len());
+                    let _ = socket.write_all(head.as_bytes()).await;
+                    let _ = socket.write_all(&body).await;
+                });
+            }
+        });
+        (address, robots_requests)
+    }
+
+    fn config(respect_robots_txt: bool) -> MetadataConfig {
+        MetadataConfig {
+            respect_robots_txt,
+            per_host_requests_per_sec: 0.0,
+            // The test server runs on localhost
+            block_private_addresses: false,
+            max_page_bytes: 1024,
+            max_document_bytes: 4096,
+            ..MetadataConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_honors_robots_txt() {
+        let (server, robots_requests) = start_server().await;
+        let fetcher = PageFetcher::new(&config(true)).unwrap();
+        let timeout = Duration::from_secs(5);
+
+        let (html, _) = fetcher.fetch(&format!("{}/public", server), timeout).await.unwrap();
+        assert!(html.contains("/public"));
+        let blocked = fetcher.fetch(&format!("{}/private/page", server), timeout).await;
+        assert!(matches!(blocked, Err(ExtractorError::BlockedByRobots(_))));
+
+        // robots.txt is downloaded once per site
+        fetcher.fetch(&format!("{}/other", server), timeout).await.unwrap();
+        assert_eq!(robots_requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_can_ignore_robots_txt() {
+        let (server, robots_requests) = start_server().await;
+        let fetcher = PageFetcher::new(&config(false)).unwrap();
+
+        let result = fetcher.fetch(&format!("{}/private/page", server), Duration::from_secs(5)).await;
+        assert!(result.is_ok());
+        assert_eq!(robots_requests.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_invalid_url() {
+        let fetcher = PageFetcher::new(&config(true)).unwrap();
+        let result = fetcher.fetch("not-a-url", Duration::from_secs(5)).await;
+        assert!(matches!(result, Err(ExtractorError::InvalidUrl(_))));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_follows_redirects_up_to_limit() {
+        let (server, _) = start_server().await;
+        let fetcher = PageFetcher::new(&config(false)).unwrap();
+        let timeout = Duration::from_secs(5);
+
+        let (html, page_url) = fetcher.fetch(&format!("{}/moved", server), timeout).await.unwrap();
+        assert!(html.contains("/public"));
+        assert_eq!(page_url.path(), "/public");
+
+        let looping = fetcher.fetch(&format!("{}/loop", server), timeout).await;
+        assert!(matches!(looping, Err(ExtractorError::TooManyRedirects(5))));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_rejects_large_and_non_html_pages() {
+        let (server, _) = start_server().await;
+        let fetcher = PageFetcher::new(&config(false)).unwrap();
+        let timeout = Duration::from_secs(5);
+
+        let large = fetcher.fetch(&format!("{}/large", server), timeout).await;
+        assert!(matches!(large, Err(ExtractorError::TooLarge(1024))));
+
+        let image = fetcher.fetch(&format!("{}/image", server), timeout).await;
+        assert!(matches!(image, Err(ExtractorError::UnsupportedContentType(ref media_type)) if media_type == "image/png"));
+    }
+
+    #[tokio::test]
+    async fn test_documents_get_a_larger_limit() {
+        let (server, _) = start_server().await;
+        let fetcher = PageFetcher::new(&config(false)).unwrap();
+        let timeout = Duration::from_secs(5);
+
+        let (image, _) = fetcher.fetch_resource(&format!("{}/large-image", server), timeout).await.unwrap();
+        assert!(matches!(image, Resource::Image { ref bytes, .. } if bytes.len() == 2048));
+
+        let huge = fetcher.fetch_resource(&format!("{}/huge-image", server), timeout).await;
+        assert!(matches!(huge, Err(ExtractorError::TooLarge(4096))));
+
+        // Undeclared bodies that turn out to be text keep the page limit
+        let text = fetcher.fetch_resource(&format!("{}/large-undeclared", server), timeout).await;
+        assert!(matches!(text, Err(ExtractorError::TooLarge(1024))));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_rejects_error_statuses() {
+        let (server, _) = start_server().await;
+        let fetcher = PageFetcher::new(&config(false)).unwrap();
+        let timeout = Duration::from_secs(5);
+
+        let missing = fetcher.fetch(&format!("{}/missing", server), timeout).await.err().unwrap();
+        assert!(matches!(missing, ExtractorError::HttpStatus(404)));
+        assert!(!missing.is_retryable());
+
+        let busy = fetcher.fetch(&format!("{}/busy", server), timeout).await.err().unwrap();
+        assert!(matches!(busy, ExtractorError::HttpStatus(503)));
+        assert!(busy.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_decodes_declared_charsets() {
+        let (server, _) = start_server().await;
+        let fetcher = PageFetcher::new(&config(false)).unwrap();
+        let timeout = Duration::from_secs(5);
+
+        let (html, _) = fetcher.fetch(&format!("{}/latin1", server), timeout).await.unwrap();
+        assert_eq!(html, "<title>Caf\u{e9}</title>");
+
+        let (html, _) = fetcher.fetch(&format!("{}/meta-charset", server), timeout).await.unwrap();
+        assert!(html.contains("\u{201c}Quoted\u{201d}"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_blocks_private_addresses() {
+        let (server, _) = start_server().await;
+        let fetcher = PageFetcher::new(&MetadataConfig {
+            block_private_addresses: true,
+            ..config(true)
+        }).unwrap();
+        let timeout = Duration::from_secs(5);
+
+        let result = fetcher.fetch(&format!("{}/public", server), timeout).await;
+        assert!(matches!(result, Err(ExtractorError::PrivateAddress(ref host)) if host == "127.0.0.1"));
+        let result = fetcher.fetch("http://localhost/", timeout).await;
+        assert!(matches!(result, Err(ExtractorError::PrivateAddress(_))));
+    }
+
+    #[tokio::test]
+    async fn test_public_resolver_refuses_private_names() {
+        let resolver = PublicResolver { allowed_hosts: Arc::new(Vec::new()) };
+        let error = resolver.resolve("localhost".parse().unwrap()).await.err().unwrap();
+        assert!(matches!(error.downcast_ref::<ExtractorError>(), Some(ExtractorError::PrivateAddress(host)) if host == "localhost"));
+
+        let resolver = PublicResolver { allowed_hosts: Arc::new(vec!["localhost".to_string()]) };
+        assert!(resolver.resolve("localhost".parse().unwrap()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_allowed_private_hosts_are_fetched() {
+        let (server, _) = start_server().await;
+        let fetcher = PageFetcher::new(&MetadataConfig {
+            block_private_addresses: true,
+            allow_private_hosts: vec!["127.0.0.1".to_string()],
+            ..config(false)
+        }).unwrap();
+
+        let (html, _) = fetcher.fetch(&format!("{}/public", server), Duration::from_secs(5)).await.unwrap();
+        assert!(html.contains("/public"));
+        assert!(!is_allowed_host("127.0.0.2", &["127.0.0.1".to_string()]));
+        assert!(is_allowed_host("wiki.corp.example.com", &["corp.example.com".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn test_proxied_requests_skip_local_resolution() {
+        // With a proxy the client never resolves the target, so a name that would resolve
+        // privately isn't refused up front; only the unreachable proxy makes the request fail
+        let fetcher = PageFetcher::new(&MetadataConfig {
+            block_private_addresses: true,
+            proxy: Some("http://127.0.0.1:9".to_string()),
+            ..config(false)
+        }).unwrap();
+        let result = fetcher.fetch("http://localhost/", Duration::from_secs(5)).await;
+        assert!(matches!(result, Err(ExtractorError::NetworkError(_))));
+
+        let result = fetcher.fetch("http://127.0.0.1/", Duration::from_secs(5)).await;
+        assert!(matches!(result, Err(ExtractorError::PrivateAddress(_))));
+    }
+
+    #[tokio::test]
+    async fn test_redirects_only_carry_their_own_sites_headers() {
+        let (server, _) = start_server().await;
+        let fetcher = PageFetcher::new(&MetadataConfig {
+            headers: [("127.0.0.1".to_string(), [("X-Api-Key".to_string(), "secret".to_string())].into())].into(),
+            ..config(false)
+        }).unwrap();
+
+        // Redirects from 127.0.0.1 to localhost, which has no headers configured
+        let (echo, page_url) = fetcher.fetch_resource(&format!("{}/elsewhere", server), Duration::from_secs(5)).await.unwrap();
+        let Resource::Text(request) = echo else { panic!("Expected the echoed request") };
+        assert_eq!(page_url.host_str(), Some("localhost"));
+        assert!(!request.to_ascii_lowercase().contains("x-api-key"));
+    }
+
+    #[test]
+    fn test_private_address_ranges() {
+        let private = |address: &str| is_private_address(address.parse().unwrap());
+
+        assert!(private("127.0.0.1"));
+        assert!(private("10.1.2.3"));
+        assert!(private("172.16.0.1"));
+        assert!(private("192.168.1.1"));
+        assert!(private("169.254.169.254"));
+        assert!(private("100.64.0.1"));
+        assert!(private("0.0.0.0"));
+        assert!(private("::1"));
+        assert!(private("fd00:ec2::254"));
+        assert!(private("fe80::1"));
+        assert!(private("::ffff:192.168.0.1"));
+        assert!(private("::127.0.0.1"));
+        assert!(private("::10.0.0.1"));
+        assert!(private("64:ff9b::169.254.169.254"));
+        assert!(private("64:ff9b::7f00:1"));
+        assert!(private("2002:a9fe:a9fe::1"));
+        assert!(private("2002:c0a8:101::"));
+        assert!(private("fec0::1"));
+        assert!(private("ff02::1"));
+        assert!(private("224.0.0.1"));
+        assert!(!private("93.184.216.34"));
+        assert!(!private("2606:2800:220:1::"));
+        assert!(!private("64:ff9b::5db8:d822"));
+        assert!(!private("2002:5db8:d822::1"));
+    }
+
+    #[test]
+    fn test_charset_and_sniffing_helpers() {
+        assert_eq!(charset_parameter("text/html; charset=\"Shift_JIS\""), Some("shift_jis".to_string()));
+        assert_eq!(charset_parameter("<meta http-equiv=\"Content-Type\" content=\"text/html; charset=koi8-r\">"), Some("koi8-r".to_string()));
+        assert_eq!(charset_parameter("text/html"), None);
+        assert_eq!(media_type("Text/HTML; charset=utf-8"), "text/html");
+
+        assert!(looks_like_html(b"\n  <!DOCTYPE html><html></html>"));
+        assert!(!looks_like_html(b"%PDF-1.7"));
+        assert!(!looks_like_html(b"<\x00\x01binary"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_resource_dispatches_on_content_type() {
+        let (server, _) = start_server().await;
+        let fetcher = PageFetcher::new(&config(false)).unwrap();
+        let timeout = Duration::from_secs(5);
+
+        // Served as a generic binary, recognized by its signature
+        let (pdf, _) = fetcher.fetch_resource(&format!("{}/paper", server), timeout).await.unwrap();
+        assert!(matches!(pdf, Resource::Pdf(ref bytes) if bytes.starts_with(b"%PDF-")));
+
+        let (image, _) = fetcher.fetch_resource(&format!("{}/image", server), timeout).await.unwrap();
+        assert_eq!(image.media_type(), "image/png");
+
+        let (text, _) = fetcher.fetch_resource(&format!("{}/notes.txt", server), timeout).await.unwrap();
+        assert!(matches!(text, Resource::Text(ref text) if text == "Notes \u{2014} draft"));
+
+        let archive = fetcher.fetch_resource(&format!("{}/archive.zip", server), timeout).await;
+        assert!(matches!(archive, Err(ExtractorError::UnsupportedContentType(ref media_type)) if media_type == "application/zip"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_sends_configured_headers() {
+        let (server, _) = start_server().await;
+        let fetcher = PageFetcher::new(&MetadataConfig {
+            headers: [("127.0.0.1".to_string(), [("X-Api-Key".to_string(), "secret".to_string())].into())].into(),
+            ..config(false)
+        }).unwrap();
+
+        let (echo, _) = fetcher.fetch_resource(&format!("{}/echo", server), Duration::from_secs(5)).await.unwrap();
+        let Resource::Text(request) = echo else { panic!("Expected the echoed request") };
+        assert!(request.to_ascii_lowercase().contains("x-api-key: secret"));
+        assert!(request.contains("automark/1.0"));
+    }
+
+    /// Serve a page that may be cached for ten minutes and one validated by ETag; counts page requests
+    async fn start_caching_server() -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let page_requests = Arc::new(AtomicUsize::new(0));
+        let counter = page_requests.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let counter = counter.clone();
+                tokio::spawn(async move {
+                    let mut buffer = vec![0; 4096];
+                    let read = socket.read(&mut buffer).await.unwrap_or(0);
+                    let request = String::from_utf8_lossy(&buffer[..read]).to_ascii_lowercase();
+                    let path = request.split_whitespace().nth(1).unwrap_or("").to_string();
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    let (status, headers, body) = match path.as_str() {
+                        "/fresh" => ("200 OK", "Cache-Control: max-age=600\r\n", "<title>Fresh</title>"),
+                        "/etag" if request.contains("if-none-match: \"v1\"") => ("304 Not Modified", "ETag: \"v1\"\r\n", ""),
+                        "/etag" => ("200 OK", "ETag: \"v1\"\r\nCache-Control: no-cache\r\n", "<title>Tagged</title>"),
+                        _ => ("200 OK", "", "<title>Uncached</title>"),
+                    };
+                    let head = format!("HTTP/1.1 {}\r\nContent-Type: text/html\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n", status, headers, body.len());
+                    let _ = socket.write_all(head.as_bytes()).await;
+                    let _ = socket.write_all(body.as_bytes()).await;
+                });
+            }
+        });
+        (address, page_requests)
+    }
+
+    #[tokio::test]
+    async fn test_fetch_uses_http_cache() {
+        let (server, page_requests) = start_caching_server().await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache = Arc::new(HttpCache::new(temp_dir.path().to_path_buf(), 1024 * 1024));
+        let fetcher = PageFetcher::new(&config(false)).unwrap().with_cache(Some(cache.clone()));
+        let timeout = Duration::from_secs(5);
+
+        // Fresh responses are reused without asking the server
+        for _ in 0..2 {
+            let (html, _) = fetcher.fetch(&format!("{}/fresh", server), timeout).await.unwrap();
+            assert!(html.contains("Fresh"));
+        }
+        assert_eq!(page_requests.load(Ordering::SeqCst), 1);
+
+        // Stale responses are revalidated, and a 304 serves the stored body
+        for _ in 0..2 {
+            let (html, _) = fetcher.fetch(&format!("{}/etag", server), timeout).await.unwrap();
+            assert!(html.contains("Tagged"));
+        }
+        assert_eq!(page_requests.load(Ordering::SeqCst), 3);
+
+        // Responses without validators or a lifetime are fetched every time
+        for _ in 0..2 {
+            fetcher.fetch(&format!("{}/plain", server), timeout).await.unwrap();
+        }
+        assert_eq!(page_requests.load(Ordering::SeqCst), 5);
+
+        // Other fetchers sharing the cache see the same responses
+        let other = PageFetcher::new(&config(false)).unwrap().with_cache(Some(cache));
+        other.fetch(&format!("{}/fresh", server), timeout).await.unwrap();
+        assert_eq!(page_requests.load(Ordering::SeqCst), 5);
+    }
+
+    #[test]
+    fn test_sniff_resource_kind() {
+        assert_eq!(ResourceKind::sniff(b"%PDF-1.7\n"), Some(ResourceKind::Pdf));
+        assert_eq!(ResourceKind::sniff(b"<!doctype html>"), Some(ResourceKind::Html));
+        assert_eq!(ResourceKind::sniff(b"plain words"), Some(ResourceKind::Text));
+        assert_eq!(ResourceKind::sniff(include_bytes!("fixtures/pixel.png")), Some(ResourceKind::Image));
+        assert_eq!(ResourceKind::sniff(b"\x00\x01\x02\xff"), None);
+    }
+}
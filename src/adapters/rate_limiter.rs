@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Token bucket per host, shared by every request made through it
+///
+/// Each host may make `burst` requests straight away and then `rate` requests per
+/// second. Callers reserve their token up front and sleep until it is due, so
+/// concurrent requests to one host queue up in order instead of racing.
+pub struct HostRateLimiter {
+    /// Tokens added per second; zero or less disables limiting
+    rate: f64,
+    burst: f64,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+struct Bucket {
+    /// Available tokens; negative when requests are already waiting
+    tokens: f64,
+    updated: Instant,
+}
+
+impl HostRateLimiter {
+    pub fn new(rate: f64, burst: u32) -> Self {
+        Self {
+            rate,
+            burst: f64::from(burst.max(1)),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// One request per `delay` to each host, with no bursts
+    pub fn with_spacing(delay: Duration) -> Self {
+        let rate = if delay.is_zero() { 0.0 } else { 1.0 / delay.as_secs_f64() };
+        Self::new(rate, 1)
+    }
+
+    /// Wait until a request to `host` is allowed, and take the token for it
+    pub async fn wait(&self, host: &str) {
+        if self.rate <= 0.0 {
+            return;
+        }
+
+        let wait = {
+            let mut buckets = self.buckets.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            let now = Instant::now();
+            let bucket = buckets.entry(host.to_string())
+                .or_insert(Bucket { tokens: self.burst, updated: now });
+            let refill = now.duration_since(bucket.updated).as_secs_f64() * self.rate;
+            bucket.tokens = (bucket.tokens + refill).min(self.burst) - 1.0;
+            bucket.updated = now;
+            if bucket.tokens < 0.0 {
+                Duration::from_secs_f64(-bucket.tokens / self.rate)
+            } else {
+                Duration::ZERO
+            }
+        };
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_spacing_per_host() {
+        let limiter = HostRateLimiter::with_spacing(Duration::from_millis(50));
+        let start = Instant::now();
+        limiter.wait("a.com").await;
+        limiter.wait("b.com").await;
+        assert!(start.elapsed() < Duration::from_millis(50));
+
+        limiter.wait("a.com").await;
+        limiter.wait("a.com").await;
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn test_burst_then_rate() {
+        let limiter = HostRateLimiter::new(20.0, 3);
+        let start = Instant::now();
+        for _ in 0..3 {
+            limiter.wait("a.com").await;
+        }
+        assert!(start.elapsed() < Duration::from_millis(50));
+
+        // The fourth request waits for a token to refill (1/20s)
+        limiter.wait("a.com").await;
+        assert!(start.elapsed() >= Duration::from_millis(45));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_requests_queue_up() {
+        let limiter = HostRateLimiter::with_spacing(Duration::from_millis(30));
+        let start = Instant::now();
+        futures_util::future::join_all((0..4).map(|_| limiter.wait("a.com"))).await;
+        assert!(start.elapsed() >= Duration::from_millis(90));
+    }
+
+    #[tokio::test]
+    async fn test_zero_rate_is_unlimited() {
+        let limiter = HostRateLimiter::with_spacing(Duration::ZERO);
+        let start = Instant::now();
+        for _ in 0..100 {
+            limiter.wait("a.com").await;
+        }
+        assert!(start.elapsed() < Duration::from_millis(50));
+    }
+}
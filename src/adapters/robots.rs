@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::OnceCell;
+use url::Url;
+
+use super::page_fetcher::PageFetcher;
+
+/// Allow and disallow rules from one robots.txt that apply to our user agent
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RobotsRules {
+    /// `(allow, pattern)` pairs
+    rules: Vec<(bool, String)>,
+}
+
+impl RobotsRules {
+    /// Rules that block nothing, used when a site has no usable robots.txt
+    pub fn allow_all() -> Self {
+        Self::default()
+    }
+
+    /// Parse a robots.txt for `user_agent`
+    ///
+    /// Uses the groups naming the agent's product token (`automark` in
+    /// `automark/1.0 (...)`), or the `*` groups if none do.
+    pub fn parse(robots_txt: &str, user_agent: &str) -> Self {
+        let token = user_agent.split(['/', ' ']).next().unwrap_or("").to_lowercase();
+
+        let mut specific = Vec::new();
+        let mut wildcard = Vec::new();
+        let mut agents: Vec<String> = Vec::new();
+        let mut in_rules = false;
+        let mut named = false;
+        for line in robots_txt.lines() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            let Some((field, value)) = line.split_once(':') else { continue };
+            let (field, value) = (field.trim().to_lowercase(), value.trim());
+
+            match field.as_str() {
+                "user-agent" => {
+                    // A user-agent line after rules starts a new group
+                    if in_rules {
+                        agents.clear();
+                        in_rules = false;
+                    }
+                    let agent = value.to_lowercase();
+                    named |= !token.is_empty() && agent == token;
+                    agents.push(agent);
+                }
+                "allow" | "disallow" => {
+                    in_rules = true;
+                    // An empty disallow allows everything
+                    if value.is_empty() {
+                        continue;
+                    }
+                    let rule = (field == "allow", value.to_string());
+                    if agents.contains(&token) {
+                        specific.push(rule.clone());
+                    }
+                    if agents.iter().any(|agent| agent == "*") {
+                        wildcard.push(rule);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Self { rules: if named { specific } else { wildcard } }
+    }
+
+    /// Whether a URL may be fetched: the longest matching rule wins, and allow wins ties
+    pub fn is_allowed(&self, url: &Url) -> bool {
+        let mut path = url.path().to_string();
+        if let Some(query) = url.query() {
+            path.push('?');
+            path.push_str(query);
+        }
+
+        self.rules.iter()
+            .filter(|(_, pattern)| pattern_matches(pattern, &path))
+            .max_by_key(|(allow, pattern)| (pattern.len(), *allow))
+            .is_none_or(|(allow, _)| *allow)
+    }
+}
+
+/// Match a robots.txt path pattern, where `*` matches anything and a trailing `$` anchors the end
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = path.strip_prefix(first) else { return false };
+    let parts: Vec<&str> = parts.collect();
+    for (index, part) in parts.iter().enumerate() {
+        let is_last = index == parts.len() - 1;
+        if is_last && anchored {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(position) => rest = &rest[position + part.len()..],
+            None => return false,
+        }
+    }
+    !anchored || rest.is_empty()
+}
+
+/// Fetches each site's robots.txt once and keeps the rules for our user agent
+pub struct RobotsCache {
+    user_agent: String,
+    sites: Mutex<HashMap<String, Arc<OnceCell<RobotsRules>>>>,
+}
+
+impl RobotsCache {
+    pub fn new(user_agent: &str) -> Self {
+        Self {
+            user_agent: user_agent.to_string(),
+            sites: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The rules for the site serving `url`, downloading robots.txt on first use
+    ///
+    /// Concurrent lookups for one site share a single download, made through
+    /// `fetcher` with its rate limit and safety checks. A missing, unreachable
+    /// or oversized robots.txt allows everything.
+    pub async fn rules_for(&self, url: &Url, fetcher: &PageFetcher, timeout: Duration) -> RobotsRules {
+        let origin = url.origin().ascii_serialization();
+        let cell = {
+            let mut sites = self.sites.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            sites.entry(origin.clone()).or_default().clone()
+        };
+
+        cell.get_or_init(|| async {
+            let Ok(robots_url) = Url::parse(&format!("{}/robots.txt", origin)) else {
+                return RobotsRules::allow_all();
+            };
+            match fetcher.send(robots_url, timeout).await {
+                Ok(response) if response.status().is_success() => match fetcher.read_body(response).await {
+                    Ok(body) => RobotsRules::parse(&String::from_utf8_lossy(&body), &self.user_agent),
+                    Err(_) => RobotsRules::allow_all(),
+                },
+                _ => RobotsRules::allow_all(),
+            }
+        }).await.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USER_AGENT: &str = "automark/1.0 (https://github.com/evcraddock/automark)";
+
+    fn allowed(rules: &RobotsRules, path: &str) -> bool {
+        rules.is_allowed(&Url::parse(&format!("https://example.com{}", path)).unwrap())
+    }
+
+    #[test]
+    fn test_wildcard_group() {
+        let rules = RobotsRules::parse("User-agent: *\nDisallow: /private/\nAllow: /private/public\n", USER_AGENT);
+
+        assert!(allowed(&rules, "/"));
+        assert!(allowed(&rules, "/blog/post"));
+        assert!(!allowed(&rules, "/private/notes"));
+        assert!(allowed(&rules, "/private/public/page"));
+    }
+
+    #[test]
+    fn test_specific_group_replaces_wildcard() {
+        let robots = "\
+User-agent: *
+Disallow: /
+
+# Friendly bots
+User-agent: googlebot
+User-agent: Automark
+Disallow: /admin
+";
+        let rules = RobotsRules::parse(robots, USER_AGENT);
+        assert!(allowed(&rules, "/blog/post"));
+        assert!(!allowed(&rules, "/admin/users"));
+
+        let other = RobotsRules::parse(robots, "otherbot/2.0");
+        assert!(!allowed(&other, "/blog/post"));
+    }
+
+    #[test]
+    fn test_patterns_and_empty_disallow() {
+        let rules = RobotsRules::parse("User-agent: *\nDisallow: /*.pdf$\nDisallow: /search?*q=\nDisallow:\n", USER_AGENT);
+
+        assert!(!allowed(&rules, "/papers/a.pdf"));
+        assert!(allowed(&rules, "/papers/a.pdf.html"));
+        assert!(!allowed(&rules, "/search?lang=en&q=rust"));
+        assert!(allowed(&rules, "/search"));
+        assert!(allowed(&RobotsRules::allow_all(), "/anything"));
+    }
+
+    #[test]
+    fn test_longest_match_wins() {
+        let rules = RobotsRules::parse("User-agent: *\nAllow: /docs\nDisallow: /docs/drafts\nAllow: /docs/drafts/shared$\n", USER_AGENT);
+
+        assert!(allowed(&rules, "/docs/guide"));
+        assert!(!allowed(&rules, "/docs/drafts/wip"));
+        assert!(allowed(&rules, "/docs/drafts/shared"));
+    }
+}
@@ -0,0 +1,453 @@
+use crate::adapters::text_analysis::{analyze, edit_distance_within, stem, tokenize};
+use crate::types::Bookmark;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+/// Bumped whenever the analysis or file layout changes so old indexes are rebuilt
+const INDEX_VERSION: u32 = 2;
+
+// BM25 parameters
+const K1: f64 = 1.2;
+const B: f64 = 0.75;
+
+// Field weights applied to term frequencies
+const TITLE_WEIGHT: f64 = 3.0;
+const AUTHOR_WEIGHT: f64 = 2.0;
+const DESCRIPTION_WEIGHT: f64 = 1.5;
+const URL_WEIGHT: f64 = 1.0;
+const NOTES_WEIGHT: f64 = 1.0;
+const CONTENT_WEIGHT: f64 = 0.5;
+
+// Score multipliers for expanded query terms
+const PREFIX_WEIGHT: f64 = 0.7;
+const FUZZY_WEIGHT: f64 = 0.4;
+const MAX_EXPANSIONS: usize = 50;
+
+/// Score multiplier for a bookmark containing a quoted phrase of the query word for word
+const PHRASE_WEIGHT: f64 = 2.0;
+
+/// Indexed terms of a single bookmark
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedDoc {
+    id: String,
+    /// Weighted number of terms in the document
+    length: f64,
+    /// Weighted frequency of each term
+    terms: Vec<(String, f64)>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct IndexFile {
+    version: u32,
+    heads: Vec<String>,
+    docs: Vec<IndexedDoc>,
+}
+
+/// Inverted index over bookmark title, URL, author, notes and captured page text, ranked with BM25
+#[derive(Debug, Default)]
+pub struct SearchIndex {
+    /// Document heads the index reflects, used to detect a stale index
+    heads: Vec<String>,
+    /// Removed documents leave a `None` until the index is next loaded
+    docs: Vec<Option<IndexedDoc>>,
+    slots: HashMap<String, usize>,
+    /// Term -> (slot, weighted term frequency), ordered for prefix lookups
+    postings: BTreeMap<String, Vec<(usize, f64)>>,
+    total_length: f64,
+}
+
+impl SearchIndex {
+    /// Build a fresh index for a set of bookmarks, looking up captured page text by ID
+    pub fn build(bookmarks: &[Bookmark], heads: Vec<String>, content: impl Fn(&str) -> Option<String>) -> Self {
+        let mut index = SearchIndex { heads, ..Default::default() };
+        for bookmark in bookmarks {
+            index.upsert(bookmark, content(&bookmark.id).as_deref());
+        }
+        index
+    }
+
+    /// Load an index from disk, returning None if it is missing, unreadable or from an older version
+    pub fn load(path: &Path) -> Option<Self> {
+        let bytes = fs::read(path).ok()?;
+        let file: IndexFile = serde_json::from_slice(&bytes).ok()?;
+        if file.version != INDEX_VERSION {
+            return None;
+        }
+
+        let mut index = SearchIndex { heads: file.heads, ..Default::default() };
+        for doc in file.docs {
+            index.insert_doc(doc);
+        }
+        Some(index)
+    }
+
+    /// Write the index to disk atomically
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let file = IndexFile {
+            version: INDEX_VERSION,
+            heads: self.heads.clone(),
+            docs: self.docs.iter().flatten().cloned().collect(),
+        };
+        let bytes = serde_json::to_vec(&file)?;
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, bytes)?;
+        fs::rename(&temp_path, path)
+    }
+
+    pub fn heads(&self) -> &[String] {
+        &self.heads
+    }
+
+    pub fn set_heads(&mut self, heads: Vec<String>) {
+        self.heads = heads;
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Add or replace a bookmark and its captured page text in the index
+    pub fn upsert(&mut self, bookmark: &Bookmark, content: Option<&str>) {
+        self.remove(&bookmark.id);
+
+        let mut frequencies: HashMap<String, f64> = HashMap::new();
+        let mut add_field = |text: &str, weight: f64| {
+            for term in analyze(text) {
+                *frequencies.entry(term).or_default() += weight;
+            }
+        };
+        add_field(&bookmark.title, TITLE_WEIGHT);
+        add_field(&bookmark.url, URL_WEIGHT);
+        if let Some(ref author) = bookmark.author {
+            add_field(author, AUTHOR_WEIGHT);
+        }
+        if let Some(ref description) = bookmark.description {
+            add_field(description, DESCRIPTION_WEIGHT);
+        }
+        for note in &bookmark.notes {
+            add_field(&note.content, NOTES_WEIGHT);
+        }
+        if let Some(content) = content {
+            add_field(content, CONTENT_WEIGHT);
+        }
+
+        let mut terms: Vec<(String, f64)> = frequencies.into_iter().collect();
+        terms.sort_by(|a, b| a.0.cmp(&b.0));
+        let length = terms.iter().map(|(_, frequency)| frequency).sum();
+        self.insert_doc(IndexedDoc { id: bookmark.id.clone(), length, terms });
+    }
+
+    /// Remove a bookmark from the index if present
+    pub fn remove(&mut self, id: &str) {
+        let Some(slot) = self.slots.remove(id) else { return };
+        let Some(doc) = self.docs[slot].take() else { return };
+
+        self.total_length -= doc.length;
+        for (term, _) in &doc.terms {
+            if let Some(postings) = self.postings.get_mut(term) {
+                postings.retain(|(posting_slot, _)| *posting_slot != slot);
+                if postings.is_empty() {
+                    self.postings.remove(term);
+                }
+            }
+        }
+    }
+
+    fn insert_doc(&mut self, doc: IndexedDoc) {
+        let slot = self.docs.len();
+        self.total_length += doc.length;
+        for (term, frequency) in &doc.terms {
+            self.postings.entry(term.clone()).or_default().push((slot, *frequency));
+        }
+        self.slots.insert(doc.id.clone(), slot);
+        self.docs.push(Some(doc));
+    }
+
+    /// Rank bookmarks matching every word of the query, keyed by bookmark ID
+    ///
+    /// Each word matches its stem exactly, words starting with it, or (if neither exists)
+    /// terms within a small edit distance. Returns None if the query has no searchable words,
+    /// so callers can fall back to substring matching.
+    pub fn search(&self, query: &str) -> Option<HashMap<String, f64>> {
+        let words = tokenize(query);
+        if words.is_empty() {
+            return None;
+        }
+
+        let doc_count = self.slots.len() as f64;
+        let average_length = if self.slots.is_empty() { 0.0 } else { self.total_length / doc_count };
+        let mut scores: Option<HashMap<usize, f64>> = None;
+
+        for word in words {
+            let mut word_scores: HashMap<usize, f64> = HashMap::new();
+            for (term, weight) in self.expand(&word) {
+                let Some(postings) = self.postings.get(&term) else { continue };
+                let idf = (1.0 + (doc_count - postings.len() as f64 + 0.5) / (postings.len() as f64 + 0.5)).ln();
+                for &(slot, frequency) in postings {
+                    let length = self.docs[slot].as_ref().map_or(0.0, |doc| doc.length);
+                    let normalization = K1 * (1.0 - B + B * length / average_length.max(f64::EPSILON));
+                    let score = weight * idf * frequency * (K1 + 1.0) / (frequency + normalization);
+                    let entry = word_scores.entry(slot).or_default();
+                    *entry = entry.max(score);
+                }
+            }
+
+            // Every word must match (AND), summing the per-word scores
+            scores = Some(match scores {
+                None => word_scores,
+                Some(previous) => previous.into_iter()
+                    .filter_map(|(slot, score)| word_scores.get(&slot).map(|word_score| (slot, score + word_score)))
+                    .collect(),
+            });
+        }
+
+        Some(scores.unwrap_or_default()
+            .into_iter()
+            .filter_map(|(slot, score)| self.docs[slot].as_ref().map(|doc| (doc.id.clone(), score)))
+            .collect())
+    }
+
+    /// Index terms a query word can match, with their score weights
+    fn expand(&self, word: &str) -> Vec<(String, f64)> {
+        let stemmed = stem(word);
+        let mut expansions = Vec::new();
+        if self.postings.contains_key(&stemmed) {
+            expansions.push((stemmed.clone(), 1.0));
+        }
+
+        // Index terms are stems, so "progr" and "programming" both reach "program"
+        for prefix in [word, stemmed.as_str()] {
+            if prefix.chars().count() < 3 {
+                continue;
+            }
+            let matches = self.postings
+                .range::<str, _>((std::ops::Bound::Included(prefix), std::ops::Bound::Unbounded))
+                .take_while(|(term, _)| term.starts_with(prefix))
+                .filter(|(term, _)| **term != stemmed && !expansions.iter().any(|(existing, _)| existing == *term))
+                .take(MAX_EXPANSIONS)
+                .map(|(term, _)| (term.clone(), PREFIX_WEIGHT))
+                .collect::<Vec<_>>();
+            expansions.extend(matches);
+        }
+
+        if expansions.is_empty() {
+            let max_distance = match stemmed.chars().count() {
+                0..=3 => 0,
+                4..=7 => 1,
+                _ => 2,
+            };
+            if max_distance > 0 {
+                expansions.extend(self.postings.keys()
+                    .filter(|term| edit_distance_within(term, &stemmed, max_distance).is_some())
+                    .take(MAX_EXPANSIONS)
+                    .map(|term| (term.clone(), FUZZY_WEIGHT)));
+            }
+        }
+
+        expansions
+    }
+}
+
+/// Rank bookmarks containing each `"quoted phrase"` of a query word for word above
+/// those that only contain its words
+///
+/// The index keeps no word positions, so phrases are looked for in the bookmark's
+/// title, URL, author, description and notes; captured page text only counts toward
+/// the individual words.
+pub fn boost_phrases(scores: &mut HashMap<String, f64>, bookmarks: &[Bookmark], query: &str) {
+    let phrases: Vec<Vec<String>> = query.split('"')
+        .skip(1)
+        .step_by(2)
+        .map(tokenize)
+        .filter(|words| words.len() > 1)
+        .collect();
+    if phrases.is_empty() {
+        return;
+    }
+
+    for bookmark in bookmarks {
+        let Some(score) = scores.get_mut(&bookmark.id) else { continue };
+        let fields = [Some(&bookmark.title), Some(&bookmark.url), bookmark.author.as_ref(), bookmark.description.as_ref()]
+            .into_iter()
+            .flatten()
+            .chain(bookmark.notes.iter().map(|note| &note.content))
+            .map(|text| tokenize(text))
+            .collect::<Vec<_>>();
+        for phrase in &phrases {
+            if fields.iter().any(|words| words.windows(phrase.len()).any(|window| window == phrase.as_slice())) {
+                *score *= PHRASE_WEIGHT;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn bookmark(url: &str, title: &str) -> Bookmark {
+        Bookmark::new(url, title).unwrap()
+    }
+
+    fn ranked_titles(index: &SearchIndex, bookmarks: &[Bookmark], query: &str) -> Vec<String> {
+        let scores = index.search(query).unwrap();
+        let mut matches: Vec<&Bookmark> = bookmarks.iter().filter(|b| scores.contains_key(&b.id)).collect();
+        matches.sort_by(|a, b| scores[&b.id].total_cmp(&scores[&a.id]));
+        matches.iter().map(|b| b.title.clone()).collect()
+    }
+
+    #[test]
+    fn test_search_ranks_title_matches_higher() {
+        let mut in_notes = bookmark("https://example.com/a", "Systems Languages");
+        in_notes.add_note("Mentions rust once");
+        let bookmarks = vec![
+            in_notes,
+            bookmark("https://example.com/b", "Rust Programming in Rust"),
+            bookmark("https://example.com/c", "Python Tips"),
+        ];
+        let index = SearchIndex::build(&bookmarks, Vec::new(), |_| None);
+
+        assert_eq!(ranked_titles(&index, &bookmarks, "rust"), vec!["Rust Programming in Rust", "Systems Languages"]);
+    }
+
+    #[test]
+    fn test_search_requires_all_words_and_stems() {
+        let bookmarks = vec![
+            bookmark("https://example.com/a", "Programming Rust"),
+            bookmark("https://example.com/b", "Programs in Python"),
+        ];
+        let index = SearchIndex::build(&bookmarks, Vec::new(), |_| None);
+
+        assert_eq!(ranked_titles(&index, &bookmarks, "program").len(), 2);
+        assert_eq!(ranked_titles(&index, &bookmarks, "programmed rust"), vec!["Programming Rust"]);
+        assert!(index.search("the").is_none());
+    }
+
+    #[test]
+    fn test_prefix_and_fuzzy_matching() {
+        let bookmarks = vec![
+            bookmark("https://tokio.rs", "Tokio Runtime"),
+            bookmark("https://example.com", "Asynchronous Patterns"),
+        ];
+        let index = SearchIndex::build(&bookmarks, Vec::new(), |_| None);
+
+        assert_eq!(ranked_titles(&index, &bookmarks, "asynch"), vec!["Asynchronous Patterns"]);
+        assert_eq!(ranked_titles(&index, &bookmarks, "runtim"), vec!["Tokio Runtime"]);
+        assert_eq!(ranked_titles(&index, &bookmarks, "tokoi"), Vec::<String>::new());
+        assert_eq!(ranked_titles(&index, &bookmarks, "tokip"), vec!["Tokio Runtime"]);
+    }
+
+    #[test]
+    fn test_search_matches_captured_content() {
+        let bookmarks = vec![
+            bookmark("https://example.com/a", "Weekly Notes"),
+            bookmark("https://example.com/b", "Borrow Checker Explained"),
+        ];
+        let first_id = bookmarks[0].id.clone();
+        let index = SearchIndex::build(&bookmarks, Vec::new(), |id| {
+            (id == first_id).then(|| "A long article body about the borrow checker".to_string())
+        });
+
+        // Title matches rank above matches only in the page text
+        assert_eq!(ranked_titles(&index, &bookmarks, "borrow checker"), vec!["Borrow Checker Explained", "Weekly Notes"]);
+    }
+
+    #[test]
+    fn test_upsert_and_remove() {
+        let mut rust = bookmark("https://example.com/a", "Rust");
+        let mut index = SearchIndex::build(std::slice::from_ref(&rust), Vec::new(), |_| None);
+        assert_eq!(index.search("rust").unwrap().len(), 1);
+
+        rust.title = "Python".to_string();
+        index.upsert(&rust, None);
+        assert_eq!(index.len(), 1);
+        assert!(index.search("rust").unwrap().is_empty());
+        assert_eq!(index.search("python").unwrap().len(), 1);
+
+        index.remove(&rust.id);
+        assert_eq!(index.len(), 0);
+        assert!(index.search("python").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("search_index.json");
+        let bookmarks = vec![bookmark("https://example.com/a", "Rust Book")];
+        let index = SearchIndex::build(&bookmarks, vec!["abc".to_string()], |_| None);
+        index.save(&path).unwrap();
+
+        let loaded = SearchIndex::load(&path).unwrap();
+        assert_eq!(loaded.heads(), ["abc".to_string()]);
+        assert_eq!(loaded.search("book"), index.search("book"));
+
+        fs::write(&path, "not json").unwrap();
+        assert!(SearchIndex::load(&path).is_none());
+    }
+
+    #[test]
+    fn test_quoted_phrase_ranks_contiguous_match_first() {
+        let bookmarks = vec![
+            bookmark("https://example.com/a", "Learning machine shop"),
+            bookmark("https://example.com/b", "A gentle guide to machine learning for working programmers"),
+        ];
+        let index = SearchIndex::build(&bookmarks, Vec::new(), |_| None);
+
+        let query = "\"machine learning\"";
+        let mut scores = index.search(query).unwrap();
+        // The shorter title wins on words alone
+        assert!(scores[&bookmarks[0].id] > scores[&bookmarks[1].id]);
+        boost_phrases(&mut scores, &bookmarks, query);
+        assert!(scores[&bookmarks[1].id] > scores[&bookmarks[0].id]);
+    }
+
+    #[test]
+    fn test_search_matches_description() {
+        let mut described = bookmark("https://example.com/a", "Weekly Notes");
+        described.description = Some("Thoughts on async runtimes".to_string());
+        let bookmarks = vec![described, bookmark("https://example.com/b", "Other")];
+        let index = SearchIndex::build(&bookmarks, Vec::new(), |_| None);
+
+        assert_eq!(ranked_titles(&index, &bookmarks, "runtime"), vec!["Weekly Notes"]);
+    }
+
+    /// Run with `cargo test --release -- --ignored test_search_performance` to check timings
+    #[test]
+    #[ignore]
+    fn test_search_performance_with_50k_bookmarks() {
+        const SYLLABLES: [&str; 12] = ["ka", "lo", "mi", "ne", "ru", "so", "ta", "vi", "ze", "po", "da", "gu"];
+        let word = |n: usize| format!("{}{}{}", SYLLABLES[n % 12], SYLLABLES[n / 12 % 12], SYLLABLES[n / 144 % 12]);
+        // A simple linear congruential generator keeps the library the same on every run
+        let mut seed: usize = 42;
+        let mut next = move || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            seed >> 33
+        };
+
+        let bookmarks: Vec<Bookmark> = (0..50_000)
+            .map(|n| {
+                let title: Vec<String> = (0..6).map(|_| word(next() % 1728)).collect();
+                let mut bookmark = bookmark(&format!("https://example.com/{}", n), &title.join(" "));
+                bookmark.description = Some((0..20).map(|_| word(next() % 1728)).collect::<Vec<_>>().join(" "));
+                bookmark
+            })
+            .collect();
+
+        let started = std::time::Instant::now();
+        let index = SearchIndex::build(&bookmarks, Vec::new(), |_| None);
+        println!("built index of {} bookmarks in {:?}", bookmarks.len(), started.elapsed());
+
+        // Exact, two-word, prefix and fuzzy queries
+        for query in [word(7), format!("{} {}", word(7), word(300)), word(7)[..4].to_string(), word(7).replacen('a', "e", 1)] {
+            let started = std::time::Instant::now();
+            let matches = index.search(&query).unwrap().len();
+            let elapsed = started.elapsed();
+            println!("{:>14}: {} matches in {:?}", query, matches, elapsed);
+            assert!(elapsed < std::time::Duration::from_secs(1), "searching {:?} took {:?}", query, elapsed);
+        }
+    }
+}
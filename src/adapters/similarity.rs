@@ -0,0 +1,354 @@
+use crate::adapters::text_analysis::{stem, tokenize};
+use crate::traits::BookmarkRepository;
+use crate::types::{normalize_tag, Bookmark, BookmarkResult};
+use std::collections::HashMap;
+use url::Url;
+
+// Field weights applied to term frequencies
+const TITLE_WEIGHT: f64 = 3.0;
+const DESCRIPTION_WEIGHT: f64 = 2.0;
+const DOMAIN_WEIGHT: f64 = 2.0;
+const TAG_WEIGHT: f64 = 2.5;
+const CONTENT_WEIGHT: f64 = 0.5;
+
+/// Words of page text considered, so a long article doesn't drown out its title
+const MAX_CONTENT_WORDS: usize = 2000;
+
+/// Bookmarks less similar than this are not considered related
+const MIN_RELATED_SCORE: f64 = 0.1;
+
+/// Words used to label a cluster
+const CLUSTER_LABEL_WORDS: usize = 3;
+
+/// Weighted frequency of each term in a bookmark's title, description, domain and page text
+///
+/// Text is stemmed; the domain is a single `site:` term so `github.com` doesn't
+/// match every page that mentions GitHub.
+pub(crate) fn term_frequencies(bookmark: &Bookmark, content: Option<&str>) -> HashMap<String, f64> {
+    let mut frequencies: HashMap<String, f64> = HashMap::new();
+    for_each_word(bookmark, content, |word, weight| {
+        *frequencies.entry(stem(word)).or_default() += weight;
+    });
+
+    let host = Url::parse(&bookmark.url).ok()
+        .and_then(|url| url.host_str().map(|host| host.trim_start_matches("www.").to_string()));
+    if let Some(host) = host {
+        *frequencies.entry(format!("site:{}", host)).or_default() += DOMAIN_WEIGHT;
+    }
+    frequencies
+}
+
+fn for_each_word(bookmark: &Bookmark, content: Option<&str>, mut visit: impl FnMut(&str, f64)) {
+    for word in tokenize(&bookmark.title) {
+        visit(&word, TITLE_WEIGHT);
+    }
+    if let Some(ref description) = bookmark.description {
+        for word in tokenize(description) {
+            visit(&word, DESCRIPTION_WEIGHT);
+        }
+    }
+    if let Some(content) = content {
+        for word in content.split_whitespace().take(MAX_CONTENT_WORDS).flat_map(tokenize) {
+            visit(&word, CONTENT_WEIGHT);
+        }
+    }
+}
+
+/// Inverse document frequencies learned from a set of documents
+pub(crate) struct TermWeights {
+    document_frequency: HashMap<String, usize>,
+    documents: usize,
+}
+
+impl TermWeights {
+    pub(crate) fn new(documents: &[HashMap<String, f64>]) -> Self {
+        let mut document_frequency: HashMap<String, usize> = HashMap::new();
+        for terms in documents {
+            for term in terms.keys() {
+                *document_frequency.entry(term.clone()).or_default() += 1;
+            }
+        }
+        Self { document_frequency, documents: documents.len() }
+    }
+
+    /// Turn term frequencies into a unit-length TF-IDF vector
+    pub(crate) fn weigh(&self, terms: &HashMap<String, f64>) -> HashMap<String, f64> {
+        let mut vector: HashMap<String, f64> = terms.iter()
+            .map(|(term, frequency)| {
+                let df = self.document_frequency.get(term).copied().unwrap_or(0) as f64;
+                let idf = ((1.0 + self.documents as f64) / (1.0 + df)).ln() + 1.0;
+                (term.clone(), frequency.ln_1p() * idf)
+            })
+            .collect();
+        normalize(&mut vector);
+        vector
+    }
+}
+
+/// Scale a vector to unit length, leaving an empty vector alone
+pub(crate) fn normalize(vector: &mut HashMap<String, f64>) {
+    let length = vector.values().map(|weight| weight * weight).sum::<f64>().sqrt();
+    if length > 0.0 {
+        for weight in vector.values_mut() {
+            *weight /= length;
+        }
+    }
+}
+
+/// Dot product of two sparse vectors; their cosine similarity when both have unit length
+pub(crate) fn dot(a: &HashMap<String, f64>, b: &HashMap<String, f64>) -> f64 {
+    let (small, large) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    small.iter()
+        .filter_map(|(term, weight)| large.get(term).map(|other| weight * other))
+        .sum()
+}
+
+/// A bookmark similar to another, with how similar it is (0 to 1)
+#[derive(Debug, Clone)]
+pub struct Related {
+    pub bookmark: Bookmark,
+    pub score: f64,
+}
+
+/// A group of bookmarks about the same topic
+#[derive(Debug, Clone)]
+pub struct Cluster {
+    /// The words that best describe the group
+    pub label: Vec<String>,
+    /// Tags carried by at least half of the group, most common first
+    pub tags: Vec<String>,
+    /// Members, most typical first
+    pub bookmarks: Vec<Bookmark>,
+}
+
+/// Every bookmark in a repository with its captured page text, read once so
+/// [`SimilarityIndex`] and [`TagSuggester`](super::TagSuggester) can share it
+pub struct LibraryText {
+    pub bookmarks: Vec<Bookmark>,
+    contents: HashMap<String, String>,
+}
+
+impl LibraryText {
+    pub async fn load(repository: &dyn BookmarkRepository) -> BookmarkResult<Self> {
+        let bookmarks = repository.find_all(None).await?;
+        let mut contents = HashMap::new();
+        for bookmark in &bookmarks {
+            if let Some(content) = repository.find_content(&bookmark.id).await? {
+                contents.insert(bookmark.id.clone(), content);
+            }
+        }
+        Ok(Self { bookmarks, contents })
+    }
+
+    /// Captured page text of the bookmark with `id`
+    pub fn content(&self, id: &str) -> Option<String> {
+        self.contents.get(id).cloned()
+    }
+}
+
+/// TF-IDF vectors of a whole library, for finding similar bookmarks
+///
+/// Each bookmark is described by the stemmed words of its title, description and
+/// captured page text, its domain, and its tags, so bookmarks sharing tags or a
+/// site count as similar even when their titles don't overlap.
+pub struct SimilarityIndex {
+    bookmarks: Vec<Bookmark>,
+    vectors: Vec<HashMap<String, f64>>,
+    /// The most common spelling of each stem, for readable cluster labels
+    spellings: HashMap<String, String>,
+}
+
+impl SimilarityIndex {
+    /// Index bookmarks, looking up captured page text by ID
+    pub fn build(bookmarks: Vec<Bookmark>, content: impl Fn(&str) -> Option<String>) -> Self {
+        let mut spelling_counts: HashMap<String, HashMap<String, usize>> = HashMap::new();
+        let frequencies: Vec<HashMap<String, f64>> = bookmarks.iter()
+            .map(|bookmark| {
+                let content = content(&bookmark.id);
+                for_each_word(bookmark, None, |word, _| {
+                    *spelling_counts.entry(stem(word)).or_default().entry(word.to_string()).or_default() += 1;
+                });
+                let mut terms = term_frequencies(bookmark, content.as_deref());
+                for tag in &bookmark.tags {
+                    *terms.entry(format!("tag:{}", normalize_tag(tag))).or_default() += TAG_WEIGHT;
+                }
+                terms
+            })
+            .collect();
+
+        let weights = TermWeights::new(&frequencies);
+        let vectors = frequencies.iter().map(|terms| weights.weigh(terms)).collect();
+        let spellings = spelling_counts.into_iter()
+            .filter_map(|(stem, counts)| {
+                let spelling = counts.into_iter().max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))?.0;
+                Some((stem, spelling))
+            })
+            .collect();
+        Self { bookmarks, vectors, spellings }
+    }
+
+    /// Index every bookmark in a repository, with its captured page text if any
+    pub async fn from_repository(repository: &dyn BookmarkRepository) -> BookmarkResult<Self> {
+        Ok(Self::from_library(&LibraryText::load(repository).await?))
+    }
+
+    /// Index a library that has already been read
+    pub fn from_library(library: &LibraryText) -> Self {
+        Self::build(library.bookmarks.clone(), |id| library.content(id))
+    }
+
+    /// The bookmarks most similar to the one with `id`, best first
+    pub fn related(&self, id: &str, limit: usize) -> Vec<Related> {
+        let Some(index) = self.bookmarks.iter().position(|bookmark| bookmark.id == id) else {
+            return Vec::new();
+        };
+
+        let mut related: Vec<Related> = self.vectors.iter().enumerate()
+            .filter(|(other, _)| *other != index)
+            .map(|(other, vector)| Related { bookmark: self.bookmarks[other].clone(), score: dot(&self.vectors[index], vector) })
+            .filter(|related| related.score >= MIN_RELATED_SCORE)
+            .collect();
+        related.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.bookmark.title.cmp(&b.bookmark.title)));
+        related.truncate(limit);
+        related
+    }
+
+    /// Group the library into topics
+    ///
+    /// Each bookmark joins the group whose centroid it is most similar to, if at least
+    /// `threshold`, or starts a new one; a second pass reassigns everything against the
+    /// final centroids. Groups smaller than `min_size` are left out. Largest groups come first.
+    pub fn clusters(&self, threshold: f64, min_size: usize) -> Vec<Cluster> {
+        let mut sums: Vec<HashMap<String, f64>> = Vec::new();
+        let mut centroids: Vec<HashMap<String, f64>> = Vec::new();
+        for vector in &self.vectors {
+            match best_match(vector, &centroids, threshold) {
+                Some((cluster, _)) => {
+                    for (term, weight) in vector {
+                        *sums[cluster].entry(term.clone()).or_default() += weight;
+                    }
+                    centroids[cluster] = sums[cluster].clone();
+                    normalize(&mut centroids[cluster]);
+                }
+                None => {
+                    sums.push(vector.clone());
+                    centroids.push(vector.clone());
+                }
+            }
+        }
+
+        let mut members: Vec<Vec<(usize, f64)>> = vec![Vec::new(); centroids.len()];
+        for (index, vector) in self.vectors.iter().enumerate() {
+            if let Some((cluster, score)) = best_match(vector, &centroids, threshold) {
+                members[cluster].push((index, score));
+            }
+        }
+
+        let mut clusters: Vec<Cluster> = members.into_iter()
+            .zip(centroids)
+            .filter(|(members, _)| members.len() >= min_size.max(2))
+            .map(|(mut members, centroid)| {
+                members.sort_by(|a, b| b.1.total_cmp(&a.1));
+                let bookmarks: Vec<Bookmark> = members.iter().map(|(index, _)| self.bookmarks[*index].clone()).collect();
+                Cluster { label: self.label(&centroid), tags: common_tags(&bookmarks), bookmarks }
+            })
+            .collect();
+        clusters.sort_by(|a, b| b.bookmarks.len().cmp(&a.bookmarks.len()).then_with(|| a.label.cmp(&b.label)));
+        clusters
+    }
+
+    /// The highest-weighted words of a centroid, in their most common spelling
+    fn label(&self, centroid: &HashMap<String, f64>) -> Vec<String> {
+        let mut words: Vec<(&String, f64)> = centroid.iter()
+            .filter(|(term, _)| !term.contains(':'))
+            .filter_map(|(term, weight)| self.spellings.get(term).map(|spelling| (spelling, *weight)))
+            .collect();
+        words.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        words.into_iter().take(CLUSTER_LABEL_WORDS).map(|(word, _)| word.clone()).collect()
+    }
+}
+
+/// The centroid most similar to `vector`, if it reaches `threshold`
+fn best_match(vector: &HashMap<String, f64>, centroids: &[HashMap<String, f64>], threshold: f64) -> Option<(usize, f64)> {
+    centroids.iter()
+        .map(|centroid| dot(vector, centroid))
+        .enumerate()
+        .filter(|(_, score)| *score >= threshold)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Tags on at least half of the bookmarks, most common first
+fn common_tags(bookmarks: &[Bookmark]) -> Vec<String> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for bookmark in bookmarks {
+        for tag in &bookmark.tags {
+            *counts.entry(normalize_tag(tag)).or_default() += 1;
+        }
+    }
+    let mut tags: Vec<(String, usize)> = counts.into_iter()
+        .filter(|(_, count)| count * 2 >= bookmarks.len())
+        .collect();
+    tags.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    tags.into_iter().map(|(tag, _)| tag).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bookmark(url: &str, title: &str, tags: &[&str]) -> Bookmark {
+        Bookmark::new(url, title).unwrap().with_tags(tags.iter().map(|tag| tag.to_string()).collect())
+    }
+
+    fn library() -> Vec<Bookmark> {
+        vec![
+            bookmark("https://tokio.rs/tokio/tutorial", "Tokio tutorial: async Rust", &["rust"]),
+            bookmark("https://blog.rust-lang.org/async-closures", "Async closures in Rust", &["rust"]),
+            bookmark("https://without.boats/blog/async-rust", "Why async Rust", &[]),
+            bookmark("https://example.com/pasta", "Weeknight pasta recipes", &["cooking"]),
+            bookmark("https://example.org/soup", "Easy weeknight soup recipes", &[]),
+            bookmark("https://gardening.example.net/tomatoes", "Pruning tomato plants", &[]),
+        ]
+    }
+
+    fn titles(bookmarks: impl IntoIterator<Item = Bookmark>) -> Vec<String> {
+        bookmarks.into_iter().map(|bookmark| bookmark.title).collect()
+    }
+
+    #[test]
+    fn test_related_ranks_by_shared_terms_tags_and_domain() {
+        let bookmarks = library();
+        let id = bookmarks[2].id.clone();
+        let index = SimilarityIndex::build(bookmarks, |_| None);
+
+        let related = titles(index.related(&id, 10).into_iter().map(|related| related.bookmark));
+        assert_eq!(related.len(), 2);
+        assert!(related.contains(&"Tokio tutorial: async Rust".to_string()));
+        assert!(related.contains(&"Async closures in Rust".to_string()));
+        assert!(index.related("missing", 10).is_empty());
+    }
+
+    #[test]
+    fn test_related_uses_content() {
+        let bookmarks = library();
+        let (garden, soup) = (bookmarks[5].id.clone(), bookmarks[4].id.clone());
+        let index = SimilarityIndex::build(bookmarks, |id| {
+            (id == garden || id == soup).then(|| "tomato tomato basil harvest".to_string())
+        });
+        assert_eq!(titles(index.related(&garden, 1).into_iter().map(|related| related.bookmark)), vec!["Easy weeknight soup recipes"]);
+    }
+
+    #[test]
+    fn test_clusters_group_topics() {
+        let index = SimilarityIndex::build(library(), |_| None);
+        let clusters = index.clusters(0.2, 2);
+
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].bookmarks.len(), 3);
+        assert_eq!(clusters[0].tags, vec!["rust"]);
+        assert!(clusters[0].label.contains(&"async".to_string()));
+        assert_eq!(clusters[1].bookmarks.len(), 2);
+        assert!(clusters[1].label.contains(&"recipes".to_string()));
+        assert!(clusters.iter().all(|cluster| cluster.bookmarks.iter().all(|b| b.title != "Pruning tomato plants")));
+    }
+}
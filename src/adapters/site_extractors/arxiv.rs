@@ -0,0 +1,168 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use scraper::{Html, Selector};
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+use super::{is_host, path_segments, select_text};
+use crate::adapters::page_fetcher::PageFetcher;
+use crate::adapters::web_extractor::{clean_text, meta_content, parse_metadata};
+use crate::traits::{MetadataExtractor, SiteExtractor};
+use crate::types::{ExtractedMetadata, ExtractorError};
+
+/// arXiv papers: authors, abstract and submission date
+///
+/// Links to the PDF are looked up through the paper's abstract page.
+pub struct ArxivExtractor {
+    fetcher: Arc<PageFetcher>,
+}
+
+impl ArxivExtractor {
+    pub fn new(fetcher: Arc<PageFetcher>) -> Self {
+        Self { fetcher }
+    }
+}
+
+impl SiteExtractor for ArxivExtractor {
+    fn matches(&self, url: &Url) -> bool {
+        is_host(url, "arxiv.org") && paper_id(url).is_some()
+    }
+}
+
+#[async_trait]
+impl MetadataExtractor for ArxivExtractor {
+    async fn extract_metadata(&self, url: &str, timeout: Duration) -> Result<ExtractedMetadata, ExtractorError> {
+        let parsed_url = Url::parse(url)
+            .map_err(|_| ExtractorError::InvalidUrl(url.to_string()))?;
+        let id = paper_id(&parsed_url)
+            .ok_or_else(|| ExtractorError::InvalidUrl(url.to_string()))?;
+
+        let abstract_url = format!("https://arxiv.org/abs/{}", id);
+        let (html, page_url) = self.fetcher.fetch(&abstract_url, timeout).await?;
+        Ok(parse_abstract_page(&html, &page_url))
+    }
+}
+
+/// The paper ID in `/abs/<id>` or `/pdf/<id>.pdf`, including old-style IDs like `math/0211159`
+fn paper_id(url: &Url) -> Option<String> {
+    match path_segments(url).as_slice() {
+        ["abs" | "pdf", id @ ..] if !id.is_empty() => {
+            let id = id.join("/");
+            Some(id.strip_suffix(".pdf").map(str::to_string).unwrap_or(id))
+        }
+        _ => None,
+    }
+}
+
+fn parse_abstract_page(html: &str, page_url: &Url) -> ExtractedMetadata {
+    let mut metadata = parse_metadata(html, page_url);
+    let document = Html::parse_document(html);
+
+    if let Some(title) = meta_content(&document, &["citation_title"]) {
+        metadata.title = Some(title);
+    }
+
+    let authors = citation_authors(&document);
+    if !authors.is_empty() {
+        metadata.author = Some(authors.join(", "));
+    }
+
+    let abstract_text = meta_content(&document, &["citation_abstract"])
+        .or_else(|| select_text(&document, "blockquote.abstract")
+            .map(|text| text.trim_start_matches("Abstract:").trim().to_string()));
+    if abstract_text.is_some() {
+        metadata.description = abstract_text;
+    }
+
+    // citation_date is the first submission; citation_online_date is the latest version
+    let submitted = meta_content(&document, &["citation_date"])
+        .and_then(|date| NaiveDate::parse_from_str(&date, "%Y/%m/%d").ok())
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|date| date.and_utc());
+    if submitted.is_some() {
+        metadata.publish_date = submitted;
+    }
+
+    let id = meta_content(&document, &["citation_arxiv_id"]).or_else(|| paper_id(page_url));
+    if let Some(id) = id {
+        metadata.canonical_url = Some(format!("https://arxiv.org/abs/{}", id));
+        metadata.site_details.insert("arxiv_id".to_string(), id);
+    }
+    if let Some(pdf_url) = meta_content(&document, &["citation_pdf_url"]) {
+        metadata.site_details.insert("pdf_url".to_string(), pdf_url);
+    }
+
+    metadata
+}
+
+/// Author names from `citation_author` tags, turning `Last, First` into `First Last`
+fn citation_authors(document: &Html) -> Vec<String> {
+    let Ok(selector) = Selector::parse("meta[name='citation_author']") else {
+        return Vec::new();
+    };
+    document.select(&selector)
+        .filter_map(|element| element.value().attr("content").and_then(clean_text))
+        .map(|name| match name.split_once(',') {
+            Some((last, first)) if !first.trim().is_empty() => format!("{} {}", first.trim(), last.trim()),
+            _ => name,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::site_extractors::tests::test_fetcher;
+    use chrono::{TimeZone, Utc};
+
+    const FIXTURE: &str = include_str!("fixtures/arxiv_abs.html");
+
+    #[test]
+    fn test_paper_id() {
+        let id = |url: &str| paper_id(&Url::parse(url).unwrap());
+
+        assert_eq!(id("https://arxiv.org/abs/1706.03762"), Some("1706.03762".to_string()));
+        assert_eq!(id("https://arxiv.org/abs/1706.03762v7"), Some("1706.03762v7".to_string()));
+        assert_eq!(id("https://arxiv.org/pdf/1706.03762.pdf"), Some("1706.03762".to_string()));
+        assert_eq!(id("https://arxiv.org/abs/math/0211159"), Some("math/0211159".to_string()));
+        assert_eq!(id("https://arxiv.org/list/cs.CL/recent"), None);
+        assert_eq!(id("https://arxiv.org/abs/"), None);
+    }
+
+    #[test]
+    fn test_matches_paper_urls() {
+        let extractor = ArxivExtractor::new(test_fetcher());
+        assert!(extractor.matches(&Url::parse("https://arxiv.org/pdf/1706.03762").unwrap()));
+        assert!(extractor.matches(&Url::parse("https://export.arxiv.org/abs/1706.03762").unwrap()));
+        assert!(!extractor.matches(&Url::parse("https://arxiv.org/").unwrap()));
+        assert!(!extractor.matches(&Url::parse("https://example.com/abs/1706.03762").unwrap()));
+    }
+
+    #[test]
+    fn test_parse_abstract_fixture() {
+        let url = Url::parse("https://arxiv.org/abs/1706.03762").unwrap();
+        let metadata = parse_abstract_page(FIXTURE, &url);
+
+        assert_eq!(metadata.title, Some("Attention Is All You Need".to_string()));
+        assert_eq!(metadata.author, Some("Ashish Vaswani, Noam Shazeer, Niki Parmar".to_string()));
+        assert!(metadata.description.unwrap().ends_with("based solely on attention mechanisms."));
+        assert_eq!(metadata.publish_date, Some(Utc.with_ymd_and_hms(2017, 6, 12, 0, 0, 0).unwrap()));
+        assert_eq!(metadata.canonical_url, Some("https://arxiv.org/abs/1706.03762".to_string()));
+        assert_eq!(metadata.site_details.get("arxiv_id"), Some(&"1706.03762".to_string()));
+        assert_eq!(metadata.site_details.get("pdf_url"), Some(&"http://arxiv.org/pdf/1706.03762".to_string()));
+    }
+
+    #[test]
+    fn test_parse_abstract_from_page_body() {
+        // Without citation tags the abstract comes from the page itself
+        let html = r#"<html><head><title>[2101.00001] Paper</title></head><body>
+            <blockquote class="abstract mathjax"><span class="descriptor">Abstract:</span> We study things.</blockquote>
+        </body></html>"#;
+        let url = Url::parse("https://arxiv.org/abs/2101.00001").unwrap();
+        let metadata = parse_abstract_page(html, &url);
+
+        assert_eq!(metadata.description, Some("We study things.".to_string()));
+        assert_eq!(metadata.site_details.get("arxiv_id"), Some(&"2101.00001".to_string()));
+    }
+}
@@ -0,0 +1,132 @@
+use async_trait::async_trait;
+use scraper::Html;
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+use super::{parse_count, path_segments, select_attr, select_text};
+use crate::adapters::page_fetcher::PageFetcher;
+use crate::adapters::web_extractor::{parse_metadata};
+use crate::traits::{MetadataExtractor, SiteExtractor};
+use crate::types::{ExtractedMetadata, ExtractorError};
+
+/// First path segments on github.com that are site pages rather than users or organizations
+const RESERVED_OWNERS: &[&str] = &[
+    "about", "apps", "collections", "enterprise", "explore", "features", "login", "marketplace",
+    "notifications", "orgs", "pricing", "pulls", "search", "settings", "sponsors", "topics", "trending",
+];
+
+/// Repository pages on GitHub: stars, description and primary language
+pub struct GitHubExtractor {
+    fetcher: Arc<PageFetcher>,
+}
+
+impl GitHubExtractor {
+    pub fn new(fetcher: Arc<PageFetcher>) -> Self {
+        Self { fetcher }
+    }
+}
+
+impl SiteExtractor for GitHubExtractor {
+    fn matches(&self, url: &Url) -> bool {
+        matches!(url.host_str(), Some("github.com" | "www.github.com")) && repository_path(url).is_some()
+    }
+}
+
+#[async_trait]
+impl MetadataExtractor for GitHubExtractor {
+    async fn extract_metadata(&self, url: &str, timeout: Duration) -> Result<ExtractedMetadata, ExtractorError> {
+        let (html, page_url) = self.fetcher.fetch(url, timeout).await?;
+        Ok(parse_repository_page(&html, &page_url))
+    }
+}
+
+/// Owner and name of the repository a URL points at, for `github.com/<owner>/<repo>`
+fn repository_path(url: &Url) -> Option<(&str, &str)> {
+    match path_segments(url).as_slice() {
+        [owner, repo] if !RESERVED_OWNERS.contains(owner) => Some((owner, repo)),
+        _ => None,
+    }
+}
+
+fn parse_repository_page(html: &str, page_url: &Url) -> ExtractedMetadata {
+    let mut metadata = parse_metadata(html, page_url);
+    let document = Html::parse_document(html);
+
+    if let Some((owner, repo)) = repository_path(page_url) {
+        let full_name = format!("{}/{}", owner, repo);
+        metadata.title = Some(full_name.clone());
+        metadata.author = Some(owner.to_string());
+        // Meta descriptions end with " - owner/repo"; the About box has the plain text
+        metadata.description = select_text(&document, ".BorderGrid p.f4")
+            .or_else(|| metadata.description.as_deref()
+                .map(|description| description.trim_end_matches(&format!(" - {}", full_name)).to_string()));
+    }
+
+    let stars = select_attr(&document, "#repo-stars-counter-star", "title")
+        .and_then(|count| parse_count(&count));
+    if let Some(stars) = stars {
+        metadata.site_details.insert("stars".to_string(), stars.to_string());
+    }
+
+    let language = select_text(&document, "[itemprop='programmingLanguage']")
+        .or_else(|| select_text(&document, ".BorderGrid span.color-fg-default.text-bold"))
+        .filter(|language| language != "Other");
+    if let Some(language) = language {
+        metadata.site_details.insert("language".to_string(), language);
+    }
+
+    metadata
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::site_extractors::tests::test_fetcher;
+
+    const FIXTURE: &str = include_str!("fixtures/github_repo.html");
+
+    #[test]
+    fn test_matches_repository_urls() {
+        let extractor = GitHubExtractor::new(test_fetcher());
+        let matches = |url: &str| extractor.matches(&Url::parse(url).unwrap());
+
+        assert!(matches("https://github.com/tokio-rs/tokio"));
+        assert!(matches("https://www.github.com/tokio-rs/tokio/"));
+        assert!(!matches("https://github.com/tokio-rs"));
+        assert!(!matches("https://github.com/tokio-rs/tokio/issues/1"));
+        assert!(!matches("https://github.com/topics/rust"));
+        assert!(!matches("https://gist.github.com/user/abc123"));
+        assert!(!matches("https://example.com/tokio-rs/tokio"));
+    }
+
+    #[test]
+    fn test_parse_repository_fixture() {
+        let url = Url::parse("https://github.com/tokio-rs/tokio").unwrap();
+        let metadata = parse_repository_page(FIXTURE, &url);
+
+        assert_eq!(metadata.title, Some("tokio-rs/tokio".to_string()));
+        assert_eq!(metadata.author, Some("tokio-rs".to_string()));
+        assert_eq!(
+            metadata.description,
+            Some("A runtime for writing reliable asynchronous applications with Rust. Provides I/O, networking, scheduling, timers, ...".to_string())
+        );
+        assert_eq!(metadata.site_details.get("stars"), Some(&"27431".to_string()));
+        assert_eq!(metadata.site_details.get("language"), Some(&"Rust".to_string()));
+        // Generic fields still come through
+        assert_eq!(metadata.site_name, Some("GitHub".to_string()));
+        assert_eq!(metadata.canonical_url, Some("https://github.com/tokio-rs/tokio".to_string()));
+    }
+
+    #[test]
+    fn test_parse_repository_without_about_box() {
+        let html = r#"<html><head>
+            <meta name="description" content="Small tool - someone/tool">
+        </head><body></body></html>"#;
+        let url = Url::parse("https://github.com/someone/tool").unwrap();
+        let metadata = parse_repository_page(html, &url);
+
+        assert_eq!(metadata.description, Some("Small tool".to_string()));
+        assert!(metadata.site_details.is_empty());
+    }
+}
@@ -0,0 +1,147 @@
+use async_trait::async_trait;
+use scraper::{Html, Selector};
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+use super::{is_host, parse_count, path_segments, select_attr, select_text};
+use crate::adapters::page_fetcher::PageFetcher;
+use crate::adapters::web_extractor::{parse_date, parse_metadata, resolve_url};
+use crate::traits::{MetadataExtractor, SiteExtractor};
+use crate::types::{ExtractedMetadata, ExtractorError};
+
+/// Hacker News item pages: the linked article, points and comment count
+pub struct HackerNewsExtractor {
+    fetcher: Arc<PageFetcher>,
+}
+
+impl HackerNewsExtractor {
+    pub fn new(fetcher: Arc<PageFetcher>) -> Self {
+        Self { fetcher }
+    }
+}
+
+impl SiteExtractor for HackerNewsExtractor {
+    fn matches(&self, url: &Url) -> bool {
+        is_host(url, "news.ycombinator.com")
+            && path_segments(url) == ["item"]
+            && url.query_pairs().any(|(key, _)| key == "id")
+    }
+}
+
+#[async_trait]
+impl MetadataExtractor for HackerNewsExtractor {
+    async fn extract_metadata(&self, url: &str, timeout: Duration) -> Result<ExtractedMetadata, ExtractorError> {
+        let (html, page_url) = self.fetcher.fetch(url, timeout).await?;
+        Ok(parse_item_page(&html, &page_url))
+    }
+}
+
+fn parse_item_page(html: &str, page_url: &Url) -> ExtractedMetadata {
+    let mut metadata = parse_metadata(html, page_url);
+    let document = Html::parse_document(html);
+    metadata.site_name = Some("Hacker News".to_string());
+    // The comment thread is not the page's own text
+    metadata.content = None;
+
+    if let Some(title) = select_text(&document, ".titleline > a") {
+        metadata.title = Some(title);
+    }
+
+    // Links and Show HN posts point elsewhere; Ask HN posts link back to the item itself
+    let article_url = select_attr(&document, ".titleline > a", "href")
+        .and_then(|href| resolve_url(page_url, &href))
+        .filter(|url| Url::parse(url).is_ok_and(|url| !is_host(&url, "news.ycombinator.com")));
+    match article_url {
+        Some(article_url) => {
+            metadata.site_details.insert("article_url".to_string(), article_url);
+        }
+        None => metadata.description = select_text(&document, ".toptext").or(metadata.description),
+    }
+
+    if let Some(points) = select_text(&document, ".subline .score").and_then(|score| parse_count(&score)) {
+        metadata.site_details.insert("points".to_string(), points.to_string());
+    }
+    if let Some(comments) = comment_count(&document) {
+        metadata.site_details.insert("comments".to_string(), comments.to_string());
+    }
+    if let Some(user) = select_text(&document, ".subline .hnuser") {
+        metadata.site_details.insert("submitted_by".to_string(), user);
+    }
+
+    // The age title is an ISO timestamp, sometimes followed by a Unix timestamp
+    let submitted = select_attr(&document, ".subline .age", "title")
+        .and_then(|title| title.split_whitespace().next().and_then(parse_date));
+    if submitted.is_some() {
+        metadata.publish_date = submitted;
+    }
+
+    metadata
+}
+
+/// Number of comments from the `97 comments` (or `discuss`) link under the title
+fn comment_count(document: &Html) -> Option<u64> {
+    let selector = Selector::parse(".subline a").ok()?;
+    document.select(&selector)
+        .map(|link| link.text().collect::<String>())
+        .find_map(|text| {
+            if text.trim() == "discuss" {
+                Some(0)
+            } else if text.contains("comment") {
+                parse_count(&text)
+            } else {
+                None
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::site_extractors::tests::test_fetcher;
+    use chrono::{TimeZone, Utc};
+
+    const FIXTURE: &str = include_str!("fixtures/hacker_news_item.html");
+
+    #[test]
+    fn test_matches_item_urls() {
+        let extractor = HackerNewsExtractor::new(test_fetcher());
+        let matches = |url: &str| extractor.matches(&Url::parse(url).unwrap());
+
+        assert!(matches("https://news.ycombinator.com/item?id=38000000"));
+        assert!(!matches("https://news.ycombinator.com/"));
+        assert!(!matches("https://news.ycombinator.com/item"));
+        assert!(!matches("https://news.ycombinator.com/user?id=crdtfan"));
+    }
+
+    #[test]
+    fn test_parse_item_fixture() {
+        let url = Url::parse("https://news.ycombinator.com/item?id=38000000").unwrap();
+        let metadata = parse_item_page(FIXTURE, &url);
+
+        assert_eq!(metadata.title, Some("Automerge 2.0".to_string()));
+        assert_eq!(metadata.site_name, Some("Hacker News".to_string()));
+        assert_eq!(metadata.site_details.get("article_url"), Some(&"https://automerge.org/blog/automerge-2/".to_string()));
+        assert_eq!(metadata.site_details.get("points"), Some(&"412".to_string()));
+        assert_eq!(metadata.site_details.get("comments"), Some(&"97".to_string()));
+        assert_eq!(metadata.site_details.get("submitted_by"), Some(&"crdtfan".to_string()));
+        assert_eq!(metadata.publish_date, Some(Utc.with_ymd_and_hms(2023, 10, 24, 16, 2, 11).unwrap()));
+        assert_eq!(metadata.content, None);
+    }
+
+    #[test]
+    fn test_parse_ask_hn_item() {
+        let html = r#"<html><body><table class="fatitem">
+            <tr><td class="title"><span class="titleline"><a href="item?id=1">Ask HN: How do you organize bookmarks?</a></span></td></tr>
+            <tr><td class="subtext"><span class="subline"><span class="score">5 points</span> by <a class="hnuser">asker</a> | <a href="item?id=1">discuss</a></span></td></tr>
+            <tr><td><div class="toptext">I have thousands of tabs open.</div></td></tr>
+        </table></body></html>"#;
+        let url = Url::parse("https://news.ycombinator.com/item?id=1").unwrap();
+        let metadata = parse_item_page(html, &url);
+
+        assert_eq!(metadata.title, Some("Ask HN: How do you organize bookmarks?".to_string()));
+        assert_eq!(metadata.description, Some("I have thousands of tabs open.".to_string()));
+        assert_eq!(metadata.site_details.get("article_url"), None);
+        assert_eq!(metadata.site_details.get("comments"), Some(&"0".to_string()));
+    }
+}
@@ -0,0 +1,197 @@
+use async_trait::async_trait;
+use scraper::{Html, Selector};
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+use crate::adapters::{HttpCache, WebExtractor};
+use crate::adapters::page_fetcher::PageFetcher;
+use crate::adapters::web_extractor::clean_text;
+use crate::traits::{MetadataExtractor, SiteExtractor};
+use crate::types::{ExtractedMetadata, ExtractorError, MetadataConfig};
+
+pub mod arxiv;
+pub mod github;
+pub mod hacker_news;
+pub mod youtube;
+
+pub use arxiv::ArxivExtractor;
+pub use github::GitHubExtractor;
+pub use hacker_news::HackerNewsExtractor;
+pub use youtube::YouTubeExtractor;
+
+/// Picks a site-specific extractor by URL, falling back to generic HTML scraping
+pub struct ExtractorRegistry {
+    sites: Vec<Box<dyn SiteExtractor>>,
+    fallback: Box<dyn MetadataExtractor>,
+}
+
+impl ExtractorRegistry {
+    /// A registry with no site extractors, using `fallback` for every URL
+    pub fn new(fallback: Box<dyn MetadataExtractor>) -> Self {
+        Self { sites: Vec::new(), fallback }
+    }
+
+    /// The built-in site extractors in front of [`WebExtractor`], all sharing one fetcher and cache
+    ///
+    /// Fails if the proxy, CA bundle, cookie files or headers in `config` are invalid.
+    pub fn with_builtin_sites(config: &MetadataConfig, cache: Option<Arc<HttpCache>>) -> Result<Self, ExtractorError> {
+        let fetcher = Arc::new(PageFetcher::new(config)?.with_cache(cache));
+        Ok(Self::new(Box::new(WebExtractor::new(fetcher.clone())))
+            .register(Box::new(GitHubExtractor::new(fetcher.clone())))
+            .register(Box::new(YouTubeExtractor::new(fetcher.clone())))
+            .register(Box::new(ArxivExtractor::new(fetcher.clone())))
+            .register(Box::new(HackerNewsExtractor::new(fetcher))))
+    }
+
+    /// Add a site extractor; earlier registrations win when several match
+    pub fn register(mut self, extractor: Box<dyn SiteExtractor>) -> Self {
+        self.sites.push(extractor);
+        self
+    }
+
+    fn extractor_for(&self, url: &str) -> &dyn MetadataExtractor {
+        let site = Url::parse(url).ok()
+            .and_then(|url| self.sites.iter().find(|site| site.matches(&url)));
+        match site {
+            Some(site) => site.as_ref(),
+            None => self.fallback.as_ref(),
+        }
+    }
+}
+
+#[async_trait]
+impl MetadataExtractor for ExtractorRegistry {
+    async fn extract_metadata(&self, url: &str, timeout: Duration) -> Result<ExtractedMetadata, ExtractorError> {
+        self.extractor_for(url).extract_metadata(url, timeout).await
+    }
+}
+
+/// Whether `url` is on `domain` or one of its subdomains
+fn is_host(url: &Url, domain: &str) -> bool {
+    url.host_str().is_some_and(|host| {
+        host == domain || host.strip_suffix(domain).is_some_and(|prefix| prefix.ends_with('.'))
+    })
+}
+
+/// Non-empty path segments of a URL
+fn path_segments(url: &Url) -> Vec<&str> {
+    url.path_segments()
+        .map(|segments| segments.filter(|segment| !segment.is_empty()).collect())
+        .unwrap_or_default()
+}
+
+/// Collapsed text of the first element matching `selector`
+fn select_text(document: &Html, selector: &str) -> Option<String> {
+    let selector = Selector::parse(selector).ok()?;
+    document.select(&selector)
+        .find_map(|element| clean_text(&element.text().collect::<String>()))
+}
+
+/// An attribute of the first element matching `selector` that has it
+fn select_attr(document: &Html, selector: &str, attribute: &str) -> Option<String> {
+    let selector = Selector::parse(selector).ok()?;
+    document.select(&selector)
+        .find_map(|element| element.value().attr(attribute).and_then(clean_text))
+}
+
+/// A string value from JSON embedded in a page's scripts, e.g. `"ownerChannelName":"..."`
+fn embedded_json_string(html: &str, key: &str) -> Option<String> {
+    let marker = format!("\"{}\":\"", key);
+    let start = html.find(&marker)? + marker.len() - 1;
+    let rest = &html[start..];
+
+    // Find the closing quote, skipping escaped characters
+    let mut escaped = false;
+    let end = rest.char_indices().skip(1).find_map(|(index, c)| {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return Some(index),
+            _ => {}
+        }
+        None
+    })?;
+    serde_json::from_str(&rest[..=end]).ok()
+}
+
+/// Digits of a count such as `12,345` or `1 234`
+fn parse_count(text: &str) -> Option<u64> {
+    let digits: String = text.chars().filter(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::traits::metadata_extractor::MockMetadataExtractor;
+
+    /// A fetcher for extractors whose tests never touch the network
+    pub(crate) fn test_fetcher() -> Arc<PageFetcher> {
+        Arc::new(PageFetcher::new(&MetadataConfig::default()).unwrap())
+    }
+
+    struct MockSiteExtractor {
+        host: &'static str,
+        title: &'static str,
+    }
+
+    #[async_trait]
+    impl MetadataExtractor for MockSiteExtractor {
+        async fn extract_metadata(&self, _url: &str, _timeout: Duration) -> Result<ExtractedMetadata, ExtractorError> {
+            Ok(ExtractedMetadata {
+                title: Some(self.title.to_string()),
+                ..Default::default()
+            })
+        }
+    }
+
+    impl SiteExtractor for MockSiteExtractor {
+        fn matches(&self, url: &Url) -> bool {
+            is_host(url, self.host)
+        }
+    }
+
+    fn fallback() -> Box<dyn MetadataExtractor> {
+        Box::new(MockMetadataExtractor {
+            should_fail: false,
+            extracted_title: Some("Generic".to_string()),
+            extracted_author: None,
+            extracted_content: None,
+        })
+    }
+
+    async fn title_for(registry: &ExtractorRegistry, url: &str) -> String {
+        registry.extract_metadata(url, Duration::from_secs(1)).await.unwrap().title.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_registry_selects_by_url() {
+        let registry = ExtractorRegistry::new(fallback())
+            .register(Box::new(MockSiteExtractor { host: "example.com", title: "First" }))
+            .register(Box::new(MockSiteExtractor { host: "blog.example.com", title: "Second" }))
+            .register(Box::new(MockSiteExtractor { host: "other.org", title: "Other" }));
+
+        assert_eq!(title_for(&registry, "https://example.com/post").await, "First");
+        // Earlier registrations take precedence
+        assert_eq!(title_for(&registry, "https://blog.example.com/post").await, "First");
+        assert_eq!(title_for(&registry, "https://www.other.org/").await, "Other");
+        assert_eq!(title_for(&registry, "https://notexample.com/").await, "Generic");
+        assert_eq!(title_for(&registry, "not a url").await, "Generic");
+    }
+
+    #[test]
+    fn test_embedded_json_string() {
+        let html = r#"<script>var data = {"title":"Say \"hi\" & wave","count":"3"};</script>"#;
+        assert_eq!(embedded_json_string(html, "title"), Some("Say \"hi\" & wave".to_string()));
+        assert_eq!(embedded_json_string(html, "count"), Some("3".to_string()));
+        assert_eq!(embedded_json_string(html, "missing"), None);
+    }
+
+    #[test]
+    fn test_parse_count() {
+        assert_eq!(parse_count("12,345"), Some(12345));
+        assert_eq!(parse_count(" 87 points"), Some(87));
+        assert_eq!(parse_count("none"), None);
+    }
+}
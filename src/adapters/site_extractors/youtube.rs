@@ -0,0 +1,168 @@
+use async_trait::async_trait;
+use scraper::Html;
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+use super::{embedded_json_string, is_host, path_segments, select_attr};
+use crate::adapters::page_fetcher::PageFetcher;
+use crate::adapters::web_extractor::{parse_date, parse_metadata};
+use crate::traits::{MetadataExtractor, SiteExtractor};
+use crate::types::{ExtractedMetadata, ExtractorError};
+
+/// YouTube videos: channel, duration and upload date
+pub struct YouTubeExtractor {
+    fetcher: Arc<PageFetcher>,
+}
+
+impl YouTubeExtractor {
+    pub fn new(fetcher: Arc<PageFetcher>) -> Self {
+        Self { fetcher }
+    }
+}
+
+impl SiteExtractor for YouTubeExtractor {
+    fn matches(&self, url: &Url) -> bool {
+        let segments = path_segments(url);
+        if is_host(url, "youtu.be") {
+            return segments.len() == 1;
+        }
+        is_host(url, "youtube.com") && match segments.as_slice() {
+            ["watch"] => url.query_pairs().any(|(key, _)| key == "v"),
+            ["shorts", _] | ["live", _] => true,
+            _ => false,
+        }
+    }
+}
+
+#[async_trait]
+impl MetadataExtractor for YouTubeExtractor {
+    async fn extract_metadata(&self, url: &str, timeout: Duration) -> Result<ExtractedMetadata, ExtractorError> {
+        let (html, page_url) = self.fetcher.fetch(url, timeout).await?;
+        Ok(parse_video_page(&html, &page_url))
+    }
+}
+
+fn parse_video_page(html: &str, page_url: &Url) -> ExtractedMetadata {
+    let mut metadata = parse_metadata(html, page_url);
+    let document = Html::parse_document(html);
+
+    // Schema.org microdata first, then the player data embedded in the page script
+    let channel = select_attr(&document, "[itemprop='author'] [itemprop='name']", "content")
+        .or_else(|| embedded_json_string(html, "ownerChannelName"));
+    if let Some(channel) = channel {
+        metadata.author = Some(channel.clone());
+        metadata.site_details.insert("channel".to_string(), channel);
+    }
+
+    let seconds = select_attr(&document, "meta[itemprop='duration']", "content")
+        .and_then(|duration| parse_iso_duration(&duration))
+        .or_else(|| embedded_json_string(html, "lengthSeconds").and_then(|seconds| seconds.parse().ok()));
+    if let Some(seconds) = seconds {
+        metadata.site_details.insert("duration".to_string(), format_duration(seconds));
+    }
+
+    let uploaded = select_attr(&document, "meta[itemprop='uploadDate']", "content")
+        .or_else(|| select_attr(&document, "meta[itemprop='datePublished']", "content"))
+        .or_else(|| embedded_json_string(html, "uploadDate"))
+        .and_then(|date| parse_date(&date));
+    if uploaded.is_some() {
+        metadata.publish_date = uploaded;
+    }
+
+    metadata
+}
+
+/// Seconds in an ISO 8601 duration such as `PT1H2M3S`
+fn parse_iso_duration(duration: &str) -> Option<u64> {
+    let rest = duration.strip_prefix('P')?;
+    let mut seconds = 0;
+    let mut number = String::new();
+    let mut in_time = false;
+    for c in rest.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => in_time = true,
+            unit => {
+                let value: u64 = number.parse().ok()?;
+                number.clear();
+                seconds += value * match (unit, in_time) {
+                    ('D', false) => 86_400,
+                    ('H', true) => 3_600,
+                    ('M', true) => 60,
+                    ('S', true) => 1,
+                    _ => return None,
+                };
+            }
+        }
+    }
+    number.is_empty().then_some(seconds)
+}
+
+/// `2:29` or `1:02:03`
+fn format_duration(seconds: u64) -> String {
+    let (hours, minutes, seconds) = (seconds / 3_600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::site_extractors::tests::test_fetcher;
+    use chrono::{TimeZone, Utc};
+
+    const FIXTURE: &str = include_str!("fixtures/youtube_watch.html");
+
+    #[test]
+    fn test_matches_video_urls() {
+        let extractor = YouTubeExtractor::new(test_fetcher());
+        let matches = |url: &str| extractor.matches(&Url::parse(url).unwrap());
+
+        assert!(matches("https://www.youtube.com/watch?v=5C_HPTJg5ek"));
+        assert!(matches("https://m.youtube.com/watch?v=5C_HPTJg5ek&t=30"));
+        assert!(matches("https://youtu.be/5C_HPTJg5ek"));
+        assert!(matches("https://www.youtube.com/shorts/abc123"));
+        assert!(!matches("https://www.youtube.com/watch"));
+        assert!(!matches("https://www.youtube.com/@Fireship"));
+        assert!(!matches("https://notyoutube.com/watch?v=5C_HPTJg5ek"));
+    }
+
+    #[test]
+    fn test_parse_video_fixture() {
+        let url = Url::parse("https://www.youtube.com/watch?v=5C_HPTJg5ek").unwrap();
+        let metadata = parse_video_page(FIXTURE, &url);
+
+        assert_eq!(metadata.title, Some("Rust in 100 Seconds".to_string()));
+        assert_eq!(metadata.author, Some("Fireship".to_string()));
+        assert_eq!(metadata.site_details.get("channel"), Some(&"Fireship".to_string()));
+        assert_eq!(metadata.site_details.get("duration"), Some(&"2:29".to_string()));
+        assert_eq!(metadata.publish_date, Some(Utc.with_ymd_and_hms(2021, 9, 7, 14, 0, 11).unwrap()));
+        assert_eq!(metadata.image_url, Some("https://i.ytimg.com/vi/5C_HPTJg5ek/maxresdefault.jpg".to_string()));
+    }
+
+    #[test]
+    fn test_parse_video_from_player_data() {
+        // Pages served without microdata still embed the player response
+        let html = r#"<html><head><title>Talk - YouTube</title></head><body>
+            <script>var ytInitialPlayerResponse = {"videoDetails":{"lengthSeconds":"3723"},"microformat":{"playerMicroformatRenderer":{"ownerChannelName":"RustConf & Friends"}}};</script>
+        </body></html>"#;
+        let url = Url::parse("https://youtu.be/xyz").unwrap();
+        let metadata = parse_video_page(html, &url);
+
+        assert_eq!(metadata.site_details.get("channel"), Some(&"RustConf & Friends".to_string()));
+        assert_eq!(metadata.site_details.get("duration"), Some(&"1:02:03".to_string()));
+    }
+
+    #[test]
+    fn test_parse_iso_duration() {
+        assert_eq!(parse_iso_duration("PT2M29S"), Some(149));
+        assert_eq!(parse_iso_duration("PT1H"), Some(3600));
+        assert_eq!(parse_iso_duration("P1DT1S"), Some(86_401));
+        assert_eq!(parse_iso_duration("2:29"), None);
+        assert_eq!(parse_iso_duration("PT5"), None);
+    }
+}
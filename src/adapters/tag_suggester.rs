@@ -0,0 +1,203 @@
+use crate::adapters::similarity::{dot, normalize, term_frequencies, LibraryText, TermWeights};
+use crate::adapters::text_analysis::analyze;
+use crate::traits::BookmarkRepository;
+use crate::types::{normalize_tag, Bookmark, BookmarkResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Added to the score of a tag whose name appears in the bookmark itself
+const NAME_MATCH_BONUS: f64 = 0.3;
+
+/// Suggestions scoring below this are not worth showing
+const MIN_SCORE: f64 = 0.15;
+
+/// Most suggestions offered for one bookmark
+pub const MAX_SUGGESTIONS: usize = 5;
+
+/// Suggests tags from the user's own vocabulary, entirely offline
+///
+/// Every bookmark becomes a TF-IDF vector over the stemmed words of its title,
+/// description and captured page text plus its domain. Each tag is profiled by
+/// the normalized centroid of the bookmarks carrying it, and a new bookmark is
+/// scored against each profile by cosine similarity, with a bonus when the
+/// tag's own name appears in the bookmark.
+pub struct TagSuggester {
+    profiles: Vec<TagProfile>,
+    weights: TermWeights,
+}
+
+struct TagProfile {
+    tag: String,
+    /// Stemmed words of the tag's last level, e.g. `machine learn` for `ai/machine-learning`
+    name_terms: Vec<String>,
+    /// Unit-length centroid of the TF-IDF vectors of the tag's bookmarks
+    centroid: HashMap<String, f64>,
+}
+
+/// A tag worth adding to a bookmark, with how well it fits
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagSuggestion {
+    pub tag: String,
+    pub score: f64,
+}
+
+impl TagSuggester {
+    /// Learn tag profiles from existing bookmarks, looking up captured page text by ID
+    pub fn train(bookmarks: &[Bookmark], content: impl Fn(&str) -> Option<String>) -> Self {
+        let frequencies: Vec<HashMap<String, f64>> = bookmarks.iter()
+            .map(|bookmark| term_frequencies(bookmark, content(&bookmark.id).as_deref()))
+            .collect();
+
+        let weights = TermWeights::new(&frequencies);
+
+        // Tags are profiled by their normalized form, so `Rust` and `rust` share one profile
+        let mut centroids: HashMap<String, HashMap<String, f64>> = HashMap::new();
+        for (bookmark, terms) in bookmarks.iter().zip(&frequencies) {
+            let vector = weights.weigh(terms);
+            let tags: BTreeSet<String> = bookmark.tags.iter()
+                .map(|tag| normalize_tag(tag))
+                .filter(|tag| !tag.is_empty())
+                .collect();
+            for tag in tags {
+                let centroid = centroids.entry(tag).or_default();
+                for (term, weight) in &vector {
+                    *centroid.entry(term.clone()).or_default() += weight;
+                }
+            }
+        }
+
+        let mut profiles: Vec<TagProfile> = centroids.into_iter()
+            .map(|(tag, mut centroid)| {
+                normalize(&mut centroid);
+                let name = tag.rsplit('/').next().unwrap_or(&tag).replace(['-', '_'], " ");
+                TagProfile { name_terms: analyze(&name), tag, centroid }
+            })
+            .collect();
+        profiles.sort_by(|a, b| a.tag.cmp(&b.tag));
+        Self { profiles, weights }
+    }
+
+    /// Train on every bookmark in a repository, with its captured page text if any
+    ///
+    /// Page text is only read when some bookmark has a tag to learn.
+    pub async fn from_repository(repository: &dyn BookmarkRepository) -> BookmarkResult<Self> {
+        let bookmarks = repository.find_all(None).await?;
+        if bookmarks.iter().all(|bookmark| bookmark.tags.is_empty()) {
+            return Ok(Self::train(&bookmarks, |_| None));
+        }
+        Ok(Self::from_library(&LibraryText::load(repository).await?))
+    }
+
+    /// Train on a library that has already been read
+    pub fn from_library(library: &LibraryText) -> Self {
+        Self::train(&library.bookmarks, |id| library.content(id))
+    }
+
+    /// The best-fitting tags the bookmark doesn't already have, best first
+    pub fn suggest(&self, bookmark: &Bookmark, content: Option<&str>) -> Vec<TagSuggestion> {
+        let terms = term_frequencies(bookmark, content);
+        let vector = self.weights.weigh(&terms);
+
+        let existing: HashSet<String> = bookmark.tags.iter().map(|tag| normalize_tag(tag)).collect();
+
+        let mut suggestions: Vec<TagSuggestion> = self.profiles.iter()
+            .filter(|profile| !existing.contains(&profile.tag))
+            .map(|profile| {
+                let similarity = dot(&vector, &profile.centroid);
+                let named = !profile.name_terms.is_empty()
+                    && profile.name_terms.iter().all(|term| terms.contains_key(term));
+                let bonus = if named { NAME_MATCH_BONUS } else { 0.0 };
+                TagSuggestion { tag: profile.tag.clone(), score: similarity + bonus }
+            })
+            .filter(|suggestion| suggestion.score >= MIN_SCORE)
+            .collect();
+        suggestions.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.tag.cmp(&b.tag)));
+        suggestions.truncate(MAX_SUGGESTIONS);
+        suggestions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::repository::MockBookmarkRepository;
+
+    fn bookmark(url: &str, title: &str, tags: &[&str]) -> Bookmark {
+        Bookmark::new(url, title).unwrap().with_tags(tags.iter().map(|tag| tag.to_string()).collect())
+    }
+
+    fn library() -> Vec<Bookmark> {
+        vec![
+            bookmark("https://doc.rust-lang.org/book/", "The Rust Programming Language", &["lang/rust"]),
+            bookmark("https://blog.rust-lang.org/async", "Async closures in Rust", &["lang/rust", "async"]),
+            bookmark("https://docs.python.org/3/", "Python documentation", &["lang/python"]),
+            bookmark("https://realpython.com/pandas", "Data analysis with pandas and Python", &["lang/python", "data-science"]),
+            bookmark("https://example.com/cooking", "Weeknight pasta recipes", &["cooking"]),
+        ]
+    }
+
+    fn tags(suggestions: &[TagSuggestion]) -> Vec<&str> {
+        suggestions.iter().map(|suggestion| suggestion.tag.as_str()).collect()
+    }
+
+    #[test]
+    fn test_suggests_tags_from_similar_bookmarks() {
+        let suggester = TagSuggester::train(&library(), |_| None);
+
+        let new = bookmark("https://blog.rust-lang.org/traits", "Understanding traits", &[]);
+        let suggestions = suggester.suggest(&new, Some("Traits in Rust describe shared behaviour"));
+        assert_eq!(suggestions[0].tag, "lang/rust");
+        assert!(!tags(&suggestions).contains(&"cooking"));
+
+        // Tags the bookmark already has are not suggested again, under any spelling
+        for spelling in ["lang/rust", " Lang/Rust "] {
+            let tagged = bookmark("https://blog.rust-lang.org/traits", "Understanding traits", &[spelling]);
+            assert!(!tags(&suggester.suggest(&tagged, None)).contains(&"lang/rust"));
+        }
+    }
+
+    #[test]
+    fn test_tag_spellings_share_a_profile() {
+        let mut bookmarks = library();
+        bookmarks.push(bookmark("https://this-week-in-rust.org/", "This week in Rust", &["Lang/Rust"]));
+        let suggester = TagSuggester::train(&bookmarks, |_| None);
+
+        let new = bookmark("https://blog.rust-lang.org/traits", "Understanding traits in Rust", &[]);
+        let suggestions = suggester.suggest(&new, None);
+        let suggested = tags(&suggestions).into_iter()
+            .filter(|tag| tag.eq_ignore_ascii_case("lang/rust"))
+            .collect::<Vec<_>>();
+        assert_eq!(suggested, vec!["lang/rust"]);
+    }
+
+    #[test]
+    fn test_tag_names_in_text_count() {
+        let suggester = TagSuggester::train(&library(), |_| None);
+
+        let new = bookmark("https://example.org/notebook", "A notebook for data science", &[]);
+        assert_eq!(suggester.suggest(&new, None)[0].tag, "data-science");
+    }
+
+    #[test]
+    fn test_no_suggestions_without_vocabulary_or_overlap() {
+        let empty = TagSuggester::train(&[], |_| None);
+        assert!(empty.suggest(&bookmark("https://example.com", "Anything", &[]), None).is_empty());
+
+        let suggester = TagSuggester::train(&library(), |_| None);
+        let unrelated = bookmark("https://gardening.example.net/", "Pruning tomato plants", &[]);
+        assert!(suggester.suggest(&unrelated, None).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_from_repository_uses_captured_content() {
+        let mut repo = MockBookmarkRepository::new();
+        let saved = repo.create(bookmark("https://example.com/a", "Article", &["databases"])).await.unwrap();
+        repo.save_content(&saved.id, "postgres indexes vacuum replication").await.unwrap();
+        repo.create(bookmark("https://example.com/b", "Another article", &["misc"])).await.unwrap();
+
+        let suggester = TagSuggester::from_repository(&repo).await.unwrap();
+        let new = bookmark("https://other.example.org/", "Tuning vacuum", &[]);
+        let suggestions = suggester.suggest(&new, Some("postgres replication lag"));
+        assert_eq!(tags(&suggestions), vec!["databases"]);
+    }
+}
@@ -0,0 +1,329 @@
+/// Common words that carry no meaning for search, plus URL noise
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "in", "is", "it", "of", "on", "or",
+    "that", "the", "this", "to", "was", "with", "http", "https", "www",
+];
+
+/// Split text into lowercase alphanumeric words, dropping stop words
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .filter(|word| !STOP_WORDS.contains(&word.as_str()))
+        .collect()
+}
+
+/// Tokenize and stem text into index terms
+pub fn analyze(text: &str) -> Vec<String> {
+    tokenize(text).iter().map(|word| stem(word)).collect()
+}
+
+/// Reduce an English word to its stem using the Porter algorithm
+///
+/// Words that are not plain ASCII letters (numbers, non-Latin scripts) are returned unchanged.
+pub fn stem(word: &str) -> String {
+    if word.len() <= 2 || !word.bytes().all(|b| b.is_ascii_lowercase()) {
+        return word.to_string();
+    }
+
+    let mut stemmer = Stemmer { word: word.as_bytes().to_vec() };
+    stemmer.step1a();
+    stemmer.step1b();
+    stemmer.step1c();
+    stemmer.step2();
+    stemmer.step3();
+    stemmer.step4();
+    stemmer.step5();
+    String::from_utf8(stemmer.word).unwrap_or_else(|_| word.to_string())
+}
+
+/// A short excerpt of `text` around the first word matching the query
+///
+/// Words match on their stem or by starting with a query word, like the search index.
+/// Returns None if no word in the text matches.
+pub fn snippet(text: &str, query: &str, max_chars: usize) -> Option<String> {
+    let query_words = tokenize(query);
+    let query_stems: Vec<String> = query_words.iter().map(|word| stem(word)).collect();
+    let is_match = |word: &str| {
+        let word = word.to_lowercase();
+        query_stems.contains(&stem(&word))
+            || query_words.iter().any(|query_word| query_word.chars().count() >= 3 && word.starts_with(query_word.as_str()))
+    };
+
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let hit = words.iter().position(|word| {
+        word.split(|c: char| !c.is_alphanumeric()).any(|part| !part.is_empty() && is_match(part))
+    })?;
+
+    // Start a few words before the match and extend forward until the budget runs out
+    let mut start = hit.saturating_sub(6);
+    let mut end = start;
+    let mut length = 0;
+    while end < words.len() && length + words[end].chars().count() < max_chars {
+        length += words[end].chars().count() + 1;
+        end += 1;
+    }
+    if end <= hit {
+        start = hit;
+        end = hit + 1;
+    }
+    // Near the end of the text, spend what is left of the budget on earlier words
+    while start > 0 && length + words[start - 1].chars().count() < max_chars {
+        start -= 1;
+        length += words[start].chars().count() + 1;
+    }
+
+    let mut excerpt = words[start..end].join(" ");
+    if start > 0 {
+        excerpt = format!("…{}", excerpt);
+    }
+    if end < words.len() {
+        excerpt.push('…');
+    }
+    Some(excerpt)
+}
+
+/// Levenshtein edit distance, giving up once it exceeds `max`
+pub fn edit_distance_within(a: &str, b: &str, max: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        if current.iter().min().is_some_and(|&lowest| lowest > max) {
+            return None;
+        }
+        previous = current;
+    }
+
+    let distance = previous[b.len()];
+    (distance <= max).then_some(distance)
+}
+
+struct Stemmer {
+    word: Vec<u8>,
+}
+
+impl Stemmer {
+    fn is_consonant(&self, i: usize) -> bool {
+        match self.word[i] {
+            b'a' | b'e' | b'i' | b'o' | b'u' => false,
+            b'y' => i == 0 || !self.is_consonant(i - 1),
+            _ => true,
+        }
+    }
+
+    /// Number of vowel-consonant sequences in the first `len` letters
+    fn measure(&self, len: usize) -> usize {
+        let mut count = 0;
+        let mut i = 0;
+        while i < len && self.is_consonant(i) {
+            i += 1;
+        }
+        while i < len {
+            while i < len && !self.is_consonant(i) {
+                i += 1;
+            }
+            if i >= len {
+                break;
+            }
+            count += 1;
+            while i < len && self.is_consonant(i) {
+                i += 1;
+            }
+        }
+        count
+    }
+
+    fn has_vowel(&self, len: usize) -> bool {
+        (0..len).any(|i| !self.is_consonant(i))
+    }
+
+    fn ends_with_double_consonant(&self, len: usize) -> bool {
+        len >= 2 && self.word[len - 1] == self.word[len - 2] && self.is_consonant(len - 1)
+    }
+
+    /// Consonant-vowel-consonant ending where the last consonant is not w, x or y
+    fn ends_cvc(&self, len: usize) -> bool {
+        len >= 3
+            && self.is_consonant(len - 3)
+            && !self.is_consonant(len - 2)
+            && self.is_consonant(len - 1)
+            && !matches!(self.word[len - 1], b'w' | b'x' | b'y')
+    }
+
+    fn ends_with(&self, suffix: &str) -> bool {
+        self.word.ends_with(suffix.as_bytes())
+    }
+
+    fn stem_len(&self, suffix: &str) -> usize {
+        self.word.len() - suffix.len()
+    }
+
+    fn replace_suffix(&mut self, suffix: &str, replacement: &str) {
+        let len = self.stem_len(suffix);
+        self.word.truncate(len);
+        self.word.extend_from_slice(replacement.as_bytes());
+    }
+
+    /// Replace the first matching suffix if the remaining stem has measure > `min_measure`
+    fn replace_first(&mut self, rules: &[(&str, &str)], min_measure: usize) {
+        if let Some((suffix, replacement)) = rules.iter().find(|(suffix, _)| self.ends_with(suffix)) {
+            if self.measure(self.stem_len(suffix)) > min_measure {
+                self.replace_suffix(suffix, replacement);
+            }
+        }
+    }
+
+    fn step1a(&mut self) {
+        if self.ends_with("sses") {
+            self.replace_suffix("sses", "ss");
+        } else if self.ends_with("ies") {
+            self.replace_suffix("ies", "i");
+        } else if !self.ends_with("ss") && self.ends_with("s") {
+            self.replace_suffix("s", "");
+        }
+    }
+
+    fn step1b(&mut self) {
+        if self.ends_with("eed") {
+            if self.measure(self.stem_len("eed")) > 0 {
+                self.replace_suffix("eed", "ee");
+            }
+            return;
+        }
+
+        let suffix = ["ed", "ing"].into_iter()
+            .find(|suffix| self.ends_with(suffix) && self.has_vowel(self.stem_len(suffix)));
+        let Some(suffix) = suffix else { return };
+        self.replace_suffix(suffix, "");
+
+        let len = self.word.len();
+        if self.ends_with("at") || self.ends_with("bl") || self.ends_with("iz") {
+            self.word.push(b'e');
+        } else if self.ends_with_double_consonant(len) && !matches!(self.word[len - 1], b'l' | b's' | b'z') {
+            self.word.pop();
+        } else if self.measure(len) == 1 && self.ends_cvc(len) {
+            self.word.push(b'e');
+        }
+    }
+
+    fn step1c(&mut self) {
+        if self.ends_with("y") && self.has_vowel(self.stem_len("y")) {
+            self.replace_suffix("y", "i");
+        }
+    }
+
+    fn step2(&mut self) {
+        self.replace_first(&[
+            ("ational", "ate"), ("tional", "tion"), ("enci", "ence"), ("anci", "ance"), ("izer", "ize"),
+            ("abli", "able"), ("alli", "al"), ("entli", "ent"), ("eli", "e"), ("ousli", "ous"),
+            ("ization", "ize"), ("ation", "ate"), ("ator", "ate"), ("alism", "al"), ("iveness", "ive"),
+            ("fulness", "ful"), ("ousness", "ous"), ("aliti", "al"), ("iviti", "ive"), ("biliti", "ble"),
+        ], 0);
+    }
+
+    fn step3(&mut self) {
+        self.replace_first(&[
+            ("icate", "ic"), ("ative", ""), ("alize", "al"), ("iciti", "ic"), ("ical", "ic"), ("ful", ""), ("ness", ""),
+        ], 0);
+    }
+
+    fn step4(&mut self) {
+        const SUFFIXES: &[&str] = &[
+            "al", "ance", "ence", "er", "ic", "able", "ible", "ant", "ement", "ment", "ent", "ion", "ou",
+            "ism", "ate", "iti", "ous", "ive", "ize",
+        ];
+
+        // Longest match wins (e.g. "ement" over "ment" over "ent")
+        let Some(suffix) = SUFFIXES.iter()
+            .filter(|suffix| self.ends_with(suffix))
+            .max_by_key(|suffix| suffix.len())
+        else {
+            return;
+        };
+
+        let len = self.stem_len(suffix);
+        if self.measure(len) <= 1 {
+            return;
+        }
+        if *suffix == "ion" && !matches!(self.word.get(len.wrapping_sub(1)), Some(b's') | Some(b't')) {
+            return;
+        }
+        self.word.truncate(len);
+    }
+
+    fn step5(&mut self) {
+        if self.ends_with("e") {
+            let len = self.stem_len("e");
+            let measure = self.measure(len);
+            if measure > 1 || (measure == 1 && !self.ends_cvc(len)) {
+                self.word.pop();
+            }
+        }
+
+        let len = self.word.len();
+        if self.measure(len) > 1 && self.ends_with_double_consonant(len) && self.word[len - 1] == b'l' {
+            self.word.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("The Rust Programming Language - https://www.rust-lang.org"),
+            vec!["rust", "programming", "language", "rust", "lang", "org"]
+        );
+        assert_eq!(tokenize("C++ & Go, 2024!"), vec!["c", "go", "2024"]);
+    }
+
+    #[test]
+    fn test_stem() {
+        let cases = [
+            ("caresses", "caress"), ("ponies", "poni"), ("cats", "cat"), ("agreed", "agre"),
+            ("plastered", "plaster"), ("motoring", "motor"), ("hopping", "hop"), ("filing", "file"),
+            ("happy", "happi"), ("relational", "relat"), ("programming", "program"), ("programs", "program"),
+            ("running", "run"), ("generalization", "gener"), ("effective", "effect"), ("controlling", "control"),
+            ("rust", "rust"), ("async", "async"), ("2024", "2024"),
+        ];
+        for (word, expected) in cases {
+            assert_eq!(stem(word), expected, "stem of {}", word);
+        }
+    }
+
+    #[test]
+    fn test_snippet() {
+        let text = "Rust has no garbage collector.\nInstead, ownership rules are checked by the borrow checker at compile time, \
+            which rejects programs with dangling references.";
+
+        assert_eq!(
+            snippet(text, "borrowing", 60).unwrap(),
+            "…ownership rules are checked by the borrow checker at…"
+        );
+        assert_eq!(snippet(text, "rust", 20).unwrap(), "Rust has no garbage…");
+        assert_eq!(snippet(text, "dangl", 200).unwrap(), text.split_whitespace().collect::<Vec<_>>().join(" "));
+        assert_eq!(snippet(text, "python", 60), None);
+    }
+
+    #[test]
+    fn test_edit_distance_within() {
+        assert_eq!(edit_distance_within("tokio", "tokio", 1), Some(0));
+        assert_eq!(edit_distance_within("tokoi", "tokio", 2), Some(2));
+        assert_eq!(edit_distance_within("tokip", "tokio", 1), Some(1));
+        assert_eq!(edit_distance_within("rust", "python", 2), None);
+        assert_eq!(edit_distance_within("rus", "rust", 1), Some(1));
+    }
+}
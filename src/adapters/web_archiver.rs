@@ -0,0 +1,620 @@
+use async_trait::async_trait;
+use futures_util::stream::{self, StreamExt};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+use super::http_cache::HttpCache;
+use super::page_fetcher::{decode_text, Download, PageFetcher};
+use crate::traits::PageArchiver;
+use crate::types::{ExtractorError, MetadataConfig};
+
+/// Resources larger than this are left pointing at the live site
+const MAX_RESOURCE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// How many resources are downloaded at once
+const CONCURRENT_DOWNLOADS: usize = 8;
+
+/// Saves pages as single HTML files with their stylesheets, scripts and images inlined
+///
+/// Anything that cannot be downloaded keeps its original link, so the archive
+/// degrades to the live resource rather than failing outright. Downloads go
+/// through a [`PageFetcher`], so they get the same robots.txt, rate limit,
+/// redirect and private address rules as metadata extraction. With an
+/// [`HttpCache`], the page and its resources are reused from earlier downloads.
+pub struct WebArchiver {
+    fetcher: PageFetcher,
+}
+
+/// A downloaded stylesheet, script or image
+struct Resource {
+    /// Final URL after redirects, used to resolve references inside stylesheets
+    url: Url,
+    /// The Content-Type header, including any charset
+    content_type: Option<String>,
+    body: Vec<u8>,
+}
+
+impl Resource {
+    fn text(&self) -> String {
+        decode_text(&self.body, self.content_type.as_deref())
+    }
+
+    /// The `type/subtype` part of the Content-Type header
+    fn media_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+            .and_then(|value| value.split(';').next())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    }
+
+    fn data_uri(&self) -> String {
+        let content_type = self.media_type().unwrap_or("application/octet-stream");
+        format!("data:{};base64,{}", content_type, base64_encode(&self.body))
+    }
+}
+
+impl From<Download> for Resource {
+    fn from(download: Download) -> Self {
+        Self { url: download.url, content_type: download.content_type, body: download.body }
+    }
+}
+
+impl WebArchiver {
+    /// An archiver using the network settings in `config`, which fails if they are invalid
+    pub fn new(config: &MetadataConfig) -> Result<Self, ExtractorError> {
+        Ok(Self { fetcher: PageFetcher::new(config)? })
+    }
+
+    /// Reuse responses stored in `cache`, and store new ones there
+    pub fn with_cache(mut self, cache: Option<Arc<HttpCache>>) -> Self {
+        self.fetcher = self.fetcher.with_cache(cache);
+        self
+    }
+
+    async fn fetch_resource(&self, url: &Url, timeout: Duration) -> Result<Resource, ExtractorError> {
+        self.fetcher.download(url, timeout, |_| Ok(MAX_RESOURCE_BYTES)).await.map(Resource::from)
+    }
+
+    /// Download resources concurrently, dropping any that fail
+    async fn fetch_all(&self, urls: Vec<Url>, timeout: Duration) -> HashMap<Url, Resource> {
+        stream::iter(urls)
+            .map(|url| async move {
+                let resource = self.fetch_resource(&url, timeout).await;
+                (url, resource)
+            })
+            .buffer_unordered(CONCURRENT_DOWNLOADS)
+            .filter_map(|(url, resource)| async move { resource.ok().map(|resource| (url, resource)) })
+            .collect()
+            .await
+    }
+}
+
+#[async_trait]
+impl PageArchiver for WebArchiver {
+    async fn archive_page(&self, url: &str, timeout: Duration) -> Result<String, ExtractorError> {
+        let page_url = Url::parse(url)
+            .map_err(|_| ExtractorError::InvalidUrl(url.to_string()))?;
+
+        let page = self.fetch_resource(&page_url, timeout).await?;
+        let html = page.text();
+        let base = document_base(&html, &page.url);
+
+        let mut resources = self.fetch_all(resource_references(&html, &base), timeout).await;
+
+        // Stylesheets pull in fonts and background images of their own
+        let mut nested: Vec<Url> = resources.values()
+            .filter(|resource| resource.media_type() == Some("text/css"))
+            .flat_map(|resource| css_references(&resource.text(), &resource.url))
+            .filter(|url| !resources.contains_key(url))
+            .collect();
+        nested.sort();
+        nested.dedup();
+        resources.extend(self.fetch_all(nested, timeout).await);
+
+        Ok(inline_resources(&html, &base, &resources))
+    }
+}
+
+/// An HTML start tag found while scanning a document
+struct StartTag {
+    /// Lowercase tag name
+    name: String,
+    /// Lowercase attribute names with their decoded values, in document order
+    attributes: Vec<(String, Option<String>)>,
+    self_closing: bool,
+    /// Byte offset just past the closing `>`
+    end: usize,
+}
+
+impl StartTag {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.iter()
+            .find(|(attribute, _)| attribute == name)
+            .and_then(|(_, value)| value.as_deref())
+    }
+
+    fn is_stylesheet(&self) -> bool {
+        self.name == "link"
+            && self.attribute("rel").is_some_and(|rel| {
+                rel.split_ascii_whitespace().any(|token| token.eq_ignore_ascii_case("stylesheet"))
+            })
+    }
+
+    /// Render the tag again, with `replace` deciding the fate of each attribute
+    fn render(&self, mut replace: impl FnMut(&str, Option<&str>) -> Option<Option<String>>) -> String {
+        let mut tag = format!("<{}", self.name);
+        for (name, value) in &self.attributes {
+            let value = match replace(name, value.as_deref()) {
+                Some(value) => value,
+                None => continue,
+            };
+            match value {
+                Some(value) => tag.push_str(&format!(" {}=\"{}\"", name, escape_attribute(&value))),
+                None => tag.push_str(&format!(" {}", name)),
+            }
+        }
+        if self.self_closing {
+            tag.push_str(" /");
+        }
+        tag.push('>');
+        tag
+    }
+}
+
+/// A start tag plus, for script and style elements, their raw text
+struct Element<'a> {
+    tag: StartTag,
+    source: &'a str,
+    content: Option<&'a str>,
+}
+
+/// Walk the start tags of a document, letting `visit` replace elements
+///
+/// For script and style elements the replacement covers the whole element up to
+/// and including its closing tag; for everything else just the start tag.
+/// Comments and the contents of script and style elements are never parsed as tags.
+fn scan(html: &str, mut visit: impl FnMut(&Element) -> Option<String>) -> String {
+    let lower = html.to_ascii_lowercase();
+    let mut output = String::with_capacity(html.len());
+    let mut copied = 0;
+    let mut position = 0;
+
+    while let Some(offset) = html[position..].find('<') {
+        let start = position + offset;
+        if html[start..].starts_with("<!--") {
+            position = html[start..].find("-->").map_or(html.len(), |end| start + end + 3);
+            continue;
+        }
+        let Some(tag) = parse_start_tag(html, start) else {
+            position = start + 1;
+            continue;
+        };
+
+        let mut end = tag.end;
+        let mut content = None;
+        if matches!(tag.name.as_str(), "script" | "style") && !tag.self_closing {
+            let closing = format!("</{}", tag.name);
+            let close = lower[end..].find(&closing).map_or(html.len(), |offset| end + offset);
+            content = Some(&html[end..close]);
+            end = html[close..].find('>').map_or(html.len(), |offset| close + offset + 1);
+        }
+
+        let element = Element { source: &html[start..tag.end], tag, content };
+        if let Some(replacement) = visit(&element) {
+            output.push_str(&html[copied..start]);
+            output.push_str(&replacement);
+            copied = end;
+        }
+        position = end;
+    }
+
+    output.push_str(&html[copied..]);
+    output
+}
+
+/// Parse the start tag beginning at `start`, which must point at a `<`
+fn parse_start_tag(html: &str, start: usize) -> Option<StartTag> {
+    let bytes = html.as_bytes();
+    let is_delimiter = |b: u8| b.is_ascii_whitespace() || b == b'/' || b == b'>';
+
+    let mut i = start + 1;
+    if !bytes.get(i)?.is_ascii_alphabetic() {
+        return None;
+    }
+    while i < bytes.len() && !is_delimiter(bytes[i]) {
+        i += 1;
+    }
+    let name = html[start + 1..i].to_ascii_lowercase();
+
+    let mut attributes = Vec::new();
+    let mut self_closing = false;
+    loop {
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        match bytes.get(i)? {
+            b'>' => break,
+            b'/' => {
+                self_closing = bytes.get(i + 1) == Some(&b'>');
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        let name_start = i;
+        while i < bytes.len() && !is_delimiter(bytes[i]) && bytes[i] != b'=' {
+            i += 1;
+        }
+        let attribute = html[name_start..i].to_ascii_lowercase();
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+
+        let mut value = None;
+        if bytes.get(i) == Some(&b'=') {
+            i += 1;
+            while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            let raw = match bytes.get(i)? {
+                quote @ (b'"' | b'\'') => {
+                    let value_start = i + 1;
+                    let value_end = html[value_start..].find(*quote as char)? + value_start;
+                    i = value_end + 1;
+                    &html[value_start..value_end]
+                }
+                _ => {
+                    let value_start = i;
+                    while i < bytes.len() && !bytes[i].is_ascii_whitespace() && bytes[i] != b'>' {
+                        i += 1;
+                    }
+                    &html[value_start..i]
+                }
+            };
+            value = Some(decode_entities(raw));
+        }
+        attributes.push((attribute, value));
+    }
+
+    Some(StartTag { name, attributes, self_closing, end: i + 1 })
+}
+
+fn decode_entities(value: &str) -> String {
+    value
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+fn escape_attribute(value: &str) -> String {
+    value.replace('&', "&amp;").replace('"', "&quot;")
+}
+
+/// Resolve a reference from the page, ignoring anything that is not fetched over HTTP
+fn resolve(base: &Url, reference: &str) -> Option<Url> {
+    let reference = reference.trim();
+    if reference.is_empty() || reference.starts_with('#') || reference.starts_with("data:") {
+        return None;
+    }
+    base.join(reference).ok()
+        .filter(|url| matches!(url.scheme(), "http" | "https"))
+}
+
+/// The URL relative references resolve against, honouring any `<base href>`
+fn document_base(html: &str, page_url: &Url) -> Url {
+    let mut base = None;
+    scan(html, |element| {
+        if element.tag.name == "base" && base.is_none() {
+            base = element.tag.attribute("href").and_then(|href| page_url.join(href).ok());
+        }
+        None
+    });
+    base.unwrap_or_else(|| page_url.clone())
+}
+
+/// URLs of the stylesheets, scripts and images a page needs
+fn resource_references(html: &str, base: &Url) -> Vec<Url> {
+    let mut urls = Vec::new();
+    scan(html, |element| {
+        let tag = &element.tag;
+        let reference = match tag.name.as_str() {
+            "link" if tag.is_stylesheet() => tag.attribute("href"),
+            "script" | "img" => tag.attribute("src"),
+            _ => None,
+        };
+        urls.extend(reference.and_then(|reference| resolve(base, reference)));
+        if let Some(css) = element.content.filter(|_| tag.name == "style") {
+            urls.extend(css_references(css, base));
+        }
+        if let Some(style) = tag.attribute("style") {
+            urls.extend(css_references(style, base));
+        }
+        None
+    });
+    urls.sort();
+    urls.dedup();
+    urls
+}
+
+/// Byte ranges of each `url(...)` in a stylesheet, with the reference inside
+fn css_urls(css: &str) -> Vec<(std::ops::Range<usize>, &str)> {
+    let lower = css.to_ascii_lowercase();
+    let mut urls = Vec::new();
+    let mut position = 0;
+
+    while let Some(offset) = lower[position..].find("url(") {
+        let start = position + offset;
+        let Some(close) = css[start..].find(')').map(|offset| start + offset) else {
+            break;
+        };
+        let reference = css[start + 4..close].trim().trim_matches(|c| c == '"' || c == '\'');
+        urls.push((start..close + 1, reference));
+        position = close + 1;
+    }
+    urls
+}
+
+fn css_references(css: &str, base: &Url) -> Vec<Url> {
+    css_urls(css).into_iter()
+        .filter_map(|(_, reference)| resolve(base, reference))
+        .collect()
+}
+
+/// Replace every `url(...)` that was downloaded with a data URI
+fn rewrite_css(css: &str, base: &Url, resources: &HashMap<Url, Resource>) -> String {
+    let mut output = String::with_capacity(css.len());
+    let mut copied = 0;
+    for (range, reference) in css_urls(css) {
+        let resource = resolve(base, reference).and_then(|url| resources.get(&url));
+        if let Some(resource) = resource {
+            output.push_str(&css[copied..range.start]);
+            output.push_str(&format!("url(\"{}\")", resource.data_uri()));
+            copied = range.end;
+        }
+    }
+    output.push_str(&css[copied..]);
+    output
+}
+
+/// Rewrite a page so it no longer depends on the downloaded resources
+///
+/// Stylesheets and scripts become inline elements and images become data URIs.
+/// A `<base>` tag is added so links and anything left un-inlined still point at the
+/// original site, and the declared charset is replaced since the archive is saved as UTF-8.
+fn inline_resources(html: &str, base: &Url, resources: &HashMap<Url, Resource>) -> String {
+    let fetched = |reference: Option<&str>| reference
+        .and_then(|reference| resolve(base, reference))
+        .and_then(|url| resources.get(&url));
+    let mut has_base = false;
+    scan(html, |element| {
+        if element.tag.name == "base" {
+            has_base = true;
+        }
+        None
+    });
+
+    scan(html, |element| {
+        let tag = &element.tag;
+        match tag.name.as_str() {
+            "head" if !has_base => {
+                Some(format!("{}<base href=\"{}\">", element.source, escape_attribute(base.as_str())))
+            }
+            "meta" if tag.attribute("charset").is_some()
+                || tag.attribute("http-equiv").is_some_and(|value| value.eq_ignore_ascii_case("content-type")) => {
+                Some("<meta charset=\"utf-8\">".to_string())
+            }
+            "link" if tag.is_stylesheet() => {
+                let resource = fetched(tag.attribute("href"))?;
+                let media = tag.attribute("media")
+                    .map(|media| format!(" media=\"{}\"", escape_attribute(media)))
+                    .unwrap_or_default();
+                let css = rewrite_css(&resource.text(), &resource.url, resources);
+                Some(format!("<style{}>{}</style>", media, css.replace("</style", "<\\/style")))
+            }
+            "script" => {
+                let resource = fetched(tag.attribute("src"))?;
+                let start = tag.render(|name, value| match name {
+                    "src" | "integrity" | "crossorigin" | "async" | "defer" => None,
+                    _ => Some(value.map(str::to_string)),
+                });
+                Some(format!("{}{}</script>", start, resource.text().replace("</script", "<\\/script")))
+            }
+            "style" => {
+                let css = rewrite_css(element.content?, base, resources);
+                Some(format!("{}{}</style>", element.source, css))
+            }
+            "img" => {
+                let resource = fetched(tag.attribute("src"))?;
+                Some(tag.render(|name, value| match name {
+                    "src" => Some(Some(resource.data_uri())),
+                    // The browser would prefer these over src and fetch them from the live site
+                    "srcset" | "sizes" | "loading" => None,
+                    _ => Some(value.map(str::to_string)),
+                }))
+            }
+            _ if tag.attribute("style").is_some_and(|style| !css_references(style, base).is_empty()) => {
+                Some(tag.render(|name, value| match name {
+                    "style" => Some(value.map(|style| rewrite_css(style, base, resources))),
+                    _ => Some(value.map(str::to_string)),
+                }))
+            }
+            _ => None,
+        }
+    })
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [chunk[0], chunk.get(1).copied().unwrap_or(0), chunk.get(2).copied().unwrap_or(0)];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[((n >> (18 - 6 * i)) & 63) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resource(url: &str, content_type: &str, body: &str) -> (Url, Resource) {
+        let url = Url::parse(url).unwrap();
+        (url.clone(), Resource { url, content_type: Some(content_type.to_string()), body: body.as_bytes().to_vec() })
+    }
+
+    #[test]
+    fn test_base64_encode() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64_encode(&[0xff, 0xfe]), "//4=");
+    }
+
+    #[test]
+    fn test_parse_start_tag() {
+        let html = r#"<IMG src="a.png" alt='Fish &amp; chips' width=10 hidden/>"#;
+        let tag = parse_start_tag(html, 0).unwrap();
+        assert_eq!(tag.name, "img");
+        assert_eq!(tag.attribute("src"), Some("a.png"));
+        assert_eq!(tag.attribute("alt"), Some("Fish & chips"));
+        assert_eq!(tag.attribute("width"), Some("10"));
+        assert!(tag.attributes.contains(&("hidden".to_string(), None)));
+        assert!(tag.self_closing);
+        assert_eq!(tag.end, html.len());
+
+        assert!(parse_start_tag("< p>", 0).is_none());
+        assert!(parse_start_tag("<a href=\"unterminated>", 0).is_none());
+    }
+
+    #[test]
+    fn test_resource_references() {
+        let html = r#"<html><head>
+            <base href="https://cdn.example.com/site/">
+            <link rel="stylesheet" href="style.css">
+            <link rel="icon" href="favicon.ico">
+            <style>body { background: url('/bg.png'); }</style>
+            <script src="https://example.com/app.js"></script>
+            <script>document.write('<img src="ignored.png">');</script>
+            </head><body>
+            <!-- <img src="commented.png"> -->
+            <img src="img/photo.jpg"><img src="data:image/png;base64,AAAA">
+            <div style="background-image: url(hero.webp)"></div>
+            </body></html>"#;
+
+        let base = document_base(html, &Url::parse("https://example.com/article").unwrap());
+        assert_eq!(base.as_str(), "https://cdn.example.com/site/");
+
+        let urls: Vec<String> = resource_references(html, &base).iter().map(|url| url.to_string()).collect();
+        assert_eq!(urls, vec![
+            "https://cdn.example.com/bg.png",
+            "https://cdn.example.com/site/hero.webp",
+            "https://cdn.example.com/site/img/photo.jpg",
+            "https://cdn.example.com/site/style.css",
+            "https://example.com/app.js",
+        ]);
+    }
+
+    #[test]
+    fn test_inline_resources() {
+        let html = r#"<!DOCTYPE html><html><head><meta charset="iso-8859-1">
+<link rel="stylesheet" href="/style.css" media="screen">
+<script src="/app.js" defer></script>
+<script src="/missing.js"></script>
+</head><body><img src="/logo.png" srcset="/logo-2x.png 2x" alt="Logo"><a href="/next">Next</a></body></html>"#;
+        let base = Url::parse("https://example.com/post").unwrap();
+        let resources: HashMap<Url, Resource> = [
+            resource("https://example.com/style.css", "text/css", "h1 { background: url(img/h.png) }"),
+            resource("https://example.com/img/h.png", "image/png", "png"),
+            resource("https://example.com/app.js", "text/javascript", "if (a </script> b) {}"),
+            resource("https://example.com/logo.png", "image/png", "logo"),
+        ].into_iter().collect();
+
+        let archived = inline_resources(html, &base, &resources);
+
+        assert!(archived.starts_with("<!DOCTYPE html><html><head><base href=\"https://example.com/post\"><meta charset=\"utf-8\">"));
+        assert!(archived.contains("<style media=\"screen\">h1 { background: url(\"data:image/png;base64,cG5n\") }</style>"));
+        assert!(archived.contains("<script>if (a <\\/script> b) {}</script>"));
+        assert!(archived.contains("<script src=\"/missing.js\"></script>"));
+        assert!(archived.contains("<img src=\"data:image/png;base64,bG9nbw==\" alt=\"Logo\">"));
+        assert!(archived.contains("<a href=\"/next\">Next</a>"));
+        assert!(!archived.contains("logo-2x"));
+    }
+
+    #[test]
+    fn test_existing_base_is_kept() {
+        let html = r#"<html><head><base href="/docs/"></head><body></body></html>"#;
+        let base = document_base(html, &Url::parse("https://example.com/").unwrap());
+        assert_eq!(inline_resources(html, &base, &HashMap::new()), html);
+    }
+
+    /// Serve a page with two stylesheets, one of them disallowed by robots.txt
+    async fn start_server() -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buffer = vec![0; 4096];
+                    let read = socket.read(&mut buffer).await.unwrap_or(0);
+                    let request = String::from_utf8_lossy(&buffer[..read]).to_string();
+                    let (content_type, body) = match request.split_whitespace().nth(1).unwrap_or("") {
+                        "/robots.txt" => ("text/plain", "User-agent: *\nDisallow: /private\n"),
+                        "/site.css" => ("text/css", "h1 { color: red }"),
+                        "/private.css" => ("text/css", "h1 { color: blue }"),
+                        _ => ("text/html", r#"<html><head><link rel="stylesheet" href="/site.css"><link rel="stylesheet" href="/private.css"></head></html>"#),
+                    };
+                    let head = format!("HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", content_type, body.len());
+                    let _ = socket.write_all(head.as_bytes()).await;
+                    let _ = socket.write_all(body.as_bytes()).await;
+                });
+            }
+        });
+        address
+    }
+
+    #[tokio::test]
+    async fn test_archive_follows_fetch_rules() {
+        let server = start_server().await;
+        let config = MetadataConfig {
+            per_host_requests_per_sec: 0.0,
+            block_private_addresses: false,
+            ..MetadataConfig::default()
+        };
+        let archiver = WebArchiver::new(&config).unwrap();
+
+        let archived = archiver.archive_page(&format!("{}/page", server), Duration::from_secs(5)).await.unwrap();
+        assert!(archived.contains("<style>h1 { color: red }</style>"));
+        assert!(archived.contains("href=\"/private.css\""));
+
+        // Private addresses are refused like any other fetch
+        let archiver = WebArchiver::new(&MetadataConfig::default()).unwrap();
+        let result = archiver.archive_page(&format!("{}/page", server), Duration::from_secs(5)).await;
+        assert!(matches!(result, Err(ExtractorError::PrivateAddress(_))));
+    }
+
+    #[tokio::test]
+    async fn test_archive_invalid_url() {
+        let archiver = WebArchiver::new(&MetadataConfig::default()).unwrap();
+        let result = archiver.archive_page("not a url", Duration::from_secs(1)).await;
+        assert!(matches!(result, Err(ExtractorError::InvalidUrl(_))));
+    }
+}
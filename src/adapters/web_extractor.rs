@@ -1,136 +1,387 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use reqwest::Client;
-use scraper::{Html, Selector};
+use scraper::{ElementRef, Html, Selector};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::Arc;
 use std::time::Duration;
 use url::Url;
 
+use super::document_metadata::{image_metadata, pdf_metadata, text_metadata};
+use super::page_fetcher::{PageFetcher, Resource};
 use crate::traits::MetadataExtractor;
 use crate::types::{ExtractedMetadata, ExtractorError};
 
 pub struct WebExtractor {
-    client: Client,
+    fetcher: Arc<PageFetcher>,
 }
 
 impl WebExtractor {
-    pub fn new() -> Self {
-        Self {
-            client: Client::new(),
-        }
+    pub fn new(fetcher: Arc<PageFetcher>) -> Self {
+        Self { fetcher }
     }
-
 }
 
 #[async_trait]
 impl MetadataExtractor for WebExtractor {
     async fn extract_metadata(&self, url: &str, timeout: Duration) -> Result<ExtractedMetadata, ExtractorError> {
-        // Validate URL
-        let parsed_url = Url::parse(url)
-            .map_err(|_| ExtractorError::InvalidUrl(url.to_string()))?;
-
-        // Make HTTP request
-        let response = self
-            .client
-            .get(parsed_url)
-            .timeout(timeout)
-            .send()
-            .await
-            .map_err(|e| {
-                if e.is_timeout() {
-                    ExtractorError::Timeout
-                } else {
-                    ExtractorError::NetworkError(e.to_string())
+        let (resource, page_url) = self.fetcher.fetch_resource(url, timeout).await?;
+        Ok(match resource {
+            Resource::Html(html) => parse_metadata(&html, &page_url),
+            Resource::Pdf(bytes) => pdf_metadata(&bytes, &page_url),
+            Resource::Image { media_type, bytes } => image_metadata(&bytes, &media_type, &page_url),
+            Resource::Text(text) => text_metadata(&text, &page_url),
+        })
+    }
+}
+
+/// Extract all metadata from a page's HTML
+///
+/// Each field is taken from the first source that provides it. The general order is
+/// JSON-LD (schema.org `Article` and friends), then OpenGraph, then Twitter cards, then
+/// standard `<meta>`/`<link>` tags, then the document itself. A few fields deviate where
+/// another source is more reliable:
+///
+/// - title: JSON-LD `headline`/`name`, `og:title`, `twitter:title`, `<title>`
+/// - author: JSON-LD `author`, `author`/`article:author` meta tags
+/// - publish date: JSON-LD `datePublished`, `article:published_time` and similar meta tags
+/// - description: JSON-LD `description`, `og:description`, `twitter:description`, `description` meta
+/// - site name: `og:site_name`, JSON-LD `WebSite` name, JSON-LD `publisher` name, `application-name`
+///   (the publisher is often a parent company, so OpenGraph goes first)
+/// - canonical URL: `<link rel="canonical">`, `og:url`, JSON-LD `url`
+/// - image: JSON-LD `image`, `og:image`, `twitter:image`, `<link rel="image_src">`
+/// - language: `<html lang>`, JSON-LD `inLanguage`, `og:locale`, `content-language` meta
+/// - favicon: `<link rel="icon">`/`shortcut icon`, then `apple-touch-icon`
+///
+/// URLs are resolved against the page's `<base>` or `page_url`.
+pub(crate) fn parse_metadata(html: &str, page_url: &Url) -> ExtractedMetadata {
+    let document = Html::parse_document(html);
+    let base = document_base(&document, page_url);
+
+    ExtractedMetadata {
+        title: extract_title(&document),
+        author: extract_author(&document),
+        publish_date: extract_publish_date(&document),
+        content: extract_main_text(&document),
+        description: extract_description(&document),
+        site_name: extract_site_name(&document),
+        canonical_url: extract_canonical_url(&document, &base),
+        image_url: extract_image_url(&document, &base),
+        language: extract_language(&document),
+        favicon_url: extract_favicon_url(&document, &base),
+        site_details: BTreeMap::new(),
+    }
+}
+
+/// schema.org types describing the page's main content, most specific first
+const JSON_LD_CONTENT_TYPES: &[&str] = &[
+    "Article", "NewsArticle", "BlogPosting", "TechArticle", "ScholarlyArticle",
+    "Report", "SocialMediaPosting", "Recipe", "Product", "VideoObject", "WebPage",
+];
+
+/// All JSON-LD objects in the page, with arrays and `@graph` containers flattened
+fn json_ld_nodes(document: &Html) -> Vec<Value> {
+    let Ok(selector) = Selector::parse("script[type='application/ld+json']") else {
+        return Vec::new();
+    };
+
+    let mut nodes = Vec::new();
+    let mut pending: Vec<Value> = document.select(&selector)
+        .filter_map(|script| serde_json::from_str(&script.text().collect::<String>()).ok())
+        .collect();
+    while let Some(value) = pending.pop() {
+        match value {
+            Value::Array(items) => pending.extend(items.into_iter().rev()),
+            Value::Object(mut object) => {
+                if let Some(graph) = object.remove("@graph") {
+                    pending.push(graph);
                 }
-            })?;
+                nodes.push(Value::Object(object));
+            }
+            _ => {}
+        }
+    }
+    nodes
+}
 
-        // Get HTML content
-        let html_content = response
-            .text()
-            .await
-            .map_err(|e| ExtractorError::NetworkError(e.to_string()))?;
+/// Whether a JSON-LD node has the given `@type` (which may be a list of types)
+fn json_ld_is_type(node: &Value, type_name: &str) -> bool {
+    match node.get("@type") {
+        Some(Value::String(value)) => value == type_name,
+        Some(Value::Array(values)) => values.iter().any(|value| value.as_str() == Some(type_name)),
+        _ => false,
+    }
+}
 
-        // Parse HTML
-        let document = Html::parse_document(&html_content);
+/// The JSON-LD node describing the page's main content
+fn json_ld_content(document: &Html) -> Option<Value> {
+    let nodes = json_ld_nodes(document);
+    JSON_LD_CONTENT_TYPES.iter()
+        .find_map(|type_name| nodes.iter().find(|node| json_ld_is_type(node, type_name)))
+        .cloned()
+}
 
-        // Extract metadata
-        let title = extract_title(&document);
-        let author = extract_author(&document);
-        let publish_date = extract_publish_date(&document);
+/// Text of a JSON-LD value: a string, an object's `name`, or the first of a list
+fn json_ld_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => clean_text(text),
+        Value::Object(object) => object.get("name").and_then(json_ld_text),
+        Value::Array(items) => items.iter().find_map(json_ld_text),
+        _ => None,
+    }
+}
 
-        Ok(ExtractedMetadata {
-            title,
-            author,
-            publish_date,
-        })
+/// URL of a JSON-LD value: a string, an object's `url` or `@id`, or the first of a list
+fn json_ld_url(value: &Value) -> Option<String> {
+    match value {
+        Value::String(url) => clean_text(url),
+        Value::Object(object) => object.get("url").or_else(|| object.get("@id")).and_then(json_ld_url),
+        Value::Array(items) => items.iter().find_map(json_ld_url),
+        _ => None,
     }
 }
 
+/// Collapse whitespace, treating blank text as missing
+pub(crate) fn clean_text(text: &str) -> Option<String> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+/// Content of the first `<meta>` tag whose `property` or `name` is one of `keys`, in order
+pub(crate) fn meta_content(document: &Html, keys: &[&str]) -> Option<String> {
+    keys.iter().find_map(|key| {
+        let selector = Selector::parse(&format!("meta[property='{0}'], meta[name='{0}']", key)).ok()?;
+        document.select(&selector)
+            .find_map(|element| element.value().attr("content").and_then(clean_text))
+    })
+}
+
+/// `href` of the first `<link>` whose `rel` includes one of `rels`, in order
+fn link_href(document: &Html, rels: &[&str]) -> Option<String> {
+    let selector = Selector::parse("link[rel][href]").ok()?;
+    rels.iter().find_map(|rel| {
+        document.select(&selector)
+            .filter(|element| {
+                element.value().attr("rel").unwrap_or("")
+                    .split_whitespace()
+                    .any(|token| token.eq_ignore_ascii_case(rel))
+            })
+            .find_map(|element| element.value().attr("href").and_then(clean_text))
+    })
+}
+
+/// The URL relative links resolve against: the page's `<base href>` if it has one
+fn document_base(document: &Html, page_url: &Url) -> Url {
+    Selector::parse("base[href]").ok()
+        .and_then(|selector| document.select(&selector).next())
+        .and_then(|element| page_url.join(element.value().attr("href")?).ok())
+        .unwrap_or_else(|| page_url.clone())
+}
+
+/// Resolve a possibly relative link, keeping only web URLs
+pub(crate) fn resolve_url(base: &Url, link: &str) -> Option<String> {
+    base.join(link.trim()).ok()
+        .filter(|url| matches!(url.scheme(), "http" | "https"))
+        .map(String::from)
+}
+
 fn extract_title(document: &Html) -> Option<String> {
-    let title_selector = Selector::parse("title").ok()?;
-    document
-        .select(&title_selector)
-        .next()
-        .map(|element| element.text().collect::<String>().trim().to_string())
-        .filter(|s| !s.is_empty())
+    let content = json_ld_content(document);
+    content.as_ref()
+        .and_then(|node| node.get("headline").or_else(|| node.get("name")))
+        .and_then(json_ld_text)
+        .or_else(|| meta_content(document, &["og:title", "twitter:title"]))
+        .or_else(|| {
+            let title_selector = Selector::parse("title").ok()?;
+            document
+                .select(&title_selector)
+                .next()
+                .and_then(|element| clean_text(&element.text().collect::<String>()))
+        })
 }
 
 fn extract_author(document: &Html) -> Option<String> {
-    // Try various meta tags for author
-    let meta_selectors = vec![
-        "meta[name='author']",
-        "meta[property='article:author']",
-        "meta[name='article:author']",
-        "meta[property='author']",
-    ];
-
-    for selector_str in meta_selectors {
-        if let Ok(selector) = Selector::parse(selector_str) {
-            if let Some(element) = document.select(&selector).next() {
-                if let Some(content) = element.value().attr("content") {
-                    let content = content.trim().to_string();
-                    if !content.is_empty() {
-                        return Some(content);
-                    }
-                }
-            }
+    let json_ld_author = json_ld_content(document)
+        .and_then(|node| node.get("author").cloned())
+        .and_then(|author| {
+            // Several authors are listed together
+            let names: Vec<String> = match author {
+                Value::Array(ref authors) => authors.iter().filter_map(json_ld_text).collect(),
+                ref author => json_ld_text(author).into_iter().collect(),
+            };
+            (!names.is_empty()).then(|| names.join(", "))
+        });
+
+    json_ld_author.or_else(|| meta_content(document, &[
+        "author",
+        "article:author",
+    ]))
+}
+
+fn extract_publish_date(document: &Html) -> Option<DateTime<Utc>> {
+    let json_ld_date = json_ld_content(document)
+        .and_then(|node| node.get("datePublished").and_then(Value::as_str).and_then(parse_date));
+
+    json_ld_date.or_else(|| {
+        let content = meta_content(document, &[
+            "article:published_time",
+            "published_time",
+            "article:published",
+            "publish_date",
+        ])?;
+        parse_date(&content)
+    })
+}
+
+/// Parse the date formats found in page metadata
+pub(crate) fn parse_date(content: &str) -> Option<DateTime<Utc>> {
+    let content = content.trim();
+    if let Ok(date) = DateTime::parse_from_rfc3339(content) {
+        return Some(date.with_timezone(&Utc));
+    }
+    if let Ok(date) = DateTime::parse_from_rfc2822(content) {
+        return Some(date.with_timezone(&Utc));
+    }
+    // Try ISO 8601 without timezone
+    if let Ok(date) = chrono::NaiveDateTime::parse_from_str(content, "%Y-%m-%dT%H:%M:%S") {
+        return Some(date.and_utc());
+    }
+    // Date only
+    chrono::NaiveDate::parse_from_str(content, "%Y-%m-%d").ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|date| date.and_utc())
+}
+
+fn extract_description(document: &Html) -> Option<String> {
+    json_ld_content(document)
+        .and_then(|node| node.get("description").and_then(json_ld_text))
+        .or_else(|| meta_content(document, &["og:description", "twitter:description", "description"]))
+}
+
+fn extract_site_name(document: &Html) -> Option<String> {
+    meta_content(document, &["og:site_name"])
+        .or_else(|| {
+            json_ld_nodes(document).iter()
+                .find(|node| json_ld_is_type(node, "WebSite"))
+                .and_then(|node| node.get("name").and_then(json_ld_text))
+        })
+        .or_else(|| json_ld_content(document).and_then(|node| node.get("publisher").and_then(json_ld_text)))
+        .or_else(|| meta_content(document, &["application-name"]))
+}
+
+fn extract_canonical_url(document: &Html, base: &Url) -> Option<String> {
+    link_href(document, &["canonical"])
+        .or_else(|| meta_content(document, &["og:url"]))
+        .or_else(|| json_ld_content(document).and_then(|node| node.get("url").and_then(json_ld_url)))
+        .and_then(|url| resolve_url(base, &url))
+}
+
+fn extract_image_url(document: &Html, base: &Url) -> Option<String> {
+    json_ld_content(document)
+        .and_then(|node| node.get("image").and_then(json_ld_url))
+        .or_else(|| meta_content(document, &["og:image", "og:image:url", "twitter:image", "twitter:image:src"]))
+        .or_else(|| link_href(document, &["image_src"]))
+        .and_then(|url| resolve_url(base, &url))
+}
+
+fn extract_language(document: &Html) -> Option<String> {
+    let html_lang = Selector::parse("html[lang]").ok()
+        .and_then(|selector| document.select(&selector).next())
+        .and_then(|element| element.value().attr("lang").and_then(clean_text));
+
+    html_lang
+        .or_else(|| json_ld_content(document).and_then(|node| node.get("inLanguage").and_then(json_ld_text)))
+        // OpenGraph locales use underscores (`en_US`)
+        .or_else(|| meta_content(document, &["og:locale"]).map(|locale| locale.replace('_', "-")))
+        .or_else(|| {
+            let selector = Selector::parse("meta[http-equiv]").ok()?;
+            document.select(&selector)
+                .filter(|element| element.value().attr("http-equiv").is_some_and(|name| name.eq_ignore_ascii_case("content-language")))
+                .find_map(|element| element.value().attr("content").and_then(clean_text))
+        })
+}
+
+fn extract_favicon_url(document: &Html, base: &Url) -> Option<String> {
+    link_href(document, &["icon", "apple-touch-icon"])
+        .and_then(|url| resolve_url(base, &url))
+}
+
+/// Upper bound on captured page text, so one huge page can't bloat the content store
+const MAX_CONTENT_CHARS: usize = 200_000;
+
+/// Elements whose text is never part of the article body
+const BOILERPLATE_ELEMENTS: &[&str] = &["nav", "header", "footer", "aside", "script", "style", "noscript", "form"];
+
+/// Elements that make up the readable text of an article
+const TEXT_BLOCK_ELEMENTS: &[&str] = &["p", "h1", "h2", "h3", "h4", "h5", "h6", "li", "pre", "blockquote"];
+
+/// Extract the main readable text of a page, readability-style
+///
+/// Prefers an `<article>` or `<main>` element; otherwise picks the container with the
+/// most paragraph text. Navigation, headers, footers and scripts are skipped.
+fn extract_main_text(document: &Html) -> Option<String> {
+    let root = find_content_root(document)?;
+
+    let block_selector = Selector::parse(&TEXT_BLOCK_ELEMENTS.join(", ")).ok()?;
+    let mut blocks = Vec::new();
+    for element in root.select(&block_selector) {
+        let mut ancestors = element.ancestors().filter_map(ElementRef::wrap);
+        // Skip boilerplate, and blocks nested in another block (their text is already included)
+        if ancestors.any(|ancestor| {
+            let name = ancestor.value().name();
+            BOILERPLATE_ELEMENTS.contains(&name) || (ancestor != root && TEXT_BLOCK_ELEMENTS.contains(&name))
+        }) {
+            continue;
+        }
+
+        let text = element.text().collect::<Vec<_>>().join(" ");
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !text.is_empty() {
+            blocks.push(text);
         }
     }
 
-    None
+    let content = blocks.join("\n\n");
+    if content.is_empty() {
+        return None;
+    }
+    Some(truncate_content(content))
 }
 
-fn extract_publish_date(document: &Html) -> Option<DateTime<Utc>> {
-    // Try various meta tags for publish date
-    let meta_selectors = vec![
-        "meta[property='article:published_time']",
-        "meta[name='article:published_time']",
-        "meta[property='published_time']",
-        "meta[name='published_time']",
-        "meta[property='article:published']",
-        "meta[name='publish_date']",
-    ];
-
-    for selector_str in meta_selectors {
+/// Cut captured text down to `MAX_CONTENT_CHARS`
+pub(crate) fn truncate_content(content: String) -> String {
+    match content.char_indices().nth(MAX_CONTENT_CHARS) {
+        Some((index, _)) => content[..index].to_string(),
+        None => content,
+    }
+}
+
+/// Find the element most likely to hold the article body
+fn find_content_root(document: &Html) -> Option<ElementRef<'_>> {
+    let paragraph_text = |element: ElementRef| -> usize {
+        element.children()
+            .filter_map(ElementRef::wrap)
+            .filter(|child| child.value().name() == "p")
+            .map(|child| child.text().map(str::len).sum::<usize>())
+            .sum()
+    };
+
+    for selector_str in ["article", "main", "[role='main']"] {
         if let Ok(selector) = Selector::parse(selector_str) {
             if let Some(element) = document.select(&selector).next() {
-                if let Some(content) = element.value().attr("content") {
-                    // Try to parse various date formats
-                    if let Ok(date) = DateTime::parse_from_rfc3339(content) {
-                        return Some(date.with_timezone(&Utc));
-                    }
-                    if let Ok(date) = DateTime::parse_from_rfc2822(content) {
-                        return Some(date.with_timezone(&Utc));
-                    }
-                    // Try ISO 8601 without timezone
-                    if let Ok(date) = chrono::NaiveDateTime::parse_from_str(content, "%Y-%m-%dT%H:%M:%S") {
-                        return Some(date.and_utc());
-                    }
-                }
+                return Some(element);
             }
         }
     }
 
-    None
+    let container_selector = Selector::parse("div, section, td").ok()?;
+    let best = document.select(&container_selector)
+        .map(|element| (paragraph_text(element), element))
+        .filter(|(length, _)| *length > 0)
+        .max_by_key(|(length, _)| *length)
+        .map(|(_, element)| element);
+
+    best.or_else(|| document.select(&Selector::parse("body").ok()?).next())
 }
 
 #[cfg(test)]
@@ -257,20 +508,180 @@ mod tests {
         assert_eq!(date, None);
     }
 
+    #[test]
+    fn test_extract_main_text_from_article() {
+        let html = r#"
+            <html>
+                <body>
+                    <nav><a href="/">Home</a> <a href="/blog">Blog</a></nav>
+                    <article>
+                        <h1>Understanding   Ownership</h1>
+                        <p>Ownership is Rust's most <em>unique</em> feature.</p>
+                        <ul><li>Each value has an owner</li><li><p>Nested paragraph</p></li></ul>
+                        <script>trackPageView();</script>
+                        <footer>Share this post</footer>
+                    </article>
+                    <footer>Copyright 2024</footer>
+                </body>
+            </html>
+        "#;
+        let document = Html::parse_document(html);
+        let text = extract_main_text(&document).unwrap();
+        assert_eq!(
+            text,
+            "Understanding Ownership\n\nOwnership is Rust's most unique feature.\n\nEach value has an owner\n\nNested paragraph"
+        );
+    }
+
+    #[test]
+    fn test_extract_main_text_picks_densest_container() {
+        let html = r#"
+            <html>
+                <body>
+                    <div class="sidebar"><p>Subscribe!</p></div>
+                    <div class="post">
+                        <p>The first paragraph of the post is fairly long.</p>
+                        <p>The second paragraph adds more detail.</p>
+                    </div>
+                </body>
+            </html>
+        "#;
+        let document = Html::parse_document(html);
+        let text = extract_main_text(&document).unwrap();
+        assert!(text.starts_with("The first paragraph"));
+        assert!(!text.contains("Subscribe"));
+    }
+
+    #[test]
+    fn test_extract_main_text_empty_page() {
+        let document = Html::parse_document("<html><head><title>Empty</title></head><body></body></html>");
+        assert_eq!(extract_main_text(&document), None);
+    }
+
+    fn extractor() -> WebExtractor {
+        WebExtractor::new(Arc::new(PageFetcher::new(&crate::types::MetadataConfig::default()).unwrap()))
+    }
+
     #[tokio::test]
     async fn test_web_extractor_creation() {
-        let extractor = WebExtractor::new();
+        let extractor = extractor();
         // Just test that it can be created
         assert!(std::mem::size_of_val(&extractor) > 0);
     }
 
     #[tokio::test]
     async fn test_invalid_url() {
-        let extractor = WebExtractor::new();
+        let extractor = extractor();
         let result = extractor.extract_metadata("not-a-url", Duration::from_secs(10)).await;
         assert!(matches!(result, Err(ExtractorError::InvalidUrl(_))));
     }
 
+    fn page_url() -> Url {
+        Url::parse("https://example.com/blog/post").unwrap()
+    }
+
+    #[test]
+    fn test_parse_metadata_prefers_json_ld() {
+        let html = r#"
+            <html lang="en">
+                <head>
+                    <title>Post | Example Blog</title>
+                    <meta property="og:title" content="OpenGraph Title">
+                    <meta property="og:description" content="OpenGraph description">
+                    <meta property="og:image" content="/og.png">
+                    <meta property="og:site_name" content="Example Blog">
+                    <script type="application/ld+json">
+                    {
+                        "@context": "https://schema.org",
+                        "@graph": [
+                            {"@type": "WebSite", "name": "Example Website"},
+                            {
+                                "@type": ["BlogPosting"],
+                                "headline": "  JSON-LD   Headline ",
+                                "description": "JSON-LD description",
+                                "author": [{"@type": "Person", "name": "Ada"}, {"@type": "Person", "name": "Grace"}],
+                                "datePublished": "2024-03-01",
+                                "image": {"@type": "ImageObject", "url": "https://cdn.example.com/cover.jpg"},
+                                "publisher": {"@type": "Organization", "name": "Example Media Group"}
+                            }
+                        ]
+                    }
+                    </script>
+                </head>
+                <body></body>
+            </html>
+        "#;
+        let metadata = parse_metadata(html, &page_url());
+
+        assert_eq!(metadata.title, Some("JSON-LD Headline".to_string()));
+        assert_eq!(metadata.description, Some("JSON-LD description".to_string()));
+        assert_eq!(metadata.author, Some("Ada, Grace".to_string()));
+        assert_eq!(metadata.publish_date.unwrap().to_rfc3339(), "2024-03-01T00:00:00+00:00");
+        assert_eq!(metadata.image_url, Some("https://cdn.example.com/cover.jpg".to_string()));
+        // OpenGraph names the site; the JSON-LD publisher is the parent company
+        assert_eq!(metadata.site_name, Some("Example Blog".to_string()));
+        assert_eq!(metadata.language, Some("en".to_string()));
+    }
+
+    #[test]
+    fn test_parse_metadata_falls_back_through_sources() {
+        let html = r#"
+            <html>
+                <head>
+                    <base href="https://static.example.com/assets/">
+                    <title>Document Title</title>
+                    <meta name="twitter:title" content="Twitter Title">
+                    <meta name="twitter:description" content="Twitter description">
+                    <meta name="description" content="Meta description">
+                    <meta name="twitter:image" content="card.png">
+                    <meta property="og:locale" content="de_DE">
+                    <meta property="og:url" content="https://example.com/blog/post?ref=og">
+                    <link rel="canonical" href="/blog/post">
+                    <link rel="apple-touch-icon" href="touch.png">
+                    <link rel="shortcut icon" href="favicon.ico">
+                    <script type="application/ld+json">not valid json</script>
+                </head>
+                <body></body>
+            </html>
+        "#;
+        let metadata = parse_metadata(html, &page_url());
+
+        assert_eq!(metadata.title, Some("Twitter Title".to_string()));
+        assert_eq!(metadata.description, Some("Twitter description".to_string()));
+        assert_eq!(metadata.image_url, Some("https://static.example.com/assets/card.png".to_string()));
+        assert_eq!(metadata.language, Some("de-DE".to_string()));
+        assert_eq!(metadata.canonical_url, Some("https://static.example.com/blog/post".to_string()));
+        assert_eq!(metadata.favicon_url, Some("https://static.example.com/assets/favicon.ico".to_string()));
+        assert_eq!(metadata.site_name, None);
+        assert_eq!(metadata.author, None);
+    }
+
+    #[test]
+    fn test_parse_metadata_plain_page() {
+        let html = r#"
+            <html>
+                <head>
+                    <title>Plain Page</title>
+                    <meta name="description" content="Just a page">
+                    <meta name="application-name" content="Plain Site">
+                    <link rel="icon" href="data:image/png;base64,AAAA">
+                </head>
+                <body><p>Hello</p></body>
+            </html>
+        "#;
+        let metadata = parse_metadata(html, &page_url());
+
+        assert_eq!(metadata.title, Some("Plain Page".to_string()));
+        assert_eq!(metadata.description, Some("Just a page".to_string()));
+        assert_eq!(metadata.site_name, Some("Plain Site".to_string()));
+        assert_eq!(metadata.content, Some("Hello".to_string()));
+        // Only web URLs are kept
+        assert_eq!(metadata.favicon_url, None);
+        assert_eq!(metadata.canonical_url, None);
+        assert_eq!(metadata.image_url, None);
+        assert_eq!(metadata.language, None);
+    }
+
     // Integration test with mock server would go here in a real implementation
     // For now, we'll skip network tests to avoid external dependencies in unit tests
 }
\ No newline at end of file
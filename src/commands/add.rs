@@ -1,10 +1,12 @@
 use crate::commands::{AddArgs, OutputFormat, output};
-use crate::traits::{BookmarkRepository, MetadataExtractor};
-use crate::types::{Bookmark, BookmarkResult, Config, ExtractedMetadata};
-use crate::adapters::WebExtractor;
+use crate::commands::archive::archive_bookmark;
+use crate::traits::{BookmarkRepository, MetadataExtractor, PageArchiver};
+use crate::types::{AppliedRule, Bookmark, BookmarkError, BookmarkResult, Config, ExtractedMetadata, RuleSet, normalize_tag};
+use crate::adapters::{ExtractorRegistry, HttpCache, TagSuggester, WebArchiver};
 use std::time::Duration;
 use serde::{Serialize, Deserialize};
 use tokio::time;
+use url::Url;
 
 
 /// JSON response data for add command
@@ -15,6 +17,21 @@ pub struct AddResponse {
     pub extraction_time_ms: Option<u64>,
     pub extracted_metadata: Option<ExtractedMetadataInfo>,
     pub extraction_status: ExtractionStatus,
+    /// Whether the page text was stored for full-text search
+    #[serde(default)]
+    pub content_captured: bool,
+    /// Whether an offline copy of the page was saved
+    #[serde(default)]
+    pub archived: bool,
+    /// Why saving the offline copy failed, if it was requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_error: Option<String>,
+    /// Tags from the existing vocabulary that fit the page; applied with `--auto-tag`
+    #[serde(default)]
+    pub suggested_tags: Vec<String>,
+    /// Rules from the config that matched the new bookmark
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub applied_rules: Vec<AppliedRule>,
 }
 
 /// Information about extracted metadata for response
@@ -30,6 +47,8 @@ pub struct ExtractedMetadataInfo {
 pub enum ExtractionStatus {
     Success,
     Skipped,
+    /// Put off by `--async-fetch` until the next online command or `automark refresh --pending`
+    Deferred,
     Failed(String),
     Timeout,
 }
@@ -41,22 +60,27 @@ pub async fn handle_add_command(
     config: &Config,
     format: OutputFormat,
 ) -> BookmarkResult<()> {
-    let extractor = WebExtractor::new();
-    handle_add_command_with_extractor_and_config(args, repository, &extractor, config, format).await
+    let cache = HttpCache::for_config(config);
+    let extractor = ExtractorRegistry::with_builtin_sites(&config.metadata, cache.clone())?;
+    let archiver = WebArchiver::new(&config.metadata)?.with_cache(cache);
+    handle_add_command_with_extractor_and_config(args, repository, &extractor, &archiver, config, format).await
 }
 
 pub async fn handle_add_command_with_extractor_and_config(
     args: AddArgs,
     repository: &mut dyn BookmarkRepository,
     extractor: &dyn MetadataExtractor,
+    archiver: &dyn PageArchiver,
     config: &Config,
     format: OutputFormat,
 ) -> BookmarkResult<()> {
     let start_time = std::time::Instant::now();
     
-    // Determine if metadata extraction should be performed
-    let should_extract = should_extract_metadata(&args, config);
-    let mut extraction_status = ExtractionStatus::Skipped;
+    // Determine if metadata extraction should be performed now, later, or not at all
+    let wants_metadata = should_extract_metadata(&args, config);
+    let should_extract = wants_metadata && !args.async_fetch;
+    let mut metadata_pending = wants_metadata && args.async_fetch;
+    let mut extraction_status = if metadata_pending { ExtractionStatus::Deferred } else { ExtractionStatus::Skipped };
     let mut extracted_metadata_info = None;
     let mut extracted_metadata = None;
     
@@ -80,9 +104,14 @@ pub async fn handle_add_command_with_extractor_and_config(
                     if let Some(ref author) = extracted_metadata.as_ref().unwrap().author {
                         println!("  Author: {}", author);
                     }
+                    if let Some(ref site_name) = extracted_metadata.as_ref().unwrap().site_name {
+                        println!("  Site: {}", site_name);
+                    }
                 }
             }
             Err(e) => {
+                // Network failures are retried later; refusals such as robots.txt won't change
+                metadata_pending = is_offline_error(&e);
                 extraction_status = ExtractionStatus::Failed(e.to_string());
                 if format == OutputFormat::Human {
                     println!("Metadata extraction failed: {}", e);
@@ -95,13 +124,46 @@ pub async fn handle_add_command_with_extractor_and_config(
     let extraction_time = start_time.elapsed();
     
     // Create bookmark with metadata integration
-    let bookmark = create_bookmark_with_metadata(&args, extracted_metadata.as_ref())?;
-    let saved_bookmark = repository.create(bookmark).await?;
+    let mut bookmark = create_bookmark_with_metadata(&args, extracted_metadata.as_ref(), metadata_pending)?;
+    let applied_rules = RuleSet::new(&config.rules)?.apply(&mut bookmark);
+    
+    // Suggest tags from the existing vocabulary before this bookmark joins it; --async-fetch
+    // skips this to return quickly, and suggestions never stop the bookmark being saved
+    let page_text = extracted_metadata.as_ref().and_then(|metadata| metadata.content.as_deref());
+    let suggester = if args.async_fetch { None } else { TagSuggester::from_repository(repository).await.ok() };
+    let suggested_tags: Vec<String> = suggester
+        .map(|suggester| suggester.suggest(&bookmark, page_text))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|suggestion| suggestion.tag)
+        .collect();
+    if args.auto_tag {
+        bookmark.tags.extend(suggested_tags.iter().cloned());
+    }
+    let mut saved_bookmark = repository.create(bookmark).await?;
+    
+    // Store the page text alongside the bookmark if capture is enabled
+    let captured_content = extracted_metadata.as_ref()
+        .and_then(|metadata| metadata.content.as_deref())
+        .filter(|_| config.metadata.capture_content);
+    if let Some(content) = captured_content {
+        repository.save_content(&saved_bookmark.id, content).await?;
+    }
+    
+    // Save an offline copy if asked; the bookmark is kept even if this fails
+    let mut archive_error = None;
+    if args.archive {
+        match archive_bookmark(&saved_bookmark, repository, archiver, config).await {
+            Ok((bookmark, _)) => saved_bookmark = bookmark,
+            Err(e) => archive_error = Some(e.to_string()),
+        }
+    }
     
     // Output results
     match format {
         OutputFormat::Json => {
             let response = AddResponse {
+                archived: saved_bookmark.archived_at.is_some(),
                 bookmark: saved_bookmark,
                 metadata_extracted: should_extract && matches!(extraction_status, ExtractionStatus::Success),
                 extraction_time_ms: if should_extract {
@@ -111,6 +173,10 @@ pub async fn handle_add_command_with_extractor_and_config(
                 },
                 extracted_metadata: extracted_metadata_info,
                 extraction_status,
+                content_captured: captured_content.is_some(),
+                archive_error,
+                suggested_tags,
+                applied_rules,
             };
             output::print_response(format, response)?;
         }
@@ -121,22 +187,50 @@ pub async fn handle_add_command_with_extractor_and_config(
             if let Some(ref author) = saved_bookmark.author {
                 println!("  Author: {}", author);
             }
+            if let Some(ref description) = saved_bookmark.description {
+                println!("  Description: {}", description);
+            }
+            for (name, value) in &saved_bookmark.site_details {
+                println!("  {}: {}", name.replace('_', " "), value);
+            }
             if !saved_bookmark.tags.is_empty() {
                 println!("  Tags: {}", saved_bookmark.tags.join(", "));
             }
+            for applied in applied_rules.iter().filter(|applied| !applied.changes.is_empty()) {
+                println!("  Rule {}: {}", applied.rule, applied.changes.join(", "));
+            }
+            if !suggested_tags.is_empty() {
+                if args.auto_tag {
+                    println!("  Auto-tagged: {}", suggested_tags.join(", "));
+                } else {
+                    println!("  Suggested tags: {} (apply with --auto-tag)", suggested_tags.join(", "));
+                }
+            }
             println!("  ID: {}", saved_bookmark.id);
             println!("  Added: {}", saved_bookmark.bookmarked_date.format("%Y-%m-%d %H:%M:%S UTC"));
             
             if should_extract {
                 match extraction_status {
                     ExtractionStatus::Success => println!("  Metadata extraction: successful ({:.2}s)", extraction_time.as_secs_f64()),
+                    ExtractionStatus::Failed(_) if metadata_pending => println!("  Metadata extraction: failed ({:.2}s); will retry when online", extraction_time.as_secs_f64()),
                     ExtractionStatus::Failed(_) => println!("  Metadata extraction: failed ({:.2}s)", extraction_time.as_secs_f64()),
                     ExtractionStatus::Timeout => println!("  Metadata extraction: timed out ({:.2}s)", extraction_time.as_secs_f64()),
                     _ => {}
                 }
+            } else if metadata_pending {
+                println!("  Metadata extraction: pending; filled in by the next command run online or `automark refresh --pending`");
             } else {
                 println!("  Metadata extraction: skipped");
             }
+            if let Some(content) = captured_content {
+                println!("  Page text: captured ({} words)", content.split_whitespace().count());
+            }
+            if args.archive {
+                match archive_error {
+                    None => println!("  Archive: saved"),
+                    Some(e) => println!("  Archive: failed ({}); retry with: automark archive {}", e, &saved_bookmark.id[..8.min(saved_bookmark.id.len())]),
+                }
+            }
         }
     }
     
@@ -162,7 +256,7 @@ fn should_extract_metadata(args: &AddArgs, config: &Config) -> bool {
 }
 
 /// Extract metadata with configuration settings including timeout and retries
-async fn extract_metadata_with_config(
+pub(crate) async fn extract_metadata_with_config(
     url: &str,
     extractor: &dyn MetadataExtractor,
     config: &Config,
@@ -178,6 +272,9 @@ async fn extract_metadata_with_config(
         
         match time::timeout(timeout_duration, extractor.extract_metadata(url, timeout_duration)).await {
             Ok(Ok(metadata)) => return Ok(metadata),
+            Ok(Err(e)) if !e.is_retryable() => {
+                return Err(crate::types::BookmarkError::MetadataExtraction(e));
+            }
             Ok(Err(e)) => {
                 last_error = Some(e);
                 if attempt < config.metadata.retry_attempts {
@@ -198,20 +295,26 @@ async fn extract_metadata_with_config(
     ))
 }
 
+/// Whether an extraction failed for lack of network, so trying again later may succeed
+pub(crate) fn is_offline_error(error: &BookmarkError) -> bool {
+    matches!(error, BookmarkError::MetadataExtraction(e) if e.is_retryable())
+}
+
 /// Create a bookmark integrating manual args with extracted metadata
 fn create_bookmark_with_metadata(
     args: &AddArgs,
     extracted_metadata: Option<&ExtractedMetadata>,
+    metadata_pending: bool,
 ) -> BookmarkResult<Bookmark> {
-    // Determine title: manual override > extracted > error
+    // Determine title: manual override > extracted > host name until metadata arrives > error
     let title = if let Some(ref manual_title) = args.title {
         manual_title.trim().to_string()
-    } else if let Some(metadata) = extracted_metadata {
-        if let Some(ref extracted_title) = metadata.title {
-            extracted_title.trim().to_string()
-        } else {
-            return Err(crate::types::BookmarkError::EmptyTitle);
-        }
+    } else if let Some(extracted_title) = extracted_metadata.and_then(|metadata| metadata.title.as_ref()) {
+        extracted_title.trim().to_string()
+    } else if metadata_pending {
+        Url::parse(&args.url).ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_else(|| args.url.clone())
     } else {
         return Err(crate::types::BookmarkError::EmptyTitle);
     };
@@ -222,6 +325,7 @@ fn create_bookmark_with_metadata(
     
     // Create base bookmark
     let mut bookmark = Bookmark::new(&args.url, &title)?;
+    bookmark.metadata_pending = metadata_pending;
     
     // Set author: manual override > extracted
     if let Some(ref manual_author) = args.author {
@@ -230,15 +334,22 @@ fn create_bookmark_with_metadata(
         bookmark.author = metadata.author.clone();
     }
     
-    // Set publish date from extracted metadata if available
+    // Set publish date and page details from extracted metadata if available
     if let Some(metadata) = extracted_metadata {
         bookmark.publish_date = metadata.publish_date;
+        bookmark.description = metadata.description.clone();
+        bookmark.site_name = metadata.site_name.clone();
+        bookmark.canonical_url = metadata.canonical_url.clone();
+        bookmark.image_url = metadata.image_url.clone();
+        bookmark.language = metadata.language.clone();
+        bookmark.favicon_url = metadata.favicon_url.clone();
+        bookmark.site_details = metadata.site_details.clone();
     }
     
     // Add tags from args
     if !args.tags.is_empty() {
         bookmark.tags = args.tags.iter()
-            .map(|tag| tag.trim().to_string())
+            .map(|tag| normalize_tag(tag))
             .filter(|tag| !tag.is_empty())
             .collect();
     }
@@ -250,7 +361,16 @@ fn create_bookmark_with_metadata(
 mod tests {
     use super::*;
     use crate::traits::repository::MockBookmarkRepository;
-    use crate::types::BookmarkError;
+    use crate::traits::metadata_extractor::MockMetadataExtractor;
+    use crate::traits::page_archiver::MockPageArchiver;
+    use crate::types::{ExtractorError, Rule};
+
+    fn no_archiver() -> MockPageArchiver {
+        MockPageArchiver {
+            should_fail: true,
+            html: String::new(),
+        }
+    }
 
     #[tokio::test]
     async fn test_add_valid_bookmark() {
@@ -262,6 +382,9 @@ mod tests {
             author: None,
             tags: vec![],
             no_fetch: false,
+            async_fetch: false,
+            auto_tag: false,
+            archive: false,
         };
         
         let result = handle_add_command(args, &mut repo, &config, OutputFormat::Human).await;
@@ -284,6 +407,9 @@ mod tests {
             author: None,
             tags: vec![],
             no_fetch: false,
+            async_fetch: false,
+            auto_tag: false,
+            archive: false,
         };
         
         let result = handle_add_command(args, &mut repo, &config, OutputFormat::Human).await;
@@ -303,6 +429,9 @@ mod tests {
             author: None,
             tags: vec![],
             no_fetch: false,
+            async_fetch: false,
+            auto_tag: false,
+            archive: false,
         };
         
         let config = Config::default();
@@ -323,6 +452,9 @@ mod tests {
             author: None,
             tags: vec![],
             no_fetch: false,
+            async_fetch: false,
+            auto_tag: false,
+            archive: false,
         };
         
         let config = Config::default();
@@ -353,6 +485,9 @@ mod tests {
                 author: None,
                 tags: vec![],
                 no_fetch: false,
+                async_fetch: false,
+                auto_tag: false,
+                archive: false,
             };
             
             let result = handle_add_command(args, &mut repo, &config, OutputFormat::Human).await;
@@ -373,6 +508,9 @@ mod tests {
             author: None,
             tags: vec![],
             no_fetch: false,
+            async_fetch: false,
+            auto_tag: false,
+            archive: false,
         };
         
         let result = handle_add_command(args, &mut repo, &Config::default(), OutputFormat::Human).await;
@@ -394,6 +532,9 @@ mod tests {
             author: None,
             tags: vec![],
             no_fetch: true,
+            async_fetch: false,
+            auto_tag: false,
+            archive: false,
         };
         
         // With no_fetch = true, should not use extractor and should prompt for title
@@ -415,6 +556,11 @@ mod tests {
             extraction_time_ms: Some(250),
             extracted_metadata: None,
             extraction_status: ExtractionStatus::Success,
+            content_captured: false,
+            archived: false,
+            archive_error: None,
+            suggested_tags: vec![],
+            applied_rules: vec![],
         };
         
         // Test that the response can be serialized to JSON
@@ -437,6 +583,9 @@ mod tests {
             author: Some("Jane Doe".to_string()),
             tags: vec!["rust".to_string(), "programming".to_string()],
             no_fetch: true, // Skip metadata extraction
+            async_fetch: false,
+            auto_tag: false,
+            archive: false,
         };
         
         let result = handle_add_command(args, &mut repo, &config, OutputFormat::Human).await;
@@ -458,6 +607,9 @@ mod tests {
             author: None,
             tags: vec![],
             no_fetch: true, // Skip metadata extraction to avoid network calls
+            async_fetch: false,
+            auto_tag: false,
+            archive: false,
         };
         
         let config = Config::default();
@@ -469,5 +621,249 @@ mod tests {
         assert_eq!(bookmarks.len(), 1);
         assert_eq!(bookmarks[0].title, "Test Bookmark");
     }
+
+    #[tokio::test]
+    async fn test_add_captures_content_when_enabled() {
+        let extractor = MockMetadataExtractor {
+            should_fail: false,
+            extracted_title: Some("Article".to_string()),
+            extracted_author: None,
+            extracted_content: Some("The full article text".to_string()),
+        };
+        let args = || AddArgs {
+            url: "https://example.com".to_string(),
+            title: None,
+            author: None,
+            tags: vec![],
+            no_fetch: false,
+            async_fetch: false,
+            auto_tag: false,
+            archive: false,
+        };
+        
+        // Disabled by default
+        let mut repo = MockBookmarkRepository::new();
+        handle_add_command_with_extractor_and_config(args(), &mut repo, &extractor, &no_archiver(), &Config::default(), OutputFormat::Json).await.unwrap();
+        let bookmarks = repo.find_all(None).await.unwrap();
+        assert_eq!(repo.find_content(&bookmarks[0].id).await.unwrap(), None);
+        
+        let mut config = Config::default();
+        config.metadata.capture_content = true;
+        let mut repo = MockBookmarkRepository::new();
+        handle_add_command_with_extractor_and_config(args(), &mut repo, &extractor, &no_archiver(), &config, OutputFormat::Json).await.unwrap();
+        let bookmarks = repo.find_all(None).await.unwrap();
+        assert_eq!(repo.find_content(&bookmarks[0].id).await.unwrap(), Some("The full article text".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_add_with_archive() {
+        let extractor = MockMetadataExtractor {
+            should_fail: true,
+            extracted_title: None,
+            extracted_author: None,
+            extracted_content: None,
+        };
+        let args = || AddArgs {
+            url: "https://example.com".to_string(),
+            title: Some("Example".to_string()),
+            author: None,
+            tags: vec![],
+            no_fetch: true,
+            async_fetch: false,
+            auto_tag: false,
+            archive: true,
+        };
+        
+        let archiver = MockPageArchiver {
+            should_fail: false,
+            html: "<html>copy</html>".to_string(),
+        };
+        let mut repo = MockBookmarkRepository::new();
+        handle_add_command_with_extractor_and_config(args(), &mut repo, &extractor, &archiver, &Config::default(), OutputFormat::Json).await.unwrap();
+        let bookmarks = repo.find_all(None).await.unwrap();
+        assert!(bookmarks[0].archived_at.is_some());
+        assert!(repo.find_archive(&bookmarks[0].id).await.unwrap().is_some());
+        
+        // A failed archive still keeps the bookmark
+        let mut repo = MockBookmarkRepository::new();
+        handle_add_command_with_extractor_and_config(args(), &mut repo, &extractor, &no_archiver(), &Config::default(), OutputFormat::Human).await.unwrap();
+        let bookmarks = repo.find_all(None).await.unwrap();
+        assert_eq!(bookmarks.len(), 1);
+        assert_eq!(bookmarks[0].archive_path, None);
+    }
+
+    #[tokio::test]
+    async fn test_add_applies_rules() {
+        let args = AddArgs {
+            url: "https://github.com/ietf/rfc9110".to_string(),
+            title: Some("RFC 9110 sources".to_string()),
+            author: None,
+            tags: vec![],
+            no_fetch: true,
+            async_fetch: false,
+            auto_tag: false,
+            archive: false,
+        };
+        let config = Config {
+            rules: vec![
+                Rule { name: None, query: "domain:github.com".to_string(), add_tags: vec!["code".to_string()], priority: None },
+                Rule { name: Some("RFCs".to_string()), query: r"title:~/RFC \d+/".to_string(), add_tags: vec![], priority: Some(4) },
+            ],
+            ..Config::default()
+        };
+
+        let mut repo = MockBookmarkRepository::new();
+        handle_add_command(args, &mut repo, &config, OutputFormat::Human).await.unwrap();
+        let bookmarks = repo.find_all(None).await.unwrap();
+        assert_eq!(bookmarks[0].tags, vec!["code"]);
+        assert_eq!(bookmarks[0].priority_rating, Some(4));
+    }
+
+    #[tokio::test]
+    async fn test_add_suggests_and_applies_tags() {
+        let args = |auto_tag| AddArgs {
+            url: "https://blog.example.com/borrow-checker".to_string(),
+            title: None,
+            author: None,
+            tags: vec!["reading".to_string()],
+            no_fetch: false,
+            async_fetch: false,
+            auto_tag,
+            archive: false,
+        };
+        let extractor = MockMetadataExtractor {
+            should_fail: false,
+            extracted_title: Some("Fighting the Rust borrow checker".to_string()),
+            extracted_author: None,
+            extracted_content: Some("Lifetimes and ownership in Rust".to_string()),
+        };
+        let seeded = || async {
+            let mut repo = MockBookmarkRepository::new();
+            let rust = Bookmark::new("https://doc.rust-lang.org/book/", "The Rust Programming Language").unwrap()
+                .with_tags(vec!["rust".to_string()]);
+            let cooking = Bookmark::new("https://example.com/pasta", "Weeknight pasta recipes").unwrap()
+                .with_tags(vec!["cooking".to_string()]);
+            repo.create(rust).await.unwrap();
+            repo.create(cooking).await.unwrap();
+            repo
+        };
+
+        // Suggestions are only listed by default
+        let mut repo = seeded().await;
+        handle_add_command_with_extractor_and_config(args(false), &mut repo, &extractor, &no_archiver(), &Config::default(), OutputFormat::Human).await.unwrap();
+        let added = repo.find_all(None).await.unwrap().into_iter().find(|b| b.url.contains("borrow-checker")).unwrap();
+        assert_eq!(added.tags, vec!["reading"]);
+
+        let mut repo = seeded().await;
+        handle_add_command_with_extractor_and_config(args(true), &mut repo, &extractor, &no_archiver(), &Config::default(), OutputFormat::Json).await.unwrap();
+        let added = repo.find_all(None).await.unwrap().into_iter().find(|b| b.url.contains("borrow-checker")).unwrap();
+        assert_eq!(added.tags, vec!["reading", "rust"]);
+
+        // --async-fetch returns without training the suggester
+        let mut repo = seeded().await;
+        let deferred = AddArgs { async_fetch: true, ..args(true) };
+        handle_add_command_with_extractor_and_config(deferred, &mut repo, &extractor, &no_archiver(), &Config::default(), OutputFormat::Json).await.unwrap();
+        let added = repo.find_all(None).await.unwrap().into_iter().find(|b| b.url.contains("borrow-checker")).unwrap();
+        assert_eq!(added.tags, vec!["reading"]);
+    }
+
+    #[tokio::test]
+    async fn test_add_offline_marks_metadata_pending() {
+        let args = |async_fetch| AddArgs {
+            url: "https://blog.example.com/post".to_string(),
+            title: None,
+            author: None,
+            tags: vec![],
+            no_fetch: false,
+            async_fetch,
+            auto_tag: false,
+            archive: false,
+        };
+        let offline = MockMetadataExtractor {
+            should_fail: true,
+            extracted_title: None,
+            extracted_author: None,
+            extracted_content: None,
+        };
+        let mut config = Config::default();
+        config.metadata.retry_attempts = 0;
+
+        // A network failure keeps the bookmark under its host name until metadata arrives
+        let mut repo = MockBookmarkRepository::new();
+        handle_add_command_with_extractor_and_config(args(false), &mut repo, &offline, &no_archiver(), &config, OutputFormat::Json).await.unwrap();
+        let bookmarks = repo.find_all(None).await.unwrap();
+        assert_eq!(bookmarks[0].title, "blog.example.com");
+        assert!(bookmarks[0].metadata_pending);
+
+        // --async-fetch doesn't fetch at all
+        let online = MockMetadataExtractor {
+            should_fail: false,
+            extracted_title: Some("Fetched Title".to_string()),
+            extracted_author: None,
+            extracted_content: None,
+        };
+        let mut repo = MockBookmarkRepository::new();
+        handle_add_command_with_extractor_and_config(args(true), &mut repo, &online, &no_archiver(), &config, OutputFormat::Json).await.unwrap();
+        let bookmarks = repo.find_all(None).await.unwrap();
+        assert_eq!(bookmarks[0].title, "blog.example.com");
+        assert!(bookmarks[0].metadata_pending);
+    }
+
+    #[test]
+    fn test_create_bookmark_stores_page_details() {
+        let args = AddArgs {
+            url: "https://example.com/post?utm_source=feed".to_string(),
+            title: None,
+            author: None,
+            tags: vec![],
+            no_fetch: false,
+            async_fetch: false,
+            auto_tag: false,
+            archive: false,
+        };
+        let metadata = ExtractedMetadata {
+            title: Some("Post".to_string()),
+            description: Some("A post about things".to_string()),
+            site_name: Some("Example Blog".to_string()),
+            canonical_url: Some("https://example.com/post".to_string()),
+            image_url: Some("https://example.com/cover.png".to_string()),
+            language: Some("en".to_string()),
+            favicon_url: Some("https://example.com/favicon.ico".to_string()),
+            ..Default::default()
+        };
+
+        let bookmark = create_bookmark_with_metadata(&args, Some(&metadata), false).unwrap();
+        assert_eq!(bookmark.description, metadata.description);
+        assert_eq!(bookmark.site_name, metadata.site_name);
+        assert_eq!(bookmark.canonical_url, metadata.canonical_url);
+        assert_eq!(bookmark.image_url, metadata.image_url);
+        assert_eq!(bookmark.language, metadata.language);
+        assert_eq!(bookmark.favicon_url, metadata.favicon_url);
+        // The bookmarked URL is kept as given
+        assert_eq!(bookmark.url, "https://example.com/post?utm_source=feed");
+    }
+
+    #[tokio::test]
+    async fn test_robots_block_is_not_retried() {
+        struct BlockedExtractor {
+            calls: std::sync::atomic::AtomicUsize,
+        }
+
+        #[async_trait::async_trait]
+        impl MetadataExtractor for BlockedExtractor {
+            async fn extract_metadata(&self, url: &str, _timeout: Duration) -> Result<ExtractedMetadata, ExtractorError> {
+                self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                Err(ExtractorError::BlockedByRobots(url.to_string()))
+            }
+        }
+
+        let extractor = BlockedExtractor { calls: Default::default() };
+        let mut config = Config::default();
+        config.metadata.retry_delay_ms = 0;
+
+        let result = extract_metadata_with_config("https://example.com/private", &extractor, &config).await;
+        assert!(matches!(result, Err(BookmarkError::MetadataExtraction(ExtractorError::BlockedByRobots(_)))));
+        assert_eq!(extractor.calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
 }
 
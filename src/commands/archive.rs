@@ -0,0 +1,132 @@
+use crate::commands::{OutputFormat, find_bookmark_by_partial_id, output};
+use crate::traits::{BookmarkRepository, PageArchiver};
+use crate::types::{Bookmark, BookmarkError, BookmarkResult, Config};
+use crate::adapters::{HttpCache, WebArchiver};
+use clap::Args;
+use serde::{Serialize, Deserialize};
+use std::time::Duration;
+
+/// Command-line arguments for archive command
+#[derive(Args, Debug, Clone)]
+pub struct ArchiveArgs {
+    /// ID of the bookmark to archive (can be partial ID)
+    pub id: String,
+}
+
+/// JSON response data for archive command
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ArchiveResponse {
+    pub bookmark: Bookmark,
+    /// Absolute location of the archive on this device
+    pub archive_file: String,
+    pub size_bytes: usize,
+}
+
+pub async fn handle_archive_command(
+    args: ArchiveArgs,
+    repository: &mut dyn BookmarkRepository,
+    config: &Config,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    let archiver = WebArchiver::new(&config.metadata)?.with_cache(HttpCache::for_config(config));
+    handle_archive_command_with_archiver_and_config(args, repository, &archiver, config, format).await
+}
+
+pub async fn handle_archive_command_with_archiver_and_config(
+    args: ArchiveArgs,
+    repository: &mut dyn BookmarkRepository,
+    archiver: &dyn PageArchiver,
+    config: &Config,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    let bookmarks = repository.find_all(None).await?;
+    let candidates: Vec<&Bookmark> = bookmarks.iter().collect();
+    let bookmark = find_bookmark_by_partial_id(&candidates, &args.id)?.clone();
+
+    if format == OutputFormat::Human {
+        println!("Archiving {}...", bookmark.url);
+    }
+    let (bookmark, size_bytes) = archive_bookmark(&bookmark, repository, archiver, config).await?;
+    let archive_file = repository.find_archive(&bookmark.id).await?
+        .map(|path| path.display().to_string())
+        .unwrap_or_default();
+
+    match format {
+        OutputFormat::Json => {
+            output::print_response(format, ArchiveResponse { bookmark, archive_file, size_bytes })?;
+        }
+        OutputFormat::Human => {
+            println!("✓ Archived: {}", bookmark.title);
+            println!("  File: {}", archive_file);
+            println!("  Size: {} KB", size_bytes.div_ceil(1024));
+            println!("  Open with: automark open {} --archived", &bookmark.id[..8.min(bookmark.id.len())]);
+        }
+    }
+
+    Ok(())
+}
+
+/// Download a bookmark's page as a single HTML file and record it on the bookmark
+///
+/// Returns the updated bookmark and the size of the archive in bytes.
+pub(crate) async fn archive_bookmark(
+    bookmark: &Bookmark,
+    repository: &mut dyn BookmarkRepository,
+    archiver: &dyn PageArchiver,
+    config: &Config,
+) -> BookmarkResult<(Bookmark, usize)> {
+    let timeout = Duration::from_secs(config.metadata.timeout_secs);
+    let html = archiver.archive_page(&bookmark.url, timeout).await
+        .map_err(|e| BookmarkError::ArchiveError(e.to_string()))?;
+
+    let bookmark = repository.save_archive(&bookmark.id, &html).await?;
+    Ok((bookmark, html.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::repository::MockBookmarkRepository;
+    use crate::traits::page_archiver::MockPageArchiver;
+
+    fn archiver(should_fail: bool) -> MockPageArchiver {
+        MockPageArchiver {
+            should_fail,
+            html: "<html><body>Saved copy</body></html>".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_archive_records_path_and_time() {
+        let mut repo = MockBookmarkRepository::new();
+        let mut bookmark = Bookmark::new("https://example.com/post", "Post").unwrap();
+        bookmark.id = "abcdef1234567890".to_string();
+        repo.create(bookmark).await.unwrap();
+
+        let args = ArchiveArgs { id: "abcdef12".to_string() };
+        handle_archive_command_with_archiver_and_config(
+            args, &mut repo, &archiver(false), &Config::default(), OutputFormat::Json,
+        ).await.unwrap();
+
+        let archived = repo.find_by_id("abcdef1234567890").await.unwrap();
+        assert_eq!(archived.archive_path, Some("archives/abcdef1234567890.html".to_string()));
+        assert!(archived.archived_at.is_some());
+        assert!(repo.find_archive("abcdef1234567890").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_archive_failure_leaves_bookmark_unchanged() {
+        let mut repo = MockBookmarkRepository::new();
+        let bookmark = Bookmark::new("https://example.com/post", "Post").unwrap();
+        let id = bookmark.id.clone();
+        repo.create(bookmark).await.unwrap();
+
+        let args = ArchiveArgs { id: id.clone() };
+        let result = handle_archive_command_with_archiver_and_config(
+            args, &mut repo, &archiver(true), &Config::default(), OutputFormat::Human,
+        ).await;
+
+        assert!(matches!(result, Err(BookmarkError::ArchiveError(_))));
+        assert_eq!(repo.find_by_id(&id).await.unwrap().archive_path, None);
+    }
+}
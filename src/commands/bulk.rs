@@ -0,0 +1,531 @@
+use crate::commands::{OutputFormat, find_bookmark_by_partial_id, output};
+use crate::commands::add::extract_metadata_with_config;
+use crate::commands::refresh::{FieldChange, apply_metadata, has_filters};
+use crate::commands::search::{SearchArgs, SearchCommand};
+use crate::traits::{BookmarkRepository, MetadataExtractor};
+use crate::types::{Bookmark, BookmarkBatch, BookmarkError, BookmarkResult, Config, ReadingStatus, normalize_tag};
+use crate::adapters::{ExtractorRegistry, HttpCache};
+use clap::{ArgGroup, Args};
+use serde::{Serialize, Deserialize};
+use std::io::Read;
+
+/// Command-line arguments for bulk command
+#[derive(Args, Debug, Clone)]
+#[command(group(
+    ArgGroup::new("action")
+        .required(true)
+        .multiple(true)
+        .args(["add_tags", "remove_tags", "set_status", "set_priority", "delete", "refetch"])
+))]
+pub struct BulkArgs {
+    /// Filters selecting the bookmarks to change (same as search)
+    #[command(flatten)]
+    pub filters: SearchArgs,
+
+    /// Read bookmark IDs (full or partial, one per line) from stdin instead of using filters
+    #[arg(long)]
+    pub stdin: bool,
+
+    /// Apply to every bookmark when no filters are given
+    #[arg(long, conflicts_with = "stdin")]
+    pub all: bool,
+
+    /// Tags to add to every matching bookmark (comma-separated)
+    #[arg(long, value_delimiter = ',')]
+    pub add_tags: Vec<String>,
+
+    /// Tags to remove from every matching bookmark (comma-separated)
+    #[arg(long, value_delimiter = ',')]
+    pub remove_tags: Vec<String>,
+
+    /// Set the reading status of every matching bookmark
+    #[arg(long, value_enum)]
+    pub set_status: Option<ReadingStatus>,
+
+    /// Set the priority rating (1-5) of every matching bookmark
+    #[arg(long)]
+    pub set_priority: Option<u8>,
+
+    /// Move every matching bookmark to the trash
+    #[arg(long, conflicts_with_all = ["add_tags", "remove_tags", "set_status", "set_priority", "refetch"])]
+    pub delete: bool,
+
+    /// Re-fetch metadata for every matching bookmark
+    #[arg(long)]
+    pub refetch: bool,
+
+    /// Show what would change without modifying anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// JSON response data for bulk command
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BulkResponse {
+    pub dry_run: bool,
+    pub matched_count: usize,
+    pub changed_count: usize,
+    pub deleted_count: usize,
+    pub changes: Vec<BulkChange>,
+    pub failures: Vec<BulkFailure>,
+}
+
+/// Planned or applied changes for a single bookmark
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BulkChange {
+    pub id: String,
+    pub title: String,
+    pub changes: Vec<String>,
+}
+
+/// A bookmark whose metadata could not be re-fetched
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BulkFailure {
+    pub id: String,
+    pub error: String,
+}
+
+pub async fn handle_bulk_command(
+    args: BulkArgs,
+    repository: &mut dyn BookmarkRepository,
+    config: &Config,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    let ids = if args.stdin {
+        let mut input = String::new();
+        std::io::stdin().read_to_string(&mut input)?;
+        Some(parse_id_list(&input))
+    } else {
+        None
+    };
+
+    let extractor = ExtractorRegistry::with_builtin_sites(&config.metadata, HttpCache::for_config(config))?;
+    handle_bulk_command_with_extractor_and_config(args, ids, repository, &extractor, config, format).await
+}
+
+pub async fn handle_bulk_command_with_extractor_and_config(
+    args: BulkArgs,
+    ids: Option<Vec<String>>,
+    repository: &mut dyn BookmarkRepository,
+    extractor: &dyn MetadataExtractor,
+    config: &Config,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    if let Some(priority) = args.set_priority {
+        if !(1..=5).contains(&priority) {
+            return Err(BookmarkError::InvalidId(
+                format!("Priority must be between 1 and 5, got {}", priority)
+            ));
+        }
+    }
+
+    if args.filters.save.is_some() || args.filters.list_saved || args.filters.delete_saved.is_some() {
+        return Err(BookmarkError::InvalidId(
+            "Saved searches are managed with 'automark search'; use @name to select one".to_string()
+        ));
+    }
+
+    let targets = select_bookmarks(&args, ids, repository).await?;
+    let mut batch = BookmarkBatch::default();
+    let mut changes = Vec::new();
+    let mut failures = Vec::new();
+    let mut captured_contents = Vec::new();
+
+    for mut bookmark in targets.iter().cloned() {
+        if args.delete {
+            changes.push(BulkChange {
+                id: bookmark.id.clone(),
+                title: bookmark.title.clone(),
+                changes: vec!["move to trash".to_string()],
+            });
+            batch.deletes.push(bookmark.id);
+            continue;
+        }
+
+        let mut descriptions = apply_field_changes(&args, &mut bookmark);
+
+        if args.refetch {
+            if args.dry_run {
+                descriptions.push("re-fetch metadata".to_string());
+            } else {
+                match extract_metadata_with_config(&bookmark.url, extractor, config).await {
+                    Ok(metadata) => {
+                        descriptions.extend(apply_metadata(&mut bookmark, &metadata, true).iter().map(FieldChange::summary));
+                        if let Some(content) = metadata.content.filter(|_| config.metadata.capture_content) {
+                            captured_contents.push((bookmark.id.clone(), content));
+                        }
+                    }
+                    Err(e) => failures.push(BulkFailure {
+                        id: bookmark.id.clone(),
+                        error: e.to_string(),
+                    }),
+                }
+            }
+        }
+
+        if !descriptions.is_empty() {
+            changes.push(BulkChange {
+                id: bookmark.id.clone(),
+                title: bookmark.title.clone(),
+                changes: descriptions,
+            });
+            batch.updates.push(bookmark);
+        }
+    }
+
+    let deleted_count = batch.deletes.len();
+    let changed_count = changes.len();
+    if !args.dry_run && !batch.is_empty() {
+        repository.apply_batch(batch).await?;
+    }
+    for (id, content) in &captured_contents {
+        repository.save_content(id, content).await?;
+    }
+
+    match format {
+        OutputFormat::Json => {
+            let response = BulkResponse {
+                dry_run: args.dry_run,
+                matched_count: targets.len(),
+                changed_count,
+                deleted_count,
+                changes,
+                failures,
+            };
+            output::print_response(format, response)?;
+        }
+        OutputFormat::Human => {
+            print!("{}", format_human_output(args.dry_run, targets.len(), &changes, &failures));
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolve the bookmarks a bulk operation applies to, from explicit IDs or search filters
+async fn select_bookmarks(
+    args: &BulkArgs,
+    ids: Option<Vec<String>>,
+    repository: &dyn BookmarkRepository,
+) -> BookmarkResult<Vec<Bookmark>> {
+    match ids {
+        Some(ids) => {
+            let all_bookmarks = repository.find_all(None).await?;
+            let candidates: Vec<&Bookmark> = all_bookmarks.iter().collect();
+            let mut selected: Vec<Bookmark> = Vec::new();
+            for id in ids {
+                let bookmark = find_bookmark_by_partial_id(&candidates, &id)?;
+                if !selected.iter().any(|existing| existing.id == bookmark.id) {
+                    selected.push(bookmark.clone());
+                }
+            }
+            Ok(selected)
+        }
+        None => {
+            if !args.all && !has_filters(&args.filters) {
+                return Err(BookmarkError::InvalidId(
+                    "Specify filters, --stdin, or --all to choose which bookmarks to change".to_string()
+                ));
+            }
+            let filters = SearchCommand::new(args.filters.clone()).resolve_filters(repository).await?;
+            repository.find_all(Some(filters)).await
+        }
+    }
+}
+
+/// Parse IDs from stdin, one per line, ignoring blank lines and anything after the first word
+fn parse_id_list(input: &str) -> Vec<String> {
+    input
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .map(|id| id.to_string())
+        .collect()
+}
+
+/// Apply tag, status and priority changes, returning a description of what changed
+fn apply_field_changes(args: &BulkArgs, bookmark: &mut Bookmark) -> Vec<String> {
+    let mut descriptions = Vec::new();
+
+    for tag in args.add_tags.iter().map(|tag| normalize_tag(tag)).filter(|tag| !tag.is_empty()) {
+        if !bookmark.tags.iter().any(|existing| normalize_tag(existing) == tag) {
+            bookmark.tags.push(tag.clone());
+            descriptions.push(format!("+tag {}", tag));
+        }
+    }
+
+    for tag in args.remove_tags.iter().map(|tag| normalize_tag(tag)).filter(|tag| !tag.is_empty()) {
+        if bookmark.remove_tags(std::slice::from_ref(&tag)) {
+            descriptions.push(format!("-tag {}", tag));
+        }
+    }
+
+    if let Some(ref status) = args.set_status {
+        let previous = bookmark.reading_status.clone();
+        if bookmark.set_reading_status(status.clone()) {
+            descriptions.push(format!("status {:?} -> {:?}", previous, status));
+        }
+    }
+
+    if let Some(priority) = args.set_priority {
+        if bookmark.priority_rating != Some(priority) {
+            let previous = bookmark.priority_rating
+                .map(|p| p.to_string())
+                .unwrap_or_else(|| "none".to_string());
+            descriptions.push(format!("priority {} -> {}", previous, priority));
+            bookmark.priority_rating = Some(priority);
+        }
+    }
+
+    descriptions
+}
+
+fn format_human_output(dry_run: bool, matched: usize, changes: &[BulkChange], failures: &[BulkFailure]) -> String {
+    if matched == 0 {
+        return "No bookmarks matched.\n".to_string();
+    }
+
+    let mut output = if dry_run {
+        format!("Dry run: {} of {} matching bookmark(s) would change:\n", changes.len(), matched)
+    } else {
+        format!("Changed {} of {} matching bookmark(s):\n", changes.len(), matched)
+    };
+
+    for change in changes {
+        output.push_str(&format!(
+            "  [{}] {}: {}\n",
+            &change.id[..8.min(change.id.len())],
+            change.title,
+            change.changes.join(", ")
+        ));
+    }
+
+    if !failures.is_empty() {
+        output.push_str(&format!("\nFailed to re-fetch metadata for {} bookmark(s):\n", failures.len()));
+        for failure in failures {
+            output.push_str(&format!("  [{}] {}\n", &failure.id[..8.min(failure.id.len())], failure.error));
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{Cli, Commands};
+    use crate::traits::repository::MockBookmarkRepository;
+    use crate::traits::metadata_extractor::MockMetadataExtractor;
+    use clap::Parser;
+
+    fn failing_extractor() -> MockMetadataExtractor {
+        MockMetadataExtractor {
+            should_fail: true,
+            extracted_title: None,
+            extracted_author: None,
+            extracted_content: None,
+        }
+    }
+
+    fn parse_bulk_args(args: &[&str]) -> BulkArgs {
+        let mut argv = vec!["automark", "bulk"];
+        argv.extend_from_slice(args);
+        match Cli::try_parse_from(argv).unwrap().command {
+            Some(Commands::Bulk(args)) => args,
+            _ => panic!("Expected bulk command"),
+        }
+    }
+
+    async fn create_test_repo() -> (MockBookmarkRepository, Vec<String>) {
+        let mut repo = MockBookmarkRepository::new();
+        let mut ids = Vec::new();
+        for (url, title, tags) in [
+            ("https://rust-lang.org", "Rust Language", vec!["rust"]),
+            ("https://doc.rust-lang.org", "Rust Docs", vec!["rust", "docs"]),
+            ("https://python.org", "Python", vec!["python"]),
+        ] {
+            let bookmark = Bookmark::new(url, title).unwrap()
+                .with_tags(tags.into_iter().map(String::from).collect());
+            ids.push(bookmark.id.clone());
+            repo.create(bookmark).await.unwrap();
+        }
+        (repo, ids)
+    }
+
+    #[test]
+    fn test_bulk_requires_action() {
+        assert!(Cli::try_parse_from(["automark", "bulk", "--tags", "rust"]).is_err());
+        assert!(Cli::try_parse_from(["automark", "bulk", "--tags", "rust", "--add-tags", "lang"]).is_ok());
+        assert!(Cli::try_parse_from(["automark", "bulk", "--delete", "--add-tags", "lang"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_bulk_add_tags_and_status_by_filter() {
+        let (mut repo, ids) = create_test_repo().await;
+        let args = parse_bulk_args(&["--tags", "rust", "--add-tags", "lang", "--set-status", "reading"]);
+
+        handle_bulk_command_with_extractor_and_config(
+            args, None, &mut repo, &failing_extractor(), &Config::default(), OutputFormat::Human,
+        ).await.unwrap();
+
+        let rust = repo.find_by_id(&ids[0]).await.unwrap();
+        assert!(rust.tags.contains(&"lang".to_string()));
+        assert_eq!(rust.reading_status, ReadingStatus::Reading);
+        let docs = repo.find_by_id(&ids[1]).await.unwrap();
+        assert!(docs.tags.contains(&"lang".to_string()));
+        let python = repo.find_by_id(&ids[2]).await.unwrap();
+        assert!(!python.tags.contains(&"lang".to_string()));
+        assert_eq!(python.reading_status, ReadingStatus::Unread);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_add_tags_are_normalized() {
+        let (mut repo, ids) = create_test_repo().await;
+        let args = parse_bulk_args(&["--tags", "rust", "--add-tags", "Rust, Systems/ Lang"]);
+
+        handle_bulk_command_with_extractor_and_config(
+            args, None, &mut repo, &failing_extractor(), &Config::default(), OutputFormat::Human,
+        ).await.unwrap();
+
+        // `Rust` is the tag the bookmark already has, not a second one
+        let rust = repo.find_by_id(&ids[0]).await.unwrap();
+        assert_eq!(rust.tags, vec!["rust", "systems/lang"]);
+        let docs = repo.find_by_id(&ids[1]).await.unwrap();
+        assert_eq!(docs.tags, vec!["rust", "docs", "systems/lang"]);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_without_selection_changes_nothing() {
+        let (mut repo, ids) = create_test_repo().await;
+        let args = parse_bulk_args(&["--delete"]);
+
+        let result = handle_bulk_command_with_extractor_and_config(
+            args, None, &mut repo, &failing_extractor(), &Config::default(), OutputFormat::Human,
+        ).await;
+        assert!(matches!(result, Err(BookmarkError::InvalidId(_))));
+        assert_eq!(repo.find_all(None).await.unwrap().len(), 3);
+
+        // --all opts in to changing everything
+        let args = parse_bulk_args(&["--all", "--set-priority", "2"]);
+        handle_bulk_command_with_extractor_and_config(
+            args, None, &mut repo, &failing_extractor(), &Config::default(), OutputFormat::Human,
+        ).await.unwrap();
+        for id in &ids {
+            assert_eq!(repo.find_by_id(id).await.unwrap().priority_rating, Some(2));
+        }
+        assert!(Cli::try_parse_from(["automark", "bulk", "--all", "--stdin", "--delete"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_bulk_dry_run_changes_nothing() {
+        let (mut repo, ids) = create_test_repo().await;
+        let args = parse_bulk_args(&["--all", "--remove-tags", "rust", "--set-priority", "5", "--dry-run"]);
+
+        handle_bulk_command_with_extractor_and_config(
+            args, None, &mut repo, &failing_extractor(), &Config::default(), OutputFormat::Json,
+        ).await.unwrap();
+
+        let rust = repo.find_by_id(&ids[0]).await.unwrap();
+        assert_eq!(rust.tags, vec!["rust"]);
+        assert_eq!(rust.priority_rating, None);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_delete_by_ids() {
+        let (mut repo, ids) = create_test_repo().await;
+        let args = parse_bulk_args(&["--stdin", "--delete"]);
+        let input = format!("{}\n\n{}  extra\n", &ids[0][..8], ids[2]);
+
+        handle_bulk_command_with_extractor_and_config(
+            args, Some(parse_id_list(&input)), &mut repo, &failing_extractor(), &Config::default(), OutputFormat::Human,
+        ).await.unwrap();
+
+        let remaining = repo.find_all(None).await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, ids[1]);
+        assert_eq!(repo.find_trash().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_unknown_id_changes_nothing() {
+        let (mut repo, ids) = create_test_repo().await;
+        let args = parse_bulk_args(&["--stdin", "--add-tags", "x"]);
+
+        let result = handle_bulk_command_with_extractor_and_config(
+            args, Some(vec![ids[0].clone(), "zzzzzzzz".to_string()]), &mut repo,
+            &failing_extractor(), &Config::default(), OutputFormat::Human,
+        ).await;
+
+        assert!(matches!(result, Err(BookmarkError::NotFound(_))));
+        assert!(!repo.find_by_id(&ids[0]).await.unwrap().tags.contains(&"x".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_bulk_refetch_updates_metadata() {
+        let (mut repo, ids) = create_test_repo().await;
+        let extractor = MockMetadataExtractor {
+            should_fail: false,
+            extracted_title: Some("Welcome to Python.org".to_string()),
+            extracted_author: Some("PSF".to_string()),
+            extracted_content: None,
+        };
+        let args = parse_bulk_args(&["--tags", "python", "--refetch"]);
+
+        handle_bulk_command_with_extractor_and_config(
+            args, None, &mut repo, &extractor, &Config::default(), OutputFormat::Human,
+        ).await.unwrap();
+
+        let python = repo.find_by_id(&ids[2]).await.unwrap();
+        assert_eq!(python.title, "Welcome to Python.org");
+        assert_eq!(python.author, Some("PSF".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_bulk_with_saved_search() {
+        use crate::types::{BookmarkFilters, SavedSearch};
+
+        let (mut repo, ids) = create_test_repo().await;
+        repo.save_search(SavedSearch {
+            name: "py".to_string(),
+            filters: BookmarkFilters { tags: Some(vec!["python".to_string()]), ..Default::default() },
+        }).await.unwrap();
+        let args = parse_bulk_args(&["@py", "--set-priority", "2"]);
+
+        handle_bulk_command_with_extractor_and_config(
+            args, None, &mut repo, &failing_extractor(), &Config::default(), OutputFormat::Human,
+        ).await.unwrap();
+
+        assert_eq!(repo.find_by_id(&ids[2]).await.unwrap().priority_rating, Some(2));
+        assert_eq!(repo.find_by_id(&ids[0]).await.unwrap().priority_rating, None);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_invalid_priority() {
+        let (mut repo, _) = create_test_repo().await;
+        let args = parse_bulk_args(&["--set-priority", "9"]);
+
+        let result = handle_bulk_command_with_extractor_and_config(
+            args, None, &mut repo, &failing_extractor(), &Config::default(), OutputFormat::Human,
+        ).await;
+        assert!(matches!(result, Err(BookmarkError::InvalidId(_))));
+    }
+
+    #[test]
+    fn test_parse_id_list() {
+        assert_eq!(parse_id_list("abc\n\n  def ghi\n"), vec!["abc", "def"]);
+        assert!(parse_id_list("").is_empty());
+    }
+
+    #[test]
+    fn test_format_human_output() {
+        let changes = vec![BulkChange {
+            id: "abcdef1234".to_string(),
+            title: "Example".to_string(),
+            changes: vec!["+tag rust".to_string()],
+        }];
+        let output = format_human_output(true, 2, &changes, &[]);
+        assert!(output.starts_with("Dry run: 1 of 2 matching bookmark(s) would change:"));
+        assert!(output.contains("[abcdef12] Example: +tag rust"));
+
+        assert_eq!(format_human_output(false, 0, &[], &[]), "No bookmarks matched.\n");
+    }
+}
@@ -0,0 +1,92 @@
+use crate::adapters::HttpCache;
+use crate::adapters::http_cache::CacheUsage;
+use crate::commands::{OutputFormat, output};
+use crate::types::{BookmarkError, BookmarkResult, Config};
+use clap::{Args, Subcommand};
+use serde::{Serialize, Deserialize};
+
+/// Command-line arguments for cache command
+#[derive(Args, Debug, Clone)]
+pub struct CacheArgs {
+    #[command(subcommand)]
+    pub command: CacheCommand,
+}
+
+/// Cache subcommands
+#[derive(Subcommand, Debug, Clone)]
+pub enum CacheCommand {
+    /// Remove every downloaded page kept for reuse
+    Clear,
+}
+
+/// JSON response data for cache clear command
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ClearCacheResponse {
+    pub removed_entries: usize,
+    pub removed_bytes: u64,
+}
+
+pub async fn handle_cache_command(
+    args: CacheArgs,
+    config: &Config,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    let dir = HttpCache::dir_for(config)
+        .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to locate cache: {}", e)))?;
+    // Clearing works even with caching turned off, to remove what an earlier run left
+    let cache = HttpCache::new(dir, config.cache.max_bytes);
+
+    match args.command {
+        CacheCommand::Clear => {
+            let removed = cache.clear()
+                .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to clear cache: {}", e)))?;
+            match format {
+                OutputFormat::Json => output::print_response(format, ClearCacheResponse {
+                    removed_entries: removed.entries,
+                    removed_bytes: removed.bytes,
+                })?,
+                OutputFormat::Human => println!("{}", format_cleared(removed)),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn format_cleared(removed: CacheUsage) -> String {
+    if removed.entries == 0 {
+        return "Cache is already empty".to_string();
+    }
+    format!("✓ Removed {} cached response(s), {:.1} MiB", removed.entries, removed.bytes as f64 / (1024.0 * 1024.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderMap, HeaderValue, CACHE_CONTROL};
+    use tempfile::TempDir;
+    use url::Url;
+
+    #[tokio::test]
+    async fn test_cache_clear() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.storage.data_dir = temp_dir.path().to_string_lossy().to_string();
+        let cache = HttpCache::for_config(&config).unwrap();
+        let url = Url::parse("https://example.com/").unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(CACHE_CONTROL, HeaderValue::from_static("max-age=600"));
+        cache.store(&url, &url, &headers, b"<title>Example</title>");
+        assert!(cache.lookup(&url).is_some());
+
+        let args = CacheArgs { command: CacheCommand::Clear };
+        handle_cache_command(args, &config, OutputFormat::Json).await.unwrap();
+        assert!(cache.lookup(&url).is_none());
+    }
+
+    #[test]
+    fn test_format_cleared() {
+        assert_eq!(format_cleared(CacheUsage::default()), "Cache is already empty");
+        assert_eq!(format_cleared(CacheUsage { entries: 3, bytes: 3 * 1024 * 1024 / 2 }), "✓ Removed 3 cached response(s), 1.5 MiB");
+    }
+}
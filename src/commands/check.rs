@@ -0,0 +1,371 @@
+use crate::commands::{OutputFormat, output};
+use crate::commands::search::{SearchArgs, SearchCommand};
+use crate::traits::{BookmarkRepository, LinkChecker};
+use crate::types::{Bookmark, BookmarkBatch, BookmarkError, BookmarkResult, Config, LinkStatus};
+use crate::adapters::HttpLinkChecker;
+use crate::adapters::rate_limiter::HostRateLimiter;
+use clap::Args;
+use futures_util::stream::{self, StreamExt};
+use serde::{Serialize, Deserialize};
+use std::collections::VecDeque;
+use std::time::Duration;
+use url::Url;
+
+/// Command-line arguments for check command
+#[derive(Args, Debug, Clone)]
+pub struct CheckArgs {
+    /// Filters selecting the bookmarks to check (same as search); all bookmarks by default
+    #[command(flatten)]
+    pub filters: SearchArgs,
+
+    /// Number of links to check at once (defaults to check.concurrency in the config)
+    #[arg(long)]
+    pub concurrency: Option<usize>,
+
+    /// Minimum delay between requests to the same host in milliseconds (defaults to check.per_host_delay_ms)
+    #[arg(long)]
+    pub host_delay_ms: Option<u64>,
+
+    /// Replace the URL of bookmarks that moved permanently with their new location
+    #[arg(long)]
+    pub fix_redirects: bool,
+}
+
+/// JSON response data for check command
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CheckResponse {
+    pub checked: usize,
+    pub dead: usize,
+    pub moved: usize,
+    pub fixed: usize,
+    pub results: Vec<CheckResult>,
+}
+
+/// Outcome of checking one bookmark
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CheckResult {
+    pub id: String,
+    pub title: String,
+    /// URL that was checked
+    pub url: String,
+    pub status: LinkStatus,
+    /// Whether the bookmark's URL was replaced by the redirect target
+    pub fixed: bool,
+}
+
+pub async fn handle_check_command(
+    args: CheckArgs,
+    repository: &mut dyn BookmarkRepository,
+    config: &Config,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    let checker = HttpLinkChecker::new(&config.metadata)?;
+    handle_check_command_with_checker_and_config(args, repository, &checker, config, format).await
+}
+
+pub async fn handle_check_command_with_checker_and_config(
+    args: CheckArgs,
+    repository: &mut dyn BookmarkRepository,
+    checker: &dyn LinkChecker,
+    config: &Config,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    if args.filters.save.is_some() || args.filters.list_saved || args.filters.delete_saved.is_some() {
+        return Err(BookmarkError::InvalidId(
+            "Saved searches are managed with 'automark search'; use @name to select one".to_string()
+        ));
+    }
+
+    let filters = SearchCommand::new(args.filters.clone()).resolve_filters(repository).await?;
+    let targets = repository.find_all(Some(filters)).await?;
+    if format == OutputFormat::Human && !targets.is_empty() {
+        println!("Checking {} link(s)...", targets.len());
+    }
+
+    let concurrency = args.concurrency.unwrap_or(config.check.concurrency).max(1);
+    let host_delay = Duration::from_millis(args.host_delay_ms.unwrap_or(config.check.per_host_delay_ms));
+    let timeout = Duration::from_secs(config.metadata.timeout_secs);
+    let statuses = check_all(&targets, checker, concurrency, host_delay, timeout).await;
+
+    let mut batch = BookmarkBatch::default();
+    let mut results = Vec::with_capacity(targets.len());
+    for (mut bookmark, mut status) in targets.into_iter().zip(statuses) {
+        let url = bookmark.url.clone();
+        let fixed = args.fix_redirects && status.moved_permanently();
+        let reported = status.clone();
+        if fixed {
+            // The stored status now describes the new URL, which answered directly
+            if let Some(target) = status.redirected_to.take() {
+                bookmark.url = target;
+            }
+            status.permanent_redirect = false;
+        }
+        bookmark.link_status = Some(status);
+
+        results.push(CheckResult {
+            id: bookmark.id.clone(),
+            title: bookmark.title.clone(),
+            url,
+            status: reported,
+            fixed,
+        });
+        batch.updates.push(bookmark);
+    }
+    if !batch.is_empty() {
+        repository.apply_batch(batch).await?;
+    }
+
+    let response = CheckResponse {
+        checked: results.len(),
+        dead: results.iter().filter(|result| result.status.is_dead()).count(),
+        moved: results.iter().filter(|result| result.status.moved_permanently()).count(),
+        fixed: results.iter().filter(|result| result.fixed).count(),
+        results,
+    };
+
+    match format {
+        OutputFormat::Json => output::print_response(format, response)?,
+        OutputFormat::Human => print!("{}", format_human_output(&response)),
+    }
+
+    Ok(())
+}
+
+/// Check every bookmark's URL, returning statuses in the same order as `bookmarks`
+async fn check_all(
+    bookmarks: &[Bookmark],
+    checker: &dyn LinkChecker,
+    concurrency: usize,
+    host_delay: Duration,
+    timeout: Duration,
+) -> Vec<LinkStatus> {
+    let limiter = HostRateLimiter::with_spacing(host_delay);
+    let order = interleave_by_host(bookmarks);
+
+    let mut checked: Vec<(usize, LinkStatus)> = stream::iter(order)
+        .map(|index| {
+            let limiter = &limiter;
+            async move {
+                let url = &bookmarks[index].url;
+                limiter.wait(&host_of(url)).await;
+                (index, checker.check_link(url, timeout).await)
+            }
+        })
+        .buffer_unordered(concurrency)
+        .collect()
+        .await;
+
+    checked.sort_by_key(|(index, _)| *index);
+    checked.into_iter().map(|(_, status)| status).collect()
+}
+
+/// Indexes of `bookmarks` ordered round-robin across hosts
+///
+/// Requests to one host are spaced out, so spreading them keeps the other
+/// concurrent checks from queueing up behind a single busy site.
+fn interleave_by_host(bookmarks: &[Bookmark]) -> Vec<usize> {
+    let mut hosts: Vec<(String, VecDeque<usize>)> = Vec::new();
+    for (index, bookmark) in bookmarks.iter().enumerate() {
+        let host = host_of(&bookmark.url);
+        match hosts.iter_mut().find(|(existing, _)| *existing == host) {
+            Some((_, indexes)) => indexes.push_back(index),
+            None => hosts.push((host, VecDeque::from([index]))),
+        }
+    }
+
+    let mut order = Vec::with_capacity(bookmarks.len());
+    while order.len() < bookmarks.len() {
+        for (_, indexes) in hosts.iter_mut() {
+            order.extend(indexes.pop_front());
+        }
+    }
+    order
+}
+
+fn host_of(url: &str) -> String {
+    Url::parse(url).ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn format_human_output(response: &CheckResponse) -> String {
+    let mut output = String::new();
+    for result in &response.results {
+        let short_id = &result.id[..8.min(result.id.len())];
+        if result.status.is_dead() {
+            output.push_str(&format!("✗ [{}] {}\n    {} ({})\n", short_id, result.title, result.url, result.status.describe()));
+        } else if result.status.moved_permanently() {
+            let target = result.status.redirected_to.as_deref().unwrap_or_default();
+            let note = if result.fixed { " (updated)" } else { "" };
+            output.push_str(&format!("→ [{}] {}\n    {} → {}{}\n", short_id, result.title, result.url, target, note));
+        }
+    }
+
+    let ok = response.checked - response.dead - response.moved;
+    output.push_str(&format!(
+        "Checked {} link(s): {} ok, {} dead, {} moved permanently",
+        response.checked, ok, response.dead, response.moved
+    ));
+    if response.fixed > 0 {
+        output.push_str(&format!(", {} updated", response.fixed));
+    }
+    output.push('\n');
+    if response.dead > 0 {
+        output.push_str("List dead links with: automark search --dead\n");
+    }
+    if response.moved > response.fixed {
+        output.push_str("Update moved links with: automark check --fix-redirects\n");
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{Cli, Commands};
+    use crate::traits::repository::MockBookmarkRepository;
+    use crate::traits::link_checker::MockLinkChecker;
+    use chrono::Utc;
+    use clap::Parser;
+    use std::collections::HashMap;
+
+    fn parse_check_args(args: &[&str]) -> CheckArgs {
+        let mut argv = vec!["automark", "check"];
+        argv.extend_from_slice(args);
+        match Cli::try_parse_from(argv).unwrap().command {
+            Some(Commands::Check(args)) => args,
+            _ => panic!("Expected check command"),
+        }
+    }
+
+    fn status(code: Option<u16>, redirected_to: Option<&str>, permanent: bool, error: Option<&str>) -> LinkStatus {
+        LinkStatus {
+            checked_at: Utc::now(),
+            status_code: code,
+            redirected_to: redirected_to.map(String::from),
+            permanent_redirect: permanent,
+            error: error.map(String::from),
+        }
+    }
+
+    async fn create_test_repo() -> (MockBookmarkRepository, MockLinkChecker) {
+        let mut repo = MockBookmarkRepository::new();
+        for (url, title) in [
+            ("https://ok.example.com/", "Fine"),
+            ("https://gone.example.com/", "Gone"),
+            ("https://nxdomain.example.com/", "No Such Host"),
+            ("https://old.example.com/post", "Moved"),
+        ] {
+            repo.create(Bookmark::new(url, title).unwrap()).await.unwrap();
+        }
+
+        let checker = MockLinkChecker {
+            results: HashMap::from([
+                ("https://gone.example.com/".to_string(), status(Some(404), None, false, None)),
+                ("https://nxdomain.example.com/".to_string(), status(None, None, false, Some("failed to lookup address information"))),
+                ("https://old.example.com/post".to_string(), status(Some(200), Some("https://new.example.com/post"), true, None)),
+            ]),
+        };
+        (repo, checker)
+    }
+
+    fn find<'a>(bookmarks: &'a [Bookmark], title: &str) -> &'a Bookmark {
+        bookmarks.iter().find(|bookmark| bookmark.title == title).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_check_records_status() {
+        let (mut repo, checker) = create_test_repo().await;
+        let args = parse_check_args(&["--host-delay-ms", "0"]);
+
+        handle_check_command_with_checker_and_config(args, &mut repo, &checker, &Config::default(), OutputFormat::Json)
+            .await.unwrap();
+
+        let bookmarks = repo.find_all(None).await.unwrap();
+        assert!(bookmarks.iter().all(|bookmark| bookmark.link_status.is_some()));
+        assert_eq!(find(&bookmarks, "Fine").link_status.as_ref().unwrap().status_code, Some(200));
+        assert!(find(&bookmarks, "Gone").link_status.as_ref().unwrap().is_dead());
+        assert!(find(&bookmarks, "No Such Host").link_status.as_ref().unwrap().is_dead());
+
+        // Without --fix-redirects the URL is left alone
+        let moved = find(&bookmarks, "Moved");
+        assert_eq!(moved.url, "https://old.example.com/post");
+        assert!(moved.link_status.as_ref().unwrap().moved_permanently());
+
+        // search --dead finds what the check flagged
+        let filters = SearchCommand::new(parse_check_args(&["--dead"]).filters).build_filters().unwrap();
+        let mut dead: Vec<String> = repo.find_all(Some(filters)).await.unwrap().into_iter().map(|b| b.title).collect();
+        dead.sort();
+        assert_eq!(dead, vec!["Gone", "No Such Host"]);
+    }
+
+    #[tokio::test]
+    async fn test_check_fix_redirects() {
+        let (mut repo, checker) = create_test_repo().await;
+        let args = parse_check_args(&["--fix-redirects", "--host-delay-ms", "0"]);
+
+        handle_check_command_with_checker_and_config(args, &mut repo, &checker, &Config::default(), OutputFormat::Human)
+            .await.unwrap();
+
+        let bookmarks = repo.find_all(None).await.unwrap();
+        let moved = find(&bookmarks, "Moved");
+        assert_eq!(moved.url, "https://new.example.com/post");
+        let status = moved.link_status.as_ref().unwrap();
+        assert_eq!(status.redirected_to, None);
+        assert!(!status.is_dead());
+    }
+
+    #[tokio::test]
+    async fn test_check_filtered_subset() {
+        let (mut repo, checker) = create_test_repo().await;
+        let args = parse_check_args(&["gone", "--host-delay-ms", "0"]);
+
+        handle_check_command_with_checker_and_config(args, &mut repo, &checker, &Config::default(), OutputFormat::Json)
+            .await.unwrap();
+
+        let bookmarks = repo.find_all(None).await.unwrap();
+        let checked: Vec<&str> = bookmarks.iter()
+            .filter(|bookmark| bookmark.link_status.is_some())
+            .map(|bookmark| bookmark.title.as_str())
+            .collect();
+        assert_eq!(checked, vec!["Gone"]);
+    }
+
+    #[test]
+    fn test_interleave_by_host() {
+        let bookmarks: Vec<Bookmark> = ["https://a.com/1", "https://a.com/2", "https://a.com/3", "https://b.com/1", "https://c.com/1"]
+            .iter()
+            .map(|url| Bookmark::new(url, "Title").unwrap())
+            .collect();
+        assert_eq!(interleave_by_host(&bookmarks), vec![0, 3, 4, 1, 2]);
+    }
+
+    #[test]
+    fn test_format_human_output() {
+        let result = |title: &str, status: LinkStatus, fixed: bool| CheckResult {
+            id: "abcdef1234".to_string(),
+            title: title.to_string(),
+            url: "https://old.example.com".to_string(),
+            status,
+            fixed,
+        };
+        let response = CheckResponse {
+            checked: 3,
+            dead: 1,
+            moved: 1,
+            fixed: 0,
+            results: vec![
+                result("Fine", status(Some(200), None, false, None), false),
+                result("Gone", status(Some(410), None, false, None), false),
+                result("Moved", status(Some(200), Some("https://new.example.com"), true, None), false),
+            ],
+        };
+
+        let output = format_human_output(&response);
+        assert!(!output.contains("Fine"));
+        assert!(output.contains("✗ [abcdef12] Gone\n    https://old.example.com (410)"));
+        assert!(output.contains("→ [abcdef12] Moved\n    https://old.example.com → https://new.example.com\n"));
+        assert!(output.contains("Checked 3 link(s): 1 ok, 1 dead, 1 moved permanently\n"));
+        assert!(output.contains("automark check --fix-redirects"));
+    }
+}
@@ -0,0 +1,154 @@
+use crate::commands::{OutputFormat, output};
+use crate::traits::BookmarkRepository;
+use crate::types::{BookmarkError, BookmarkResult};
+use crate::adapters::SimilarityIndex;
+use crate::adapters::similarity::Cluster;
+use clap::Args;
+use serde::{Serialize, Deserialize};
+
+/// Bookmarks listed per cluster in human output
+const SHOWN_PER_CLUSTER: usize = 5;
+
+/// Command-line arguments for clusters command
+#[derive(Args, Debug, Clone)]
+pub struct ClustersArgs {
+    /// How similar (0 to 1) a bookmark must be to a cluster to join it
+    #[arg(long, default_value_t = 0.25)]
+    pub threshold: f64,
+
+    /// Smallest cluster worth showing
+    #[arg(long, default_value_t = 3)]
+    pub min_size: usize,
+}
+
+/// JSON response data for clusters command
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ClustersResponse {
+    pub clusters: Vec<ClusterInfo>,
+    /// Bookmarks that didn't fit any cluster
+    pub unclustered: usize,
+}
+
+/// A group of bookmarks about one topic
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ClusterInfo {
+    /// Words that best describe the cluster
+    pub label: Vec<String>,
+    /// Tags shared by at least half the cluster; an empty list marks a tag candidate
+    pub tags: Vec<String>,
+    pub bookmarks: Vec<ClusterMember>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ClusterMember {
+    pub id: String,
+    pub title: String,
+}
+
+pub async fn handle_clusters_command(
+    args: ClustersArgs,
+    repository: &dyn BookmarkRepository,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    let response = find_clusters(&args, repository).await?;
+    match format {
+        OutputFormat::Json => output::print_response(format, response)?,
+        OutputFormat::Human => print!("{}", format_clusters(&response)),
+    }
+    Ok(())
+}
+
+async fn find_clusters(args: &ClustersArgs, repository: &dyn BookmarkRepository) -> BookmarkResult<ClustersResponse> {
+    if !(0.0..=1.0).contains(&args.threshold) {
+        return Err(BookmarkError::InvalidQuery(format!("Threshold must be between 0 and 1, got {}", args.threshold)));
+    }
+
+    let total = repository.find_all(None).await?.len();
+    let clusters: Vec<ClusterInfo> = SimilarityIndex::from_repository(repository).await?
+        .clusters(args.threshold, args.min_size)
+        .into_iter()
+        .map(|Cluster { label, tags, bookmarks }| ClusterInfo {
+            label,
+            tags,
+            bookmarks: bookmarks.into_iter()
+                .map(|bookmark| ClusterMember { id: bookmark.id, title: bookmark.title })
+                .collect(),
+        })
+        .collect();
+    let clustered: usize = clusters.iter().map(|cluster| cluster.bookmarks.len()).sum();
+    Ok(ClustersResponse { clusters, unclustered: total - clustered })
+}
+
+fn format_clusters(response: &ClustersResponse) -> String {
+    if response.clusters.is_empty() {
+        return "No clusters found; try a lower --threshold or --min-size\n".to_string();
+    }
+
+    let mut output = String::new();
+    for (number, cluster) in response.clusters.iter().enumerate() {
+        let tags = if cluster.tags.is_empty() {
+            "no common tag".to_string()
+        } else {
+            format!("tagged {}", cluster.tags.join(", "))
+        };
+        output.push_str(&format!(
+            "{}. {} ({} bookmarks, {})\n",
+            number + 1,
+            cluster.label.join(", "),
+            cluster.bookmarks.len(),
+            tags
+        ));
+        for member in cluster.bookmarks.iter().take(SHOWN_PER_CLUSTER) {
+            output.push_str(&format!("    [{}] {}\n", &member.id[..8.min(member.id.len())], member.title));
+        }
+        if cluster.bookmarks.len() > SHOWN_PER_CLUSTER {
+            output.push_str(&format!("    ... and {} more\n", cluster.bookmarks.len() - SHOWN_PER_CLUSTER));
+        }
+    }
+    output.push_str(&format!("{} bookmark(s) didn't fit a cluster\n", response.unclustered));
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::repository::MockBookmarkRepository;
+    use crate::types::Bookmark;
+
+    #[tokio::test]
+    async fn test_find_clusters() {
+        let mut repo = MockBookmarkRepository::new();
+        for (url, title) in [
+            ("https://tokio.rs/tokio/tutorial", "Tokio tutorial: async Rust"),
+            ("https://blog.rust-lang.org/async-closures", "Async closures in Rust"),
+            ("https://without.boats/blog/async-rust", "Why async Rust"),
+            ("https://example.com/pasta", "Weeknight pasta recipes"),
+        ] {
+            repo.create(Bookmark::new(url, title).unwrap()).await.unwrap();
+        }
+
+        let response = find_clusters(&ClustersArgs { threshold: 0.2, min_size: 3 }, &repo).await.unwrap();
+        assert_eq!(response.clusters.len(), 1);
+        assert_eq!(response.clusters[0].bookmarks.len(), 3);
+        assert_eq!(response.unclustered, 1);
+
+        assert!(find_clusters(&ClustersArgs { threshold: 2.0, min_size: 3 }, &repo).await.is_err());
+    }
+
+    #[test]
+    fn test_format_clusters() {
+        let member = |n: usize| ClusterMember { id: format!("{:08}-id", n), title: format!("Post {}", n) };
+        let response = ClustersResponse {
+            clusters: vec![ClusterInfo {
+                label: vec!["async".to_string(), "rust".to_string()],
+                tags: vec![],
+                bookmarks: (1..=6).map(member).collect(),
+            }],
+            unclustered: 2,
+        };
+        let output = format_clusters(&response);
+        assert!(output.starts_with("1. async, rust (6 bookmarks, no common tag)\n    [00000001] Post 1\n"));
+        assert!(output.contains("    ... and 1 more\n"));
+        assert!(output.ends_with("2 bookmark(s) didn't fit a cluster\n"));
+    }
+}
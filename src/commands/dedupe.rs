@@ -0,0 +1,725 @@
+use crate::commands::{OutputFormat, output};
+use crate::adapters::text_analysis::tokenize;
+use crate::traits::BookmarkRepository;
+use crate::types::{Bookmark, BookmarkBatch, BookmarkError, BookmarkResult, ReadingStatus, normalize_tag};
+use clap::Args;
+use serde::{Serialize, Deserialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, Write};
+use url::Url;
+
+/// Titles with fewer words than this are too generic ("Home", "Blog") to compare
+const MIN_TITLE_WORDS: usize = 4;
+
+/// Page text with fewer words than this is too short to fingerprint
+const MIN_FINGERPRINT_WORDS: usize = 50;
+
+/// Words per shingle when comparing page text
+const SHINGLE_WORDS: usize = 5;
+
+/// MinHash signatures are split into this many bands of `MINHASH_ROWS` values;
+/// pages that agree on a whole band become candidates
+const MINHASH_BANDS: usize = 16;
+const MINHASH_ROWS: usize = 4;
+
+/// Share of shingles two pages must have in common to count as the same text
+const MIN_CONTENT_SIMILARITY: f64 = 0.9;
+
+/// Query parameters that track a visit rather than select a page
+const TRACKING_PARAMS: &[&str] = &["fbclid", "gclid", "mc_cid", "mc_eid", "amp", "outputtype"];
+
+/// Command-line arguments for dedupe command
+#[derive(Args, Debug, Clone)]
+pub struct DedupeArgs {
+    /// Merge every group without asking
+    #[arg(long)]
+    pub auto: bool,
+
+    /// Show the duplicate groups without merging anything
+    #[arg(long, conflicts_with = "auto")]
+    pub dry_run: bool,
+
+    /// How alike (0 to 1) two titles must be for their bookmarks to count as duplicates
+    #[arg(long, default_value_t = 0.8)]
+    pub title_threshold: f64,
+}
+
+/// JSON response data for dedupe command
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DedupeResponse {
+    pub dry_run: bool,
+    pub groups: Vec<DuplicateGroup>,
+    /// Number of bookmarks moved to the trash by merging
+    pub merged_count: usize,
+}
+
+/// Bookmarks that look like the same page, and the one they merge into
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DuplicateGroup {
+    /// What the bookmarks have in common: "url", "canonical", "title" or "content"
+    pub reasons: Vec<String>,
+    /// The earliest bookmark, which the others are merged into
+    pub keep: DuplicateMember,
+    pub duplicates: Vec<DuplicateMember>,
+    /// Changes the merge makes to the kept bookmark
+    pub changes: Vec<String>,
+    pub merged: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DuplicateMember {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+}
+
+/// What to do with one duplicate group
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeDecision {
+    Merge,
+    Skip,
+    /// Skip this and every remaining group
+    Quit,
+}
+
+pub async fn handle_dedupe_command(
+    args: DedupeArgs,
+    repository: &mut dyn BookmarkRepository,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    if !args.auto && !args.dry_run && format == OutputFormat::Json {
+        return Err(BookmarkError::InvalidId(
+            "Merging interactively needs human output; use --auto or --dry-run with JSON".to_string()
+        ));
+    }
+
+    let auto = args.auto;
+    let stdin = std::io::stdin();
+    let mut lines = stdin.lock().lines();
+    let response = dedupe(&args, repository, |group| {
+        if auto {
+            return Ok(MergeDecision::Merge);
+        }
+        print!("{}Merge? [y]es / [n]o / [q]uit: ", format_group(group));
+        std::io::stdout().flush()?;
+        let answer = lines.next().transpose()?.unwrap_or_default();
+        Ok(parse_decision(&answer))
+    }).await?;
+
+    match format {
+        OutputFormat::Json => output::print_response(format, response)?,
+        OutputFormat::Human => print!("{}", format_dedupe(&response, auto)),
+    }
+    Ok(())
+}
+
+/// Find duplicate groups and merge the ones `decide` accepts in a single batch
+pub async fn dedupe(
+    args: &DedupeArgs,
+    repository: &mut dyn BookmarkRepository,
+    mut decide: impl FnMut(&DuplicateGroup) -> BookmarkResult<MergeDecision>,
+) -> BookmarkResult<DedupeResponse> {
+    if !(0.0..=1.0).contains(&args.title_threshold) {
+        return Err(BookmarkError::InvalidQuery(format!("Threshold must be between 0 and 1, got {}", args.title_threshold)));
+    }
+
+    let bookmarks = repository.find_all(None).await?;
+    let mut contents = HashMap::new();
+    let mut archives = HashMap::new();
+    for bookmark in &bookmarks {
+        if let Some(content) = repository.find_content(&bookmark.id).await? {
+            contents.insert(bookmark.id.clone(), content);
+        }
+        if let Some(path) = repository.find_archive(&bookmark.id).await? {
+            archives.insert(bookmark.id.clone(), path);
+        }
+    }
+
+    let mut groups = Vec::new();
+    let mut batch = BookmarkBatch::default();
+    let mut moved_contents = Vec::new();
+    let mut moved_archives = Vec::new();
+    let mut quit = false;
+    for (members, reasons) in find_duplicates(&bookmarks, |id| contents.get(id).map(String::as_str), args.title_threshold) {
+        let (keep, mut changes) = merge(&members);
+        // Archives are files named after their bookmark, so they are copied rather than merged
+        let archive = if archives.contains_key(&keep.id) {
+            None
+        } else {
+            members[1..].iter().find_map(|other| archives.get(&other.id))
+        };
+        if archive.is_some() {
+            changes.push("+archive".to_string());
+        }
+        let mut group = DuplicateGroup {
+            reasons,
+            keep: DuplicateMember::from(&members[0]),
+            duplicates: members[1..].iter().map(DuplicateMember::from).collect(),
+            changes,
+            merged: false,
+        };
+
+        if !args.dry_run && !quit {
+            match decide(&group)? {
+                MergeDecision::Merge => {
+                    if !contents.contains_key(&keep.id) {
+                        if let Some(content) = members[1..].iter().find_map(|other| contents.get(&other.id)) {
+                            moved_contents.push((keep.id.clone(), content.clone()));
+                        }
+                    }
+                    if let Some(path) = archive {
+                        moved_archives.push((keep.id.clone(), path.clone()));
+                    }
+                    batch.deletes.extend(members[1..].iter().map(|other| other.id.clone()));
+                    batch.updates.push(keep);
+                    group.merged = true;
+                }
+                MergeDecision::Skip => {}
+                MergeDecision::Quit => quit = true,
+            }
+        }
+        groups.push(group);
+    }
+
+    let merged_count = batch.deletes.len();
+    if !batch.is_empty() {
+        repository.apply_batch(batch).await?;
+    }
+    for (id, content) in &moved_contents {
+        repository.save_content(id, content).await?;
+    }
+    for (id, path) in &moved_archives {
+        let html = std::fs::read_to_string(path)?;
+        repository.save_archive(id, &html).await?;
+    }
+
+    Ok(DedupeResponse { dry_run: args.dry_run, groups, merged_count })
+}
+
+/// Group bookmarks that share a URL, canonical URL, title or page text
+///
+/// Each group is ordered oldest first, with the reasons its members were linked.
+fn find_duplicates<'a>(
+    bookmarks: &[Bookmark],
+    content: impl Fn(&str) -> Option<&'a str>,
+    title_threshold: f64,
+) -> Vec<(Vec<Bookmark>, Vec<String>)> {
+    let mut parents: Vec<usize> = (0..bookmarks.len()).collect();
+    let mut links: Vec<(usize, usize, &'static str)> = Vec::new();
+
+    let mut url_keys = Vec::new();
+    for (index, bookmark) in bookmarks.iter().enumerate() {
+        if let Some(key) = url_key(&bookmark.url) {
+            url_keys.push((index, key, "url"));
+        }
+    }
+    for (index, bookmark) in bookmarks.iter().enumerate() {
+        if let Some(key) = bookmark.canonical_url.as_deref().and_then(url_key) {
+            url_keys.push((index, key, "canonical"));
+        }
+    }
+    link_shared_keys(url_keys, &mut links);
+
+    let fingerprints: Vec<Option<Vec<u64>>> = bookmarks.iter()
+        .map(|bookmark| content(&bookmark.id).and_then(fingerprint))
+        .collect();
+    let mut bands: HashMap<(usize, &[u64]), Vec<usize>> = HashMap::new();
+    for (index, signature) in fingerprints.iter().enumerate() {
+        if let Some(signature) = signature {
+            for (band, rows) in signature.chunks(MINHASH_ROWS).enumerate() {
+                bands.entry((band, rows)).or_default().push(index);
+            }
+        }
+    }
+    for (a, b) in candidate_pairs(bands.into_values()) {
+        if let (Some(sig_a), Some(sig_b)) = (&fingerprints[a], &fingerprints[b]) {
+            if signature_similarity(sig_a, sig_b) >= MIN_CONTENT_SIMILARITY {
+                links.push((a, b, "content"));
+            }
+        }
+    }
+
+    let titles: Vec<HashSet<String>> = bookmarks.iter()
+        .map(|bookmark| tokenize(&bookmark.title).into_iter().collect())
+        .collect();
+    for (a, b) in title_candidates(&titles, title_threshold) {
+        if jaccard(&titles[a], &titles[b]) >= title_threshold {
+            links.push((a, b, "title"));
+        }
+    }
+
+    for &(a, b, _) in &links {
+        let (root_a, root_b) = (find_root(&mut parents, a), find_root(&mut parents, b));
+        if root_a != root_b {
+            parents[root_b] = root_a;
+        }
+    }
+
+    let mut members: HashMap<usize, Vec<usize>> = HashMap::new();
+    for index in 0..bookmarks.len() {
+        members.entry(find_root(&mut parents, index)).or_default().push(index);
+    }
+    let mut reasons: HashMap<usize, BTreeSet<&str>> = HashMap::new();
+    for &(a, _, reason) in &links {
+        reasons.entry(find_root(&mut parents, a)).or_default().insert(reason);
+    }
+
+    let mut groups: Vec<(Vec<Bookmark>, Vec<String>)> = members.into_iter()
+        .filter(|(_, indices)| indices.len() > 1)
+        .map(|(root, indices)| {
+            let mut group: Vec<Bookmark> = indices.into_iter().map(|index| bookmarks[index].clone()).collect();
+            group.sort_by_key(|bookmark| bookmark.bookmarked_date);
+            let reasons = reasons.remove(&root).unwrap_or_default().into_iter().map(str::to_string).collect();
+            (group, reasons)
+        })
+        .collect();
+    groups.sort_by_key(|(group, _)| group[0].bookmarked_date);
+    groups
+}
+
+/// Link each bookmark to the first one with the same key
+fn link_shared_keys(keys: Vec<(usize, String, &'static str)>, links: &mut Vec<(usize, usize, &'static str)>) {
+    let mut first: HashMap<String, usize> = HashMap::new();
+    for (index, key, reason) in keys {
+        match first.get(&key) {
+            Some(&other) if other != index => links.push((other, index, reason)),
+            Some(_) => {}
+            None => {
+                first.insert(key, index);
+            }
+        }
+    }
+}
+
+/// Pairs of bookmarks that share a block, each pair once with the lower index first
+fn candidate_pairs(blocks: impl IntoIterator<Item = Vec<usize>>) -> BTreeSet<(usize, usize)> {
+    let mut pairs = BTreeSet::new();
+    for mut block in blocks {
+        block.sort_unstable();
+        block.dedup();
+        for (position, &a) in block.iter().enumerate() {
+            for &b in &block[position + 1..] {
+                pairs.insert((a, b));
+            }
+        }
+    }
+    pairs
+}
+
+/// Pairs of titles that could reach `threshold`, found through their rarest words
+///
+/// Two sets with a Jaccard similarity of at least `threshold` must share one of
+/// the first `n - ceil(threshold * n) + 1` words of each, once every title is sorted
+/// rarest word first, so only titles sharing one of those words are compared.
+fn title_candidates(titles: &[HashSet<String>], threshold: f64) -> BTreeSet<(usize, usize)> {
+    let mut frequency: HashMap<&str, usize> = HashMap::new();
+    for title in titles.iter().filter(|title| title.len() >= MIN_TITLE_WORDS) {
+        for word in title {
+            *frequency.entry(word.as_str()).or_default() += 1;
+        }
+    }
+
+    let mut blocks: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (index, title) in titles.iter().enumerate() {
+        if title.len() < MIN_TITLE_WORDS {
+            continue;
+        }
+        let mut words: Vec<&str> = title.iter().map(String::as_str).collect();
+        words.sort_by_key(|word| (frequency[word], *word));
+        let prefix = title.len() - (threshold * title.len() as f64).ceil() as usize + 1;
+        for word in words.into_iter().take(prefix) {
+            blocks.entry(word).or_default().push(index);
+        }
+    }
+    candidate_pairs(blocks.into_values())
+}
+
+fn find_root(parents: &mut [usize], mut index: usize) -> usize {
+    while parents[index] != index {
+        parents[index] = parents[parents[index]];
+        index = parents[index];
+    }
+    index
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// A URL reduced to what identifies the page
+///
+/// Drops the scheme, `www.`/`m.`/`amp.` host prefixes, AMP path markers,
+/// tracking parameters, the fragment and any trailing slash, and unwraps
+/// Google AMP cache addresses.
+fn url_key(url: &str) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+    let mut host = url.host_str()?.to_lowercase();
+    let mut path = url.path().to_string();
+
+    if host.ends_with(".cdn.ampproject.org") {
+        let rest = path.strip_prefix("/c/s/").or_else(|| path.strip_prefix("/c/"))?;
+        let (inner_host, inner_path) = rest.split_once('/').unwrap_or((rest, ""));
+        host = inner_host.to_lowercase();
+        path = format!("/{}", inner_path);
+    }
+    for prefix in ["www.", "m.", "amp."] {
+        if let Some(stripped) = host.strip_prefix(prefix) {
+            host = stripped.to_string();
+        }
+    }
+
+    let mut path = path.trim_end_matches('/').to_string();
+    for suffix in ["/amp", ".amp", "/amp.html"] {
+        if let Some(stripped) = path.strip_suffix(suffix) {
+            path = stripped.trim_end_matches('/').to_string();
+        }
+    }
+    if let Some(stripped) = path.strip_prefix("/amp/") {
+        path = format!("/{}", stripped);
+    }
+
+    let mut query: Vec<(String, String)> = url.query_pairs()
+        .filter(|(name, _)| {
+            let name = name.to_lowercase();
+            !name.starts_with("utm_") && !TRACKING_PARAMS.contains(&name.as_str())
+        })
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+    query.sort();
+    let query: Vec<String> = query.iter().map(|(name, value)| format!("{}={}", name, value)).collect();
+
+    Some(format!("{}{}?{}", host, path, query.join("&")))
+}
+
+/// A MinHash signature of the page's word shingles, ignoring case, punctuation and layout
+///
+/// The share of positions where two signatures agree estimates how many
+/// shingles the pages have in common, so small edits still match.
+fn fingerprint(content: &str) -> Option<Vec<u64>> {
+    let words = tokenize(content);
+    if words.len() < MIN_FINGERPRINT_WORDS {
+        return None;
+    }
+
+    let mut signature = vec![u64::MAX; MINHASH_BANDS * MINHASH_ROWS];
+    for shingle in words.windows(SHINGLE_WORDS) {
+        let mut hasher = DefaultHasher::new();
+        shingle.hash(&mut hasher);
+        let shingle_hash = hasher.finish();
+        for (seed, min) in signature.iter_mut().enumerate() {
+            *min = (*min).min(mix(shingle_hash ^ (seed as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)));
+        }
+    }
+    Some(signature)
+}
+
+/// The splitmix64 finalizer, used to derive independent hashes from one shingle hash
+fn mix(mut value: u64) -> u64 {
+    value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    value ^ (value >> 31)
+}
+
+fn signature_similarity(a: &[u64], b: &[u64]) -> f64 {
+    let same = a.iter().zip(b).filter(|(a, b)| a == b).count();
+    same as f64 / a.len() as f64
+}
+
+/// Merge a group, ordered oldest first, into its first bookmark
+///
+/// Tags and notes are unioned, the earliest `bookmarked_date` is kept, missing
+/// metadata is filled from the others, and the highest priority and furthest
+/// reading status win. Archives are left to the caller, since their files are
+/// named after the bookmark. Returns the merged bookmark and a description of its changes.
+fn merge(group: &[Bookmark]) -> (Bookmark, Vec<String>) {
+    let mut keep = group[0].clone();
+    let mut changes = Vec::new();
+
+    for other in &group[1..] {
+        if other.bookmarked_date < keep.bookmarked_date {
+            keep.bookmarked_date = other.bookmarked_date;
+        }
+
+        for tag in &other.tags {
+            let tag = normalize_tag(tag);
+            if !tag.is_empty() && !keep.tags.iter().any(|existing| normalize_tag(existing) == tag) {
+                changes.push(format!("+tag {}", tag));
+                keep.tags.push(tag);
+            }
+        }
+
+        for note in &other.notes {
+            if !keep.notes.iter().any(|existing| existing.id == note.id || existing.content == note.content) {
+                keep.notes.push(note.clone());
+                changes.push("+note".to_string());
+            }
+        }
+
+        if other.priority_rating > keep.priority_rating {
+            changes.push(format!("priority {} -> {}", describe_priority(keep.priority_rating), describe_priority(other.priority_rating)));
+            keep.priority_rating = other.priority_rating;
+        }
+
+        if status_rank(&other.reading_status) > status_rank(&keep.reading_status) {
+            changes.push(format!("status {:?} -> {:?}", keep.reading_status, other.reading_status));
+            keep.reading_status = other.reading_status.clone();
+            keep.started_at = other.started_at;
+            keep.completed_at = other.completed_at;
+        }
+
+        let mut fill = |field: &str, value: &mut Option<String>, other: &Option<String>| {
+            if value.is_none() && other.is_some() {
+                *value = other.clone();
+                changes.push(format!("+{}", field));
+            }
+        };
+        fill("author", &mut keep.author, &other.author);
+        fill("description", &mut keep.description, &other.description);
+        fill("site name", &mut keep.site_name, &other.site_name);
+        fill("canonical URL", &mut keep.canonical_url, &other.canonical_url);
+        fill("image", &mut keep.image_url, &other.image_url);
+        fill("language", &mut keep.language, &other.language);
+        fill("favicon", &mut keep.favicon_url, &other.favicon_url);
+        if keep.publish_date.is_none() && other.publish_date.is_some() {
+            keep.publish_date = other.publish_date;
+            changes.push("+publish date".to_string());
+        }
+        for (name, value) in &other.site_details {
+            keep.site_details.entry(name.clone()).or_insert_with(|| value.clone());
+        }
+    }
+
+    keep.notes.sort_by_key(|note| note.created_at);
+    (keep, changes)
+}
+
+fn describe_priority(priority: Option<u8>) -> String {
+    priority.map(|priority| priority.to_string()).unwrap_or_else(|| "none".to_string())
+}
+
+fn status_rank(status: &ReadingStatus) -> u8 {
+    match status {
+        ReadingStatus::Unread => 0,
+        ReadingStatus::Reading => 1,
+        ReadingStatus::Completed => 2,
+    }
+}
+
+fn parse_decision(answer: &str) -> MergeDecision {
+    match answer.trim().to_lowercase().as_str() {
+        "y" | "yes" => MergeDecision::Merge,
+        "q" | "quit" => MergeDecision::Quit,
+        _ => MergeDecision::Skip,
+    }
+}
+
+impl From<&Bookmark> for DuplicateMember {
+    fn from(bookmark: &Bookmark) -> Self {
+        DuplicateMember {
+            id: bookmark.id.clone(),
+            title: bookmark.title.clone(),
+            url: bookmark.url.clone(),
+        }
+    }
+}
+
+fn format_member(member: &DuplicateMember) -> String {
+    format!("[{}] {}\n        {}\n", &member.id[..8.min(member.id.len())], member.title, member.url)
+}
+
+fn format_group(group: &DuplicateGroup) -> String {
+    let mut output = format!("Same {}:\n  keep    {}", group.reasons.join(", "), format_member(&group.keep));
+    for duplicate in &group.duplicates {
+        output.push_str(&format!("  remove  {}", format_member(duplicate)));
+    }
+    if !group.changes.is_empty() {
+        output.push_str(&format!("  merging adds: {}\n", group.changes.join(", ")));
+    }
+    output
+}
+
+fn format_dedupe(response: &DedupeResponse, show_groups: bool) -> String {
+    if response.groups.is_empty() {
+        return "No duplicates found.\n".to_string();
+    }
+
+    let mut output = String::new();
+    if response.dry_run || show_groups {
+        for group in &response.groups {
+            output.push_str(&format_group(group));
+        }
+    }
+    if response.dry_run {
+        output.push_str(&format!("Dry run: found {} group(s) of duplicates\n", response.groups.len()));
+    } else {
+        let merged_groups = response.groups.iter().filter(|group| group.merged).count();
+        output.push_str(&format!(
+            "Merged {} of {} group(s), moving {} bookmark(s) to the trash\n",
+            merged_groups,
+            response.groups.len(),
+            response.merged_count
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::repository::MockBookmarkRepository;
+    use chrono::{Duration, Utc};
+
+    fn args() -> DedupeArgs {
+        DedupeArgs { auto: true, dry_run: false, title_threshold: 0.8 }
+    }
+
+    fn bookmark(url: &str, title: &str, days_ago: i64) -> Bookmark {
+        let mut bookmark = Bookmark::new(url, title).unwrap();
+        bookmark.bookmarked_date = Utc::now() - Duration::days(days_ago);
+        bookmark
+    }
+
+    #[test]
+    fn test_url_key_ignores_amp_mirrors_and_tracking() {
+        let key = url_key("https://example.com/news/story");
+        assert_eq!(url_key("http://www.example.com/news/story/?utm_source=x#top"), key);
+        assert_eq!(url_key("https://amp.example.com/news/story/amp"), key);
+        assert_eq!(url_key("https://example-com.cdn.ampproject.org/c/s/example.com/news/story?amp=1"), key);
+        assert_ne!(url_key("https://example.com/news/story?page=2"), key);
+        // Some sites select content with `ref`, such as a branch or tag
+        assert_ne!(url_key("https://example.com/news/story?ref=v2"), key);
+    }
+
+    #[test]
+    fn test_fingerprint_matches_lightly_edited_text() {
+        let article: String = (0..300).map(|n| format!("sentence{} ", n % 97 + n / 3)).collect();
+        let edited = article.replace("sentence50 ", "changed ") + "Posted by the site editor";
+        let other: String = (0..300).map(|n| format!("paragraph{} ", n)).collect();
+
+        let original = fingerprint(&article).unwrap();
+        assert!(signature_similarity(&original, &fingerprint(&edited).unwrap()) >= MIN_CONTENT_SIMILARITY);
+        assert!(signature_similarity(&original, &fingerprint(&other).unwrap()) < 0.1);
+        assert_eq!(fingerprint("too short to compare"), None);
+    }
+
+    #[test]
+    fn test_title_candidates_only_pair_titles_sharing_rare_words() {
+        let titles: Vec<HashSet<String>> = [
+            "Understanding Rust ownership and borrowing",
+            "Understanding Rust Ownership and Borrowing rules",
+            "Guide to Python packaging tools",
+            "Guide to Go modules tools",
+            "Short title",
+        ].iter().map(|title| tokenize(title).into_iter().collect()).collect();
+
+        let pairs = title_candidates(&titles, 0.8);
+        assert!(pairs.contains(&(0, 1)));
+        assert!(!pairs.contains(&(0, 2)));
+        assert!(!pairs.contains(&(2, 3)));
+        assert!(pairs.iter().all(|&(a, b)| a != 4 && b != 4));
+    }
+
+    #[test]
+    fn test_find_duplicates_by_canonical_title_and_content() {
+        let text = "word ".repeat(10) + &"the same long article body ".repeat(20);
+        let bookmarks = vec![
+            bookmark("https://example.com/post", "A post", 3),
+            {
+                let mut mirror = bookmark("https://mirror.example.net/post", "Mirrored", 1);
+                mirror.canonical_url = Some("https://example.com/post/".to_string());
+                mirror
+            },
+            bookmark("https://blog.example.org/2019/rust-ownership", "Understanding Rust ownership and borrowing", 5),
+            bookmark("https://blog.example.org/rust-ownership", "Understanding Rust Ownership and Borrowing", 2),
+            bookmark("https://one.example/a", "First copy", 4),
+            bookmark("https://two.example/b", "Second copy", 6),
+            bookmark("https://example.com/about", "About us", 7),
+        ];
+        let contents: HashMap<String, String> = [&bookmarks[4], &bookmarks[5]].iter()
+            .map(|bookmark| (bookmark.id.clone(), text.clone()))
+            .collect();
+
+        let groups = find_duplicates(&bookmarks, |id| contents.get(id).map(String::as_str), 0.8);
+        let summary: Vec<(Vec<&str>, Vec<String>)> = groups.iter()
+            .map(|(members, reasons)| (members.iter().map(|b| b.title.as_str()).collect(), reasons.clone()))
+            .collect();
+        assert_eq!(summary, vec![
+            (vec!["Second copy", "First copy"], vec!["content".to_string()]),
+            (vec!["Understanding Rust ownership and borrowing", "Understanding Rust Ownership and Borrowing"], vec!["title".to_string()]),
+            (vec!["A post", "Mirrored"], vec!["canonical".to_string()]),
+        ]);
+    }
+
+    #[test]
+    fn test_merge_unions_tags_and_notes_and_keeps_earliest_date() {
+        let mut oldest = bookmark("https://example.com/post", "Post", 10).with_tags(vec!["rust".to_string()]);
+        oldest.add_note("first thoughts");
+        let mut newer = bookmark("https://www.example.com/post", "Post", 1).with_tags(vec!["Rust".to_string(), "async".to_string()]);
+        newer.add_note("second thoughts");
+        newer.priority_rating = Some(4);
+        newer.reading_status = ReadingStatus::Completed;
+        newer.author = Some("Ferris".to_string());
+
+        let (merged, changes) = merge(&[oldest.clone(), newer]);
+        assert_eq!(merged.id, oldest.id);
+        assert_eq!(merged.bookmarked_date, oldest.bookmarked_date);
+        assert_eq!(merged.tags, vec!["rust", "async"]);
+        assert_eq!(merged.notes.iter().map(|n| n.content.as_str()).collect::<Vec<_>>(), vec!["first thoughts", "second thoughts"]);
+        assert_eq!(merged.priority_rating, Some(4));
+        assert_eq!(merged.reading_status, ReadingStatus::Completed);
+        assert_eq!(merged.author.as_deref(), Some("Ferris"));
+        assert_eq!(changes, vec!["+tag async", "+note", "priority none -> 4", "status Unread -> Completed", "+author"]);
+    }
+
+    #[tokio::test]
+    async fn test_dedupe_merges_in_one_batch() {
+        let mut repo = MockBookmarkRepository::new();
+        let keep = repo.create(bookmark("https://example.com/a", "A", 5)).await.unwrap();
+        let dup = repo.create(bookmark("https://www.example.com/a/", "A again", 1).with_tags(vec!["x".to_string()])).await.unwrap();
+        repo.save_content(&dup.id, "captured text").await.unwrap();
+        repo.save_archive(&dup.id, "<p>archived</p>").await.unwrap();
+        let other = repo.create(bookmark("https://example.com/b", "B", 3)).await.unwrap();
+        repo.create(bookmark("https://example.com/b?utm_medium=feed", "B again", 2)).await.unwrap();
+
+        // A dry run reports groups without asking or merging
+        let dry = DedupeArgs { auto: false, dry_run: true, ..args() };
+        let response = dedupe(&dry, &mut repo, |_| panic!("dry run must not ask")).await.unwrap();
+        assert_eq!(response.groups.len(), 2);
+        assert_eq!(repo.find_all(None).await.unwrap().len(), 4);
+
+        // Merge the first group, skip the second
+        let mut answers = vec![MergeDecision::Merge, MergeDecision::Skip].into_iter();
+        let response = dedupe(&args(), &mut repo, |_| Ok(answers.next().unwrap())).await.unwrap();
+        assert_eq!(response.merged_count, 1);
+        assert!(response.groups[0].merged && !response.groups[1].merged);
+
+        let remaining = repo.find_all(None).await.unwrap();
+        assert_eq!(remaining.len(), 3);
+        assert!(remaining.iter().all(|bookmark| bookmark.id != dup.id));
+        let kept = repo.find_by_id(&keep.id).await.unwrap();
+        assert_eq!(kept.tags, vec!["x"]);
+        assert_eq!(repo.find_content(&keep.id).await.unwrap().as_deref(), Some("captured text"));
+        let archive = repo.find_archive(&keep.id).await.unwrap().unwrap();
+        assert_eq!(std::fs::read_to_string(archive).unwrap(), "<p>archived</p>");
+        assert_eq!(repo.find_by_id(&keep.id).await.unwrap().archive_path, Some(format!("archives/{}.html", keep.id)));
+        assert!(repo.find_by_id(&other.id).await.is_ok());
+
+        // Quitting leaves the rest alone
+        let response = dedupe(&args(), &mut repo, |_| Ok(MergeDecision::Quit)).await.unwrap();
+        assert_eq!(response.merged_count, 0);
+        assert_eq!(repo.find_all(None).await.unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_parse_decision() {
+        assert_eq!(parse_decision("y\n"), MergeDecision::Merge);
+        assert_eq!(parse_decision("Q"), MergeDecision::Quit);
+        assert_eq!(parse_decision(""), MergeDecision::Skip);
+    }
+}
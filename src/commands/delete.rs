@@ -1,6 +1,6 @@
-use crate::commands::{CommandHandler, DeleteArgs, OutputFormat, output};
+use crate::commands::{CommandHandler, DeleteArgs, OutputFormat, find_bookmark_by_partial_id, output};
 use crate::traits::BookmarkRepository;
-use crate::types::{Bookmark, BookmarkResult, BookmarkError};
+use crate::types::{Bookmark, BookmarkResult};
 use serde::{Serialize, Deserialize};
 
 /// JSON response data for delete command
@@ -22,47 +22,17 @@ impl DeleteCommand {
     
     async fn find_bookmark_by_id(&self, repository: &mut dyn BookmarkRepository) -> BookmarkResult<Bookmark> {
         let all_bookmarks = repository.find_all(None).await?;
-        
-        // Try exact match first
-        for bookmark in &all_bookmarks {
-            if bookmark.id == self.args.id {
-                return Ok(bookmark.clone());
-            }
-        }
-        
-        // If no exact match and input is ≤8 chars, try partial match
-        if self.args.id.len() <= 8 {
-            let matches: Vec<&Bookmark> = all_bookmarks
-                .iter()
-                .filter(|bookmark| bookmark.id.starts_with(&self.args.id))
-                .collect();
-                
-            match matches.len() {
-                0 => Err(BookmarkError::NotFound(self.args.id.clone())),
-                1 => Ok(matches[0].clone()),
-                _ => {
-                    let matching_ids: Vec<String> = matches
-                        .iter()
-                        .map(|b| b.id[..8.min(b.id.len())].to_string())
-                        .collect();
-                    Err(BookmarkError::InvalidId(format!(
-                        "Ambiguous ID '{}' matches multiple bookmarks: {}. Use a longer ID prefix.",
-                        self.args.id,
-                        matching_ids.join(", ")
-                    )))
-                }
-            }
-        } else {
-            Err(BookmarkError::NotFound(self.args.id.clone()))
-        }
+        let candidates: Vec<&Bookmark> = all_bookmarks.iter().collect();
+        find_bookmark_by_partial_id(&candidates, &self.args.id).cloned()
     }
     
     fn format_deletion_confirmation(&self, bookmark: &Bookmark) -> String {
         format!(
-            "Deleted bookmark: {}\n  URL: {}\n  ID: {}",
+            "Deleted bookmark: {}\n  URL: {}\n  ID: {}\n  Moved to trash. Restore with: automark trash restore {}",
             bookmark.title,
             bookmark.url,
-            bookmark.id
+            bookmark.id,
+            &bookmark.id[..8.min(bookmark.id.len())]
         )
     }
 }
@@ -105,7 +75,7 @@ pub async fn handle_delete_command(
 mod tests {
     use super::*;
     use crate::traits::repository::MockBookmarkRepository;
-    use crate::types::Bookmark;
+    use crate::types::{Bookmark, BookmarkError};
 
     #[tokio::test]
     async fn test_delete_with_full_id() {
@@ -192,6 +162,7 @@ mod tests {
         assert!(confirmation.starts_with("Deleted bookmark: Example Site"));
         assert!(confirmation.contains("\n  URL: https://example.com"));
         assert!(confirmation.contains("\n  ID: test123"));
+        assert!(confirmation.contains("automark trash restore test123"));
     }
 
     #[tokio::test]
@@ -267,6 +238,21 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_delete_moves_bookmark_to_trash() {
+        let mut repo = MockBookmarkRepository::new();
+        let bookmark = Bookmark::new("https://example.com", "Example Site").unwrap();
+        let bookmark_id = bookmark.id.clone();
+        repo.create(bookmark).await.unwrap();
+        
+        let args = DeleteArgs { id: bookmark_id.clone() };
+        handle_delete_command(args, &mut repo, OutputFormat::Human).await.unwrap();
+        
+        let trash = repo.find_trash().await.unwrap();
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].bookmark.id, bookmark_id);
+    }
+
     #[tokio::test]
     async fn test_delete_command_creation() {
         let args = DeleteArgs { id: "test".to_string() };
@@ -1,6 +1,6 @@
 use clap::{Parser, Subcommand, Args};
 use crate::traits::BookmarkRepository;
-use crate::types::BookmarkResult;
+use crate::types::{Bookmark, BookmarkError, BookmarkResult};
 use async_trait::async_trait;
 use serde::{Serialize, Deserialize};
 
@@ -11,6 +11,20 @@ pub mod search;
 pub mod sync;
 pub mod auto_sync;
 pub mod tui;
+pub mod trash;
+pub mod bulk;
+pub mod tags;
+pub mod archive;
+pub mod open;
+pub mod check;
+pub mod refresh;
+pub mod cache;
+pub mod rules;
+pub mod related;
+pub mod clusters;
+pub mod dedupe;
+pub mod reading;
+pub mod stats;
 
 pub use add::handle_add_command;
 pub use list::handle_list_command;
@@ -18,6 +32,20 @@ pub use delete::handle_delete_command;
 pub use search::handle_search_command;
 pub use sync::handle_sync_command;
 pub use tui::handle_tui_command;
+pub use trash::handle_trash_command;
+pub use bulk::handle_bulk_command;
+pub use tags::handle_tags_command;
+pub use archive::handle_archive_command;
+pub use open::handle_open_command;
+pub use check::handle_check_command;
+pub use refresh::{handle_refresh_command, fill_pending_metadata};
+pub use cache::handle_cache_command;
+pub use rules::handle_rules_command;
+pub use related::handle_related_command;
+pub use clusters::handle_clusters_command;
+pub use dedupe::handle_dedupe_command;
+pub use reading::{handle_start_command, handle_done_command, handle_next_command, handle_queue_command};
+pub use stats::handle_stats_command;
 
 /// Output format for CLI responses
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -128,6 +156,8 @@ pub mod output {
             crate::types::BookmarkError::InvalidId(_) => ("INVALID_ID", error.to_string()),
             crate::types::BookmarkError::MetadataExtraction(_) => ("METADATA_EXTRACTION_ERROR", error.to_string()),
             crate::types::BookmarkError::SyncError(_) => ("SYNC_ERROR", error.to_string()),
+            crate::types::BookmarkError::InvalidQuery(_) => ("INVALID_QUERY", error.to_string()),
+            crate::types::BookmarkError::ArchiveError(_) => ("ARCHIVE_ERROR", error.to_string()),
             crate::types::BookmarkError::TerminalError(_) => ("TERMINAL_ERROR", error.to_string()),
         }
     }
@@ -175,6 +205,40 @@ pub enum Commands {
     Search(search::SearchArgs),
     /// Sync bookmarks with a remote server
     Sync(sync::SyncArgs),
+    /// Manage deleted bookmarks
+    Trash(trash::TrashArgs),
+    /// Apply an action to every bookmark matching a search or a list of IDs
+    Bulk(bulk::BulkArgs),
+    /// List and manage tags across all bookmarks
+    Tags(tags::TagsArgs),
+    /// Save an offline copy of a bookmarked page
+    Archive(archive::ArchiveArgs),
+    /// Open a bookmark in the browser
+    Open(open::OpenArgs),
+    /// Check bookmarked links for dead pages and redirects
+    Check(check::CheckArgs),
+    /// Re-extract metadata for existing bookmarks
+    Refresh(refresh::RefreshArgs),
+    /// Manage downloaded pages kept for reuse
+    Cache(cache::CacheArgs),
+    /// Apply or test the tagging and priority rules from the config
+    Rules(rules::RulesArgs),
+    /// Find bookmarks similar to a given one
+    Related(related::RelatedArgs),
+    /// Group the library into topic clusters
+    Clusters(clusters::ClustersArgs),
+    /// Find near-duplicate bookmarks and merge them
+    Dedupe(dedupe::DedupeArgs),
+    /// Mark a bookmark as being read
+    Start(reading::StartArgs),
+    /// Mark a bookmark as completed
+    Done(reading::DoneArgs),
+    /// Suggest the unread bookmark to read next
+    Next(reading::NextArgs),
+    /// Show what is being read and what is up next
+    Queue(reading::QueueArgs),
+    /// Report reading statistics and a breakdown of the library
+    Stats(stats::StatsArgs),
 }
 
 #[derive(Args, Clone)]
@@ -193,6 +257,15 @@ pub struct AddArgs {
     /// Skip metadata extraction and prompt for title if not provided
     #[arg(long)]
     pub no_fetch: bool,
+    /// Save immediately and fetch metadata later (`automark refresh --pending`)
+    #[arg(long, conflicts_with = "no_fetch")]
+    pub async_fetch: bool,
+    /// Apply the suggested tags instead of only listing them
+    #[arg(long)]
+    pub auto_tag: bool,
+    /// Also save an offline copy of the page
+    #[arg(long)]
+    pub archive: bool,
 }
 
 #[derive(Args, Clone)]
@@ -201,6 +274,44 @@ pub struct DeleteArgs {
     pub id: String,
 }
 
+/// Resolve a full or partial bookmark ID against a set of bookmarks
+///
+/// Exact matches win; otherwise IDs of up to 8 characters are treated as a prefix
+/// and must match exactly one bookmark.
+pub fn find_bookmark_by_partial_id<'a>(bookmarks: &[&'a Bookmark], id: &str) -> BookmarkResult<&'a Bookmark> {
+    // Try exact match first
+    if let Some(bookmark) = bookmarks.iter().find(|bookmark| bookmark.id == id) {
+        return Ok(bookmark);
+    }
+    
+    // If no exact match and input is ≤8 chars, try partial match
+    if id.len() > 8 {
+        return Err(BookmarkError::NotFound(id.to_string()));
+    }
+    
+    let matches: Vec<&Bookmark> = bookmarks
+        .iter()
+        .copied()
+        .filter(|bookmark| bookmark.id.starts_with(id))
+        .collect();
+        
+    match matches.len() {
+        0 => Err(BookmarkError::NotFound(id.to_string())),
+        1 => Ok(matches[0]),
+        _ => {
+            let matching_ids: Vec<String> = matches
+                .iter()
+                .map(|b| b.id[..8.min(b.id.len())].to_string())
+                .collect();
+            Err(BookmarkError::InvalidId(format!(
+                "Ambiguous ID '{}' matches multiple bookmarks: {}. Use a longer ID prefix.",
+                id,
+                matching_ids.join(", ")
+            )))
+        }
+    }
+}
+
 #[async_trait]
 pub trait CommandHandler {
     async fn execute(&self, repository: &mut dyn BookmarkRepository, format: OutputFormat) -> BookmarkResult<()>;
@@ -212,6 +323,7 @@ mod tests {
     use clap::error::ErrorKind;
 
     #[test]
+    #[allow(clippy::bool_assert_comparison, clippy::needless_borrows_for_generic_args)]
     fn test_add_command_parsing() {
         let cli = Cli::try_parse_from(&["automark", "add", "https://example.com", "--title", "Example Title"]);
         assert!(cli.is_ok());
@@ -228,6 +340,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_borrows_for_generic_args)]
     fn test_list_command_parsing() {
         let cli = Cli::try_parse_from(&["automark", "list"]);
         assert!(cli.is_ok());
@@ -240,6 +353,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_borrows_for_generic_args)]
     fn test_delete_command_parsing() {
         let cli = Cli::try_parse_from(&["automark", "delete", "abc123"]);
         assert!(cli.is_ok());
@@ -252,6 +366,41 @@ mod tests {
     }
 
     #[test]
+    fn test_trash_command_parsing() {
+        let cli = Cli::try_parse_from(["automark", "trash", "empty", "--older-than", "30d"]);
+        
+        if let Ok(Cli { command: Some(Commands::Trash(args)), .. }) = cli {
+            assert!(matches!(args.command, trash::TrashCommand::Empty { older_than: Some(ref age) } if age == "30d"));
+        } else {
+            panic!("Expected Trash command");
+        }
+        
+        let cli = Cli::try_parse_from(["automark", "trash", "restore", "abc123"]);
+        if let Ok(Cli { command: Some(Commands::Trash(args)), .. }) = cli {
+            assert!(matches!(args.command, trash::TrashCommand::Restore { ref id } if id == "abc123"));
+        } else {
+            panic!("Expected Trash command");
+        }
+    }
+
+    #[test]
+    fn test_tags_command_parsing() {
+        let cli = Cli::try_parse_from(["automark", "tags"]);
+        assert!(matches!(cli, Ok(Cli { command: Some(Commands::Tags(tags::TagsArgs { command: None, tree: false })), .. })));
+
+        let cli = Cli::try_parse_from(["automark", "tags", "merge", "js", "javascript", "--into", "javascript"]);
+        if let Ok(Cli { command: Some(Commands::Tags(args)), .. }) = cli {
+            assert!(matches!(args.command, Some(tags::TagsCommand::Merge { ref tags, ref into }) if tags.len() == 2 && into == "javascript"));
+        } else {
+            panic!("Expected Tags command");
+        }
+
+        // Merge requires a target
+        assert!(Cli::try_parse_from(["automark", "tags", "merge", "js"]).is_err());
+    }
+
+    #[test]
+    #[allow(clippy::needless_borrows_for_generic_args)]
     fn test_missing_arguments() {
         // Missing URL for add command
         let cli = Cli::try_parse_from(&["automark", "add"]);
@@ -263,6 +412,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_borrows_for_generic_args)]
     fn test_help_output() {
         let cli = Cli::try_parse_from(&["automark", "--help"]);
         match cli {
@@ -279,6 +429,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_borrows_for_generic_args)]
     fn test_version_output() {
         let cli = Cli::try_parse_from(&["automark", "--version"]);
         match cli {
@@ -290,12 +441,14 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_borrows_for_generic_args)]
     fn test_invalid_command() {
         let cli = Cli::try_parse_from(&["automark", "invalid"]);
         assert!(cli.is_err());
     }
 
     #[test]
+    #[allow(clippy::needless_borrows_for_generic_args)]
     fn test_add_with_spaces_in_title() {
         let cli = Cli::try_parse_from(&["automark", "add", "https://example.com", "--title", "Multi Word Title"]);
         assert!(cli.is_ok());
@@ -306,6 +459,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison, clippy::needless_borrows_for_generic_args)]
     fn test_add_without_title() {
         let cli = Cli::try_parse_from(&["automark", "add", "https://example.com"]);
         assert!(cli.is_ok());
@@ -322,6 +476,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison, clippy::needless_borrows_for_generic_args)]
     fn test_add_with_no_fetch_flag() {
         let cli = Cli::try_parse_from(&["automark", "add", "https://example.com", "--no-fetch"]);
         assert!(cli.is_ok());
@@ -338,6 +493,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison, clippy::needless_borrows_for_generic_args)]
     fn test_add_with_title_and_no_fetch_flag() {
         let cli = Cli::try_parse_from(&["automark", "add", "https://example.com", "--title", "Title", "--no-fetch"]);
         assert!(cli.is_ok());
@@ -354,6 +510,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison, clippy::needless_borrows_for_generic_args)]
     fn test_output_format_parsing() {
         // Test default output format (human)
         let cli = Cli::try_parse_from(&["automark", "list"]);
@@ -399,6 +556,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_borrows_for_generic_args)]
     fn test_no_command_defaults_to_tui() {
         let cli = Cli::try_parse_from(&["automark"]);
         assert!(cli.is_ok());
@@ -417,6 +575,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_json_response_success() {
         let data = "test data";
         let response = JsonResponse::success(data);
@@ -428,6 +587,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_json_response_error() {
         let response = JsonResponse::<()>::error("TEST_ERROR", "Test error message".to_string());
         
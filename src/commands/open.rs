@@ -0,0 +1,137 @@
+use crate::commands::{CommandHandler, OutputFormat, find_bookmark_by_partial_id, output};
+use crate::traits::BookmarkRepository;
+use crate::types::{Bookmark, BookmarkError, BookmarkResult};
+use crate::adapters::browser::open_url;
+use clap::Args;
+use serde::{Serialize, Deserialize};
+
+/// Command-line arguments for open command
+#[derive(Args, Debug, Clone)]
+pub struct OpenArgs {
+    /// ID of the bookmark to open (can be partial ID)
+    pub id: String,
+    /// Open the offline copy saved with 'automark archive' instead of the live page
+    #[arg(long)]
+    pub archived: bool,
+}
+
+/// JSON response data for open command
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OpenResponse {
+    pub bookmark_id: String,
+    /// URL or archive file that was opened
+    pub target: String,
+    pub archived: bool,
+}
+
+pub struct OpenCommand {
+    args: OpenArgs,
+}
+
+impl OpenCommand {
+    pub fn new(args: OpenArgs) -> Self {
+        Self { args }
+    }
+
+    /// The bookmark and the URL or file path to open for it
+    async fn resolve_target(&self, repository: &dyn BookmarkRepository) -> BookmarkResult<(Bookmark, String)> {
+        let bookmarks = repository.find_all(None).await?;
+        let candidates: Vec<&Bookmark> = bookmarks.iter().collect();
+        let bookmark = find_bookmark_by_partial_id(&candidates, &self.args.id)?.clone();
+
+        if !self.args.archived {
+            let url = bookmark.url.clone();
+            return Ok((bookmark, url));
+        }
+
+        match repository.find_archive(&bookmark.id).await? {
+            Some(path) => Ok((bookmark, path.display().to_string())),
+            None => Err(missing_archive_error(&bookmark)),
+        }
+    }
+}
+
+/// Explain why there is no archive to open and how to create one
+fn missing_archive_error(bookmark: &Bookmark) -> BookmarkError {
+    let short_id = &bookmark.id[..8.min(bookmark.id.len())];
+    let reason = if bookmark.archive_path.is_some() {
+        "was archived on another device"
+    } else {
+        "has not been archived"
+    };
+    BookmarkError::ArchiveError(format!(
+        "'{}' {}. Create a copy with: automark archive {}",
+        bookmark.title, reason, short_id
+    ))
+}
+
+#[async_trait::async_trait]
+impl CommandHandler for OpenCommand {
+    async fn execute(&self, repository: &mut dyn BookmarkRepository, format: OutputFormat) -> BookmarkResult<()> {
+        let (bookmark, target) = self.resolve_target(repository).await?;
+        open_url(&target)
+            .map_err(|e| BookmarkError::InvalidUrl(format!("Failed to open {}: {}", target, e)))?;
+
+        match format {
+            OutputFormat::Json => {
+                output::print_response(format, OpenResponse {
+                    bookmark_id: bookmark.id,
+                    target,
+                    archived: self.args.archived,
+                })?;
+            }
+            OutputFormat::Human => {
+                println!("Opened: {}", bookmark.title);
+                println!("  {}", target);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub async fn handle_open_command(
+    args: OpenArgs,
+    repository: &mut dyn BookmarkRepository,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    let command = OpenCommand::new(args);
+    command.execute(repository, format).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::repository::MockBookmarkRepository;
+    use std::path::PathBuf;
+
+    fn open_args(id: &str, archived: bool) -> OpenArgs {
+        OpenArgs { id: id.to_string(), archived }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_live_and_archived_targets() {
+        let mut repo = MockBookmarkRepository::new();
+        let bookmark = Bookmark::new("https://example.com/post", "Post").unwrap();
+        let id = bookmark.id.clone();
+        repo.create(bookmark).await.unwrap();
+
+        let (_, target) = OpenCommand::new(open_args(&id, false)).resolve_target(&repo).await.unwrap();
+        assert_eq!(target, "https://example.com/post");
+
+        let result = OpenCommand::new(open_args(&id, true)).resolve_target(&repo).await;
+        assert!(matches!(result, Err(BookmarkError::ArchiveError(ref message)) if message.contains("has not been archived")));
+
+        repo.save_archive(&id, "<html></html>").await.unwrap();
+        let (_, target) = OpenCommand::new(open_args(&id, true)).resolve_target(&repo).await.unwrap();
+        assert_eq!(Some(PathBuf::from(target)), repo.find_archive(&id).await.unwrap());
+    }
+
+    #[test]
+    fn test_missing_archive_from_other_device() {
+        let mut bookmark = Bookmark::new("https://example.com/post", "Post").unwrap();
+        bookmark.archive_path = Some(format!("archives/{}.html", bookmark.id));
+        let error = missing_archive_error(&bookmark);
+        assert!(error.to_string().contains("archived on another device"));
+    }
+}
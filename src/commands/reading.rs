@@ -0,0 +1,390 @@
+use crate::commands::{OutputFormat, find_bookmark_by_partial_id, output};
+use crate::traits::BookmarkRepository;
+use crate::types::{Bookmark, BookmarkFilters, BookmarkResult, ReadingStatus};
+use chrono::{DateTime, Duration, Utc};
+use clap::Args;
+use serde::{Serialize, Deserialize};
+
+/// Priority assumed for bookmarks without a rating when ranking the queue
+const UNRATED_PRIORITY: f64 = 3.0;
+
+/// Days an unread bookmark waits before it ranks one priority level higher
+const DAYS_PER_PRIORITY_LEVEL: f64 = 30.0;
+
+/// Window for the reading throughput shown with the queue
+const THROUGHPUT_DAYS: i64 = 7;
+
+/// Command-line arguments for start command
+#[derive(Args, Debug, Clone)]
+pub struct StartArgs {
+    /// ID of the bookmark to start reading (can be partial ID)
+    pub id: String,
+}
+
+/// Command-line arguments for done command
+#[derive(Args, Debug, Clone)]
+pub struct DoneArgs {
+    /// ID of the bookmark finished reading (can be partial ID)
+    pub id: String,
+}
+
+/// Command-line arguments for next command
+#[derive(Args, Debug, Clone)]
+pub struct NextArgs {
+    /// Also mark the suggested bookmark as being read
+    #[arg(long)]
+    pub start: bool,
+}
+
+/// Command-line arguments for queue command
+#[derive(Args, Debug, Clone)]
+pub struct QueueArgs {
+    /// Number of unread bookmarks to show
+    #[arg(short = 'n', long, default_value_t = 10)]
+    pub limit: usize,
+}
+
+/// JSON response data for start and done commands
+#[derive(Serialize, Deserialize, Debug)]
+pub struct StatusChangeResponse {
+    pub id: String,
+    pub title: String,
+    pub previous_status: ReadingStatus,
+    pub reading_status: ReadingStatus,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// JSON response data for next command
+#[derive(Serialize, Deserialize, Debug)]
+pub struct NextResponse {
+    /// The unread bookmark to read next, if any are left
+    pub next: Option<QueueEntry>,
+    pub started: bool,
+    pub unread_count: usize,
+}
+
+/// JSON response data for queue command
+#[derive(Serialize, Deserialize, Debug)]
+pub struct QueueResponse {
+    /// Bookmarks being read, most recently started first
+    pub reading: Vec<QueueEntry>,
+    /// Unread bookmarks in the order `next` would suggest them
+    pub unread: Vec<QueueEntry>,
+    pub unread_count: usize,
+    /// Bookmarks completed in the last week
+    pub completed_last_week: usize,
+}
+
+/// A bookmark waiting to be read
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QueueEntry {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    pub priority: Option<u8>,
+    /// Days since the bookmark was saved
+    pub age_days: i64,
+    /// Priority plus one level for every 30 days of waiting
+    pub score: f64,
+}
+
+pub async fn handle_start_command(
+    args: StartArgs,
+    repository: &mut dyn BookmarkRepository,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    let response = change_status(&args.id, ReadingStatus::Reading, repository).await?;
+    match format {
+        OutputFormat::Json => output::print_response(format, response)?,
+        OutputFormat::Human => print!("{}", format_status_change(&response)),
+    }
+    Ok(())
+}
+
+pub async fn handle_done_command(
+    args: DoneArgs,
+    repository: &mut dyn BookmarkRepository,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    let response = change_status(&args.id, ReadingStatus::Completed, repository).await?;
+    match format {
+        OutputFormat::Json => output::print_response(format, response)?,
+        OutputFormat::Human => print!("{}", format_status_change(&response)),
+    }
+    Ok(())
+}
+
+pub async fn handle_next_command(
+    args: NextArgs,
+    repository: &mut dyn BookmarkRepository,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    let response = next_bookmark(&args, repository).await?;
+    match format {
+        OutputFormat::Json => output::print_response(format, response)?,
+        OutputFormat::Human => print!("{}", format_next(&response)),
+    }
+    Ok(())
+}
+
+pub async fn handle_queue_command(
+    args: QueueArgs,
+    repository: &dyn BookmarkRepository,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    let response = build_queue(&args, repository).await?;
+    match format {
+        OutputFormat::Json => output::print_response(format, response)?,
+        OutputFormat::Human => print!("{}", format_queue(&response)),
+    }
+    Ok(())
+}
+
+async fn change_status(
+    id: &str,
+    status: ReadingStatus,
+    repository: &mut dyn BookmarkRepository,
+) -> BookmarkResult<StatusChangeResponse> {
+    let bookmarks = repository.find_all(None).await?;
+    let candidates: Vec<&Bookmark> = bookmarks.iter().collect();
+    let mut bookmark = find_bookmark_by_partial_id(&candidates, id)?.clone();
+
+    let previous_status = bookmark.reading_status.clone();
+    if bookmark.set_reading_status(status) {
+        bookmark = repository.update(bookmark).await?;
+    }
+
+    Ok(StatusChangeResponse {
+        id: bookmark.id,
+        title: bookmark.title,
+        previous_status,
+        reading_status: bookmark.reading_status,
+        started_at: bookmark.started_at,
+        completed_at: bookmark.completed_at,
+    })
+}
+
+async fn next_bookmark(args: &NextArgs, repository: &mut dyn BookmarkRepository) -> BookmarkResult<NextResponse> {
+    let unread = ranked_unread(repository, Utc::now()).await?;
+    let unread_count = unread.len();
+    let next = unread.into_iter().next();
+
+    let started = match next {
+        Some((ref bookmark, _)) if args.start => {
+            let mut bookmark = bookmark.clone();
+            bookmark.set_reading_status(ReadingStatus::Reading);
+            repository.update(bookmark).await?;
+            true
+        }
+        _ => false,
+    };
+
+    Ok(NextResponse {
+        next: next.map(|(_, entry)| entry),
+        started,
+        unread_count,
+    })
+}
+
+async fn build_queue(args: &QueueArgs, repository: &dyn BookmarkRepository) -> BookmarkResult<QueueResponse> {
+    let now = Utc::now();
+    let unread = ranked_unread(repository, now).await?;
+
+    let mut reading = repository.find_all(Some(status_filter(ReadingStatus::Reading))).await?;
+    reading.sort_by_key(|bookmark| std::cmp::Reverse(bookmark.started_at));
+
+    let week_ago = now - Duration::days(THROUGHPUT_DAYS);
+    let completed_last_week = repository.find_all(Some(status_filter(ReadingStatus::Completed))).await?
+        .iter()
+        .filter(|bookmark| bookmark.completed_at.is_some_and(|at| at >= week_ago))
+        .count();
+
+    Ok(QueueResponse {
+        reading: reading.iter().map(|bookmark| queue_entry(bookmark, now)).collect(),
+        unread_count: unread.len(),
+        unread: unread.into_iter().take(args.limit).map(|(_, entry)| entry).collect(),
+        completed_last_week,
+    })
+}
+
+/// Unread bookmarks, best to read next first
+async fn ranked_unread(repository: &dyn BookmarkRepository, now: DateTime<Utc>) -> BookmarkResult<Vec<(Bookmark, QueueEntry)>> {
+    let mut ranked: Vec<(Bookmark, QueueEntry)> = repository.find_all(Some(status_filter(ReadingStatus::Unread))).await?
+        .into_iter()
+        .map(|bookmark| {
+            let entry = queue_entry(&bookmark, now);
+            (bookmark, entry)
+        })
+        .collect();
+    ranked.sort_by(|(a, a_entry), (b, b_entry)| {
+        b_entry.score.total_cmp(&a_entry.score).then(a.bookmarked_date.cmp(&b.bookmarked_date))
+    });
+    Ok(ranked)
+}
+
+fn status_filter(status: ReadingStatus) -> BookmarkFilters {
+    BookmarkFilters { reading_status: Some(status), ..Default::default() }
+}
+
+fn queue_entry(bookmark: &Bookmark, now: DateTime<Utc>) -> QueueEntry {
+    let age = now - bookmark.bookmarked_date;
+    QueueEntry {
+        id: bookmark.id.clone(),
+        title: bookmark.title.clone(),
+        url: bookmark.url.clone(),
+        priority: bookmark.priority_rating,
+        age_days: age.num_days(),
+        score: queue_score(bookmark.priority_rating, age),
+    }
+}
+
+/// Rank for reading next: the priority, plus one level for every 30 days spent waiting
+fn queue_score(priority: Option<u8>, age: Duration) -> f64 {
+    let priority = priority.map(f64::from).unwrap_or(UNRATED_PRIORITY);
+    let waited = age.num_seconds().max(0) as f64 / Duration::days(1).num_seconds() as f64;
+    priority + waited / DAYS_PER_PRIORITY_LEVEL
+}
+
+fn format_days(days: i64) -> String {
+    match days {
+        0 => "today".to_string(),
+        1 => "1 day".to_string(),
+        days => format!("{} days", days),
+    }
+}
+
+fn format_entry(entry: &QueueEntry) -> String {
+    let priority = entry.priority.map(|priority| format!("p{}", priority)).unwrap_or_else(|| "--".to_string());
+    let age = match entry.age_days {
+        0 => "saved today".to_string(),
+        days => format!("saved {} ago", format_days(days)),
+    };
+    format!("  {} [{}] {} ({})\n", priority, &entry.id[..8.min(entry.id.len())], entry.title, age)
+}
+
+fn format_status_change(response: &StatusChangeResponse) -> String {
+    if response.previous_status == response.reading_status {
+        return format!("'{}' is already {:?}\n", response.title, response.reading_status);
+    }
+
+    match (&response.reading_status, response.started_at, response.completed_at) {
+        (ReadingStatus::Completed, Some(started_at), Some(completed_at)) => format!(
+            "Completed: {} (read in {})\n",
+            response.title,
+            format_days((completed_at - started_at).num_days()).replace("today", "under a day")
+        ),
+        (ReadingStatus::Completed, _, _) => format!("Completed: {}\n", response.title),
+        _ => format!("Started reading: {}\n", response.title),
+    }
+}
+
+fn format_next(response: &NextResponse) -> String {
+    let Some(ref next) = response.next else {
+        return "Nothing left to read.\n".to_string();
+    };
+
+    let mut output = if response.started {
+        "Started reading:\n".to_string()
+    } else {
+        "Read next:\n".to_string()
+    };
+    output.push_str(&format_entry(next));
+    output.push_str(&format!("    {}\n", next.url));
+    output.push_str(&format!("{} unread bookmark(s) in the queue\n", response.unread_count));
+    output
+}
+
+fn format_queue(response: &QueueResponse) -> String {
+    let mut output = String::new();
+    if !response.reading.is_empty() {
+        output.push_str("Reading:\n");
+        for entry in &response.reading {
+            output.push_str(&format_entry(entry));
+        }
+    }
+
+    if response.unread.is_empty() {
+        output.push_str("Nothing unread.\n");
+    } else {
+        output.push_str(&format!("Up next ({} of {} unread):\n", response.unread.len(), response.unread_count));
+        for entry in &response.unread {
+            output.push_str(&format_entry(entry));
+        }
+    }
+    output.push_str(&format!("Completed {} in the last {} days\n", response.completed_last_week, THROUGHPUT_DAYS));
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::repository::MockBookmarkRepository;
+
+    async fn saved(repo: &mut MockBookmarkRepository, title: &str, priority: Option<u8>, days_ago: i64) -> Bookmark {
+        let mut bookmark = Bookmark::new(&format!("https://example.com/{}", title), title).unwrap();
+        bookmark.priority_rating = priority;
+        bookmark.bookmarked_date = Utc::now() - Duration::days(days_ago);
+        repo.create(bookmark).await.unwrap()
+    }
+
+    #[test]
+    fn test_queue_score_weighs_priority_by_age() {
+        assert!(queue_score(Some(5), Duration::days(1)) > queue_score(Some(4), Duration::days(20)));
+        assert!(queue_score(Some(3), Duration::days(90)) > queue_score(Some(5), Duration::days(0)));
+        assert_eq!(queue_score(None, Duration::zero()), UNRATED_PRIORITY);
+    }
+
+    #[tokio::test]
+    async fn test_start_and_done_record_timestamps() {
+        let mut repo = MockBookmarkRepository::new();
+        let bookmark = saved(&mut repo, "article", None, 2).await;
+
+        let started = change_status(&bookmark.id[..8], ReadingStatus::Reading, &mut repo).await.unwrap();
+        assert_eq!(started.previous_status, ReadingStatus::Unread);
+        assert!(started.started_at.is_some() && started.completed_at.is_none());
+
+        let done = change_status(&bookmark.id, ReadingStatus::Completed, &mut repo).await.unwrap();
+        let stored = repo.find_by_id(&bookmark.id).await.unwrap();
+        assert_eq!(stored.reading_status, ReadingStatus::Completed);
+        assert_eq!(stored.started_at, started.started_at);
+        assert_eq!(stored.completed_at, done.completed_at);
+        assert!(format_status_change(&done).starts_with("Completed: article (read in under a day)"));
+
+        // Finishing again changes nothing
+        let again = change_status(&bookmark.id, ReadingStatus::Completed, &mut repo).await.unwrap();
+        assert_eq!(again.completed_at, done.completed_at);
+        assert_eq!(format_status_change(&again), "'article' is already Completed\n");
+    }
+
+    #[tokio::test]
+    async fn test_next_and_queue_order() {
+        let mut repo = MockBookmarkRepository::new();
+        saved(&mut repo, "fresh-low", Some(2), 0).await;
+        let old = saved(&mut repo, "old-unrated", None, 120).await;
+        saved(&mut repo, "fresh-high", Some(5), 1).await;
+        let finished = saved(&mut repo, "finished", Some(5), 3).await;
+        change_status(&finished.id, ReadingStatus::Completed, &mut repo).await.unwrap();
+
+        let queue = build_queue(&QueueArgs { limit: 2 }, &repo).await.unwrap();
+        let titles: Vec<&str> = queue.unread.iter().map(|entry| entry.title.as_str()).collect();
+        assert_eq!(titles, vec!["old-unrated", "fresh-high"]);
+        assert_eq!(queue.unread_count, 3);
+        assert_eq!(queue.completed_last_week, 1);
+
+        let next = next_bookmark(&NextArgs { start: true }, &mut repo).await.unwrap();
+        assert_eq!(next.next.unwrap().id, old.id);
+        assert!(next.started);
+        assert_eq!(repo.find_by_id(&old.id).await.unwrap().reading_status, ReadingStatus::Reading);
+
+        let queue = build_queue(&QueueArgs { limit: 10 }, &repo).await.unwrap();
+        assert_eq!(queue.reading.len(), 1);
+        assert_eq!(queue.unread_count, 2);
+    }
+
+    #[test]
+    fn test_format_next_with_empty_queue() {
+        let response = NextResponse { next: None, started: false, unread_count: 0 };
+        assert_eq!(format_next(&response), "Nothing left to read.\n");
+    }
+}
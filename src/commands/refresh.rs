@@ -0,0 +1,688 @@
+use crate::commands::{OutputFormat, find_bookmark_by_partial_id, output};
+use crate::commands::add::{extract_metadata_with_config, is_offline_error};
+use crate::commands::search::{SearchArgs, SearchCommand};
+use crate::traits::{BookmarkRepository, MetadataExtractor};
+use crate::types::{Bookmark, BookmarkBatch, BookmarkError, BookmarkResult, Config, ExtractedMetadata, RuleSet};
+use crate::adapters::{ExtractorRegistry, HttpCache};
+use clap::Args;
+use futures_util::stream::{self, StreamExt};
+use serde::{Serialize, Deserialize};
+use std::io::{IsTerminal, Write};
+use url::Url;
+
+/// Longest field value shown in the human-readable diff
+const DIFF_VALUE_CHARS: usize = 60;
+
+/// Command-line arguments for refresh command
+#[derive(Args, Debug, Clone)]
+pub struct RefreshArgs {
+    /// Bookmark ID (full or partial) to refresh
+    pub id: Option<String>,
+
+    /// Refresh every bookmark
+    #[arg(long, conflicts_with = "id")]
+    pub all: bool,
+
+    /// Refresh bookmarks whose metadata couldn't be fetched when they were added
+    #[arg(long, conflicts_with = "id")]
+    pub pending: bool,
+
+    /// Filters selecting the bookmarks to refresh (same as search)
+    #[command(flatten)]
+    pub filters: SearchArgs,
+
+    /// Replace fields that already have a value, not just missing ones
+    #[arg(long)]
+    pub overwrite: bool,
+
+    /// Number of pages to fetch at once (defaults to metadata.refresh_concurrency in the config)
+    #[arg(long)]
+    pub concurrency: Option<usize>,
+}
+
+/// JSON response data for refresh command
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RefreshResponse {
+    pub refreshed: usize,
+    pub changed: usize,
+    pub failed: usize,
+    pub overwrite: bool,
+    pub results: Vec<RefreshResult>,
+}
+
+/// Outcome of refreshing one bookmark
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RefreshResult {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    pub changes: Vec<FieldChange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Metadata is still missing and will be fetched again later
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pending: bool,
+}
+
+/// One field whose value was filled in or replaced
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+impl FieldChange {
+    fn new(field: &str, old: Option<String>, new: Option<String>) -> Self {
+        Self { field: field.to_string(), old, new }
+    }
+
+    /// A one-line summary, quoting the values only for the title
+    pub fn summary(&self) -> String {
+        match self.field.as_str() {
+            "title" => format!(
+                "title \"{}\" -> \"{}\"",
+                self.old.as_deref().unwrap_or_default(),
+                self.new.as_deref().unwrap_or_default()
+            ),
+            field => format!("{} updated", field),
+        }
+    }
+}
+
+pub async fn handle_refresh_command(
+    args: RefreshArgs,
+    repository: &mut dyn BookmarkRepository,
+    config: &Config,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    let extractor = ExtractorRegistry::with_builtin_sites(&config.metadata, HttpCache::for_config(config))?;
+    handle_refresh_command_with_extractor_and_config(args, repository, &extractor, config, format).await
+}
+
+pub async fn handle_refresh_command_with_extractor_and_config(
+    args: RefreshArgs,
+    repository: &mut dyn BookmarkRepository,
+    extractor: &dyn MetadataExtractor,
+    config: &Config,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    if args.filters.save.is_some() || args.filters.list_saved || args.filters.delete_saved.is_some() {
+        return Err(BookmarkError::InvalidId(
+            "Saved searches are managed with 'automark search'; use @name to select one".to_string()
+        ));
+    }
+
+    let targets = select_bookmarks(&args, repository).await?;
+    let concurrency = args.concurrency.unwrap_or(config.metadata.refresh_concurrency).max(1);
+    let show_progress = format == OutputFormat::Human && std::io::stderr().is_terminal();
+    let results = refresh_bookmarks(targets, repository, extractor, config, args.overwrite, concurrency, show_progress).await?;
+
+    let response = RefreshResponse {
+        refreshed: results.iter().filter(|result| result.error.is_none()).count(),
+        changed: results.iter().filter(|result| !result.changes.is_empty()).count(),
+        failed: results.iter().filter(|result| result.error.is_some()).count(),
+        overwrite: args.overwrite,
+        results,
+    };
+
+    match format {
+        OutputFormat::Json => output::print_response(format, response)?,
+        OutputFormat::Human => print!("{}", format_human_output(&response)),
+    }
+
+    Ok(())
+}
+
+/// Fetch metadata for each bookmark, apply the config's rules, and store what changed
+///
+/// A successful fetch clears the pending marker; so does a failure that won't go away
+/// by trying again, such as a robots.txt refusal.
+async fn refresh_bookmarks(
+    targets: Vec<Bookmark>,
+    repository: &mut dyn BookmarkRepository,
+    extractor: &dyn MetadataExtractor,
+    config: &Config,
+    overwrite: bool,
+    concurrency: usize,
+    show_progress: bool,
+) -> BookmarkResult<Vec<RefreshResult>> {
+    let rules = RuleSet::new(&config.rules)?;
+    let mut fetched = Vec::with_capacity(targets.len());
+    {
+        let mut fetches = stream::iter(targets.iter().enumerate())
+            .map(|(index, bookmark)| async move {
+                (index, extract_metadata_with_config(&bookmark.url, extractor, config).await)
+            })
+            .buffer_unordered(concurrency);
+        while let Some(result) = fetches.next().await {
+            fetched.push(result);
+            if show_progress {
+                eprint!("\rRefreshing {}/{}...", fetched.len(), targets.len());
+                let _ = std::io::stderr().flush();
+            }
+        }
+    }
+    if show_progress && !targets.is_empty() {
+        eprintln!();
+    }
+    fetched.sort_by_key(|(index, _)| *index);
+
+    let mut batch = BookmarkBatch::default();
+    let mut captured_contents = Vec::new();
+    let mut results = Vec::with_capacity(targets.len());
+    for (mut bookmark, (_, extraction)) in targets.into_iter().zip(fetched) {
+        let was_pending = bookmark.metadata_pending;
+        let mut result = RefreshResult {
+            id: bookmark.id.clone(),
+            title: bookmark.title.clone(),
+            url: bookmark.url.clone(),
+            changes: Vec::new(),
+            error: None,
+            pending: false,
+        };
+        match extraction {
+            Ok(metadata) => {
+                result.changes = apply_metadata(&mut bookmark, &metadata, overwrite);
+                result.changes.extend(apply_rules(&mut bookmark, &rules));
+                result.title = bookmark.title.clone();
+                if let Some(content) = metadata.content.filter(|_| config.metadata.capture_content) {
+                    captured_contents.push((bookmark.id.clone(), content));
+                }
+                bookmark.metadata_pending = false;
+                if !result.changes.is_empty() || was_pending {
+                    batch.updates.push(bookmark);
+                }
+            }
+            Err(e) => {
+                result.error = Some(e.to_string());
+                result.pending = was_pending && is_offline_error(&e);
+                if was_pending && !result.pending {
+                    bookmark.metadata_pending = false;
+                    batch.updates.push(bookmark);
+                }
+            }
+        }
+        results.push(result);
+    }
+
+    if !batch.is_empty() {
+        repository.apply_batch(batch).await?;
+    }
+    for (id, content) in &captured_contents {
+        repository.save_content(id, content).await?;
+    }
+    Ok(results)
+}
+
+/// Fill in bookmarks whose metadata couldn't be fetched when they were added
+///
+/// Runs before other commands so bookmarks saved offline or with `add --async-fetch`
+/// catch up once the network is back. Problems are reported but never fail the command.
+pub async fn fill_pending_metadata(repository: &mut dyn BookmarkRepository, config: &Config, format: OutputFormat) {
+    if !config.metadata.enabled || !config.metadata.auto_fill_pending {
+        return;
+    }
+    let extractor = match ExtractorRegistry::with_builtin_sites(&config.metadata, HttpCache::for_config(config)) {
+        Ok(extractor) => extractor,
+        Err(e) => {
+            if format == OutputFormat::Human {
+                eprintln!("Warning: Could not fill in pending metadata: {}", e);
+            }
+            return;
+        }
+    };
+    match fill_pending_metadata_with_extractor(repository, &extractor, config).await {
+        Ok(results) if format == OutputFormat::Human => {
+            let filled = results.iter().filter(|result| result.error.is_none()).count();
+            if filled > 0 {
+                eprintln!("Filled in metadata for {} bookmark(s) added offline", filled);
+            }
+        }
+        Ok(_) => {}
+        Err(e) => {
+            if format == OutputFormat::Human {
+                eprintln!("Warning: Could not fill in pending metadata: {}", e);
+            }
+        }
+    }
+}
+
+/// Try pending bookmarks a few at a time, once each, until a whole group fails for
+/// lack of network, which means we are most likely still offline
+async fn fill_pending_metadata_with_extractor(
+    repository: &mut dyn BookmarkRepository,
+    extractor: &dyn MetadataExtractor,
+    config: &Config,
+) -> BookmarkResult<Vec<RefreshResult>> {
+    let pending: Vec<Bookmark> = repository.find_all(None).await?
+        .into_iter()
+        .filter(|bookmark| bookmark.metadata_pending)
+        .collect();
+    let mut config = config.clone();
+    config.metadata.retry_attempts = 0;
+    let concurrency = config.metadata.refresh_concurrency.max(1);
+
+    let mut results = Vec::new();
+    for group in pending.chunks(concurrency) {
+        let group_results = refresh_bookmarks(group.to_vec(), repository, extractor, &config, false, concurrency, false).await?;
+        let offline = group_results.iter().all(|result| result.pending);
+        results.extend(group_results);
+        if offline {
+            break;
+        }
+    }
+    Ok(results)
+}
+
+/// Resolve the bookmarks to refresh from an ID, `--all`, or search filters
+async fn select_bookmarks(args: &RefreshArgs, repository: &dyn BookmarkRepository) -> BookmarkResult<Vec<Bookmark>> {
+    let filtered = has_filters(&args.filters);
+    if let Some(ref id) = args.id {
+        if filtered {
+            return Err(BookmarkError::InvalidId(
+                "Give either a bookmark ID or filters, not both".to_string()
+            ));
+        }
+        let all_bookmarks = repository.find_all(None).await?;
+        let candidates: Vec<&Bookmark> = all_bookmarks.iter().collect();
+        return Ok(vec![find_bookmark_by_partial_id(&candidates, id)?.clone()]);
+    }
+
+    if !args.all && !args.pending && !filtered {
+        return Err(BookmarkError::InvalidId(
+            "Specify a bookmark ID, --all, --pending, or filters to choose what to refresh".to_string()
+        ));
+    }
+    let filters = SearchCommand::new(args.filters.clone()).resolve_filters(repository).await?;
+    let mut bookmarks = repository.find_all(Some(filters)).await?;
+    if args.pending {
+        bookmarks.retain(|bookmark| bookmark.metadata_pending);
+    }
+    Ok(bookmarks)
+}
+
+/// Whether any search filter was given, so an empty command line doesn't refresh everything
+pub(crate) fn has_filters(filters: &SearchArgs) -> bool {
+    filters.query.is_some()
+        || filters.tags.is_some()
+        || filters.status.is_some()
+        || filters.dead
+        || filters.priority.is_some()
+        || filters.since.is_some()
+        || filters.until.is_some()
+        || filters.published_since.is_some()
+        || filters.published_until.is_some()
+}
+
+/// Whether a title is just a stand-in for the URL, as left by `add --no-fetch` or imports
+fn is_placeholder_title(bookmark: &Bookmark) -> bool {
+    let title = bookmark.title.trim();
+    if title == bookmark.url {
+        return true;
+    }
+    Url::parse(&bookmark.url).ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .is_some_and(|host| title.eq_ignore_ascii_case(&host) || title.eq_ignore_ascii_case(host.trim_start_matches("www.")))
+}
+
+/// Copy freshly extracted metadata into a bookmark, returning what changed
+///
+/// Without `overwrite` only missing fields are filled in; a title counts as missing
+/// when it is just the URL or host name. Fields the page doesn't provide are never cleared.
+pub(crate) fn apply_metadata(bookmark: &mut Bookmark, metadata: &ExtractedMetadata, overwrite: bool) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+
+    if let Some(title) = metadata.title.as_ref().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        if bookmark.title != title && (overwrite || is_placeholder_title(bookmark)) {
+            changes.push(FieldChange::new("title", Some(bookmark.title.clone()), Some(title.to_string())));
+            bookmark.title = title.to_string();
+        }
+    }
+
+    let text_fields = [
+        ("author", &mut bookmark.author, &metadata.author),
+        ("description", &mut bookmark.description, &metadata.description),
+        ("site name", &mut bookmark.site_name, &metadata.site_name),
+        ("canonical URL", &mut bookmark.canonical_url, &metadata.canonical_url),
+        ("image", &mut bookmark.image_url, &metadata.image_url),
+        ("language", &mut bookmark.language, &metadata.language),
+        ("favicon", &mut bookmark.favicon_url, &metadata.favicon_url),
+    ];
+    for (name, current, extracted) in text_fields {
+        if extracted.is_some() && current != extracted && (overwrite || current.is_none()) {
+            changes.push(FieldChange::new(name, current.clone(), extracted.clone()));
+            *current = extracted.clone();
+        }
+    }
+
+    if metadata.publish_date.is_some() && bookmark.publish_date != metadata.publish_date
+        && (overwrite || bookmark.publish_date.is_none())
+    {
+        let format_date = |date: Option<chrono::DateTime<chrono::Utc>>| date.map(|date| date.format("%Y-%m-%d").to_string());
+        changes.push(FieldChange::new("publish date", format_date(bookmark.publish_date), format_date(metadata.publish_date)));
+        bookmark.publish_date = metadata.publish_date;
+    }
+
+    if !metadata.site_details.is_empty() && bookmark.site_details != metadata.site_details
+        && (overwrite || bookmark.site_details.is_empty())
+    {
+        let format_details = |details: &std::collections::BTreeMap<String, String>| {
+            (!details.is_empty()).then(|| {
+                details.iter().map(|(name, value)| format!("{}: {}", name, value)).collect::<Vec<_>>().join(", ")
+            })
+        };
+        changes.push(FieldChange::new("site details", format_details(&bookmark.site_details), format_details(&metadata.site_details)));
+        bookmark.site_details = metadata.site_details.clone();
+    }
+
+    changes
+}
+
+/// Apply rules to a bookmark, reporting the tags and priority they change like any other field
+fn apply_rules(bookmark: &mut Bookmark, rules: &RuleSet) -> Vec<FieldChange> {
+    let (tags, priority) = (bookmark.tags.clone(), bookmark.priority_rating);
+    rules.apply(bookmark);
+
+    let mut changes = Vec::new();
+    if bookmark.tags != tags {
+        let old = (!tags.is_empty()).then(|| tags.join(", "));
+        changes.push(FieldChange::new("tags", old, Some(bookmark.tags.join(", "))));
+    }
+    if bookmark.priority_rating != priority {
+        changes.push(FieldChange::new("priority", priority.map(|p| p.to_string()), bookmark.priority_rating.map(|p| p.to_string())));
+    }
+    changes
+}
+
+fn format_human_output(response: &RefreshResponse) -> String {
+    if response.results.is_empty() {
+        return "No bookmarks matched.\n".to_string();
+    }
+
+    let mut output = String::new();
+    for result in &response.results {
+        let short_id = &result.id[..8.min(result.id.len())];
+        if let Some(ref error) = result.error {
+            let retry = if result.pending { " (will retry when online)" } else { "" };
+            output.push_str(&format!("✗ [{}] {}\n    {}{}\n", short_id, result.title, error, retry));
+            continue;
+        }
+        if result.changes.is_empty() {
+            continue;
+        }
+        output.push_str(&format!("[{}] {}\n", short_id, result.title));
+        for change in &result.changes {
+            output.push_str(&format!(
+                "    {}: {} → {}\n",
+                change.field,
+                format_value(change.old.as_deref()),
+                format_value(change.new.as_deref())
+            ));
+        }
+    }
+
+    output.push_str(&format!(
+        "Refreshed {} bookmark(s): {} changed, {} failed\n",
+        response.results.len(), response.changed, response.failed
+    ));
+    if !response.overwrite && response.refreshed > response.changed {
+        output.push_str("Only missing fields were filled in; replace existing values with --overwrite\n");
+    }
+    output
+}
+
+fn format_value(value: Option<&str>) -> String {
+    match value {
+        None => "(none)".to_string(),
+        Some(value) if value.chars().count() > DIFF_VALUE_CHARS => {
+            format!("\"{}…\"", value.chars().take(DIFF_VALUE_CHARS).collect::<String>())
+        }
+        Some(value) => format!("\"{}\"", value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{Cli, Commands};
+    use crate::traits::repository::MockBookmarkRepository;
+    use crate::traits::metadata_extractor::MockMetadataExtractor;
+    use crate::types::Rule;
+    use clap::Parser;
+
+    fn parse_refresh_args(args: &[&str]) -> RefreshArgs {
+        let mut argv = vec!["automark", "refresh"];
+        argv.extend_from_slice(args);
+        match Cli::try_parse_from(argv).unwrap().command {
+            Some(Commands::Refresh(args)) => args,
+            _ => panic!("Expected refresh command"),
+        }
+    }
+
+    fn extractor(should_fail: bool) -> MockMetadataExtractor {
+        MockMetadataExtractor {
+            should_fail,
+            extracted_title: Some("The Rust Programming Language".to_string()),
+            extracted_author: Some("Steve Klabnik".to_string()),
+            extracted_content: None,
+        }
+    }
+
+    fn config() -> Config {
+        let mut config = Config::default();
+        config.metadata.retry_attempts = 0;
+        config
+    }
+
+    async fn create_test_repo() -> (MockBookmarkRepository, Vec<String>) {
+        let mut repo = MockBookmarkRepository::new();
+        let mut ids = Vec::new();
+        for (url, title, author, tags) in [
+            ("https://doc.rust-lang.org/book/", "doc.rust-lang.org", None, vec!["rust"]),
+            ("https://www.python.org/", "My Python Notes", Some("Me"), vec!["python"]),
+        ] {
+            let mut bookmark = Bookmark::new(url, title).unwrap()
+                .with_tags(tags.into_iter().map(String::from).collect());
+            bookmark.author = author.map(String::from);
+            ids.push(bookmark.id.clone());
+            repo.create(bookmark).await.unwrap();
+        }
+        (repo, ids)
+    }
+
+    #[tokio::test]
+    async fn test_refresh_fills_missing_fields() {
+        let (mut repo, ids) = create_test_repo().await;
+        let args = parse_refresh_args(&["--all"]);
+
+        handle_refresh_command_with_extractor_and_config(args, &mut repo, &extractor(false), &config(), OutputFormat::Json)
+            .await.unwrap();
+
+        // The host-name title is a placeholder, so it is replaced
+        let rust = repo.find_by_id(&ids[0]).await.unwrap();
+        assert_eq!(rust.title, "The Rust Programming Language");
+        assert_eq!(rust.author, Some("Steve Klabnik".to_string()));
+
+        // Titles and authors the user set are kept
+        let python = repo.find_by_id(&ids[1]).await.unwrap();
+        assert_eq!(python.title, "My Python Notes");
+        assert_eq!(python.author, Some("Me".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_applies_rules_to_new_metadata() {
+        let (mut repo, ids) = create_test_repo().await;
+        let mut config = config();
+        config.rules.push(Rule {
+            name: None,
+            query: "title:~/programming language$/".to_string(),
+            add_tags: vec!["books".to_string()],
+            priority: Some(3),
+        });
+
+        handle_refresh_command_with_extractor_and_config(parse_refresh_args(&["--all"]), &mut repo, &extractor(false), &config, OutputFormat::Human)
+            .await.unwrap();
+
+        // The rule fires on the title just fetched, not on the user's own title
+        let rust = repo.find_by_id(&ids[0]).await.unwrap();
+        assert_eq!(rust.tags, vec!["rust", "books"]);
+        assert_eq!(rust.priority_rating, Some(3));
+        let python = repo.find_by_id(&ids[1]).await.unwrap();
+        assert_eq!(python.tags, vec!["python"]);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_overwrite_by_id() {
+        let (mut repo, ids) = create_test_repo().await;
+        let args = parse_refresh_args(&[&ids[1][..8], "--overwrite"]);
+
+        handle_refresh_command_with_extractor_and_config(args, &mut repo, &extractor(false), &config(), OutputFormat::Human)
+            .await.unwrap();
+
+        let python = repo.find_by_id(&ids[1]).await.unwrap();
+        assert_eq!(python.title, "The Rust Programming Language");
+        assert_eq!(python.author, Some("Steve Klabnik".to_string()));
+        let rust = repo.find_by_id(&ids[0]).await.unwrap();
+        assert_eq!(rust.title, "doc.rust-lang.org");
+    }
+
+    #[tokio::test]
+    async fn test_refresh_by_filter_and_failures() {
+        let (mut repo, ids) = create_test_repo().await;
+        let args = parse_refresh_args(&["--tags", "rust"]);
+
+        // A failed fetch leaves the bookmark as it was
+        handle_refresh_command_with_extractor_and_config(args, &mut repo, &extractor(true), &config(), OutputFormat::Human)
+            .await.unwrap();
+        assert_eq!(repo.find_by_id(&ids[0]).await.unwrap().title, "doc.rust-lang.org");
+    }
+
+    #[tokio::test]
+    async fn test_refresh_requires_selection() {
+        let (mut repo, ids) = create_test_repo().await;
+
+        let result = handle_refresh_command_with_extractor_and_config(
+            parse_refresh_args(&[]), &mut repo, &extractor(false), &config(), OutputFormat::Json,
+        ).await;
+        assert!(matches!(result, Err(BookmarkError::InvalidId(_))));
+
+        let result = handle_refresh_command_with_extractor_and_config(
+            parse_refresh_args(&[&ids[0], "--tags", "rust"]), &mut repo, &extractor(false), &config(), OutputFormat::Json,
+        ).await;
+        assert!(matches!(result, Err(BookmarkError::InvalidId(_))));
+
+        assert!(Cli::try_parse_from(["automark", "refresh", "abc", "--all"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_pending_only() {
+        let (mut repo, ids) = create_test_repo().await;
+        let mut rust = repo.find_by_id(&ids[0]).await.unwrap();
+        rust.metadata_pending = true;
+        repo.update(rust).await.unwrap();
+
+        // Still offline: the marker stays so the next run tries again
+        handle_refresh_command_with_extractor_and_config(parse_refresh_args(&["--pending"]), &mut repo, &extractor(true), &config(), OutputFormat::Json)
+            .await.unwrap();
+        assert!(repo.find_by_id(&ids[0]).await.unwrap().metadata_pending);
+
+        handle_refresh_command_with_extractor_and_config(parse_refresh_args(&["--pending"]), &mut repo, &extractor(false), &config(), OutputFormat::Json)
+            .await.unwrap();
+        let rust = repo.find_by_id(&ids[0]).await.unwrap();
+        assert_eq!(rust.title, "The Rust Programming Language");
+        assert!(!rust.metadata_pending);
+        // Bookmarks that weren't pending are left alone
+        assert_eq!(repo.find_by_id(&ids[1]).await.unwrap().author, Some("Me".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_fill_pending_metadata_stops_when_offline() {
+        let (mut repo, ids) = create_test_repo().await;
+        for id in &ids {
+            let mut bookmark = repo.find_by_id(id).await.unwrap();
+            bookmark.metadata_pending = true;
+            repo.update(bookmark).await.unwrap();
+        }
+        let mut config = config();
+        config.metadata.refresh_concurrency = 1;
+
+        let results = fill_pending_metadata_with_extractor(&mut repo, &extractor(true), &config).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].pending);
+
+        let results = fill_pending_metadata_with_extractor(&mut repo, &extractor(false), &config).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(repo.find_all(None).await.unwrap().iter().all(|bookmark| !bookmark.metadata_pending));
+    }
+
+    #[test]
+    fn test_apply_metadata_diff() {
+        let mut bookmark = Bookmark::new("https://example.com/post", "https://example.com/post").unwrap();
+        bookmark.description = Some("Kept".to_string());
+        let metadata = ExtractedMetadata {
+            title: Some("Post".to_string()),
+            description: Some("Fresh".to_string()),
+            site_name: Some("Example".to_string()),
+            ..Default::default()
+        };
+
+        let changes = apply_metadata(&mut bookmark.clone(), &metadata, false);
+        assert_eq!(changes, vec![
+            FieldChange::new("title", Some("https://example.com/post".to_string()), Some("Post".to_string())),
+            FieldChange::new("site name", None, Some("Example".to_string())),
+        ]);
+
+        let changes = apply_metadata(&mut bookmark, &metadata, true);
+        assert_eq!(changes.len(), 3);
+        assert_eq!(bookmark.description, Some("Fresh".to_string()));
+        assert!(apply_metadata(&mut bookmark, &metadata, true).is_empty());
+    }
+
+    #[test]
+    fn test_format_human_output() {
+        let response = RefreshResponse {
+            refreshed: 2,
+            changed: 1,
+            failed: 1,
+            overwrite: false,
+            results: vec![
+                RefreshResult {
+                    id: "abcdef1234".to_string(),
+                    title: "Post".to_string(),
+                    url: "https://example.com/post".to_string(),
+                    changes: vec![
+                        FieldChange::new("title", Some("example.com".to_string()), Some("Post".to_string())),
+                        FieldChange::new("description", None, Some("x".repeat(80))),
+                    ],
+                    error: None,
+                    pending: false,
+                },
+                RefreshResult {
+                    id: "1234abcdef".to_string(),
+                    title: "Unchanged".to_string(),
+                    url: "https://example.com/same".to_string(),
+                    changes: vec![],
+                    error: None,
+                    pending: false,
+                },
+                RefreshResult {
+                    id: "99999999aa".to_string(),
+                    title: "Offline".to_string(),
+                    url: "https://offline.example.com/".to_string(),
+                    changes: vec![],
+                    error: Some("Request timed out".to_string()),
+                    pending: true,
+                },
+            ],
+        };
+
+        let output = format_human_output(&response);
+        assert!(output.contains("[abcdef12] Post\n    title: \"example.com\" → \"Post\"\n"));
+        assert!(output.contains(&format!("    description: (none) → \"{}…\"\n", "x".repeat(60))));
+        assert!(!output.contains("Unchanged"));
+        assert!(output.contains("✗ [99999999] Offline\n    Request timed out (will retry when online)\n"));
+        assert!(output.contains("Refreshed 3 bookmark(s): 1 changed, 1 failed\n"));
+        assert!(output.contains("--overwrite"));
+    }
+}
@@ -0,0 +1,124 @@
+use crate::commands::{OutputFormat, find_bookmark_by_partial_id, output};
+use crate::traits::BookmarkRepository;
+use crate::types::{Bookmark, BookmarkResult};
+use crate::adapters::SimilarityIndex;
+use clap::Args;
+use serde::{Serialize, Deserialize};
+
+/// Command-line arguments for related command
+#[derive(Args, Debug, Clone)]
+pub struct RelatedArgs {
+    /// ID of the bookmark to find related bookmarks for (can be partial ID)
+    pub id: String,
+
+    /// Number of related bookmarks to show
+    #[arg(short = 'n', long, default_value_t = 10)]
+    pub limit: usize,
+}
+
+/// JSON response data for related command
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RelatedResponse {
+    pub bookmark_id: String,
+    pub title: String,
+    pub related: Vec<RelatedBookmark>,
+}
+
+/// A bookmark similar to the requested one
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RelatedBookmark {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    /// Similarity from 0 to 1 over tags, domain and title/content words
+    pub score: f64,
+}
+
+pub async fn handle_related_command(
+    args: RelatedArgs,
+    repository: &dyn BookmarkRepository,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    let response = find_related(&args, repository).await?;
+    match format {
+        OutputFormat::Json => output::print_response(format, response)?,
+        OutputFormat::Human => print!("{}", format_related(&response)),
+    }
+    Ok(())
+}
+
+async fn find_related(args: &RelatedArgs, repository: &dyn BookmarkRepository) -> BookmarkResult<RelatedResponse> {
+    let index = SimilarityIndex::from_repository(repository).await?;
+    let bookmarks = repository.find_all(None).await?;
+    let candidates: Vec<&Bookmark> = bookmarks.iter().collect();
+    let bookmark = find_bookmark_by_partial_id(&candidates, &args.id)?;
+
+    Ok(RelatedResponse {
+        bookmark_id: bookmark.id.clone(),
+        title: bookmark.title.clone(),
+        related: index.related(&bookmark.id, args.limit).into_iter()
+            .map(|related| RelatedBookmark {
+                id: related.bookmark.id,
+                title: related.bookmark.title,
+                url: related.bookmark.url,
+                score: related.score,
+            })
+            .collect(),
+    })
+}
+
+fn format_related(response: &RelatedResponse) -> String {
+    if response.related.is_empty() {
+        return format!("Nothing else in the library is related to '{}'\n", response.title);
+    }
+
+    let mut output = format!("Related to '{}':\n", response.title);
+    for related in &response.related {
+        output.push_str(&format!(
+            "  {:.2}  [{}] {}\n        {}\n",
+            related.score,
+            &related.id[..8.min(related.id.len())],
+            related.title,
+            related.url
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::repository::MockBookmarkRepository;
+
+    #[tokio::test]
+    async fn test_find_related_by_partial_id() {
+        let mut repo = MockBookmarkRepository::new();
+        let tokio = repo.create(Bookmark::new("https://tokio.rs/tokio/tutorial", "Tokio tutorial: async Rust").unwrap()).await.unwrap();
+        repo.create(Bookmark::new("https://without.boats/blog/async-rust", "Why async Rust").unwrap()).await.unwrap();
+        repo.create(Bookmark::new("https://example.com/pasta", "Weeknight pasta recipes").unwrap()).await.unwrap();
+
+        let args = RelatedArgs { id: tokio.id[..8].to_string(), limit: 10 };
+        let response = find_related(&args, &repo).await.unwrap();
+        assert_eq!(response.bookmark_id, tokio.id);
+        assert_eq!(response.related.len(), 1);
+        assert_eq!(response.related[0].title, "Why async Rust");
+    }
+
+    #[test]
+    fn test_format_related() {
+        let response = RelatedResponse {
+            bookmark_id: "11111111-aaaa".to_string(),
+            title: "Tokio tutorial".to_string(),
+            related: vec![RelatedBookmark {
+                id: "22222222-bbbb".to_string(),
+                title: "Why async Rust".to_string(),
+                url: "https://without.boats/blog/async-rust".to_string(),
+                score: 0.4321,
+            }],
+        };
+        assert_eq!(
+            format_related(&response),
+            "Related to 'Tokio tutorial':\n  0.43  [22222222] Why async Rust\n        https://without.boats/blog/async-rust\n"
+        );
+    }
+}
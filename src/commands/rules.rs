@@ -0,0 +1,335 @@
+use crate::commands::{OutputFormat, output};
+use crate::commands::add::extract_metadata_with_config;
+use crate::commands::bulk::BulkChange;
+use crate::commands::refresh::{apply_metadata, has_filters};
+use crate::commands::search::{SearchArgs, SearchCommand};
+use crate::traits::{BookmarkRepository, MetadataExtractor};
+use crate::types::{AppliedRule, Bookmark, BookmarkBatch, BookmarkError, BookmarkResult, Config, RuleSet};
+use crate::adapters::{ExtractorRegistry, HttpCache};
+use clap::{Args, Subcommand};
+use serde::{Serialize, Deserialize};
+use url::Url;
+
+/// Command-line arguments for rules command
+#[derive(Args, Debug, Clone)]
+pub struct RulesArgs {
+    #[command(subcommand)]
+    pub command: RulesCommand,
+}
+
+/// Rules subcommands
+#[derive(Subcommand, Debug, Clone)]
+pub enum RulesCommand {
+    /// Apply the rules in the config to existing bookmarks
+    Apply(ApplyRulesArgs),
+    /// Show which rules fire for a URL
+    Test(TestRulesArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ApplyRulesArgs {
+    /// Apply to every bookmark
+    #[arg(long)]
+    pub all: bool,
+
+    /// Filters selecting the bookmarks to apply rules to (same as search)
+    #[command(flatten)]
+    pub filters: SearchArgs,
+
+    /// Show what would change without modifying anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct TestRulesArgs {
+    /// URL to test; a bookmark already saved with this URL is tested as it is
+    pub url: String,
+
+    /// Title to test title rules against instead of the page's
+    #[arg(short, long)]
+    pub title: Option<String>,
+
+    /// Don't fetch the page, so only rules on the URL and domain can fire
+    #[arg(long)]
+    pub no_fetch: bool,
+}
+
+/// JSON response data for rules apply command
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ApplyRulesResponse {
+    pub dry_run: bool,
+    pub matched_count: usize,
+    pub changed_count: usize,
+    pub changes: Vec<BulkChange>,
+}
+
+/// JSON response data for rules test command
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TestRulesResponse {
+    pub url: String,
+    pub title: String,
+    /// Whether the URL is already bookmarked, so its tags and priority were taken into account
+    pub bookmarked: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fetch_error: Option<String>,
+    pub rule_count: usize,
+    pub fired: Vec<AppliedRule>,
+}
+
+pub async fn handle_rules_command(
+    args: RulesArgs,
+    repository: &mut dyn BookmarkRepository,
+    config: &Config,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    let extractor = ExtractorRegistry::with_builtin_sites(&config.metadata, HttpCache::for_config(config))?;
+    handle_rules_command_with_extractor_and_config(args, repository, &extractor, config, format).await
+}
+
+pub async fn handle_rules_command_with_extractor_and_config(
+    args: RulesArgs,
+    repository: &mut dyn BookmarkRepository,
+    extractor: &dyn MetadataExtractor,
+    config: &Config,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    let rules = RuleSet::new(&config.rules)?;
+    if rules.is_empty() && format == OutputFormat::Human {
+        println!("No rules configured; add [[rules]] entries to the config file.");
+        return Ok(());
+    }
+
+    match args.command {
+        RulesCommand::Apply(args) => apply_rules(args, &rules, repository, format).await,
+        RulesCommand::Test(args) => test_rules(args, &rules, repository, extractor, config, format).await,
+    }
+}
+
+async fn apply_rules(
+    args: ApplyRulesArgs,
+    rules: &RuleSet,
+    repository: &mut dyn BookmarkRepository,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    if args.filters.save.is_some() || args.filters.list_saved || args.filters.delete_saved.is_some() {
+        return Err(BookmarkError::InvalidId(
+            "Saved searches are managed with 'automark search'; use @name to select one".to_string()
+        ));
+    }
+    if !args.all && !has_filters(&args.filters) {
+        return Err(BookmarkError::InvalidId(
+            "Specify --all or filters to choose which bookmarks to apply rules to".to_string()
+        ));
+    }
+
+    let filters = SearchCommand::new(args.filters.clone()).resolve_filters(repository).await?;
+    let targets = repository.find_all(Some(filters)).await?;
+    let mut batch = BookmarkBatch::default();
+    let mut changes = Vec::new();
+    for mut bookmark in targets.iter().cloned() {
+        let descriptions: Vec<String> = rules.apply(&mut bookmark).into_iter()
+            .flat_map(|applied| applied.changes)
+            .collect();
+        if !descriptions.is_empty() {
+            changes.push(BulkChange {
+                id: bookmark.id.clone(),
+                title: bookmark.title.clone(),
+                changes: descriptions,
+            });
+            batch.updates.push(bookmark);
+        }
+    }
+
+    let changed_count = changes.len();
+    if !args.dry_run && !batch.is_empty() {
+        repository.apply_batch(batch).await?;
+    }
+
+    let response = ApplyRulesResponse {
+        dry_run: args.dry_run,
+        matched_count: targets.len(),
+        changed_count,
+        changes,
+    };
+    match format {
+        OutputFormat::Json => output::print_response(format, response)?,
+        OutputFormat::Human => print!("{}", format_applied(&response)),
+    }
+    Ok(())
+}
+
+async fn test_rules(
+    args: TestRulesArgs,
+    rules: &RuleSet,
+    repository: &dyn BookmarkRepository,
+    extractor: &dyn MetadataExtractor,
+    config: &Config,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    let existing = repository.find_all(None).await?.into_iter().find(|bookmark| bookmark.url == args.url);
+    let bookmarked = existing.is_some();
+    let mut fetch_error = None;
+    let mut bookmark = match existing {
+        Some(bookmark) => bookmark,
+        None => {
+            let host = Url::parse(&args.url).ok().and_then(|url| url.host_str().map(str::to_string));
+            let mut bookmark = Bookmark::new(&args.url, host.as_deref().unwrap_or(&args.url))?;
+            if !args.no_fetch {
+                match extract_metadata_with_config(&args.url, extractor, config).await {
+                    Ok(metadata) => {
+                        apply_metadata(&mut bookmark, &metadata, true);
+                    }
+                    Err(e) => fetch_error = Some(e.to_string()),
+                }
+            }
+            bookmark
+        }
+    };
+    if let Some(title) = args.title {
+        bookmark.title = title;
+    }
+
+    let response = TestRulesResponse {
+        fired: rules.apply(&mut bookmark),
+        url: args.url,
+        title: bookmark.title,
+        bookmarked,
+        fetch_error,
+        rule_count: config.rules.len(),
+    };
+    match format {
+        OutputFormat::Json => output::print_response(format, response)?,
+        OutputFormat::Human => print!("{}", format_tested(&response)),
+    }
+    Ok(())
+}
+
+fn format_applied(response: &ApplyRulesResponse) -> String {
+    if response.matched_count == 0 {
+        return "No bookmarks matched.\n".to_string();
+    }
+
+    let mut output = if response.dry_run {
+        format!("Dry run: rules would change {} of {} bookmark(s):\n", response.changed_count, response.matched_count)
+    } else {
+        format!("Rules changed {} of {} bookmark(s):\n", response.changed_count, response.matched_count)
+    };
+    for change in &response.changes {
+        output.push_str(&format!(
+            "  [{}] {}: {}\n",
+            &change.id[..8.min(change.id.len())],
+            change.title,
+            change.changes.join(", ")
+        ));
+    }
+    output
+}
+
+fn format_tested(response: &TestRulesResponse) -> String {
+    let mut output = format!("{} ({})\n", response.title, response.url);
+    if let Some(ref error) = response.fetch_error {
+        output.push_str(&format!("  Couldn't fetch the page ({}); only the URL was tested\n", error));
+    }
+    if response.fired.is_empty() {
+        output.push_str(&format!("None of the {} rule(s) fire\n", response.rule_count));
+        return output;
+    }
+
+    for applied in &response.fired {
+        let changes = if applied.changes.is_empty() {
+            "no change".to_string()
+        } else {
+            applied.changes.join(", ")
+        };
+        output.push_str(&format!("  ✓ {}: {}\n", applied.rule, changes));
+    }
+    output.push_str(&format!("{} of {} rule(s) fire\n", response.fired.len(), response.rule_count));
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{Cli, Commands};
+    use crate::traits::repository::MockBookmarkRepository;
+    use crate::traits::metadata_extractor::MockMetadataExtractor;
+    use crate::types::Rule;
+    use clap::Parser;
+
+    fn parse_rules_args(args: &[&str]) -> RulesArgs {
+        let cli = Cli::try_parse_from([&["automark", "rules"], args].concat()).unwrap();
+        match cli.command {
+            Some(Commands::Rules(args)) => args,
+            _ => panic!("Expected rules command"),
+        }
+    }
+
+    fn config() -> Config {
+        let mut config = Config::default();
+        config.metadata.retry_attempts = 0;
+        config.rules = vec![
+            Rule { name: None, query: "domain:github.com".to_string(), add_tags: vec!["code".to_string()], priority: None },
+            Rule { name: Some("RFCs".to_string()), query: r"title:~/RFC \d+/".to_string(), add_tags: vec![], priority: Some(4) },
+        ];
+        config
+    }
+
+    fn extractor() -> MockMetadataExtractor {
+        MockMetadataExtractor {
+            should_fail: false,
+            extracted_title: Some("RFC 9110: HTTP Semantics".to_string()),
+            extracted_author: None,
+            extracted_content: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_apply_backfills_library() {
+        let mut repo = MockBookmarkRepository::new();
+        let tool = repo.create(Bookmark::new("https://github.com/tokio-rs/tokio", "Tokio").unwrap()).await.unwrap();
+        let spec = repo.create(Bookmark::new("https://www.rfc-editor.org/rfc/rfc9110", "RFC 9110").unwrap()).await.unwrap();
+        let other = repo.create(Bookmark::new("https://example.com", "Example").unwrap()).await.unwrap();
+
+        // A dry run changes nothing; --all is required
+        let config = config();
+        handle_rules_command_with_extractor_and_config(parse_rules_args(&["apply", "--all", "--dry-run"]), &mut repo, &extractor(), &config, OutputFormat::Json)
+            .await.unwrap();
+        assert!(repo.find_by_id(&tool.id).await.unwrap().tags.is_empty());
+        assert!(handle_rules_command_with_extractor_and_config(parse_rules_args(&["apply"]), &mut repo, &extractor(), &config, OutputFormat::Json)
+            .await.is_err());
+
+        handle_rules_command_with_extractor_and_config(parse_rules_args(&["apply", "--all"]), &mut repo, &extractor(), &config, OutputFormat::Human)
+            .await.unwrap();
+        assert_eq!(repo.find_by_id(&tool.id).await.unwrap().tags, vec!["code"]);
+        assert_eq!(repo.find_by_id(&spec.id).await.unwrap().priority_rating, Some(4));
+        let other = repo.find_by_id(&other.id).await.unwrap();
+        assert!(other.tags.is_empty() && other.priority_rating.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rules_test_leaves_repository_alone() {
+        let mut repo = MockBookmarkRepository::new();
+        let args = parse_rules_args(&["test", "https://github.com/httpwg/rfc9110"]);
+        handle_rules_command_with_extractor_and_config(args, &mut repo, &extractor(), &config(), OutputFormat::Json)
+            .await.unwrap();
+        assert!(repo.find_all(None).await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_format_tested() {
+        let response = TestRulesResponse {
+            url: "https://github.com/httpwg/rfc9110".to_string(),
+            title: "RFC 9110: HTTP Semantics".to_string(),
+            bookmarked: false,
+            fetch_error: None,
+            rule_count: 3,
+            fired: vec![
+                AppliedRule { rule: "domain:github.com".to_string(), changes: vec!["+tag code".to_string()] },
+                AppliedRule { rule: "RFCs".to_string(), changes: vec![] },
+            ],
+        };
+        assert_eq!(format_tested(&response), "RFC 9110: HTTP Semantics (https://github.com/httpwg/rfc9110)\n  \
+            ✓ domain:github.com: +tag code\n  ✓ RFCs: no change\n2 of 3 rule(s) fire\n");
+    }
+}
@@ -1,24 +1,45 @@
+use crate::adapters::text_analysis::snippet;
 use crate::commands::{CommandHandler, OutputFormat, output};
 use crate::traits::BookmarkRepository;
-use crate::types::{Bookmark, BookmarkResult, BookmarkError, BookmarkFilters, ReadingStatus, SortBy, SortDirection};
+use crate::types::{Bookmark, BookmarkResult, BookmarkError, BookmarkFilters, ReadingStatus, SavedSearch, SortBy, SortDirection};
 use clap::Args;
 use serde::{Serialize, Deserialize};
 use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// Maximum length of a captured-content snippet shown with search results
+const SNIPPET_CHARS: usize = 160;
 
 /// Command-line arguments for search command
 #[derive(Args, Debug, Clone)]
 pub struct SearchArgs {
-    /// Text query to search for in title, URL, author, and notes
+    /// Text or query (e.g. `tag:rust -status:completed priority>=4`) to search for, or @name to run a saved search
     pub query: Option<String>,
     
     /// Filter by tags (comma-separated for multiple tags, uses AND logic)
     #[arg(long, value_delimiter = ',')]
     pub tags: Option<Vec<String>>,
     
+    /// Let tag filters also match descendant tags (e.g. "work" matches "work/projects")
+    #[arg(long)]
+    pub include_descendants: bool,
+    
+    /// Match tags exactly, turning off --include-descendants saved with a search
+    #[arg(long, conflicts_with = "include_descendants")]
+    pub no_include_descendants: bool,
+    
     /// Filter by reading status
     #[arg(long, value_enum)]
     pub status: Option<ReadingStatus>,
     
+    /// Only bookmarks whose last 'automark check' found the link dead
+    #[arg(long)]
+    pub dead: bool,
+    
+    /// Include live links again, turning off --dead saved with a search
+    #[arg(long, conflicts_with = "dead")]
+    pub no_dead: bool,
+    
     /// Filter by priority range (format: "min-max" or "exact")
     #[arg(long)]
     pub priority: Option<String>,
@@ -39,13 +60,25 @@ pub struct SearchArgs {
     #[arg(long)]
     pub published_until: Option<String>,
     
-    /// Sort results by field
+    /// Sort results by field (text searches default to relevance)
     #[arg(long, value_enum)]
     pub sort_by: Option<SortBy>,
     
-    /// Sort direction
-    #[arg(long, value_enum, default_value = "descending")]
-    pub sort_order: SortDirection,
+    /// Sort direction (default: descending)
+    #[arg(long, value_enum)]
+    pub sort_order: Option<SortDirection>,
+    
+    /// Save these filters under a name to re-run later with @name
+    #[arg(long, value_name = "NAME")]
+    pub save: Option<String>,
+    
+    /// List saved searches
+    #[arg(long)]
+    pub list_saved: bool,
+    
+    /// Delete a saved search
+    #[arg(long, value_name = "NAME")]
+    pub delete_saved: Option<String>,
 }
 
 /// JSON response for search command
@@ -54,6 +87,9 @@ pub struct SearchResponse {
     pub results: Vec<Bookmark>,
     pub total_count: usize,
     pub query_summary: QuerySummary,
+    /// Excerpts of captured page text matching the query, keyed by bookmark ID
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub snippets: HashMap<String, String>,
 }
 
 /// Summary of the search query and filters applied
@@ -67,6 +103,20 @@ pub struct QuerySummary {
     pub sort_info: Option<String>,
 }
 
+/// JSON response for listing saved searches
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SavedSearchesResponse {
+    pub searches: Vec<SavedSearch>,
+    pub total_count: usize,
+}
+
+/// JSON response for saving or deleting a saved search
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SavedSearchChangeResponse {
+    pub name: String,
+    pub operation: String,
+}
+
 pub struct SearchCommand {
     args: SearchArgs,
 }
@@ -123,7 +173,7 @@ impl SearchCommand {
     }
     
     /// Build BookmarkFilters from command arguments
-    fn build_filters(&self) -> BookmarkResult<BookmarkFilters> {
+    pub(crate) fn build_filters(&self) -> BookmarkResult<BookmarkFilters> {
         let priority_range = if let Some(ref priority_str) = self.args.priority {
             Some(self.parse_priority_range(priority_str)?)
         } else {
@@ -154,8 +204,8 @@ impl SearchCommand {
             None
         };
         
-        Ok(BookmarkFilters {
-            text_query: self.args.query.clone(),
+        let filters = BookmarkFilters {
+            text_query: None,
             tags: self.args.tags.clone(),
             reading_status: self.args.status.clone(),
             priority_range,
@@ -164,8 +214,72 @@ impl SearchCommand {
             published_since,
             published_until,
             sort_by: self.args.sort_by.clone(),
-            sort_order: Some(self.args.sort_order.clone()),
-        })
+            sort_order: self.args.sort_order.clone(),
+            include_descendant_tags: self.args.include_descendants,
+            query: None,
+            dead_links: self.args.dead,
+        };
+
+        match self.args.query {
+            Some(ref query) if self.saved_search_name().is_none() => filters.with_search_input(query),
+            _ => Ok(filters),
+        }
+    }
+    
+    /// Whether --include-descendants or --no-include-descendants was given, and which
+    fn include_descendants(&self) -> Option<bool> {
+        flag_override(self.args.include_descendants, self.args.no_include_descendants)
+    }
+    
+    /// Whether --dead or --no-dead was given, and which
+    fn dead_links(&self) -> Option<bool> {
+        flag_override(self.args.dead, self.args.no_dead)
+    }
+    
+    /// Name of the saved search referenced by an @name query, if any
+    fn saved_search_name(&self) -> Option<&str> {
+        self.args.query.as_deref().and_then(|query| query.strip_prefix('@'))
+    }
+    
+    /// Build filters, starting from the referenced saved search when the query is @name
+    /// 
+    /// Filters given on the command line take precedence over the saved ones.
+    pub(crate) async fn resolve_filters(&self, repository: &dyn BookmarkRepository) -> BookmarkResult<BookmarkFilters> {
+        let filters = self.build_filters()?;
+        let name = match self.saved_search_name() {
+            Some(name) => name,
+            None => return Ok(filters),
+        };
+        
+        let saved = repository.find_saved_searches().await?
+            .into_iter()
+            .find(|search| search.name == name)
+            .ok_or_else(|| BookmarkError::NotFound(format!("saved search @{}", name)))?;
+        
+        Ok(merge_filters(saved.filters, filters, self.include_descendants(), self.dead_links()))
+    }
+    
+    async fn list_saved_searches(&self, repository: &dyn BookmarkRepository, format: OutputFormat) -> BookmarkResult<()> {
+        let searches = repository.find_saved_searches().await?;
+        match format {
+            OutputFormat::Json => {
+                let response = SavedSearchesResponse {
+                    total_count: searches.len(),
+                    searches,
+                };
+                output::print_response(format, response)?;
+            }
+            OutputFormat::Human => {
+                if searches.is_empty() {
+                    println!("No saved searches.");
+                } else {
+                    for search in &searches {
+                        println!("@{}: {}", search.name, search.filters.describe());
+                    }
+                }
+            }
+        }
+        Ok(())
     }
     
     /// Generate query summary for JSON output
@@ -178,7 +292,7 @@ impl SearchCommand {
         };
         
         let sort_info = match (&self.args.sort_by, &self.args.sort_order) {
-            (Some(sort_by), sort_order) => Some(format!("{:?} {:?}", sort_by, sort_order)),
+            (Some(sort_by), sort_order) => Some(format!("{:?} {:?}", sort_by, sort_order.as_ref().unwrap_or(&SortDirection::Descending))),
             (None, _) => None,
         };
         
@@ -192,8 +306,28 @@ impl SearchCommand {
         }
     }
     
+    /// Excerpts of captured page text that match the text query
+    async fn content_snippets(
+        &self,
+        repository: &dyn BookmarkRepository,
+        bookmarks: &[Bookmark],
+        text_query: Option<&str>,
+    ) -> BookmarkResult<HashMap<String, String>> {
+        let mut snippets = HashMap::new();
+        let Some(query) = text_query else { return Ok(snippets) };
+        
+        for bookmark in bookmarks {
+            if let Some(content) = repository.find_content(&bookmark.id).await? {
+                if let Some(excerpt) = snippet(&content, query, SNIPPET_CHARS) {
+                    snippets.insert(bookmark.id.clone(), excerpt);
+                }
+            }
+        }
+        Ok(snippets)
+    }
+    
     /// Format search results for human output
-    fn format_human_output(&self, bookmarks: &[Bookmark]) -> String {
+    fn format_human_output(&self, bookmarks: &[Bookmark], snippets: &HashMap<String, String>) -> String {
         if bookmarks.is_empty() {
             return "No bookmarks found matching your search criteria.".to_string();
         }
@@ -226,6 +360,14 @@ impl SearchCommand {
                 output.push_str(&format!("\n   Notes: {} note(s)", bookmark.notes.len()));
             }
             
+            if let Some(status) = bookmark.link_status.as_ref().filter(|status| status.is_dead()) {
+                output.push_str(&format!("\n   Dead link: {} (checked {})", status.describe(), status.checked_at.format("%Y-%m-%d")));
+            }
+            
+            if let Some(excerpt) = snippets.get(&bookmark.id) {
+                output.push_str(&format!("\n   Match: {}", excerpt));
+            }
+            
             output.push_str("\n\n");
         }
         
@@ -236,8 +378,36 @@ impl SearchCommand {
 #[async_trait::async_trait]
 impl CommandHandler for SearchCommand {
     async fn execute(&self, repository: &mut dyn BookmarkRepository, format: OutputFormat) -> BookmarkResult<()> {
-        let filters = self.build_filters()?;
+        if self.args.list_saved {
+            return self.list_saved_searches(repository, format).await;
+        }
+        
+        if let Some(ref name) = self.args.delete_saved {
+            let name = name.trim_start_matches('@');
+            repository.delete_saved_search(name).await?;
+            match format {
+                OutputFormat::Json => output::print_response(format, SavedSearchChangeResponse {
+                    name: name.to_string(),
+                    operation: "deleted".to_string(),
+                })?,
+                OutputFormat::Human => println!("Deleted saved search @{}", name),
+            }
+            return Ok(());
+        }
+        
+        let filters = self.resolve_filters(repository).await?;
+        
+        if let Some(ref name) = self.args.save {
+            let name = validate_saved_search_name(name)?;
+            repository.save_search(SavedSearch { name: name.clone(), filters: filters.clone() }).await?;
+            if format == OutputFormat::Human {
+                println!("Saved search @{}\n", name);
+            }
+        }
+        
+        let text_query = filters.text_query.clone();
         let bookmarks = repository.find_all(Some(filters)).await?;
+        let snippets = self.content_snippets(repository, &bookmarks, text_query.as_deref()).await?;
         
         match format {
             OutputFormat::Json => {
@@ -245,11 +415,12 @@ impl CommandHandler for SearchCommand {
                     total_count: bookmarks.len(),
                     query_summary: self.generate_query_summary(),
                     results: bookmarks,
+                    snippets,
                 };
                 output::print_response(format, response)?;
             }
             OutputFormat::Human => {
-                let formatted_output = self.format_human_output(&bookmarks);
+                let formatted_output = self.format_human_output(&bookmarks, &snippets);
                 print!("{}", formatted_output);
             }
         }
@@ -258,6 +429,53 @@ impl CommandHandler for SearchCommand {
     }
 }
 
+/// Check a saved search name, accepting an optional leading @
+pub(crate) fn validate_saved_search_name(name: &str) -> BookmarkResult<String> {
+    let name = name.trim().trim_start_matches('@');
+    if name.is_empty() || name.chars().any(char::is_whitespace) {
+        return Err(BookmarkError::InvalidId(
+            format!("Invalid saved search name '{}'. Names cannot be empty or contain spaces", name)
+        ));
+    }
+    Ok(name.to_string())
+}
+
+/// The value of an on/off flag pair, or None if neither was given
+fn flag_override(on: bool, off: bool) -> Option<bool> {
+    match (on, off) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    }
+}
+
+/// Overlay filters given on the command line onto a saved search
+///
+/// Each filter given on the command line replaces its saved value on its own;
+/// `include_descendants` and `dead_links` are None unless one of their flags was given.
+fn merge_filters(
+    saved: BookmarkFilters,
+    overrides: BookmarkFilters,
+    include_descendants: Option<bool>,
+    dead_links: Option<bool>,
+) -> BookmarkFilters {
+    BookmarkFilters {
+        text_query: overrides.text_query.or(saved.text_query),
+        tags: overrides.tags.or(saved.tags),
+        reading_status: overrides.reading_status.or(saved.reading_status),
+        priority_range: overrides.priority_range.or(saved.priority_range),
+        bookmarked_since: overrides.bookmarked_since.or(saved.bookmarked_since),
+        bookmarked_until: overrides.bookmarked_until.or(saved.bookmarked_until),
+        published_since: overrides.published_since.or(saved.published_since),
+        published_until: overrides.published_until.or(saved.published_until),
+        sort_by: overrides.sort_by.or(saved.sort_by),
+        sort_order: overrides.sort_order.or(saved.sort_order),
+        include_descendant_tags: include_descendants.unwrap_or(saved.include_descendant_tags),
+        query: overrides.query.or(saved.query),
+        dead_links: dead_links.unwrap_or(saved.dead_links),
+    }
+}
+
 pub async fn handle_search_command(
     args: SearchArgs,
     repository: &mut dyn BookmarkRepository,
@@ -287,13 +505,20 @@ mod tests {
             query: Some("rust".to_string()),
             tags: None,
             status: None,
+            dead: false,
+            no_dead: false,
             priority: None,
             since: None,
             until: None,
             published_since: None,
             published_until: None,
             sort_by: None,
-            sort_order: SortDirection::Descending,
+            sort_order: None,
+            include_descendants: false,
+            no_include_descendants: false,
+            save: None,
+            list_saved: false,
+            delete_saved: None,
         };
         
         let result = handle_search_command(args, &mut repo, OutputFormat::Human).await;
@@ -316,13 +541,20 @@ mod tests {
             query: None,
             tags: Some(vec!["rust".to_string()]),
             status: None,
+            dead: false,
+            no_dead: false,
             priority: None,
             since: None,
             until: None,
             published_since: None,
             published_until: None,
             sort_by: None,
-            sort_order: SortDirection::Descending,
+            sort_order: None,
+            include_descendants: false,
+            no_include_descendants: false,
+            save: None,
+            list_saved: false,
+            delete_saved: None,
         };
         
         let result = handle_search_command(args, &mut repo, OutputFormat::Human).await;
@@ -346,13 +578,20 @@ mod tests {
             query: None,
             tags: None,
             status: Some(ReadingStatus::Completed),
+            dead: false,
+            no_dead: false,
             priority: None,
             since: None,
             until: None,
             published_since: None,
             published_until: None,
             sort_by: None,
-            sort_order: SortDirection::Descending,
+            sort_order: None,
+            include_descendants: false,
+            no_include_descendants: false,
+            save: None,
+            list_saved: false,
+            delete_saved: None,
         };
         
         let result = handle_search_command(args, &mut repo, OutputFormat::Human).await;
@@ -365,13 +604,20 @@ mod tests {
             query: None,
             tags: None,
             status: None,
+            dead: false,
+            no_dead: false,
             priority: None,
             since: None,
             until: None,
             published_since: None,
             published_until: None,
             sort_by: None,
-            sort_order: SortDirection::Descending,
+            sort_order: None,
+            include_descendants: false,
+            no_include_descendants: false,
+            save: None,
+            list_saved: false,
+            delete_saved: None,
         };
         let command = SearchCommand::new(args);
         
@@ -396,13 +642,20 @@ mod tests {
             query: None,
             tags: None,
             status: None,
+            dead: false,
+            no_dead: false,
             priority: None,
             since: None,
             until: None,
             published_since: None,
             published_until: None,
             sort_by: None,
-            sort_order: SortDirection::Descending,
+            sort_order: None,
+            include_descendants: false,
+            no_include_descendants: false,
+            save: None,
+            list_saved: false,
+            delete_saved: None,
         };
         let command = SearchCommand::new(args);
         
@@ -426,13 +679,20 @@ mod tests {
             query: Some("rust".to_string()),
             tags: Some(vec!["programming".to_string()]),
             status: Some(ReadingStatus::Unread),
+            dead: false,
+            no_dead: false,
             priority: Some("3-5".to_string()),
             since: Some("01-01-2023".to_string()),
             until: Some("12-31-2023".to_string()),
             published_since: None,
             published_until: None,
             sort_by: Some(SortBy::Title),
-            sort_order: SortDirection::Ascending,
+            sort_order: Some(SortDirection::Ascending),
+            include_descendants: false,
+            no_include_descendants: false,
+            save: None,
+            list_saved: false,
+            delete_saved: None,
         };
         let command = SearchCommand::new(args);
         
@@ -454,17 +714,24 @@ mod tests {
             query: None,
             tags: None,
             status: None,
+            dead: false,
+            no_dead: false,
             priority: None,
             since: None,
             until: None,
             published_since: None,
             published_until: None,
             sort_by: None,
-            sort_order: SortDirection::Descending,
+            sort_order: None,
+            include_descendants: false,
+            no_include_descendants: false,
+            save: None,
+            list_saved: false,
+            delete_saved: None,
         };
         let command = SearchCommand::new(args);
         
-        let output = command.format_human_output(&[]);
+        let output = command.format_human_output(&[], &HashMap::new());
         assert!(output.contains("No bookmarks found"));
     }
 
@@ -474,18 +741,25 @@ mod tests {
             query: None,
             tags: None,
             status: None,
+            dead: false,
+            no_dead: false,
             priority: None,
             since: None,
             until: None,
             published_since: None,
             published_until: None,
             sort_by: None,
-            sort_order: SortDirection::Descending,
+            sort_order: None,
+            include_descendants: false,
+            no_include_descendants: false,
+            save: None,
+            list_saved: false,
+            delete_saved: None,
         };
         let command = SearchCommand::new(args);
         
         let bookmark = Bookmark::new("https://example.com", "Example Title").unwrap();
-        let output = command.format_human_output(&[bookmark]);
+        let output = command.format_human_output(&[bookmark], &HashMap::new());
         
         assert!(output.contains("Found 1 bookmark"));
         assert!(output.contains("Example Title"));
@@ -498,13 +772,20 @@ mod tests {
             query: Some("rust".to_string()),
             tags: Some(vec!["programming".to_string()]),
             status: Some(ReadingStatus::Unread),
+            dead: false,
+            no_dead: false,
             priority: Some("3-5".to_string()),
             since: Some("01-01-2023".to_string()),
             until: Some("12-31-2023".to_string()),
             published_since: None,
             published_until: None,
             sort_by: Some(SortBy::Title),
-            sort_order: SortDirection::Ascending,
+            sort_order: Some(SortDirection::Ascending),
+            include_descendants: false,
+            no_include_descendants: false,
+            save: None,
+            list_saved: false,
+            delete_saved: None,
         };
         let command = SearchCommand::new(args);
         
@@ -528,16 +809,253 @@ mod tests {
             query: Some("example".to_string()),
             tags: None,
             status: None,
+            dead: false,
+            no_dead: false,
             priority: None,
             since: None,
             until: None,
             published_since: None,
             published_until: None,
             sort_by: None,
-            sort_order: SortDirection::Descending,
+            sort_order: None,
+            include_descendants: false,
+            no_include_descendants: false,
+            save: None,
+            list_saved: false,
+            delete_saved: None,
         };
         
         let result = handle_search_command(args, &mut repo, OutputFormat::Json).await;
         assert!(result.is_ok());
     }
-}
\ No newline at end of file
+
+    fn search_args(query: Option<&str>) -> SearchArgs {
+        SearchArgs {
+            query: query.map(String::from),
+            tags: None,
+            include_descendants: false,
+            no_include_descendants: false,
+            status: None,
+            dead: false,
+            no_dead: false,
+            priority: None,
+            since: None,
+            until: None,
+            published_since: None,
+            published_until: None,
+            sort_by: None,
+            sort_order: None,
+            save: None,
+            list_saved: false,
+            delete_saved: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_save_and_run_saved_search() {
+        let mut repo = MockBookmarkRepository::new();
+        let rust = Bookmark::new("https://rust-lang.org", "Rust").unwrap()
+            .with_tags(vec!["rust".to_string()])
+            .with_priority(5).unwrap();
+        let python = Bookmark::new("https://python.org", "Python").unwrap()
+            .with_tags(vec!["python".to_string()]);
+        repo.create(rust).await.unwrap();
+        repo.create(python).await.unwrap();
+
+        let mut args = search_args(None);
+        args.tags = Some(vec!["rust".to_string()]);
+        args.priority = Some("4-5".to_string());
+        args.sort_by = Some(SortBy::Priority);
+        args.save = Some("@rust-todo".to_string());
+        handle_search_command(args, &mut repo, OutputFormat::Human).await.unwrap();
+
+        let searches = repo.find_saved_searches().await.unwrap();
+        assert_eq!(searches.len(), 1);
+        assert_eq!(searches[0].name, "rust-todo");
+        assert_eq!(searches[0].filters.priority_range, Some((4, 5)));
+
+        let command = SearchCommand::new(search_args(Some("@rust-todo")));
+        let filters = command.resolve_filters(&repo).await.unwrap();
+        assert_eq!(filters.text_query, None);
+        assert_eq!(filters.tags, Some(vec!["rust".to_string()]));
+        assert_eq!(filters.sort_by, Some(SortBy::Priority));
+        let results = repo.find_all(Some(filters)).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Rust");
+
+        // Command-line filters override the saved ones
+        let mut args = search_args(Some("@rust-todo"));
+        args.tags = Some(vec!["python".to_string()]);
+        let filters = SearchCommand::new(args).resolve_filters(&repo).await.unwrap();
+        assert_eq!(filters.tags, Some(vec!["python".to_string()]));
+        assert_eq!(filters.priority_range, Some((4, 5)));
+    }
+
+    #[tokio::test]
+    async fn test_saved_search_flags_override_individually() {
+        let mut repo = MockBookmarkRepository::new();
+        let mut args = search_args(None);
+        args.tags = Some(vec!["work".to_string()]);
+        args.include_descendants = true;
+        args.sort_by = Some(SortBy::Priority);
+        args.save = Some("work".to_string());
+        handle_search_command(args, &mut repo, OutputFormat::Human).await.unwrap();
+
+        // Without flags the saved search runs as saved
+        let filters = SearchCommand::new(search_args(Some("@work"))).resolve_filters(&repo).await.unwrap();
+        assert!(filters.include_descendant_tags);
+        assert_eq!(filters.sort_by, Some(SortBy::Priority));
+        assert_eq!(filters.sort_order, None);
+
+        // --sort-order applies without --sort-by, and descendant matching can be turned off
+        let mut args = search_args(Some("@work"));
+        args.sort_order = Some(SortDirection::Ascending);
+        args.no_include_descendants = true;
+        let filters = SearchCommand::new(args).resolve_filters(&repo).await.unwrap();
+        assert!(!filters.include_descendant_tags);
+        assert_eq!(filters.sort_by, Some(SortBy::Priority));
+        assert_eq!(filters.sort_order, Some(SortDirection::Ascending));
+        assert_eq!(filters.tags, Some(vec!["work".to_string()]));
+        assert!(!filters.dead_links);
+    }
+
+    #[tokio::test]
+    async fn test_saved_dead_link_search_can_be_widened() {
+        let mut repo = MockBookmarkRepository::new();
+        let mut args = search_args(None);
+        args.dead = true;
+        args.save = Some("broken".to_string());
+        handle_search_command(args, &mut repo, OutputFormat::Human).await.unwrap();
+
+        let filters = SearchCommand::new(search_args(Some("@broken"))).resolve_filters(&repo).await.unwrap();
+        assert!(filters.dead_links);
+
+        let mut args = search_args(Some("@broken"));
+        args.no_dead = true;
+        let filters = SearchCommand::new(args).resolve_filters(&repo).await.unwrap();
+        assert!(!filters.dead_links);
+    }
+
+    #[test]
+    fn test_descendant_flags_conflict() {
+        use crate::commands::Cli;
+        use clap::Parser;
+
+        assert!(Cli::try_parse_from(["automark", "search", "--tags", "work", "--no-include-descendants"]).is_ok());
+        assert!(Cli::try_parse_from(["automark", "search", "--include-descendants", "--no-include-descendants"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unknown_saved_search() {
+        let mut repo = MockBookmarkRepository::new();
+        let result = handle_search_command(search_args(Some("@missing")), &mut repo, OutputFormat::Human).await;
+        assert!(matches!(result, Err(BookmarkError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_list_and_delete_saved_searches() {
+        let mut repo = MockBookmarkRepository::new();
+        repo.save_search(SavedSearch { name: "all".to_string(), filters: BookmarkFilters::default() }).await.unwrap();
+
+        let mut args = search_args(None);
+        args.list_saved = true;
+        assert!(handle_search_command(args, &mut repo, OutputFormat::Json).await.is_ok());
+
+        let mut args = search_args(None);
+        args.delete_saved = Some("all".to_string());
+        handle_search_command(args, &mut repo, OutputFormat::Human).await.unwrap();
+        assert!(repo.find_saved_searches().await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_validate_saved_search_name() {
+        assert_eq!(validate_saved_search_name("@rust").unwrap(), "rust");
+        assert_eq!(validate_saved_search_name("daily-reading").unwrap(), "daily-reading");
+        assert!(validate_saved_search_name("").is_err());
+        assert!(validate_saved_search_name("@").is_err());
+        assert!(validate_saved_search_name("two words").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_with_query_language() {
+        let mut repo = MockBookmarkRepository::new();
+        let async_rust = Bookmark::new("https://tokio.rs", "Tokio").unwrap()
+            .with_tags(vec!["rust".to_string(), "async".to_string()])
+            .with_priority(4).unwrap();
+        let mut finished = Bookmark::new("https://async.rs", "Async Std").unwrap()
+            .with_tags(vec!["rust".to_string(), "async".to_string()])
+            .with_priority(5).unwrap();
+        finished.reading_status = ReadingStatus::Completed;
+        let python = Bookmark::new("https://python.org", "Python").unwrap()
+            .with_tags(vec!["python".to_string()]);
+        repo.create(async_rust).await.unwrap();
+        repo.create(finished).await.unwrap();
+        repo.create(python).await.unwrap();
+
+        let command = SearchCommand::new(search_args(Some("tag:rust (tag:async OR tag:tokio) -status:completed priority>=4")));
+        let filters = command.build_filters().unwrap();
+        assert!(filters.text_query.is_none());
+        assert!(filters.query.is_some());
+
+        let results = repo.find_all(Some(filters)).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Tokio");
+
+        // Plain words keep the substring search
+        let filters = SearchCommand::new(search_args(Some("python"))).build_filters().unwrap();
+        assert_eq!(filters.text_query, Some("python".to_string()));
+        assert!(filters.query.is_none());
+
+        let command = SearchCommand::new(search_args(Some("tag:rust)")));
+        assert!(matches!(command.build_filters(), Err(BookmarkError::InvalidQuery(_))));
+    }
+
+    #[tokio::test]
+    async fn test_search_snippets_from_captured_content() {
+        let mut repo = MockBookmarkRepository::new();
+        let article = Bookmark::new("https://example.com/post", "Weekly Notes").unwrap();
+        let article_id = article.id.clone();
+        repo.create(article).await.unwrap();
+        repo.create(Bookmark::new("https://example.com/other", "Borrow Checker").unwrap()).await.unwrap();
+        repo.save_content(&article_id, "This week we looked at how the borrow checker handles closures.").await.unwrap();
+        
+        let command = SearchCommand::new(search_args(Some("borrow")));
+        let filters = command.build_filters().unwrap();
+        let bookmarks = repo.find_all(Some(filters)).await.unwrap();
+        assert_eq!(bookmarks.len(), 2);
+        
+        let snippets = command.content_snippets(&repo, &bookmarks, Some("borrow")).await.unwrap();
+        assert_eq!(snippets.len(), 1);
+        assert_eq!(snippets[&article_id], "This week we looked at how the borrow checker handles closures.");
+        
+        let output = command.format_human_output(&bookmarks, &snippets);
+        assert!(output.contains("   Match: This week we looked at how the borrow checker"));
+    }
+
+    #[tokio::test]
+    async fn test_text_search_ranks_prefix_and_fuzzy_matches() {
+        let mut repo = MockBookmarkRepository::new();
+        let notes = Bookmark::new("https://example.com/notes", "Weekly Notes").unwrap();
+        let notes_id = notes.id.clone();
+        repo.create(notes).await.unwrap();
+        repo.create(Bookmark::new("https://example.com/rust", "Programming with the Borrow Checker").unwrap()).await.unwrap();
+        repo.create(Bookmark::new("https://example.com/python", "Python Cookbook").unwrap()).await.unwrap();
+        repo.save_content(&notes_id, "Some thoughts on programming and on the borrow checker.").await.unwrap();
+
+        let titles = |bookmarks: Vec<Bookmark>| bookmarks.into_iter().map(|b| b.title).collect::<Vec<_>>();
+
+        // A word prefix matches, and title matches outrank page text
+        let filters = SearchCommand::new(search_args(Some("progr"))).build_filters().unwrap();
+        let results = repo.find_all(Some(filters)).await.unwrap();
+        assert_eq!(titles(results), vec!["Programming with the Borrow Checker", "Weekly Notes"]);
+
+        // A misspelled word still finds both
+        let filters = SearchCommand::new(search_args(Some("borow checker"))).build_filters().unwrap();
+        let results = repo.find_all(Some(filters)).await.unwrap();
+        assert_eq!(titles(results), vec!["Programming with the Borrow Checker", "Weekly Notes"]);
+
+        // Every word must match
+        let filters = SearchCommand::new(search_args(Some("python borrow"))).build_filters().unwrap();
+        assert!(repo.find_all(Some(filters)).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,159 @@
+use crate::commands::{OutputFormat, output};
+use crate::traits::BookmarkRepository;
+use crate::types::BookmarkResult;
+use crate::adapters::LibraryStats;
+use chrono::Utc;
+use clap::Args;
+
+/// Command-line arguments for stats command
+#[derive(Args, Debug, Clone)]
+pub struct StatsArgs {
+    /// Number of tags, domains and oldest unread bookmarks to list
+    #[arg(long, default_value_t = 10)]
+    pub top: usize,
+
+    /// Number of recent weeks to show activity for
+    #[arg(long, default_value_t = 8)]
+    pub weeks: usize,
+}
+
+pub async fn handle_stats_command(
+    args: StatsArgs,
+    repository: &dyn BookmarkRepository,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    let bookmarks = repository.find_all(None).await?;
+    let stats = LibraryStats::compute(&bookmarks, Utc::now(), args.top, args.weeks);
+    match format {
+        OutputFormat::Json => output::print_response(format, stats)?,
+        OutputFormat::Human => print!("{}", format_stats(&stats)),
+    }
+    Ok(())
+}
+
+/// Lay out rows under a header, the first column left-aligned and the rest right-aligned
+fn format_table(header: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = header.iter().map(|cell| cell.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |cells: Vec<&str>| {
+        let line: Vec<String> = cells.iter().zip(&widths).enumerate()
+            .map(|(column, (cell, width))| {
+                if column == 0 {
+                    format!("{:<width$}", cell, width = width)
+                } else {
+                    format!("{:>width$}", cell, width = width)
+                }
+            })
+            .collect();
+        format!("  {}\n", line.join("  ").trim_end())
+    };
+
+    let mut output = format_row(header.to_vec());
+    for row in rows {
+        output.push_str(&format_row(row.iter().map(String::as_str).collect()));
+    }
+    output
+}
+
+fn format_stats(stats: &LibraryStats) -> String {
+    if stats.total == 0 {
+        return "No bookmarks yet.\n".to_string();
+    }
+
+    let percent = |count: usize| format!("{:.0}%", count as f64 * 100.0 / stats.total as f64);
+    let mut output = format!(
+        "{} bookmarks, {:.0}% completed",
+        stats.total,
+        stats.completion_rate * 100.0
+    );
+    match stats.average_days_to_complete {
+        Some(days) => output.push_str(&format!(", {:.1} days from bookmark to completion on average\n", days)),
+        None => output.push('\n'),
+    }
+
+    output.push_str("\nStatus\n");
+    let statuses = [("Unread", stats.unread), ("Reading", stats.reading), ("Completed", stats.completed)];
+    output.push_str(&format_table(&["Status", "Count", "Share"], &statuses.iter()
+        .map(|(status, count)| vec![status.to_string(), count.to_string(), percent(*count)])
+        .collect::<Vec<_>>()));
+
+    output.push_str("\nPriority\n");
+    output.push_str(&format_table(&["Priority", "Count"], &stats.by_priority.iter()
+        .map(|entry| vec![
+            entry.priority.map(|priority| priority.to_string()).unwrap_or_else(|| "unrated".to_string()),
+            entry.count.to_string(),
+        ])
+        .collect::<Vec<_>>()));
+
+    if !stats.top_tags.is_empty() {
+        output.push_str("\nTop tags\n");
+        output.push_str(&format_table(&["Tag", "Count"], &stats.top_tags.iter()
+            .map(|tag| vec![tag.name.clone(), tag.count.to_string()])
+            .collect::<Vec<_>>()));
+    }
+
+    output.push_str("\nTop domains\n");
+    output.push_str(&format_table(&["Domain", "Count"], &stats.top_domains.iter()
+        .map(|domain| vec![domain.name.clone(), domain.count.to_string()])
+        .collect::<Vec<_>>()));
+
+    if !stats.weeks.is_empty() {
+        output.push_str("\nWeekly activity\n");
+        output.push_str(&format_table(&["Week of", "Added", "Completed"], &stats.weeks.iter()
+            .map(|week| vec![week.week_start.to_string(), week.added.to_string(), week.completed.to_string()])
+            .collect::<Vec<_>>()));
+    }
+
+    if !stats.oldest_unread.is_empty() {
+        output.push_str("\nOldest unread\n");
+        output.push_str(&format_table(&["Bookmark", "Saved", "Days"], &stats.oldest_unread.iter()
+            .map(|bookmark| vec![
+                format!("[{}] {}", &bookmark.id[..8.min(bookmark.id.len())], bookmark.title),
+                bookmark.bookmarked_date.format("%Y-%m-%d").to_string(),
+                bookmark.age_days.to_string(),
+            ])
+            .collect::<Vec<_>>()));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Bookmark, ReadingStatus};
+
+    #[test]
+    fn test_format_table_aligns_columns() {
+        let rows = vec![
+            vec!["rust".to_string(), "12".to_string()],
+            vec!["databases".to_string(), "3".to_string()],
+        ];
+        assert_eq!(
+            format_table(&["Tag", "Count"], &rows),
+            "  Tag        Count\n  rust          12\n  databases      3\n"
+        );
+    }
+
+    #[test]
+    fn test_format_stats() {
+        let mut done = Bookmark::new("https://example.com/a", "Done").unwrap().with_tags(vec!["rust".to_string()]);
+        done.set_reading_status(ReadingStatus::Completed);
+        let waiting = Bookmark::new("https://example.com/b", "Waiting").unwrap();
+        let stats = LibraryStats::compute(&[done, waiting], Utc::now(), 5, 2);
+
+        let output = format_stats(&stats);
+        assert!(output.starts_with("2 bookmarks, 50% completed, 0.0 days from bookmark to completion on average\n"));
+        assert!(output.contains("  Completed      1    50%\n"));
+        assert!(output.contains("  unrated       2\n"));
+        assert!(output.contains("  example.com      2\n"));
+        assert!(output.contains("] Waiting"));
+
+        let empty = LibraryStats::compute(&[], Utc::now(), 5, 2);
+        assert_eq!(format_stats(&empty), "No bookmarks yet.\n");
+    }
+}
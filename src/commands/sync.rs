@@ -90,6 +90,7 @@ pub enum ProtocolMessage {
     },
 }
 
+#[allow(clippy::collapsible_match)]
 pub async fn handle_sync_command(
     args: &SyncArgs,
     repository: &mut dyn BookmarkRepository,
@@ -0,0 +1,372 @@
+use crate::commands::{CommandHandler, OutputFormat, output};
+use crate::traits::BookmarkRepository;
+use crate::types::{Bookmark, BookmarkBatch, BookmarkError, BookmarkResult, TagNode, build_tag_tree, flatten_tag_tree, normalize_tag};
+use clap::{Args, Subcommand};
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+
+/// Command-line arguments for tags command
+#[derive(Args, Debug, Clone)]
+pub struct TagsArgs {
+    #[command(subcommand)]
+    pub command: Option<TagsCommand>,
+    /// Show tags as a hierarchy (levels are separated by '/')
+    #[arg(long)]
+    pub tree: bool,
+}
+
+/// Tags subcommands
+#[derive(Subcommand, Debug, Clone)]
+pub enum TagsCommand {
+    /// List all tags with usage counts (default)
+    List,
+    /// Rename a tag and its descendants on every bookmark
+    Rename {
+        /// Existing tag name
+        old: String,
+        /// New tag name
+        new: String,
+    },
+    /// Merge several tags (and their descendants) into one
+    Merge {
+        /// Tags to merge
+        #[arg(required = true)]
+        tags: Vec<String>,
+        /// Tag the merged tags are replaced with
+        #[arg(long)]
+        into: String,
+    },
+    /// Remove a tag from every bookmark (descendant tags are kept)
+    Delete {
+        /// Tag to remove
+        tag: String,
+    },
+}
+
+/// A tag and the number of bookmarks using it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TagCount {
+    pub name: String,
+    pub count: usize,
+}
+
+/// JSON response data for tags list command
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TagsListResponse {
+    pub tags: Vec<TagCount>,
+    pub total_count: usize,
+}
+
+/// JSON response data for tags list command with --tree
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TagTreeResponse {
+    pub tree: Vec<TagNode>,
+}
+
+/// JSON response data for tag rename, merge and delete commands
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TagChangeResponse {
+    pub operation: String,
+    pub source_tags: Vec<String>,
+    pub target_tag: Option<String>,
+    pub updated_count: usize,
+    pub updated_ids: Vec<String>,
+}
+
+pub struct TagsCommandHandler {
+    args: TagsArgs,
+}
+
+impl TagsCommandHandler {
+    pub fn new(args: TagsArgs) -> Self {
+        Self { args }
+    }
+
+    async fn list(&self, repository: &mut dyn BookmarkRepository, format: OutputFormat) -> BookmarkResult<()> {
+        let bookmarks = repository.find_all(None).await?;
+
+        if self.args.tree {
+            let tree = build_tag_tree(&bookmarks);
+            match format {
+                OutputFormat::Json => output::print_response(format, TagTreeResponse { tree })?,
+                OutputFormat::Human => print!("{}", format_tag_tree(&tree)),
+            }
+            return Ok(());
+        }
+
+        let tags = count_tags(&bookmarks);
+
+        match format {
+            OutputFormat::Json => {
+                let response = TagsListResponse {
+                    total_count: tags.len(),
+                    tags,
+                };
+                output::print_response(format, response)?;
+            }
+            OutputFormat::Human => {
+                print!("{}", format_tag_list(&tags));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replace `sources` with `target` (or remove them) across the repository in one batch
+    async fn replace(
+        &self,
+        operation: &str,
+        sources: Vec<String>,
+        target: Option<String>,
+        repository: &mut dyn BookmarkRepository,
+        format: OutputFormat,
+    ) -> BookmarkResult<()> {
+        let sources: Vec<String> = sources.iter().map(|tag| normalize_tag(tag)).collect();
+        let target = target.map(|tag| normalize_tag(&tag));
+
+        if sources.iter().any(|tag| tag.is_empty()) || target.as_ref().is_some_and(|tag| tag.is_empty()) {
+            return Err(BookmarkError::InvalidId("Tag names cannot be empty".to_string()));
+        }
+
+        let mut batch = BookmarkBatch::default();
+        for mut bookmark in repository.find_all(None).await? {
+            let changed = match target {
+                Some(ref target) => bookmark.rename_tags(&sources, target),
+                None => bookmark.remove_tags(&sources),
+            };
+            if changed {
+                batch.updates.push(bookmark);
+            }
+        }
+
+        let updated_ids: Vec<String> = batch.updates.iter().map(|bookmark| bookmark.id.clone()).collect();
+        if !batch.is_empty() {
+            repository.apply_batch(batch).await?;
+        }
+
+        match format {
+            OutputFormat::Json => {
+                let response = TagChangeResponse {
+                    operation: operation.to_string(),
+                    source_tags: sources,
+                    target_tag: target,
+                    updated_count: updated_ids.len(),
+                    updated_ids,
+                };
+                output::print_response(format, response)?;
+            }
+            OutputFormat::Human => {
+                let sources = sources.join(", ");
+                if updated_ids.is_empty() {
+                    println!("No bookmarks tagged {}.", sources);
+                } else {
+                    match target {
+                        Some(target) => println!("Replaced {} with {} on {} bookmark(s).", sources, target, updated_ids.len()),
+                        None => println!("Removed {} from {} bookmark(s).", sources, updated_ids.len()),
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandHandler for TagsCommandHandler {
+    async fn execute(&self, repository: &mut dyn BookmarkRepository, format: OutputFormat) -> BookmarkResult<()> {
+        match self.args.command.clone().unwrap_or(TagsCommand::List) {
+            TagsCommand::List => self.list(repository, format).await,
+            TagsCommand::Rename { old, new } => {
+                self.replace("rename", vec![old], Some(new), repository, format).await
+            }
+            TagsCommand::Merge { tags, into } => {
+                self.replace("merge", tags, Some(into), repository, format).await
+            }
+            TagsCommand::Delete { tag } => {
+                self.replace("delete", vec![tag], None, repository, format).await
+            }
+        }
+    }
+}
+
+/// Count tag usage across bookmarks, most used first then alphabetically
+pub fn count_tags(bookmarks: &[Bookmark]) -> Vec<TagCount> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for bookmark in bookmarks {
+        let mut seen = Vec::new();
+        for tag in bookmark.tags.iter().map(|tag| normalize_tag(tag)) {
+            if !tag.is_empty() && !seen.contains(&tag) {
+                *counts.entry(tag.clone()).or_insert(0) += 1;
+                seen.push(tag);
+            }
+        }
+    }
+
+    let mut tags: Vec<TagCount> = counts
+        .into_iter()
+        .map(|(name, count)| TagCount { name, count })
+        .collect();
+    tags.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    tags
+}
+
+fn format_tag_list(tags: &[TagCount]) -> String {
+    if tags.is_empty() {
+        return "No tags found.\n".to_string();
+    }
+
+    let width = tags.iter().map(|tag| tag.name.len()).max().unwrap_or(0);
+    let mut output = format!("{} tag(s):\n\n", tags.len());
+    for tag in tags {
+        output.push_str(&format!("  {:<width$}  {}\n", tag.name, tag.count, width = width));
+    }
+    output
+}
+
+fn format_tag_tree(tree: &[TagNode]) -> String {
+    if tree.is_empty() {
+        return "No tags found.\n".to_string();
+    }
+
+    let mut output = String::new();
+    for entry in flatten_tag_tree(tree) {
+        output.push_str(&format!("{}{} ({})\n", "  ".repeat(entry.depth), entry.name, entry.total_count));
+    }
+    output
+}
+
+pub async fn handle_tags_command(
+    args: TagsArgs,
+    repository: &mut dyn BookmarkRepository,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    let command = TagsCommandHandler::new(args);
+    command.execute(repository, format).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::repository::MockBookmarkRepository;
+
+    async fn create_test_repo() -> (MockBookmarkRepository, Vec<String>) {
+        let mut repo = MockBookmarkRepository::new();
+        let mut ids = Vec::new();
+        for (url, tags) in [
+            ("https://example1.com", vec!["rust", "js"]),
+            ("https://example2.com", vec!["javascript", "web"]),
+            ("https://example3.com", vec!["Rust"]),
+        ] {
+            let bookmark = Bookmark::new(url, "Example").unwrap()
+                .with_tags(tags.into_iter().map(String::from).collect());
+            ids.push(bookmark.id.clone());
+            repo.create(bookmark).await.unwrap();
+        }
+        (repo, ids)
+    }
+
+    fn args(command: TagsCommand) -> TagsArgs {
+        TagsArgs { command: Some(command), tree: false }
+    }
+
+    #[tokio::test]
+    async fn test_count_tags() {
+        let (repo, _) = create_test_repo().await;
+        let tags = count_tags(&repo.find_all(None).await.unwrap());
+
+        assert_eq!(tags[0], TagCount { name: "rust".to_string(), count: 2 });
+        assert_eq!(tags.len(), 4);
+        assert_eq!(tags[1].name, "javascript");
+    }
+
+    #[tokio::test]
+    async fn test_tags_list_default() {
+        let (mut repo, _) = create_test_repo().await;
+        let result = handle_tags_command(TagsArgs { command: None, tree: false }, &mut repo, OutputFormat::Json).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_tags_rename() {
+        let (mut repo, ids) = create_test_repo().await;
+        let command = TagsCommand::Rename { old: "rust".to_string(), new: "Rust-Lang".to_string() };
+        handle_tags_command(args(command), &mut repo, OutputFormat::Human).await.unwrap();
+
+        assert_eq!(repo.find_by_id(&ids[0]).await.unwrap().tags, vec!["rust-lang", "js"]);
+        assert_eq!(repo.find_by_id(&ids[2]).await.unwrap().tags, vec!["rust-lang"]);
+        assert_eq!(repo.find_by_id(&ids[1]).await.unwrap().tags, vec!["javascript", "web"]);
+    }
+
+    #[tokio::test]
+    async fn test_tags_merge() {
+        let (mut repo, ids) = create_test_repo().await;
+        let command = TagsCommand::Merge {
+            tags: vec!["js".to_string(), "javascript".to_string()],
+            into: "javascript".to_string(),
+        };
+        handle_tags_command(args(command), &mut repo, OutputFormat::Json).await.unwrap();
+
+        assert_eq!(repo.find_by_id(&ids[0]).await.unwrap().tags, vec!["rust", "javascript"]);
+        assert_eq!(repo.find_by_id(&ids[1]).await.unwrap().tags, vec!["javascript", "web"]);
+    }
+
+    #[tokio::test]
+    async fn test_tags_delete() {
+        let (mut repo, ids) = create_test_repo().await;
+        handle_tags_command(args(TagsCommand::Delete { tag: "rust".to_string() }), &mut repo, OutputFormat::Human)
+            .await.unwrap();
+
+        assert_eq!(repo.find_by_id(&ids[0]).await.unwrap().tags, vec!["js"]);
+        assert!(repo.find_by_id(&ids[2]).await.unwrap().tags.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tags_rename_to_empty_name() {
+        let (mut repo, _) = create_test_repo().await;
+        let command = TagsCommand::Rename { old: "rust".to_string(), new: "  ".to_string() };
+        let result = handle_tags_command(args(command), &mut repo, OutputFormat::Human).await;
+        assert!(matches!(result, Err(BookmarkError::InvalidId(_))));
+    }
+
+    #[tokio::test]
+    async fn test_tags_rename_moves_descendants() {
+        let mut repo = MockBookmarkRepository::new();
+        let bookmark = Bookmark::new("https://example.com", "Example").unwrap()
+            .with_tags(vec!["work/projects".to_string(), "work".to_string()]);
+        let bookmark_id = bookmark.id.clone();
+        repo.create(bookmark).await.unwrap();
+
+        let command = TagsCommand::Rename { old: "work".to_string(), new: "job".to_string() };
+        handle_tags_command(args(command), &mut repo, OutputFormat::Human).await.unwrap();
+        assert_eq!(repo.find_by_id(&bookmark_id).await.unwrap().tags, vec!["job/projects", "job"]);
+    }
+
+    #[tokio::test]
+    async fn test_tags_tree() {
+        let (mut repo, _) = create_test_repo().await;
+        let result = handle_tags_command(TagsArgs { command: None, tree: true }, &mut repo, OutputFormat::Json).await;
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_format_tag_tree() {
+        let bookmark = Bookmark::new("https://example.com", "Example").unwrap()
+            .with_tags(vec!["work/projects".to_string(), "rust".to_string()]);
+        let output = format_tag_tree(&build_tag_tree(&[bookmark]));
+        assert_eq!(output, "rust (1)\nwork (1)\n  projects (1)\n");
+        assert_eq!(format_tag_tree(&[]), "No tags found.\n");
+    }
+
+    #[test]
+    fn test_format_tag_list() {
+        let tags = vec![
+            TagCount { name: "rust".to_string(), count: 3 },
+            TagCount { name: "javascript".to_string(), count: 1 },
+        ];
+        let output = format_tag_list(&tags);
+        assert!(output.starts_with("2 tag(s):"));
+        assert!(output.contains("  rust        3\n"));
+        assert_eq!(format_tag_list(&[]), "No tags found.\n");
+    }
+}
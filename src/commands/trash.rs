@@ -0,0 +1,282 @@
+use crate::commands::{CommandHandler, OutputFormat, find_bookmark_by_partial_id, output};
+use crate::traits::BookmarkRepository;
+use crate::types::{Bookmark, BookmarkError, BookmarkResult, TrashedBookmark};
+use chrono::{Duration, Utc};
+use clap::{Args, Subcommand};
+use serde::{Serialize, Deserialize};
+
+/// Command-line arguments for trash command
+#[derive(Args, Debug, Clone)]
+pub struct TrashArgs {
+    #[command(subcommand)]
+    pub command: TrashCommand,
+}
+
+/// Trash subcommands
+#[derive(Subcommand, Debug, Clone)]
+pub enum TrashCommand {
+    /// List bookmarks in the trash
+    List,
+    /// Restore a bookmark from the trash (can be partial ID)
+    Restore {
+        /// ID of the trashed bookmark to restore
+        id: String,
+    },
+    /// Permanently delete bookmarks from the trash
+    Empty {
+        /// Only remove bookmarks deleted longer ago than this (e.g. 30d, 12h, 2w)
+        #[arg(long)]
+        older_than: Option<String>,
+    },
+}
+
+/// JSON response data for trash list command
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TrashListResponse {
+    pub items: Vec<TrashedBookmark>,
+    pub total_count: usize,
+}
+
+/// JSON response data for trash restore command
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RestoreResponse {
+    pub restored_bookmark: Bookmark,
+    pub operation_status: String,
+}
+
+/// JSON response data for trash empty command
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EmptyTrashResponse {
+    pub purged_ids: Vec<String>,
+    pub purged_count: usize,
+}
+
+pub struct TrashCommandHandler {
+    args: TrashArgs,
+}
+
+impl TrashCommandHandler {
+    pub fn new(args: TrashArgs) -> Self {
+        Self { args }
+    }
+
+    async fn list(&self, repository: &mut dyn BookmarkRepository, format: OutputFormat) -> BookmarkResult<()> {
+        let mut items = repository.find_trash().await?;
+        items.sort_by_key(|item| std::cmp::Reverse(item.deleted_at));
+
+        match format {
+            OutputFormat::Json => {
+                let response = TrashListResponse {
+                    total_count: items.len(),
+                    items,
+                };
+                output::print_response(format, response)?;
+            }
+            OutputFormat::Human => {
+                print!("{}", format_trash_list(&items));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn restore(&self, id: &str, repository: &mut dyn BookmarkRepository, format: OutputFormat) -> BookmarkResult<()> {
+        let items = repository.find_trash().await?;
+        let candidates: Vec<&Bookmark> = items.iter().map(|item| &item.bookmark).collect();
+        let bookmark_id = find_bookmark_by_partial_id(&candidates, id)?.id.clone();
+
+        let restored = repository.restore(&bookmark_id).await?;
+
+        match format {
+            OutputFormat::Json => {
+                let response = RestoreResponse {
+                    restored_bookmark: restored,
+                    operation_status: "success".to_string(),
+                };
+                output::print_response(format, response)?;
+            }
+            OutputFormat::Human => {
+                println!("Restored bookmark: {}\n  URL: {}\n  ID: {}", restored.title, restored.url, restored.id);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn empty(&self, older_than: Option<&str>, repository: &mut dyn BookmarkRepository, format: OutputFormat) -> BookmarkResult<()> {
+        let cutoff = match older_than {
+            Some(age) => Some(Utc::now() - parse_age(age)?),
+            None => None,
+        };
+
+        let purged = repository.purge_trash(cutoff).await?;
+
+        match format {
+            OutputFormat::Json => {
+                let response = EmptyTrashResponse {
+                    purged_count: purged.len(),
+                    purged_ids: purged.into_iter().map(|item| item.bookmark.id).collect(),
+                };
+                output::print_response(format, response)?;
+            }
+            OutputFormat::Human => {
+                if purged.is_empty() {
+                    println!("No bookmarks removed from trash.");
+                } else {
+                    println!("Permanently removed {} bookmark(s) from trash.", purged.len());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandHandler for TrashCommandHandler {
+    async fn execute(&self, repository: &mut dyn BookmarkRepository, format: OutputFormat) -> BookmarkResult<()> {
+        match &self.args.command {
+            TrashCommand::List => self.list(repository, format).await,
+            TrashCommand::Restore { id } => self.restore(id, repository, format).await,
+            TrashCommand::Empty { older_than } => self.empty(older_than.as_deref(), repository, format).await,
+        }
+    }
+}
+
+/// Parse an age such as "30d", "12h", "2w" or "45m" into a duration
+pub fn parse_age(age: &str) -> BookmarkResult<Duration> {
+    let age = age.trim();
+    let invalid = || BookmarkError::InvalidId(
+        format!("Invalid age '{}'. Use a number followed by m, h, d or w (e.g., 30d)", age)
+    );
+
+    let unit = age.chars().last().ok_or_else(invalid)?;
+    let amount: i64 = age[..age.len() - unit.len_utf8()].parse().map_err(|_| invalid())?;
+    if amount < 0 {
+        return Err(invalid());
+    }
+
+    match unit {
+        'm' => Ok(Duration::minutes(amount)),
+        'h' => Ok(Duration::hours(amount)),
+        'd' => Ok(Duration::days(amount)),
+        'w' => Ok(Duration::weeks(amount)),
+        _ => Err(invalid()),
+    }
+}
+
+fn format_trash_list(items: &[TrashedBookmark]) -> String {
+    if items.is_empty() {
+        return "Trash is empty.\n".to_string();
+    }
+
+    let mut output = format!("Trash contains {} bookmark(s):\n\n", items.len());
+    for (index, item) in items.iter().enumerate() {
+        let bookmark = &item.bookmark;
+        output.push_str(&format!(
+            "{}. [{}] {}\n  URL: {}\n  Deleted: {}\n",
+            index + 1,
+            &bookmark.id[..8.min(bookmark.id.len())],
+            bookmark.title,
+            bookmark.url,
+            item.deleted_at.format("%Y-%m-%d %H:%M:%S UTC")
+        ));
+        if index < items.len() - 1 {
+            output.push('\n');
+        }
+    }
+    output
+}
+
+pub async fn handle_trash_command(
+    args: TrashArgs,
+    repository: &mut dyn BookmarkRepository,
+    format: OutputFormat,
+) -> BookmarkResult<()> {
+    let command = TrashCommandHandler::new(args);
+    command.execute(repository, format).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::repository::MockBookmarkRepository;
+
+    async fn repo_with_trashed_bookmark() -> (MockBookmarkRepository, String) {
+        let mut repo = MockBookmarkRepository::new();
+        let mut bookmark = Bookmark::new("https://example.com", "Example Site").unwrap();
+        bookmark.id = "abcdef1234567890".to_string();
+        repo.create(bookmark).await.unwrap();
+        repo.delete("abcdef1234567890").await.unwrap();
+        (repo, "abcdef1234567890".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_trash_list() {
+        let (mut repo, _) = repo_with_trashed_bookmark().await;
+
+        let args = TrashArgs { command: TrashCommand::List };
+        assert!(handle_trash_command(args.clone(), &mut repo, OutputFormat::Human).await.is_ok());
+        assert!(handle_trash_command(args, &mut repo, OutputFormat::Json).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_trash_restore_with_partial_id() {
+        let (mut repo, bookmark_id) = repo_with_trashed_bookmark().await;
+
+        let args = TrashArgs { command: TrashCommand::Restore { id: "abcdef12".to_string() } };
+        handle_trash_command(args, &mut repo, OutputFormat::Human).await.unwrap();
+
+        assert_eq!(repo.find_by_id(&bookmark_id).await.unwrap().title, "Example Site");
+        assert!(repo.find_trash().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_trash_restore_nonexistent() {
+        let (mut repo, _) = repo_with_trashed_bookmark().await;
+
+        let args = TrashArgs { command: TrashCommand::Restore { id: "zzz".to_string() } };
+        let result = handle_trash_command(args, &mut repo, OutputFormat::Human).await;
+        assert!(matches!(result, Err(BookmarkError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_trash_empty_older_than_keeps_recent() {
+        let (mut repo, _) = repo_with_trashed_bookmark().await;
+
+        let args = TrashArgs { command: TrashCommand::Empty { older_than: Some("30d".to_string()) } };
+        handle_trash_command(args, &mut repo, OutputFormat::Human).await.unwrap();
+        assert_eq!(repo.find_trash().await.unwrap().len(), 1);
+
+        let args = TrashArgs { command: TrashCommand::Empty { older_than: None } };
+        handle_trash_command(args, &mut repo, OutputFormat::Json).await.unwrap();
+        assert!(repo.find_trash().await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("30d").unwrap(), Duration::days(30));
+        assert_eq!(parse_age("12h").unwrap(), Duration::hours(12));
+        assert_eq!(parse_age("2w").unwrap(), Duration::weeks(2));
+        assert_eq!(parse_age("45m").unwrap(), Duration::minutes(45));
+
+        assert!(parse_age("").is_err());
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("30").is_err());
+        assert!(parse_age("30y").is_err());
+        assert!(parse_age("-5d").is_err());
+    }
+
+    #[test]
+    fn test_format_trash_list() {
+        let bookmark = Bookmark::new("https://example.com", "Example Site").unwrap();
+        let items = vec![TrashedBookmark { bookmark, deleted_at: Utc::now() }];
+
+        let output = format_trash_list(&items);
+        assert!(output.starts_with("Trash contains 1 bookmark(s):"));
+        assert!(output.contains("Example Site"));
+        assert!(output.contains("  Deleted: "));
+
+        assert_eq!(format_trash_list(&[]), "Trash is empty.\n");
+    }
+}
@@ -31,6 +31,7 @@ mod tests {
     use super::*;
 
     #[tokio::test]
+    #[allow(clippy::assertions_on_constants)]
     async fn test_tui_args_creation() {
         let _args = TuiArgs {};
         
@@ -6,7 +6,7 @@ mod tui;
 
 use std::process;
 use clap::Parser;
-use commands::{Cli, Commands, OutputFormat, handle_add_command, handle_list_command, handle_delete_command, handle_search_command, handle_sync_command, handle_tui_command, auto_sync, output};
+use commands::{Cli, Commands, OutputFormat, handle_add_command, handle_list_command, handle_delete_command, handle_search_command, handle_sync_command, handle_trash_command, handle_bulk_command, handle_tags_command, handle_archive_command, handle_open_command, handle_check_command, handle_refresh_command, handle_cache_command, handle_rules_command, handle_related_command, handle_clusters_command, handle_dedupe_command, handle_start_command, handle_done_command, handle_next_command, handle_queue_command, handle_stats_command, handle_tui_command, fill_pending_metadata, auto_sync, output};
 use adapters::{AutomergeBookmarkRepository, FileStorageManager};
 use types::{BookmarkError, ConfigError};
 
@@ -31,6 +31,8 @@ fn handle_bookmark_error(error: BookmarkError, format: OutputFormat) -> ! {
         BookmarkError::InvalidId(_) => 3,
         BookmarkError::MetadataExtraction(_) => 4,
         BookmarkError::SyncError(_) => 5,
+        BookmarkError::InvalidQuery(_) => 2,
+        BookmarkError::ArchiveError(_) => 4,
         BookmarkError::TerminalError(_) => 6,
     };
     process::exit(exit_code);
@@ -75,6 +77,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     };
     
+    // Commands that go online first fill in metadata for bookmarks added offline;
+    // refresh fetches metadata itself, so it is left to decide which bookmarks to fetch
+    let goes_online = match &cli.command {
+        Some(Commands::Add(args)) => !args.no_fetch && !args.async_fetch,
+        Some(Commands::Sync(_) | Commands::Archive(_) | Commands::Check(_)) => true,
+        _ => false,
+    };
+    if goes_online {
+        fill_pending_metadata(&mut repository, &config, format).await;
+    }
+    
     // Execute commands
     let result = match &cli.command {
         Some(Commands::Add(args)) => {
@@ -95,11 +108,114 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             result
         }
         Some(Commands::Search(args)) => {
-            handle_search_command(args.clone(), &mut repository, format).await
+            let modifies = args.save.is_some() || args.delete_saved.is_some();
+            let result = handle_search_command(args.clone(), &mut repository, format).await;
+            if result.is_ok() && modifies {
+                auto_sync::auto_sync_if_enabled(&mut repository, &config, format).await?;
+            }
+            result
         }
         Some(Commands::Sync(args)) => {
             handle_sync_command(args, &mut repository, &config, format).await
         }
+        Some(Commands::Trash(args)) => {
+            let modifies = !matches!(args.command, commands::trash::TrashCommand::List);
+            let result = handle_trash_command(args.clone(), &mut repository, format).await;
+            if result.is_ok() && modifies {
+                auto_sync::auto_sync_if_enabled(&mut repository, &config, format).await?;
+            }
+            result
+        }
+        Some(Commands::Bulk(args)) => {
+            let result = handle_bulk_command(args.clone(), &mut repository, &config, format).await;
+            if result.is_ok() && !args.dry_run {
+                auto_sync::auto_sync_if_enabled(&mut repository, &config, format).await?;
+            }
+            result
+        }
+        Some(Commands::Tags(args)) => {
+            let modifies = !matches!(args.command, None | Some(commands::tags::TagsCommand::List));
+            let result = handle_tags_command(args.clone(), &mut repository, format).await;
+            if result.is_ok() && modifies {
+                auto_sync::auto_sync_if_enabled(&mut repository, &config, format).await?;
+            }
+            result
+        }
+        Some(Commands::Archive(args)) => {
+            let result = handle_archive_command(args.clone(), &mut repository, &config, format).await;
+            if result.is_ok() {
+                auto_sync::auto_sync_if_enabled(&mut repository, &config, format).await?;
+            }
+            result
+        }
+        Some(Commands::Open(args)) => {
+            handle_open_command(args.clone(), &mut repository, format).await
+        }
+        Some(Commands::Check(args)) => {
+            let result = handle_check_command(args.clone(), &mut repository, &config, format).await;
+            if result.is_ok() {
+                auto_sync::auto_sync_if_enabled(&mut repository, &config, format).await?;
+            }
+            result
+        }
+        Some(Commands::Refresh(args)) => {
+            let result = handle_refresh_command(args.clone(), &mut repository, &config, format).await;
+            if result.is_ok() {
+                auto_sync::auto_sync_if_enabled(&mut repository, &config, format).await?;
+            }
+            result
+        }
+        Some(Commands::Cache(args)) => {
+            handle_cache_command(args.clone(), &config, format).await
+        }
+        Some(Commands::Rules(args)) => {
+            let modifies = matches!(args.command, commands::rules::RulesCommand::Apply(ref apply) if !apply.dry_run);
+            let result = handle_rules_command(args.clone(), &mut repository, &config, format).await;
+            if result.is_ok() && modifies {
+                auto_sync::auto_sync_if_enabled(&mut repository, &config, format).await?;
+            }
+            result
+        }
+        Some(Commands::Related(args)) => {
+            handle_related_command(args.clone(), &repository, format).await
+        }
+        Some(Commands::Clusters(args)) => {
+            handle_clusters_command(args.clone(), &repository, format).await
+        }
+        Some(Commands::Start(args)) => {
+            let result = handle_start_command(args.clone(), &mut repository, format).await;
+            if result.is_ok() {
+                auto_sync::auto_sync_if_enabled(&mut repository, &config, format).await?;
+            }
+            result
+        }
+        Some(Commands::Done(args)) => {
+            let result = handle_done_command(args.clone(), &mut repository, format).await;
+            if result.is_ok() {
+                auto_sync::auto_sync_if_enabled(&mut repository, &config, format).await?;
+            }
+            result
+        }
+        Some(Commands::Next(args)) => {
+            let result = handle_next_command(args.clone(), &mut repository, format).await;
+            if result.is_ok() && args.start {
+                auto_sync::auto_sync_if_enabled(&mut repository, &config, format).await?;
+            }
+            result
+        }
+        Some(Commands::Queue(args)) => {
+            handle_queue_command(args.clone(), &repository, format).await
+        }
+        Some(Commands::Stats(args)) => {
+            handle_stats_command(args.clone(), &repository, format).await
+        }
+        Some(Commands::Dedupe(args)) => {
+            let result = handle_dedupe_command(args.clone(), &mut repository, format).await;
+            if result.is_ok() && !args.dry_run {
+                auto_sync::auto_sync_if_enabled(&mut repository, &config, format).await?;
+            }
+            result
+        }
         None => {
             // Default to TUI when no command is provided
             let tui_args = commands::tui::TuiArgs {};
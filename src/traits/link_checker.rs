@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+use std::time::Duration;
+use crate::types::LinkStatus;
+
+#[async_trait]
+pub trait LinkChecker: Send + Sync {
+    /// Probe a URL, following redirects, and report where it ended up
+    ///
+    /// Network failures are reported in the returned status rather than as errors.
+    async fn check_link(&self, url: &str, timeout: Duration) -> LinkStatus;
+}
+
+#[cfg(test)]
+pub struct MockLinkChecker {
+    /// Canned results by URL; anything else answers 200
+    pub results: std::collections::HashMap<String, LinkStatus>,
+}
+
+#[cfg(test)]
+#[async_trait]
+impl LinkChecker for MockLinkChecker {
+    async fn check_link(&self, url: &str, _timeout: Duration) -> LinkStatus {
+        self.results.get(url).cloned().unwrap_or_else(|| LinkStatus {
+            checked_at: chrono::Utc::now(),
+            status_code: Some(200),
+            redirected_to: None,
+            permanent_redirect: false,
+            error: None,
+        })
+    }
+}
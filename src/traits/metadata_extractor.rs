@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 use std::time::Duration;
+use url::Url;
 use crate::types::{ExtractedMetadata, ExtractorError};
 
 #[async_trait]
@@ -7,11 +8,17 @@ pub trait MetadataExtractor: Send + Sync {
     async fn extract_metadata(&self, url: &str, timeout: Duration) -> Result<ExtractedMetadata, ExtractorError>;
 }
 
+/// An extractor that understands one site and is only used for the URLs it recognizes
+pub trait SiteExtractor: MetadataExtractor {
+    fn matches(&self, url: &Url) -> bool;
+}
+
 #[cfg(test)]
 pub struct MockMetadataExtractor {
     pub should_fail: bool,
     pub extracted_title: Option<String>,
     pub extracted_author: Option<String>,
+    pub extracted_content: Option<String>,
 }
 
 #[cfg(test)]
@@ -30,6 +37,8 @@ impl MetadataExtractor for MockMetadataExtractor {
             title: self.extracted_title.clone(),
             author: self.extracted_author.clone(),
             publish_date: None,
+            content: self.extracted_content.clone(),
+            ..Default::default()
         })
     }
 }
\ No newline at end of file
@@ -1,7 +1,11 @@
 pub mod repository;
 pub mod metadata_extractor;
+pub mod page_archiver;
+pub mod link_checker;
 
-pub use metadata_extractor::MetadataExtractor;
+pub use metadata_extractor::{MetadataExtractor, SiteExtractor};
+pub use page_archiver::PageArchiver;
+pub use link_checker::LinkChecker;
 
 
-pub use repository::BookmarkRepository;
\ No newline at end of file
+pub use repository::BookmarkRepository;
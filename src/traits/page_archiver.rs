@@ -0,0 +1,26 @@
+use async_trait::async_trait;
+use std::time::Duration;
+use crate::types::ExtractorError;
+
+#[async_trait]
+pub trait PageArchiver: Send + Sync {
+    /// Download a page and return it as a single self-contained HTML document
+    async fn archive_page(&self, url: &str, timeout: Duration) -> Result<String, ExtractorError>;
+}
+
+#[cfg(test)]
+pub struct MockPageArchiver {
+    pub should_fail: bool,
+    pub html: String,
+}
+
+#[cfg(test)]
+#[async_trait]
+impl PageArchiver for MockPageArchiver {
+    async fn archive_page(&self, _url: &str, _timeout: Duration) -> Result<String, ExtractorError> {
+        if self.should_fail {
+            return Err(ExtractorError::NetworkError("Mock network error".to_string()));
+        }
+        Ok(self.html.clone())
+    }
+}
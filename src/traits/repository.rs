@@ -1,8 +1,12 @@
 #![allow(dead_code)]
-use crate::types::{Bookmark, BookmarkResult, BookmarkFilters};
+use crate::types::{Bookmark, BookmarkBatch, BookmarkResult, BookmarkFilters, SavedSearch, TrashedBookmark};
 #[cfg(test)]
-use crate::types::BookmarkError;
+use crate::adapters::search_index::{boost_phrases, SearchIndex};
+#[cfg(test)]
+use crate::types::{BookmarkError, SortBy, SortDirection, normalize_tag, tag_matches};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::path::PathBuf;
 
 /// Repository trait for managing bookmarks with CRDT support
 /// 
@@ -63,13 +67,114 @@ pub trait BookmarkRepository: Send + Sync {
     
     /// Delete a bookmark by ID
     /// 
+    /// The bookmark is moved to the trash rather than removed outright,
+    /// so it can be brought back with `restore` until the trash is emptied.
+    /// 
     /// # Arguments
     /// * `id` - The ID of the bookmark to delete
     /// 
     /// # CRDT Behavior
-    /// Uses tombstone markers to ensure deletion propagates across replicas
+    /// Copies the bookmark into the trash map with a `deleted_at` timestamp and
+    /// removes it from the bookmarks map in the same change
     async fn delete(&mut self, id: &str) -> BookmarkResult<()>;
     
+    /// List all bookmarks currently in the trash
+    /// 
+    /// # Returns
+    /// Vector of trashed bookmarks with their deletion timestamps
+    /// 
+    /// # CRDT Behavior
+    /// Reads current state without modifying the CRDT document
+    async fn find_trash(&self) -> BookmarkResult<Vec<TrashedBookmark>>;
+    
+    /// Restore a bookmark from the trash
+    /// 
+    /// # Arguments
+    /// * `id` - The ID of the trashed bookmark to restore
+    /// 
+    /// # Returns
+    /// The restored bookmark, NotFound error if it is not in the trash
+    /// 
+    /// # CRDT Behavior
+    /// Moves the bookmark back into the bookmarks map in a single change
+    async fn restore(&mut self, id: &str) -> BookmarkResult<Bookmark>;
+    
+    /// Permanently remove bookmarks from the trash
+    /// 
+    /// # Arguments
+    /// * `deleted_before` - Only purge bookmarks deleted before this time (None purges everything)
+    /// 
+    /// # Returns
+    /// The bookmarks that were permanently removed
+    /// 
+    /// # CRDT Behavior
+    /// Removes the trash entries; this cannot be undone
+    async fn purge_trash(&mut self, deleted_before: Option<DateTime<Utc>>) -> BookmarkResult<Vec<TrashedBookmark>>;
+    
+    /// Apply a batch of updates and deletes as a single change
+    /// 
+    /// Every bookmark in the batch must exist; if any is missing nothing is
+    /// modified and NotFound is returned.
+    /// 
+    /// # Arguments
+    /// * `batch` - Bookmarks to update and IDs to move to the trash
+    /// 
+    /// # CRDT Behavior
+    /// All modifications are recorded in one commit and persisted with one save
+    async fn apply_batch(&mut self, batch: BookmarkBatch) -> BookmarkResult<()>;
+    
+    /// Save a named search, replacing any existing search with the same name
+    /// 
+    /// # Arguments
+    /// * `search` - The name and filters to store
+    /// 
+    /// # CRDT Behavior
+    /// Stored in the document so saved searches sync across devices;
+    /// concurrent saves of the same name use last-writer-wins semantics
+    async fn save_search(&mut self, search: SavedSearch) -> BookmarkResult<()>;
+    
+    /// List all saved searches, sorted by name
+    async fn find_saved_searches(&self) -> BookmarkResult<Vec<SavedSearch>>;
+    
+    /// Delete a saved search by name
+    /// 
+    /// # Returns
+    /// NotFound error if no search with that name exists
+    async fn delete_saved_search(&mut self, name: &str) -> BookmarkResult<()>;
+    
+    /// Store the captured page text for a bookmark, replacing any previous capture
+    /// 
+    /// # Arguments
+    /// * `bookmark_id` - ID of the bookmark the text belongs to
+    /// * `content` - Readable text of the page
+    /// 
+    /// # Returns
+    /// NotFound error if the bookmark does not exist
+    /// 
+    /// # CRDT Behavior
+    /// Kept in a local sidecar store rather than the CRDT document, so it does not sync
+    async fn save_content(&mut self, bookmark_id: &str, content: &str) -> BookmarkResult<()>;
+    
+    /// Get the captured page text for a bookmark, if any
+    async fn find_content(&self, bookmark_id: &str) -> BookmarkResult<Option<String>>;
+    
+    /// Store a self-contained HTML snapshot of a bookmark's page, replacing any previous one
+    /// 
+    /// # Arguments
+    /// * `bookmark_id` - ID of the bookmark the snapshot belongs to
+    /// * `html` - Page with its stylesheets, scripts and images inlined
+    /// 
+    /// # Returns
+    /// The bookmark with `archive_path` and `archived_at` recorded, or NotFound
+    /// 
+    /// # CRDT Behavior
+    /// The snapshot is kept in a local sidecar store and does not sync; only its path and
+    /// timestamp are recorded in the document
+    async fn save_archive(&mut self, bookmark_id: &str, html: &str) -> BookmarkResult<Bookmark>;
+    
+    /// Get the location of a bookmark's archived copy on this device, if there is one
+    async fn find_archive(&self, bookmark_id: &str) -> BookmarkResult<Option<PathBuf>>;
+    
     /// Search bookmarks by text content
     /// 
     /// Searches across title, URL, author, and note content.
@@ -140,6 +245,11 @@ pub trait BookmarkRepository: Send + Sync {
 #[cfg(test)]
 pub struct MockBookmarkRepository {
     bookmarks: std::collections::HashMap<String, Bookmark>,
+    trash: std::collections::HashMap<String, TrashedBookmark>,
+    saved_searches: std::collections::HashMap<String, SavedSearch>,
+    contents: std::collections::HashMap<String, String>,
+    /// Archived pages are written to real files so callers can read them back
+    archive_dir: Option<tempfile::TempDir>,
 }
 
 #[cfg(test)]
@@ -147,27 +257,50 @@ impl MockBookmarkRepository {
     pub fn new() -> Self {
         Self {
             bookmarks: std::collections::HashMap::new(),
+            trash: std::collections::HashMap::new(),
+            saved_searches: std::collections::HashMap::new(),
+            contents: std::collections::HashMap::new(),
+            archive_dir: None,
         }
     }
     
     fn apply_filters(&self, mut bookmarks: Vec<Bookmark>, filters: &BookmarkFilters) -> Vec<Bookmark> {
-        // Apply text query filter
+        // Apply text query filter through a search index, as the real repository does
+        let mut relevance = std::collections::HashMap::new();
         if let Some(ref query) = filters.text_query {
-            let query_lower = query.to_lowercase();
-            bookmarks.retain(|bookmark| {
-                bookmark.title.to_lowercase().contains(&query_lower) ||
-                bookmark.url.to_lowercase().contains(&query_lower) ||
-                bookmark.author.as_ref().map_or(false, |author| author.to_lowercase().contains(&query_lower)) ||
-                bookmark.notes.iter().any(|note| note.content.to_lowercase().contains(&query_lower))
-            });
+            let index = SearchIndex::build(&bookmarks, Vec::new(), |id| self.contents.get(id).cloned());
+            match index.search(query) {
+                Some(mut scores) => {
+                    bookmarks.retain(|bookmark| scores.contains_key(&bookmark.id));
+                    boost_phrases(&mut scores, &bookmarks, query);
+                    relevance = scores;
+                }
+                None => {
+                    let query_lower = query.replace('"', "").to_lowercase();
+                    bookmarks.retain(|bookmark| {
+                        bookmark.title.to_lowercase().contains(&query_lower) ||
+                        bookmark.url.to_lowercase().contains(&query_lower) ||
+                        bookmark.author.as_ref().is_some_and(|author| author.to_lowercase().contains(&query_lower)) ||
+                        bookmark.description.as_ref().is_some_and(|description| description.to_lowercase().contains(&query_lower)) ||
+                        bookmark.notes.iter().any(|note| note.content.to_lowercase().contains(&query_lower))
+                    });
+                }
+            }
+        }
+        
+        // Apply query language expression
+        if let Some(ref query) = filters.query {
+            bookmarks.retain(|bookmark| query.matches(bookmark));
         }
         
         // Apply tags filter (AND logic - must contain ALL tags)
         if let Some(ref filter_tags) = filters.tags {
-            let tags_lower: Vec<String> = filter_tags.iter().map(|tag| tag.to_lowercase()).collect();
+            let filter_tags: Vec<String> = filter_tags.iter().map(|tag| normalize_tag(tag)).collect();
             bookmarks.retain(|bookmark| {
-                tags_lower.iter().all(|tag| {
-                    bookmark.tags.iter().any(|bookmark_tag| bookmark_tag.to_lowercase() == *tag)
+                filter_tags.iter().all(|tag| {
+                    bookmark.tags.iter().any(|bookmark_tag| {
+                        tag_matches(tag, &normalize_tag(bookmark_tag), filters.include_descendant_tags)
+                    })
                 })
             });
         }
@@ -177,6 +310,10 @@ impl MockBookmarkRepository {
             bookmarks.retain(|bookmark| bookmark.reading_status == *status);
         }
         
+        if filters.dead_links {
+            bookmarks.retain(|bookmark| bookmark.link_status.as_ref().is_some_and(|status| status.is_dead()));
+        }
+        
         // Apply priority range filter
         if let Some((min_priority, max_priority)) = filters.priority_range {
             bookmarks.retain(|bookmark| {
@@ -188,6 +325,15 @@ impl MockBookmarkRepository {
             });
         }
         
+        // Text searches are ranked by relevance unless another order is requested
+        if filters.text_query.is_some() && filters.sort_by.as_ref().is_none_or(|sort_by| *sort_by == SortBy::Relevance) {
+            let score = |bookmark: &Bookmark| relevance.get(&bookmark.id).copied().unwrap_or(0.0);
+            bookmarks.sort_by(|a, b| match filters.sort_order {
+                Some(SortDirection::Ascending) => score(a).total_cmp(&score(b)),
+                _ => score(b).total_cmp(&score(a)),
+            });
+        }
+        
         bookmarks
     }
 }
@@ -218,6 +364,7 @@ impl BookmarkRepository for MockBookmarkRepository {
             .ok_or_else(|| BookmarkError::NotFound(id.to_string()))
     }
     
+    #[allow(clippy::map_entry)]
     async fn update(&mut self, bookmark: Bookmark) -> BookmarkResult<Bookmark> {
         let id = bookmark.id.clone();
         if self.bookmarks.contains_key(&id) {
@@ -230,11 +377,120 @@ impl BookmarkRepository for MockBookmarkRepository {
 
     async fn delete(&mut self, id: &str) -> BookmarkResult<()> {
         match self.bookmarks.remove(id) {
-            Some(_) => Ok(()),
+            Some(bookmark) => {
+                self.trash.insert(id.to_string(), TrashedBookmark {
+                    bookmark,
+                    deleted_at: Utc::now(),
+                });
+                Ok(())
+            }
+            None => Err(BookmarkError::NotFound(id.to_string())),
+        }
+    }
+    
+    async fn find_trash(&self) -> BookmarkResult<Vec<TrashedBookmark>> {
+        Ok(self.trash.values().cloned().collect())
+    }
+    
+    async fn restore(&mut self, id: &str) -> BookmarkResult<Bookmark> {
+        match self.trash.remove(id) {
+            Some(trashed) => {
+                self.bookmarks.insert(id.to_string(), trashed.bookmark.clone());
+                Ok(trashed.bookmark)
+            }
             None => Err(BookmarkError::NotFound(id.to_string())),
         }
     }
     
+    async fn purge_trash(&mut self, deleted_before: Option<DateTime<Utc>>) -> BookmarkResult<Vec<TrashedBookmark>> {
+        let purged_ids: Vec<String> = self.trash
+            .values()
+            .filter(|trashed| deleted_before.is_none_or(|cutoff| trashed.deleted_at < cutoff))
+            .map(|trashed| trashed.bookmark.id.clone())
+            .collect();
+        
+        for id in &purged_ids {
+            self.contents.remove(id);
+            if let Some(ref dir) = self.archive_dir {
+                let _ = std::fs::remove_file(dir.path().join(format!("{}.html", id)));
+            }
+        }
+        Ok(purged_ids.iter().filter_map(|id| self.trash.remove(id)).collect())
+    }
+    
+    async fn apply_batch(&mut self, batch: BookmarkBatch) -> BookmarkResult<()> {
+        let ids = batch.updates.iter().map(|bookmark| &bookmark.id).chain(batch.deletes.iter());
+        for id in ids {
+            if !self.bookmarks.contains_key(id) {
+                return Err(BookmarkError::NotFound(id.clone()));
+            }
+        }
+        
+        for bookmark in batch.updates {
+            self.bookmarks.insert(bookmark.id.clone(), bookmark);
+        }
+        for id in batch.deletes {
+            if let Some(bookmark) = self.bookmarks.remove(&id) {
+                self.trash.insert(id, TrashedBookmark {
+                    bookmark,
+                    deleted_at: Utc::now(),
+                });
+            }
+        }
+        Ok(())
+    }
+    
+    async fn save_search(&mut self, search: SavedSearch) -> BookmarkResult<()> {
+        self.saved_searches.insert(search.name.clone(), search);
+        Ok(())
+    }
+    
+    async fn find_saved_searches(&self) -> BookmarkResult<Vec<SavedSearch>> {
+        let mut searches: Vec<SavedSearch> = self.saved_searches.values().cloned().collect();
+        searches.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(searches)
+    }
+    
+    async fn delete_saved_search(&mut self, name: &str) -> BookmarkResult<()> {
+        self.saved_searches.remove(name)
+            .map(|_| ())
+            .ok_or_else(|| BookmarkError::NotFound(name.to_string()))
+    }
+    
+    async fn save_content(&mut self, bookmark_id: &str, content: &str) -> BookmarkResult<()> {
+        if !self.bookmarks.contains_key(bookmark_id) {
+            return Err(BookmarkError::NotFound(bookmark_id.to_string()));
+        }
+        self.contents.insert(bookmark_id.to_string(), content.to_string());
+        Ok(())
+    }
+    
+    async fn find_content(&self, bookmark_id: &str) -> BookmarkResult<Option<String>> {
+        Ok(self.contents.get(bookmark_id).cloned())
+    }
+    
+    async fn save_archive(&mut self, bookmark_id: &str, html: &str) -> BookmarkResult<Bookmark> {
+        let bookmark = self.bookmarks.get_mut(bookmark_id)
+            .ok_or_else(|| BookmarkError::NotFound(bookmark_id.to_string()))?;
+        bookmark.archive_path = Some(format!("archives/{}.html", bookmark_id));
+        bookmark.archived_at = Some(Utc::now());
+        let bookmark = bookmark.clone();
+        if self.archive_dir.is_none() {
+            self.archive_dir = Some(tempfile::TempDir::new()?);
+        }
+        if let Some(ref dir) = self.archive_dir {
+            std::fs::write(dir.path().join(format!("{}.html", bookmark_id)), html)?;
+        }
+        Ok(bookmark)
+    }
+    
+    async fn find_archive(&self, bookmark_id: &str) -> BookmarkResult<Option<PathBuf>> {
+        Ok(self.archive_dir.as_ref()
+            .map(|dir| dir.path().join(format!("{}.html", bookmark_id)))
+            .filter(|path| path.exists()))
+    }
+    
+    #[allow(clippy::unnecessary_map_or)]
     async fn search_by_text(&self, query: &str) -> BookmarkResult<Vec<Bookmark>> {
         let query_lower = query.to_lowercase();
         let results = self.bookmarks
@@ -243,6 +499,7 @@ impl BookmarkRepository for MockBookmarkRepository {
                 bookmark.title.to_lowercase().contains(&query_lower) ||
                 bookmark.url.to_lowercase().contains(&query_lower) ||
                 bookmark.author.as_ref().map_or(false, |author| author.to_lowercase().contains(&query_lower)) ||
+                bookmark.description.as_ref().is_some_and(|description| description.to_lowercase().contains(&query_lower)) ||
                 bookmark.notes.iter().any(|note| note.content.to_lowercase().contains(&query_lower))
             })
             .cloned()
@@ -362,6 +619,81 @@ mod tests {
         assert!(bookmarks.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_delete_moves_bookmark_to_trash() {
+        let mut repo = MockBookmarkRepository::new();
+        let bookmark = Bookmark::new("https://example.com", "Example").unwrap();
+        let bookmark_id = bookmark.id.clone();
+        
+        repo.create(bookmark).await.unwrap();
+        repo.delete(&bookmark_id).await.unwrap();
+        
+        let trash = repo.find_trash().await.unwrap();
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].bookmark.id, bookmark_id);
+        
+        // Restore brings it back
+        let restored = repo.restore(&bookmark_id).await.unwrap();
+        assert_eq!(restored.title, "Example");
+        assert_eq!(repo.find_all(None).await.unwrap().len(), 1);
+        assert!(repo.find_trash().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_purge_trash_respects_cutoff() {
+        let mut repo = MockBookmarkRepository::new();
+        let bookmark = Bookmark::new("https://example.com", "Example").unwrap();
+        let bookmark_id = bookmark.id.clone();
+        
+        repo.create(bookmark).await.unwrap();
+        repo.delete(&bookmark_id).await.unwrap();
+        
+        // Cutoff before the deletion keeps the bookmark
+        let cutoff = Utc::now() - chrono::Duration::days(1);
+        let purged = repo.purge_trash(Some(cutoff)).await.unwrap();
+        assert!(purged.is_empty());
+        
+        // No cutoff purges everything
+        let purged = repo.purge_trash(None).await.unwrap();
+        assert_eq!(purged.len(), 1);
+        assert!(matches!(repo.restore(&bookmark_id).await, Err(BookmarkError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_apply_batch_updates_and_deletes() {
+        let mut repo = MockBookmarkRepository::new();
+        let mut first = Bookmark::new("https://example1.com", "Example 1").unwrap();
+        let second = Bookmark::new("https://example2.com", "Example 2").unwrap();
+        repo.create(first.clone()).await.unwrap();
+        repo.create(second.clone()).await.unwrap();
+        
+        first.tags.push("rust".to_string());
+        repo.apply_batch(BookmarkBatch {
+            updates: vec![first.clone()],
+            deletes: vec![second.id.clone()],
+        }).await.unwrap();
+        
+        assert_eq!(repo.find_by_id(&first.id).await.unwrap().tags, vec!["rust"]);
+        assert!(repo.find_by_id(&second.id).await.is_err());
+        assert_eq!(repo.find_trash().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_apply_batch_missing_bookmark_changes_nothing() {
+        let mut repo = MockBookmarkRepository::new();
+        let mut bookmark = Bookmark::new("https://example.com", "Example").unwrap();
+        repo.create(bookmark.clone()).await.unwrap();
+        
+        bookmark.title = "Changed".to_string();
+        let result = repo.apply_batch(BookmarkBatch {
+            updates: vec![bookmark.clone()],
+            deletes: vec!["missing".to_string()],
+        }).await;
+        
+        assert!(matches!(result, Err(BookmarkError::NotFound(_))));
+        assert_eq!(repo.find_by_id(&bookmark.id).await.unwrap().title, "Example");
+    }
+
     #[tokio::test]
     async fn test_delete_nonexistent_bookmark() {
         let mut repo = MockBookmarkRepository::new();
@@ -671,6 +1003,33 @@ mod tests {
         assert_eq!(results[0].title, "Rust");
     }
     
+    #[tokio::test]
+    async fn test_find_all_with_descendant_tags_filter() {
+        use crate::types::BookmarkFilters;
+        
+        let mut repo = MockBookmarkRepository::new();
+        let parent = Bookmark::new("https://example1.com", "Work").unwrap()
+            .with_tags(vec!["work".to_string()]);
+        let child = Bookmark::new("https://example2.com", "Project").unwrap()
+            .with_tags(vec!["work/projects/automark".to_string()]);
+        let unrelated = Bookmark::new("https://example3.com", "Workshop").unwrap()
+            .with_tags(vec!["workshop".to_string()]);
+        repo.create(parent).await.unwrap();
+        repo.create(child).await.unwrap();
+        repo.create(unrelated).await.unwrap();
+        
+        let mut filters = BookmarkFilters {
+            tags: Some(vec!["Work".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(repo.find_all(Some(filters.clone())).await.unwrap().len(), 1);
+        
+        filters.include_descendant_tags = true;
+        let results = repo.find_all(Some(filters)).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|b| b.title != "Workshop"));
+    }
+
     #[tokio::test]
     async fn test_find_all_with_reading_status_filter() {
         use crate::types::{BookmarkFilters, ReadingStatus};
@@ -745,6 +1104,9 @@ mod tests {
             published_until: None,
             sort_by: None,
             sort_order: None,
+            include_descendant_tags: false,
+            query: None,
+            dead_links: false,
         };
         
         let results = repo.find_all(Some(filters)).await.unwrap();
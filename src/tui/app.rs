@@ -13,11 +13,20 @@ use ratatui::{
 };
 use std::io;
 
+use crate::adapters::{LibraryStats, LibraryText, SimilarityIndex, TagSuggester};
+use crate::adapters::similarity::Related;
 use crate::traits::BookmarkRepository;
-use crate::types::{Bookmark, BookmarkResult, BookmarkFilters};
+use crate::types::{Bookmark, BookmarkResult, BookmarkFilters, SavedSearch, TagTreeEntry, build_tag_tree, flatten_tag_tree, normalize_tag};
 use super::components::*;
 use super::handlers::*;
 
+/// Related bookmarks listed in the detail view
+const RELATED_SHOWN: usize = 5;
+
+/// Entries listed per section of the stats panel, and weeks in its chart
+const STATS_TOP: usize = 5;
+const STATS_WEEKS: usize = 12;
+
 /// Different view modes for the TUI application
 #[derive(Debug, Clone, PartialEq)]
 pub enum ViewMode {
@@ -31,6 +40,12 @@ pub enum ViewMode {
     Add,
     /// Delete confirmation mode
     Delete,
+    /// Tag hierarchy sidebar
+    Tags,
+    /// Saved search picker
+    SavedSearches,
+    /// Library statistics panel
+    Stats,
 }
 
 /// Message types for user feedback
@@ -81,6 +96,32 @@ pub struct TuiApp {
     pub input_buffer: String,
     /// Cursor position in input buffer
     pub cursor_position: usize,
+    /// ID of the most recently deleted bookmark, for undo
+    pub last_deleted: Option<String>,
+    /// Flattened tag hierarchy shown in the tag sidebar
+    pub tag_entries: Vec<TagTreeEntry>,
+    /// List state for tag sidebar navigation
+    pub tag_list_state: ListState,
+    /// Saved searches shown in the picker
+    pub saved_searches: Vec<SavedSearch>,
+    /// List state for saved search picker navigation
+    pub saved_search_state: ListState,
+    /// Tags suggested for the bookmark shown in the detail view
+    pub tag_suggestions: Vec<String>,
+    /// Bookmarks similar to the one shown in the detail view
+    pub related: Vec<Related>,
+    /// Statistics shown in the stats panel
+    pub stats: Option<LibraryStats>,
+    /// Models behind the detail view, kept until the library changes
+    library_models: Option<LibraryModels>,
+}
+
+/// A tag suggester and similarity index trained on one read of the library
+struct LibraryModels {
+    /// The library they were trained on, compared to spot changes
+    bookmarks: Vec<Bookmark>,
+    suggester: TagSuggester,
+    similarity: SimilarityIndex,
 }
 
 impl TuiApp {
@@ -106,6 +147,15 @@ impl TuiApp {
             should_quit: false,
             input_buffer: String::new(),
             cursor_position: 0,
+            last_deleted: None,
+            tag_entries: Vec::new(),
+            tag_list_state: ListState::default(),
+            saved_searches: Vec::new(),
+            saved_search_state: ListState::default(),
+            tag_suggestions: Vec::new(),
+            related: Vec::new(),
+            stats: None,
+            library_models: None,
         })
     }
 
@@ -186,10 +236,7 @@ impl TuiApp {
         if self.search_query.trim().is_empty() {
             self.filters = None;
         } else {
-            self.filters = Some(BookmarkFilters {
-                text_query: Some(self.search_query.clone()),
-                ..Default::default()
-            });
+            self.filters = Some(BookmarkFilters::default().with_search_input(&self.search_query)?);
         }
         
         self.refresh_bookmarks(repository).await?;
@@ -206,6 +253,168 @@ impl TuiApp {
         Ok(())
     }
 
+    /// Open the tag sidebar with the hierarchy of all tags
+    pub async fn open_tag_sidebar(&mut self, repository: &dyn BookmarkRepository) -> BookmarkResult<()> {
+        let all_bookmarks = repository.find_all(None).await?;
+        self.tag_entries = flatten_tag_tree(&build_tag_tree(&all_bookmarks));
+
+        // Keep the currently filtered tag selected when reopening
+        let current_tag = self.filters.as_ref()
+            .and_then(|filters| filters.tags.as_ref())
+            .and_then(|tags| tags.first());
+        let selected = current_tag
+            .and_then(|tag| self.tag_entries.iter().position(|entry| entry.path == *tag))
+            .or(if self.tag_entries.is_empty() { None } else { Some(0) });
+        self.tag_list_state.select(selected);
+
+        self.mode = ViewMode::Tags;
+        Ok(())
+    }
+
+    /// Navigate up in the tag sidebar
+    pub fn navigate_tags_up(&mut self) {
+        if self.tag_entries.is_empty() {
+            return;
+        }
+        let current = self.tag_list_state.selected().unwrap_or(0);
+        let new_index = if current == 0 { self.tag_entries.len() - 1 } else { current - 1 };
+        self.tag_list_state.select(Some(new_index));
+    }
+
+    /// Navigate down in the tag sidebar
+    pub fn navigate_tags_down(&mut self) {
+        if self.tag_entries.is_empty() {
+            return;
+        }
+        let current = self.tag_list_state.selected().unwrap_or(0);
+        let new_index = if current >= self.tag_entries.len() - 1 { 0 } else { current + 1 };
+        self.tag_list_state.select(Some(new_index));
+    }
+
+    /// Filter bookmarks by the selected tag, including its descendants
+    pub async fn apply_tag_filter(&mut self, repository: &dyn BookmarkRepository) -> BookmarkResult<()> {
+        let tag = match self.tag_list_state.selected().and_then(|index| self.tag_entries.get(index)) {
+            Some(entry) => entry.path.clone(),
+            None => return Ok(()),
+        };
+
+        self.search_query.clear();
+        self.filters = Some(BookmarkFilters {
+            tags: Some(vec![tag.clone()]),
+            include_descendant_tags: true,
+            ..Default::default()
+        });
+        self.refresh_bookmarks(repository).await?;
+        self.set_message(TuiMessage::Info(format!("{} bookmarks tagged {}", self.bookmarks.len(), tag)));
+        Ok(())
+    }
+
+    /// Show the selected bookmark in detail, with suggested tags and related bookmarks
+    pub async fn open_detail(&mut self, repository: &dyn BookmarkRepository) -> BookmarkResult<()> {
+        let Some(bookmark) = self.selected_bookmark().cloned() else {
+            return Ok(());
+        };
+        let content = repository.find_content(&bookmark.id).await?;
+        let library = repository.find_all(None).await?;
+        let models = match self.library_models.take() {
+            Some(models) if models.bookmarks == library => models,
+            _ => {
+                let text = LibraryText::load(repository).await?;
+                LibraryModels {
+                    suggester: TagSuggester::from_library(&text),
+                    similarity: SimilarityIndex::from_library(&text),
+                    bookmarks: text.bookmarks,
+                }
+            }
+        };
+        self.tag_suggestions = models.suggester
+            .suggest(&bookmark, content.as_deref())
+            .into_iter()
+            .map(|suggestion| suggestion.tag)
+            .collect();
+        self.related = models.similarity.related(&bookmark.id, RELATED_SHOWN);
+        self.library_models = Some(models);
+        self.mode = ViewMode::Detail;
+        Ok(())
+    }
+
+    /// Add the suggested tags to the bookmark shown in the detail view
+    pub async fn apply_tag_suggestions(&mut self, repository: &mut dyn BookmarkRepository) -> BookmarkResult<()> {
+        let Some(mut bookmark) = self.selected_bookmark().cloned() else {
+            return Ok(());
+        };
+        if self.tag_suggestions.is_empty() {
+            self.set_message(TuiMessage::Info("No tag suggestions".to_string()));
+            return Ok(());
+        }
+
+        let mut added: Vec<String> = Vec::new();
+        for tag in std::mem::take(&mut self.tag_suggestions).iter().map(|tag| normalize_tag(tag)) {
+            if !tag.is_empty() && !bookmark.tags.iter().any(|existing| normalize_tag(existing) == tag) {
+                bookmark.tags.push(tag.clone());
+                added.push(tag);
+            }
+        }
+        if added.is_empty() {
+            self.set_message(TuiMessage::Info("Already has the suggested tags".to_string()));
+            return Ok(());
+        }
+        repository.update(bookmark).await?;
+        self.refresh_bookmarks(repository).await?;
+        self.set_message(TuiMessage::Success(format!("Added tags: {}", added.join(", "))));
+        Ok(())
+    }
+
+    /// Open the stats panel with statistics over the whole library
+    pub async fn open_stats(&mut self, repository: &dyn BookmarkRepository) -> BookmarkResult<()> {
+        let all_bookmarks = repository.find_all(None).await?;
+        self.stats = Some(LibraryStats::compute(&all_bookmarks, chrono::Utc::now(), STATS_TOP, STATS_WEEKS));
+        self.mode = ViewMode::Stats;
+        Ok(())
+    }
+
+    /// Open the saved search picker
+    pub async fn open_saved_searches(&mut self, repository: &dyn BookmarkRepository) -> BookmarkResult<()> {
+        self.saved_searches = repository.find_saved_searches().await?;
+        self.saved_search_state.select(if self.saved_searches.is_empty() { None } else { Some(0) });
+        self.mode = ViewMode::SavedSearches;
+        Ok(())
+    }
+
+    /// Navigate up in the saved search picker
+    pub fn navigate_saved_searches_up(&mut self) {
+        if self.saved_searches.is_empty() {
+            return;
+        }
+        let current = self.saved_search_state.selected().unwrap_or(0);
+        let new_index = if current == 0 { self.saved_searches.len() - 1 } else { current - 1 };
+        self.saved_search_state.select(Some(new_index));
+    }
+
+    /// Navigate down in the saved search picker
+    pub fn navigate_saved_searches_down(&mut self) {
+        if self.saved_searches.is_empty() {
+            return;
+        }
+        let current = self.saved_search_state.selected().unwrap_or(0);
+        let new_index = if current >= self.saved_searches.len() - 1 { 0 } else { current + 1 };
+        self.saved_search_state.select(Some(new_index));
+    }
+
+    /// Run the selected saved search
+    pub async fn apply_saved_search(&mut self, repository: &dyn BookmarkRepository) -> BookmarkResult<()> {
+        let search = match self.saved_search_state.selected().and_then(|index| self.saved_searches.get(index)) {
+            Some(search) => search.clone(),
+            None => return Ok(()),
+        };
+
+        self.search_query.clear();
+        self.filters = Some(search.filters);
+        self.refresh_bookmarks(repository).await?;
+        self.set_message(TuiMessage::Info(format!("@{}: {} bookmarks", search.name, self.bookmarks.len())));
+        Ok(())
+    }
+
     /// Add character to input buffer
     pub fn add_char_to_input(&mut self, c: char) {
         self.input_buffer.insert(self.cursor_position, c);
@@ -320,6 +529,9 @@ fn ui(f: &mut Frame, app: &mut TuiApp) {
         ViewMode::Search => draw_search_input(f, chunks[1], app),
         ViewMode::Add => draw_add_input(f, chunks[1], app),
         ViewMode::Delete => draw_delete_confirmation(f, chunks[1], app),
+        ViewMode::Tags => draw_tag_sidebar(f, chunks[1], app),
+        ViewMode::SavedSearches => draw_saved_searches(f, chunks[1], app),
+        ViewMode::Stats => draw_stats_panel(f, chunks[1], app),
     }
 
     // Draw status bar
@@ -334,6 +546,9 @@ fn draw_header(f: &mut Frame, area: Rect, app: &TuiApp) {
         ViewMode::Search => "Search Bookmarks",
         ViewMode::Add => "Add New Bookmark",
         ViewMode::Delete => "Delete Bookmark",
+        ViewMode::Tags => "Browse Tags",
+        ViewMode::SavedSearches => "Saved Searches",
+        ViewMode::Stats => "Library Stats",
     };
 
     let header = Paragraph::new(title)
@@ -348,10 +563,39 @@ fn draw_bookmark_list(f: &mut Frame, area: Rect, app: &mut TuiApp) {
     render_bookmark_list(f, area, &app.bookmarks, &mut app.list_state, false);
 }
 
+/// Draw the tag sidebar next to the bookmark list
+fn draw_tag_sidebar(f: &mut Frame, area: Rect, app: &mut TuiApp) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+        .split(area);
+
+    render_tag_sidebar(f, columns[0], &app.tag_entries, &mut app.tag_list_state);
+    render_bookmark_list(f, columns[1], &app.bookmarks, &mut app.list_state, false);
+}
+
+/// Draw the stats panel next to the bookmark list
+fn draw_stats_panel(f: &mut Frame, area: Rect, app: &mut TuiApp) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(area);
+
+    if let Some(ref stats) = app.stats {
+        render_stats_panel(f, columns[0], stats);
+    }
+    render_bookmark_list(f, columns[1], &app.bookmarks, &mut app.list_state, false);
+}
+
+/// Draw the saved search picker
+fn draw_saved_searches(f: &mut Frame, area: Rect, app: &mut TuiApp) {
+    render_saved_search_picker(f, area, &app.saved_searches, &mut app.saved_search_state);
+}
+
 /// Draw bookmark detail view
 fn draw_bookmark_detail(f: &mut Frame, area: Rect, app: &TuiApp) {
     if let Some(bookmark) = app.selected_bookmark() {
-        render_bookmark_detail(f, area, bookmark);
+        render_bookmark_detail(f, area, bookmark, &app.tag_suggestions, &app.related);
     } else {
         render_no_bookmark_selected(f, area);
     }
@@ -420,6 +664,24 @@ mod tests {
         assert_eq!(app.selected_index, Some(1));
     }
 
+    #[tokio::test]
+    async fn test_detail_models_follow_library_changes() {
+        let mut repo = MockBookmarkRepository::new();
+        repo.create(Bookmark::new("https://doc.rust-lang.org/book/", "The Rust Programming Language").unwrap()
+            .with_tags(vec!["rust".to_string()])).await.unwrap();
+        let mut app = TuiApp::new(&repo).await.unwrap();
+
+        app.open_detail(&repo).await.unwrap();
+        assert_eq!(app.library_models.as_ref().unwrap().bookmarks.len(), 1);
+        app.open_detail(&repo).await.unwrap();
+        assert_eq!(app.library_models.as_ref().unwrap().bookmarks.len(), 1);
+
+        repo.create(Bookmark::new("https://blog.example.com/rust-traits", "Rust traits explained").unwrap()).await.unwrap();
+        app.open_detail(&repo).await.unwrap();
+        assert_eq!(app.library_models.as_ref().unwrap().bookmarks.len(), 2);
+        assert_eq!(app.related.len(), 1);
+    }
+
     #[test]
     fn test_message_system() {
         let mut app = TuiApp {
@@ -434,6 +696,15 @@ mod tests {
             should_quit: false,
             input_buffer: String::new(),
             cursor_position: 0,
+            last_deleted: None,
+            tag_entries: Vec::new(),
+            tag_list_state: ListState::default(),
+            saved_searches: Vec::new(),
+            saved_search_state: ListState::default(),
+            tag_suggestions: Vec::new(),
+            related: Vec::new(),
+            stats: None,
+            library_models: None,
         };
 
         app.set_message(TuiMessage::Success("Test message".to_string()));
@@ -461,6 +732,15 @@ mod tests {
             should_quit: false,
             input_buffer: String::new(),
             cursor_position: 0,
+            last_deleted: None,
+            tag_entries: Vec::new(),
+            tag_list_state: ListState::default(),
+            saved_searches: Vec::new(),
+            saved_search_state: ListState::default(),
+            tag_suggestions: Vec::new(),
+            related: Vec::new(),
+            stats: None,
+            library_models: None,
         };
 
         app.add_char_to_input('h');
@@ -6,10 +6,11 @@ use ratatui::{
     Frame,
 };
 
+use crate::adapters::similarity::Related;
 use crate::types::Bookmark;
 
-/// Render the bookmark detail component
-pub fn render_bookmark_detail(f: &mut Frame, area: Rect, bookmark: &Bookmark) {
+/// Render the bookmark detail component, offering any suggested tags and related bookmarks
+pub fn render_bookmark_detail(f: &mut Frame, area: Rect, bookmark: &Bookmark, suggested_tags: &[String], related: &[Related]) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -17,6 +18,7 @@ pub fn render_bookmark_detail(f: &mut Frame, area: Rect, bookmark: &Bookmark) {
             Constraint::Length(3), // URL
             Constraint::Length(3), // Metadata
             Constraint::Min(0),    // Notes/Description
+            Constraint::Length(related_height(related)), // Related bookmarks
         ])
         .split(area);
 
@@ -87,6 +89,14 @@ pub fn render_bookmark_detail(f: &mut Frame, area: Rect, bookmark: &Bookmark) {
         ]));
     }
 
+    // Site the page belongs to
+    if let Some(ref site_name) = bookmark.site_name {
+        content_lines.push(Line::from(vec![
+            Span::styled("Site: ", Style::default().fg(Color::Gray)),
+            Span::styled(site_name.clone(), Style::default().fg(Color::Green)),
+        ]));
+    }
+
     // Publish date if available
     if let Some(ref publish_date) = bookmark.publish_date {
         content_lines.push(Line::from(vec![
@@ -98,6 +108,31 @@ pub fn render_bookmark_detail(f: &mut Frame, area: Rect, bookmark: &Bookmark) {
         ]));
     }
 
+    // Tags that fit the page, applied with 'g'
+    if !suggested_tags.is_empty() {
+        content_lines.push(Line::from(vec![
+            Span::styled("Suggested tags: ", Style::default().fg(Color::Gray)),
+            Span::styled(suggested_tags.join(", "), Style::default().fg(Color::Cyan)),
+            Span::styled(" (g to add)", Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+
+    // Site-specific details (stars, duration, ...)
+    for (name, value) in &bookmark.site_details {
+        content_lines.push(Line::from(vec![
+            Span::styled(format!("{}: ", name.replace('_', " ")), Style::default().fg(Color::Gray)),
+            Span::styled(value.clone(), Style::default().fg(Color::Green)),
+        ]));
+    }
+
+    // Page summary
+    if let Some(ref description) = bookmark.description {
+        if !content_lines.is_empty() {
+            content_lines.push(Line::from(""));
+        }
+        content_lines.push(Line::from(Span::raw(description.clone())));
+    }
+
     // Notes
     if !bookmark.notes.is_empty() {
         if !content_lines.is_empty() {
@@ -132,6 +167,27 @@ pub fn render_bookmark_detail(f: &mut Frame, area: Rect, bookmark: &Bookmark) {
         .block(Block::default().borders(Borders::ALL).title("Details"))
         .wrap(Wrap { trim: true });
     f.render_widget(content, chunks[3]);
+
+    if !related.is_empty() {
+        let related_lines: Vec<Line> = related.iter()
+            .map(|related| Line::from(vec![
+                Span::styled(format!("{:.2}  ", related.score), Style::default().fg(Color::DarkGray)),
+                Span::raw(related.bookmark.title.clone()),
+            ]))
+            .collect();
+        let related_pane = Paragraph::new(related_lines)
+            .block(Block::default().borders(Borders::ALL).title("Related"));
+        f.render_widget(related_pane, chunks[4]);
+    }
+}
+
+/// Rows for the related pane: one per bookmark plus borders, or none when empty
+fn related_height(related: &[Related]) -> u16 {
+    if related.is_empty() {
+        0
+    } else {
+        related.len() as u16 + 2
+    }
 }
 
 /// Render a simple "no bookmark selected" message
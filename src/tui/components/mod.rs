@@ -2,8 +2,14 @@ pub mod bookmark_list;
 pub mod bookmark_detail;
 pub mod search_bar;
 pub mod status_bar;
+pub mod tag_sidebar;
+pub mod saved_search_picker;
+pub mod stats_panel;
 
 pub use bookmark_list::*;
 pub use bookmark_detail::*;
 pub use search_bar::*;
-pub use status_bar::*;
\ No newline at end of file
+pub use status_bar::*;
+pub use tag_sidebar::*;
+pub use saved_search_picker::*;
+pub use stats_panel::*;
\ No newline at end of file
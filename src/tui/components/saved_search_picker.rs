@@ -0,0 +1,66 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, List, ListItem, ListState},
+    Frame,
+};
+
+use crate::types::SavedSearch;
+
+/// Render the saved search picker component
+pub fn render_saved_search_picker(
+    f: &mut Frame,
+    area: Rect,
+    searches: &[SavedSearch],
+    list_state: &mut ListState,
+) {
+    let items: Vec<ListItem> = if searches.is_empty() {
+        vec![ListItem::new("No saved searches. Save one with: automark search ... --save <name>")]
+    } else {
+        searches
+            .iter()
+            .map(|search| ListItem::new(format!("@{} - {}", search.name, search.filters.describe())))
+            .collect()
+    };
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(format!("Saved Searches ({})", searches.len())))
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD)
+        )
+        .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, area, list_state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::BookmarkFilters;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_saved_search_picker_rendering() {
+        let backend = TestBackend::new(60, 6);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let searches = vec![SavedSearch {
+            name: "rust".to_string(),
+            filters: BookmarkFilters {
+                tags: Some(vec!["rust".to_string()]),
+                ..Default::default()
+            },
+        }];
+        let mut state = ListState::default();
+        state.select(Some(0));
+
+        terminal.draw(|f| {
+            let area = Rect::new(0, 0, 60, 6);
+            render_saved_search_picker(f, area, &searches, &mut state);
+        }).unwrap();
+
+        let rendered: String = terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect();
+        assert!(rendered.contains("@rust - tags: rust"));
+    }
+}
@@ -0,0 +1,87 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Sparkline},
+    Frame,
+};
+
+use crate::adapters::LibraryStats;
+
+/// Tags and domains listed in the panel
+const PANEL_TOP: usize = 5;
+
+/// Render the library statistics panel component
+pub fn render_stats_panel(f: &mut Frame, area: Rect, stats: &LibraryStats) {
+    let block = Block::default().borders(Borders::ALL).title("Stats");
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(3)])
+        .split(inner);
+
+    let heading = |text: &str| Line::from(Span::styled(text.to_string(), Style::default().add_modifier(Modifier::BOLD)));
+    let mut lines = vec![
+        Line::from(format!("Bookmarks: {}", stats.total)),
+        Line::from(format!("Unread {} | Reading {} | Done {}", stats.unread, stats.reading, stats.completed)),
+        Line::from(format!("Completed: {:.0}%", stats.completion_rate * 100.0)),
+    ];
+    if let Some(days) = stats.average_days_to_complete {
+        lines.push(Line::from(format!("Avg. to finish: {:.1} days", days)));
+    }
+    if let Some(oldest) = stats.oldest_unread.first() {
+        lines.push(Line::from(format!("Oldest unread: {} days", oldest.age_days)));
+    }
+
+    for (title, counts) in [("Top tags", &stats.top_tags), ("Top domains", &stats.top_domains)] {
+        if counts.is_empty() {
+            continue;
+        }
+        lines.push(Line::from(""));
+        lines.push(heading(title));
+        for entry in counts.iter().take(PANEL_TOP) {
+            lines.push(Line::from(format!("  {} ({})", entry.name, entry.count)));
+        }
+    }
+    f.render_widget(Paragraph::new(lines), rows[0]);
+
+    let added: Vec<u64> = stats.weeks.iter().map(|week| week.added as u64).collect();
+    let sparkline = Sparkline::default()
+        .block(Block::default().title("Added per week"))
+        .data(&added)
+        .style(Style::default().fg(Color::Cyan));
+    f.render_widget(sparkline, rows[1]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Bookmark;
+    use chrono::Utc;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_stats_panel_rendering() {
+        let backend = TestBackend::new(40, 20);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let bookmarks = vec![
+            Bookmark::new("https://example.com/a", "A").unwrap().with_tags(vec!["rust".to_string()]),
+            Bookmark::new("https://example.com/b", "B").unwrap(),
+        ];
+        let stats = LibraryStats::compute(&bookmarks, Utc::now(), 10, 8);
+
+        terminal.draw(|f| {
+            render_stats_panel(f, Rect::new(0, 0, 40, 20), &stats);
+        }).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let rendered: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(rendered.contains("Bookmarks: 2"));
+        assert!(rendered.contains("Unread 2 | Reading 0 | Done 0"));
+        assert!(rendered.contains("rust (1)"));
+        assert!(rendered.contains("example.com (2)"));
+        assert!(rendered.contains("Added per week"));
+    }
+}
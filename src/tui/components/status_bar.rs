@@ -33,11 +33,14 @@ pub fn render_status_bar(f: &mut Frame, area: Rect, mode: &ViewMode, message: Op
 /// Get key hints for the current mode
 fn get_key_hints(mode: &ViewMode) -> &'static str {
     match mode {
-        ViewMode::List => "↑/↓ or j/k: navigate | Enter: open URL | e: details | /: search | a: add | d: delete | q: quit",
-        ViewMode::Detail => "Esc: back to list | q: quit",
+        ViewMode::List => "↑/↓ or j/k: navigate | Enter: open URL | o: open archive | e: details | /: search | a: add | d: delete | u: undo delete | t: tags | s: saved searches | i: stats | q: quit",
+        ViewMode::Detail => "g: add suggested tags | Esc: back to list | q: quit",
         ViewMode::Search => "Type to search | Enter: apply search | Esc: cancel",
         ViewMode::Add => "Type URL | Enter: add bookmark | Esc: cancel",
         ViewMode::Delete => "y: confirm delete | any other key: cancel",
+        ViewMode::Tags => "↑/↓ or j/k: navigate | Enter: filter by tag | Esc/t: close | q: quit",
+        ViewMode::SavedSearches => "↑/↓ or j/k: navigate | Enter: run search | Esc: cancel | q: quit",
+        ViewMode::Stats => "↑/↓ or j/k: navigate | Enter: open URL | Esc/i: close | q: quit",
     }
 }
 
@@ -79,8 +82,11 @@ mod tests {
         assert!(!get_key_hints(&ViewMode::List).is_empty());
         assert!(!get_key_hints(&ViewMode::Detail).is_empty());
         assert!(!get_key_hints(&ViewMode::Search).is_empty());
+        assert!(!get_key_hints(&ViewMode::Tags).is_empty());
+        assert!(!get_key_hints(&ViewMode::SavedSearches).is_empty());
         assert!(!get_key_hints(&ViewMode::Add).is_empty());
         assert!(!get_key_hints(&ViewMode::Delete).is_empty());
+        assert!(!get_key_hints(&ViewMode::Stats).is_empty());
     }
 
 }
\ No newline at end of file
@@ -0,0 +1,71 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, List, ListItem, ListState},
+    Frame,
+};
+
+use crate::types::TagTreeEntry;
+
+/// Render the tag hierarchy sidebar component
+pub fn render_tag_sidebar(
+    f: &mut Frame,
+    area: Rect,
+    entries: &[TagTreeEntry],
+    list_state: &mut ListState,
+) {
+    let items: Vec<ListItem> = if entries.is_empty() {
+        vec![ListItem::new("No tags")]
+    } else {
+        entries
+            .iter()
+            .map(|entry| {
+                ListItem::new(format!(
+                    "{}{} ({})",
+                    "  ".repeat(entry.depth),
+                    entry.name,
+                    entry.total_count
+                ))
+            })
+            .collect()
+    };
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title("Tags"))
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD)
+        )
+        .highlight_symbol("> ");
+
+    f.render_stateful_widget(list, area, list_state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_tag_sidebar_rendering() {
+        let backend = TestBackend::new(40, 10);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let entries = vec![
+            TagTreeEntry { depth: 0, name: "work".to_string(), path: "work".to_string(), total_count: 2 },
+            TagTreeEntry { depth: 1, name: "projects".to_string(), path: "work/projects".to_string(), total_count: 1 },
+        ];
+        let mut state = ListState::default();
+        state.select(Some(1));
+
+        terminal.draw(|f| {
+            let area = Rect::new(0, 0, 40, 10);
+            render_tag_sidebar(f, area, &entries, &mut state);
+        }).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let rendered: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(rendered.contains("work (2)"));
+        assert!(rendered.contains("  projects (1)"));
+    }
+}
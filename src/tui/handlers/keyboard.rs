@@ -1,8 +1,8 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use crate::traits::BookmarkRepository;
-use crate::types::{Bookmark, BookmarkResult};
+use crate::types::{Bookmark, BookmarkError, BookmarkResult};
 use crate::tui::app::{TuiApp, ViewMode, TuiMessage};
-use std::process::Command;
+use crate::adapters::browser::open_url;
 
 /// Handle keyboard events based on current application mode
 pub async fn handle_key_event(
@@ -12,14 +12,18 @@ pub async fn handle_key_event(
 ) -> BookmarkResult<()> {
     match app.mode {
         ViewMode::List => handle_list_mode_keys(key, app, repository).await,
-        ViewMode::Detail => handle_detail_mode_keys(key, app),
+        ViewMode::Detail => handle_detail_mode_keys(key, app, repository).await,
         ViewMode::Search => handle_search_mode_keys(key, app, repository).await,
         ViewMode::Add => handle_add_mode_keys(key, app, repository).await,
         ViewMode::Delete => handle_delete_mode_keys(key, app, repository).await,
+        ViewMode::Tags => handle_tags_mode_keys(key, app, repository).await,
+        ViewMode::SavedSearches => handle_saved_searches_mode_keys(key, app, repository).await,
+        ViewMode::Stats => handle_stats_mode_keys(key, app),
     }
 }
 
 /// Handle keys in list view mode
+#[allow(clippy::collapsible_match)]
 async fn handle_list_mode_keys(
     key: KeyEvent,
     app: &mut TuiApp,
@@ -45,11 +49,26 @@ async fn handle_list_mode_keys(
                 }
             }
         }
-        KeyCode::Char('e') | KeyCode::Char('E') => {
-            if app.selected_bookmark().is_some() {
-                app.mode = ViewMode::Detail;
+        KeyCode::Char('o') | KeyCode::Char('O') => {
+            if let Some(bookmark) = app.selected_bookmark().cloned() {
+                // Open the offline copy saved with `automark archive`
+                match repository.find_archive(&bookmark.id).await? {
+                    Some(path) => match open_url(&path.display().to_string()) {
+                        Ok(()) => app.set_message(TuiMessage::Success(format!("Opened archived copy: {}", bookmark.title))),
+                        Err(e) => app.set_message(TuiMessage::Error(format!("Failed to open archive: {}", e))),
+                    },
+                    None => {
+                        app.set_message(TuiMessage::Error(format!(
+                            "No archived copy on this device; create one with: automark archive {}",
+                            &bookmark.id[..8.min(bookmark.id.len())]
+                        )));
+                    }
+                }
             }
         }
+        KeyCode::Char('e') | KeyCode::Char('E') if app.selected_bookmark().is_some() => {
+            app.open_detail(repository).await?;
+        }
         KeyCode::Char('/') => {
             app.mode = ViewMode::Search;
             app.search_query.clear();
@@ -69,6 +88,31 @@ async fn handle_list_mode_keys(
             app.refresh_bookmarks(repository).await?;
             app.set_message(TuiMessage::Success("Bookmarks refreshed".to_string()));
         }
+        KeyCode::Char('t') | KeyCode::Char('T') => {
+            app.open_tag_sidebar(repository).await?;
+        }
+        KeyCode::Char('s') | KeyCode::Char('S') => {
+            app.open_saved_searches(repository).await?;
+        }
+        KeyCode::Char('i') | KeyCode::Char('I') => {
+            app.open_stats(repository).await?;
+        }
+        KeyCode::Char('u') | KeyCode::Char('U') => {
+            match app.last_deleted.take() {
+                Some(bookmark_id) => match repository.restore(&bookmark_id).await {
+                    Ok(bookmark) => {
+                        app.refresh_bookmarks(repository).await?;
+                        app.set_message(TuiMessage::Success(format!("Restored bookmark: {}", bookmark.title)));
+                    }
+                    Err(e) => {
+                        app.set_message(TuiMessage::Error(format!("Failed to restore bookmark: {}", e)));
+                    }
+                },
+                None => {
+                    app.set_message(TuiMessage::Info("Nothing to undo".to_string()));
+                }
+            }
+        }
         KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             app.should_quit = true;
         }
@@ -83,11 +127,18 @@ async fn handle_list_mode_keys(
 }
 
 /// Handle keys in detail view mode
-fn handle_detail_mode_keys(key: KeyEvent, app: &mut TuiApp) -> BookmarkResult<()> {
+async fn handle_detail_mode_keys(
+    key: KeyEvent,
+    app: &mut TuiApp,
+    repository: &mut dyn BookmarkRepository,
+) -> BookmarkResult<()> {
     match key.code {
         KeyCode::Char('q') | KeyCode::Char('Q') => {
             app.should_quit = true;
         }
+        KeyCode::Char('g') | KeyCode::Char('G') => {
+            app.apply_tag_suggestions(repository).await?;
+        }
         KeyCode::Esc | KeyCode::Char('b') => {
             app.mode = ViewMode::List;
         }
@@ -107,8 +158,16 @@ async fn handle_search_mode_keys(
 ) -> BookmarkResult<()> {
     match key.code {
         KeyCode::Enter => {
-            app.apply_search(repository).await?;
-            app.mode = ViewMode::List;
+            // Stay in search mode on a malformed query so it can be corrected
+            match app.apply_search(repository).await {
+                Err(BookmarkError::InvalidQuery(message)) => {
+                    app.set_message(TuiMessage::Error(format!("Invalid query: {}", message)));
+                }
+                result => {
+                    result?;
+                    app.mode = ViewMode::List;
+                }
+            }
         }
         KeyCode::Esc => {
             app.mode = ViewMode::List;
@@ -177,6 +236,100 @@ async fn handle_add_mode_keys(
     Ok(())
 }
 
+/// Handle keys in the tag sidebar
+async fn handle_tags_mode_keys(
+    key: KeyEvent,
+    app: &mut TuiApp,
+    repository: &mut dyn BookmarkRepository,
+) -> BookmarkResult<()> {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.navigate_tags_down();
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.navigate_tags_up();
+        }
+        KeyCode::Enter => {
+            app.apply_tag_filter(repository).await?;
+            app.mode = ViewMode::List;
+        }
+        KeyCode::Esc | KeyCode::Char('t') | KeyCode::Char('T') => {
+            app.mode = ViewMode::List;
+        }
+        KeyCode::Char('q') | KeyCode::Char('Q') => {
+            app.should_quit = true;
+        }
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.should_quit = true;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Handle keys while the stats panel is open
+fn handle_stats_mode_keys(key: KeyEvent, app: &mut TuiApp) -> BookmarkResult<()> {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.navigate_down();
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.navigate_up();
+        }
+        KeyCode::Enter => {
+            if let Some(bookmark) = app.selected_bookmark() {
+                if let Err(e) = open_url(&bookmark.url) {
+                    app.set_message(TuiMessage::Error(format!("Failed to open URL: {}", e)));
+                } else {
+                    app.set_message(TuiMessage::Success(format!("Opened: {}", bookmark.title)));
+                }
+            }
+        }
+        KeyCode::Esc | KeyCode::Char('i') | KeyCode::Char('I') => {
+            app.mode = ViewMode::List;
+        }
+        KeyCode::Char('q') | KeyCode::Char('Q') => {
+            app.should_quit = true;
+        }
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.should_quit = true;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Handle keys in the saved search picker
+async fn handle_saved_searches_mode_keys(
+    key: KeyEvent,
+    app: &mut TuiApp,
+    repository: &mut dyn BookmarkRepository,
+) -> BookmarkResult<()> {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.navigate_saved_searches_down();
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.navigate_saved_searches_up();
+        }
+        KeyCode::Enter => {
+            app.apply_saved_search(repository).await?;
+            app.mode = ViewMode::List;
+        }
+        KeyCode::Esc => {
+            app.mode = ViewMode::List;
+        }
+        KeyCode::Char('q') | KeyCode::Char('Q') => {
+            app.should_quit = true;
+        }
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.should_quit = true;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
 /// Handle keys in delete confirmation mode
 async fn handle_delete_mode_keys(
     key: KeyEvent,
@@ -191,8 +344,9 @@ async fn handle_delete_mode_keys(
                 
                 match repository.delete(&bookmark_id).await {
                     Ok(_) => {
+                        app.last_deleted = Some(bookmark_id);
                         app.refresh_bookmarks(repository).await?;
-                        app.set_message(TuiMessage::Success(format!("Deleted bookmark: {}", title)));
+                        app.set_message(TuiMessage::Success(format!("Deleted bookmark: {} (press u to undo)", title)));
                     }
                     Err(e) => {
                         app.set_message(TuiMessage::Error(format!("Failed to delete bookmark: {}", e)));
@@ -235,28 +389,6 @@ fn extract_domain(url: &str) -> Option<String> {
         .map(|s| s.to_string())
 }
 
-/// Open URL in the default browser
-fn open_url(url: &str) -> Result<(), Box<dyn std::error::Error>> {
-    #[cfg(target_os = "macos")]
-    {
-        Command::new("open").arg(url).spawn()?;
-        Ok(())
-    }
-    #[cfg(target_os = "linux")]
-    {
-        Command::new("xdg-open").arg(url).spawn()?;
-        Ok(())
-    }
-    #[cfg(target_os = "windows")]
-    {
-        Command::new("cmd").args(["/C", "start", "", url]).spawn()?;
-        Ok(())
-    }
-    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
-    {
-        Err(format!("Opening URLs is not supported on this platform").into())
-    }
-}
 
 #[cfg(test)]
 mod tests {
@@ -291,6 +423,99 @@ mod tests {
         assert_eq!(app.selected_index, Some(0));
     }
 
+    #[tokio::test]
+    async fn test_delete_then_undo() {
+        let mut repo = MockBookmarkRepository::new();
+        let bookmark = Bookmark::new("https://example.com", "Example").unwrap();
+        let bookmark_id = bookmark.id.clone();
+        repo.create(bookmark).await.unwrap();
+
+        let mut app = TuiApp::new(&repo).await.unwrap();
+
+        handle_key_event(create_test_key_event(KeyCode::Char('d')), &mut app, &mut repo).await.unwrap();
+        handle_key_event(create_test_key_event(KeyCode::Char('y')), &mut app, &mut repo).await.unwrap();
+        assert!(app.bookmarks.is_empty());
+        assert_eq!(app.last_deleted, Some(bookmark_id.clone()));
+
+        handle_key_event(create_test_key_event(KeyCode::Char('u')), &mut app, &mut repo).await.unwrap();
+        assert_eq!(app.bookmarks.len(), 1);
+        assert_eq!(app.bookmarks[0].id, bookmark_id);
+        assert!(app.last_deleted.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stats_panel_opens_and_closes() {
+        let mut repo = MockBookmarkRepository::new();
+        repo.create(Bookmark::new("https://example1.com", "Example 1").unwrap()).await.unwrap();
+        repo.create(Bookmark::new("https://example2.com", "Example 2").unwrap()).await.unwrap();
+
+        let mut app = TuiApp::new(&repo).await.unwrap();
+        handle_key_event(create_test_key_event(KeyCode::Char('i')), &mut app, &mut repo).await.unwrap();
+        assert_eq!(app.mode, ViewMode::Stats);
+        assert_eq!(app.stats.as_ref().unwrap().total, 2);
+
+        // The list next to the panel stays navigable
+        handle_key_event(create_test_key_event(KeyCode::Down), &mut app, &mut repo).await.unwrap();
+        assert_eq!(app.selected_index, Some(1));
+
+        handle_key_event(create_test_key_event(KeyCode::Esc), &mut app, &mut repo).await.unwrap();
+        assert_eq!(app.mode, ViewMode::List);
+    }
+
+    #[tokio::test]
+    async fn test_tag_sidebar_filters_by_tag_tree() {
+        let mut repo = MockBookmarkRepository::new();
+        let work = Bookmark::new("https://example1.com", "Work").unwrap()
+            .with_tags(vec!["work/projects".to_string()]);
+        let rust = Bookmark::new("https://example2.com", "Rust").unwrap()
+            .with_tags(vec!["rust".to_string()]);
+        repo.create(work).await.unwrap();
+        repo.create(rust).await.unwrap();
+
+        let mut app = TuiApp::new(&repo).await.unwrap();
+        handle_key_event(create_test_key_event(KeyCode::Char('t')), &mut app, &mut repo).await.unwrap();
+        assert_eq!(app.mode, ViewMode::Tags);
+        let paths: Vec<&str> = app.tag_entries.iter().map(|entry| entry.path.as_str()).collect();
+        assert_eq!(paths, vec!["rust", "work", "work/projects"]);
+
+        // Select "work" and filter; the descendant tag matches
+        handle_key_event(create_test_key_event(KeyCode::Down), &mut app, &mut repo).await.unwrap();
+        handle_key_event(create_test_key_event(KeyCode::Enter), &mut app, &mut repo).await.unwrap();
+        assert_eq!(app.mode, ViewMode::List);
+        assert_eq!(app.bookmarks.len(), 1);
+        assert_eq!(app.bookmarks[0].title, "Work");
+
+        // Escape clears the filter
+        handle_key_event(create_test_key_event(KeyCode::Esc), &mut app, &mut repo).await.unwrap();
+        assert_eq!(app.bookmarks.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_saved_search_picker() {
+        use crate::types::{BookmarkFilters, SavedSearch};
+
+        let mut repo = MockBookmarkRepository::new();
+        let rust = Bookmark::new("https://example1.com", "Rust").unwrap()
+            .with_tags(vec!["rust".to_string()]);
+        let python = Bookmark::new("https://example2.com", "Python").unwrap();
+        repo.create(rust).await.unwrap();
+        repo.create(python).await.unwrap();
+        repo.save_search(SavedSearch {
+            name: "rust".to_string(),
+            filters: BookmarkFilters { tags: Some(vec!["rust".to_string()]), ..Default::default() },
+        }).await.unwrap();
+
+        let mut app = TuiApp::new(&repo).await.unwrap();
+        handle_key_event(create_test_key_event(KeyCode::Char('s')), &mut app, &mut repo).await.unwrap();
+        assert_eq!(app.mode, ViewMode::SavedSearches);
+        assert_eq!(app.saved_searches.len(), 1);
+
+        handle_key_event(create_test_key_event(KeyCode::Enter), &mut app, &mut repo).await.unwrap();
+        assert_eq!(app.mode, ViewMode::List);
+        assert_eq!(app.bookmarks.len(), 1);
+        assert_eq!(app.bookmarks[0].title, "Rust");
+    }
+
     #[tokio::test]
     async fn test_mode_transitions() {
         let mut repo = MockBookmarkRepository::new();
@@ -345,6 +570,45 @@ mod tests {
         assert_eq!(app.search_query, "");
     }
 
+    #[tokio::test]
+    async fn test_search_with_query_language() {
+        let mut repo = MockBookmarkRepository::new();
+        let rust = Bookmark::new("https://example1.com", "Rust").unwrap()
+            .with_tags(vec!["rust".to_string()])
+            .with_priority(5).unwrap();
+        let python = Bookmark::new("https://example2.com", "Python").unwrap()
+            .with_tags(vec!["python".to_string()]);
+        repo.create(rust).await.unwrap();
+        repo.create(python).await.unwrap();
+
+        let mut app = TuiApp::new(&repo).await.unwrap();
+        app.mode = ViewMode::Search;
+        app.search_query = "(tag:rust OR tag:go) priority>=4".to_string();
+        handle_key_event(create_test_key_event(KeyCode::Enter), &mut app, &mut repo).await.unwrap();
+        assert_eq!(app.mode, ViewMode::List);
+        assert_eq!(app.bookmarks.len(), 1);
+        assert_eq!(app.bookmarks[0].title, "Rust");
+
+        // A malformed query keeps the search bar open with an error
+        app.mode = ViewMode::Search;
+        app.search_query = "(tag:rust".to_string();
+        handle_key_event(create_test_key_event(KeyCode::Enter), &mut app, &mut repo).await.unwrap();
+        assert_eq!(app.mode, ViewMode::Search);
+        assert!(matches!(app.message, Some(TuiMessage::Error(_))));
+        assert_eq!(app.bookmarks.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_open_archive_without_copy() {
+        let mut repo = MockBookmarkRepository::new();
+        repo.create(Bookmark::new("https://example.com", "Example").unwrap()).await.unwrap();
+
+        let mut app = TuiApp::new(&repo).await.unwrap();
+        app.selected_index = Some(0);
+        handle_key_event(create_test_key_event(KeyCode::Char('o')), &mut app, &mut repo).await.unwrap();
+        assert!(matches!(app.message, Some(TuiMessage::Error(ref message)) if message.contains("automark archive")));
+    }
+
     #[tokio::test]
     #[ignore]
     async fn test_enter_opens_url() {
@@ -377,17 +641,43 @@ mod tests {
         assert_eq!(app.mode, ViewMode::Detail);
     }
 
-    #[test]
-    #[ignore]
-    fn test_open_url_function() {
-        // Test that open_url function doesn't panic with valid URLs
-        // We can't actually test browser opening in CI, but we can test the function exists
-        let result = open_url("https://example.com");
-        // The function should complete without panicking
-        // Result may be Ok or Err depending on the environment, which is fine
-        match result {
-            Ok(_) => {}, // Success case
-            Err(_) => {}, // Expected failure in CI environment
-        }
+    #[tokio::test]
+    async fn test_detail_offers_and_applies_tag_suggestions() {
+        let mut repo = MockBookmarkRepository::new();
+        let tagged = Bookmark::new("https://doc.rust-lang.org/book/", "The Rust Programming Language").unwrap()
+            .with_tags(vec!["rust".to_string()]);
+        repo.create(tagged).await.unwrap();
+        repo.create(Bookmark::new("https://blog.example.com/rust-traits", "Rust traits explained").unwrap()).await.unwrap();
+
+        let mut app = TuiApp::new(&repo).await.unwrap();
+        let untagged = app.bookmarks.iter().position(|b| b.tags.is_empty()).unwrap();
+        app.selected_index = Some(untagged);
+        handle_key_event(create_test_key_event(KeyCode::Char('e')), &mut app, &mut repo).await.unwrap();
+        assert_eq!(app.mode, ViewMode::Detail);
+        assert_eq!(app.tag_suggestions, vec!["rust"]);
+        assert_eq!(app.related.len(), 1);
+        assert_eq!(app.related[0].bookmark.title, "The Rust Programming Language");
+
+        handle_key_event(create_test_key_event(KeyCode::Char('g')), &mut app, &mut repo).await.unwrap();
+        assert!(app.tag_suggestions.is_empty());
+        let bookmarks = repo.find_all(None).await.unwrap();
+        assert!(bookmarks.iter().all(|b| b.tags == vec!["rust"]));
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_applying_suggestions_skips_tags_under_another_spelling() {
+        let mut repo = MockBookmarkRepository::new();
+        repo.create(Bookmark::new("https://example.com", "Example").unwrap().with_tags(vec!["rust".to_string()]))
+            .await.unwrap();
+
+        let mut app = TuiApp::new(&repo).await.unwrap();
+        app.selected_index = Some(0);
+        app.mode = ViewMode::Detail;
+        app.tag_suggestions = vec![" Rust ".to_string(), " Web/Async ".to_string()];
+        handle_key_event(create_test_key_event(KeyCode::Char('g')), &mut app, &mut repo).await.unwrap();
+
+        let bookmarks = repo.find_all(None).await.unwrap();
+        assert_eq!(bookmarks[0].tags, vec!["rust", "web/async"]);
+        assert!(matches!(app.message, Some(TuiMessage::Success(ref message)) if message == "Added tags: web/async"));
+    }
+}
@@ -1,10 +1,13 @@
 #![allow(dead_code)]
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use uuid::Uuid;
 use url::Url;
 
 use super::{BookmarkError, BookmarkResult};
+use super::QueryExpr;
+use super::tag_tree::TAG_SEPARATOR;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Bookmark {
@@ -18,6 +21,87 @@ pub struct Bookmark {
     pub notes: Vec<Note>,
     pub reading_status: ReadingStatus,
     pub priority_rating: Option<u8>,
+    /// When the bookmark was last marked as being read
+    #[serde(default)]
+    pub started_at: Option<DateTime<Utc>>,
+    /// When the bookmark was marked as completed
+    #[serde(default)]
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Summary of the page from its description metadata
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Name of the site the page belongs to (e.g. "The Rust Blog")
+    #[serde(default)]
+    pub site_name: Option<String>,
+    /// The URL the page declares as its preferred address
+    #[serde(default)]
+    pub canonical_url: Option<String>,
+    /// Preview image for the page
+    #[serde(default)]
+    pub image_url: Option<String>,
+    /// Language of the page as a BCP 47 tag (e.g. `en-US`)
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Icon the site declares for itself
+    #[serde(default)]
+    pub favicon_url: Option<String>,
+    /// Site-specific facts such as GitHub stars or video duration, keyed by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub site_details: BTreeMap<String, String>,
+    /// Offline copy of the page, relative to the data directory
+    #[serde(default)]
+    pub archive_path: Option<String>,
+    /// When the offline copy was taken
+    #[serde(default)]
+    pub archived_at: Option<DateTime<Utc>>,
+    /// Result of the last `automark check` of the URL
+    #[serde(default)]
+    pub link_status: Option<LinkStatus>,
+    /// Metadata extraction was skipped or failed and should be retried when online
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub metadata_pending: bool,
+}
+
+/// Outcome of probing a bookmark's URL
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LinkStatus {
+    pub checked_at: DateTime<Utc>,
+    /// HTTP status of the final response, if one was received
+    pub status_code: Option<u16>,
+    /// Where the URL ended up after following redirects
+    pub redirected_to: Option<String>,
+    /// Whether every redirect on the way was permanent (301 or 308)
+    pub permanent_redirect: bool,
+    /// Why no response was received (DNS failure, refused connection, timeout)
+    pub error: Option<String>,
+}
+
+impl LinkStatus {
+    /// A link is dead if it failed outright or ended in a 4xx/5xx response
+    pub fn is_dead(&self) -> bool {
+        self.error.is_some() || self.status_code.is_some_and(|code| code >= 400)
+    }
+
+    /// The URL moved for good and the new location works
+    pub fn moved_permanently(&self) -> bool {
+        self.permanent_redirect && self.redirected_to.is_some() && !self.is_dead()
+    }
+
+    /// Short description such as `404`, `301 -> https://...` or the error
+    pub fn describe(&self) -> String {
+        let status = match (&self.error, self.status_code) {
+            (Some(error), _) => error.clone(),
+            (None, Some(code)) => code.to_string(),
+            (None, None) => "no response".to_string(),
+        };
+        match self.redirected_to {
+            Some(ref target) => {
+                let kind = if self.permanent_redirect { "moved to" } else { "redirects to" };
+                format!("{} ({} {})", status, kind, target)
+            }
+            None => status,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -27,6 +111,28 @@ pub struct Note {
     pub created_at: DateTime<Utc>,
 }
 
+/// A bookmark that has been moved to the trash and can still be restored
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrashedBookmark {
+    pub bookmark: Bookmark,
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// A set of changes applied to the repository as a single unit
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BookmarkBatch {
+    /// Bookmarks to update in place (matched by ID)
+    pub updates: Vec<Bookmark>,
+    /// IDs of bookmarks to move to the trash
+    pub deletes: Vec<String>,
+}
+
+impl BookmarkBatch {
+    pub fn is_empty(&self) -> bool {
+        self.updates.is_empty() && self.deletes.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, clap::ValueEnum)]
 pub enum ReadingStatus {
     Unread,
@@ -47,6 +153,81 @@ pub struct BookmarkFilters {
     pub published_until: Option<DateTime<Utc>>,
     pub sort_by: Option<SortBy>,
     pub sort_order: Option<SortDirection>,
+    /// Whether a tag filter also matches descendant tags (`work` matches `work/projects`)
+    #[serde(default)]
+    pub include_descendant_tags: bool,
+    /// Parsed query language expression (`tag:rust -status:completed priority>=4`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<QueryExpr>,
+    /// Only bookmarks whose last link check found them dead
+    #[serde(default)]
+    pub dead_links: bool,
+}
+
+impl BookmarkFilters {
+    /// Apply a search string, keeping plain words as a substring search
+    /// and parsing anything else with the query language
+    pub fn with_search_input(mut self, input: &str) -> BookmarkResult<Self> {
+        self.text_query = None;
+        self.query = None;
+        match QueryExpr::parse(input)? {
+            Some(expr) if expr.is_plain_text() => self.text_query = Some(expr.to_plain_text()),
+            Some(expr) => self.query = Some(expr),
+            None => {}
+        }
+        Ok(self)
+    }
+
+    /// Short human-readable description of the filters
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(ref query) = self.text_query {
+            parts.push(format!("\"{}\"", query));
+        }
+        if let Some(ref query) = self.query {
+            parts.push(format!("query: {}", query));
+        }
+        if let Some(ref tags) = self.tags {
+            let suffix = if self.include_descendant_tags { " (+descendants)" } else { "" };
+            parts.push(format!("tags: {}{}", tags.join(", "), suffix));
+        }
+        if let Some(ref status) = self.reading_status {
+            parts.push(format!("status: {:?}", status));
+        }
+        if let Some((min, max)) = self.priority_range {
+            if min == max {
+                parts.push(format!("priority: {}", min));
+            } else {
+                parts.push(format!("priority: {}-{}", min, max));
+            }
+        }
+        if self.bookmarked_since.is_some() || self.bookmarked_until.is_some() {
+            parts.push("date range".to_string());
+        }
+        if self.published_since.is_some() || self.published_until.is_some() {
+            parts.push("publish date range".to_string());
+        }
+        if self.dead_links {
+            parts.push("dead links".to_string());
+        }
+        if let Some(ref sort_by) = self.sort_by {
+            let order = self.sort_order.clone().unwrap_or(SortDirection::Descending);
+            parts.push(format!("sort: {:?} {:?}", sort_by, order));
+        }
+
+        if parts.is_empty() {
+            "all bookmarks".to_string()
+        } else {
+            parts.join(" | ")
+        }
+    }
+}
+
+/// A named set of filters that can be re-run later
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedSearch {
+    pub name: String,
+    pub filters: BookmarkFilters,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, clap::ValueEnum)]
@@ -55,6 +236,8 @@ pub enum SortBy {
     PublishDate,
     Title,
     Priority,
+    /// Search relevance (BM25 score); only meaningful with a text query
+    Relevance,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, clap::ValueEnum)]
@@ -63,11 +246,41 @@ pub enum SortDirection {
     Descending,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct ExtractedMetadata {
     pub title: Option<String>,
     pub author: Option<String>,
     pub publish_date: Option<DateTime<Utc>>,
+    /// Main readable text of the page, if any was found
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub site_name: Option<String>,
+    #[serde(default)]
+    pub canonical_url: Option<String>,
+    #[serde(default)]
+    pub image_url: Option<String>,
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub favicon_url: Option<String>,
+    /// Facts only a site-specific extractor knows, e.g. `stars` for a GitHub repository
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub site_details: BTreeMap<String, String>,
+}
+
+/// Normalize a tag for storage and comparison
+///
+/// Tags are lowercased, and each level of a hierarchical tag is trimmed with
+/// empty levels dropped, so ` Work//Projects/ ` becomes `work/projects`.
+pub fn normalize_tag(tag: &str) -> String {
+    tag.split(TAG_SEPARATOR)
+        .map(|segment| segment.trim().to_lowercase())
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join(&TAG_SEPARATOR.to_string())
 }
 
 impl Bookmark {
@@ -91,14 +304,79 @@ impl Bookmark {
             notes: Vec::new(),
             reading_status: ReadingStatus::Unread,
             priority_rating: None,
+            started_at: None,
+            completed_at: None,
+            description: None,
+            site_name: None,
+            canonical_url: None,
+            image_url: None,
+            language: None,
+            favicon_url: None,
+            site_details: BTreeMap::new(),
+            archive_path: None,
+            archived_at: None,
+            link_status: None,
+            metadata_pending: false,
         })
     }
 
     pub fn with_tags(mut self, tags: Vec<String>) -> Self {
-        self.tags = tags.into_iter().map(|tag| tag.to_lowercase()).collect();
+        self.tags = tags.iter().map(|tag| normalize_tag(tag)).collect();
         self
     }
 
+    /// Remove any of the given tags (compared after normalization)
+    ///
+    /// Returns true if the bookmark's tags changed.
+    pub fn remove_tags(&mut self, tags: &[String]) -> bool {
+        let tags: Vec<String> = tags.iter().map(|tag| normalize_tag(tag)).collect();
+        let before = self.tags.len();
+        self.tags.retain(|tag| !tags.contains(&normalize_tag(tag)));
+        self.tags.len() != before
+    }
+
+    /// Rename any of the `from` tags, and their descendants, to `to`
+    ///
+    /// `work` renamed to `job` also turns `work/projects` into `job/projects`.
+    /// Renamed tags keep their position and are not duplicated if the bookmark
+    /// already has the new name. Returns true if the bookmark's tags changed.
+    pub fn rename_tags(&mut self, from: &[String], to: &str) -> bool {
+        let from: Vec<String> = from.iter().map(|tag| normalize_tag(tag)).collect();
+        let to = normalize_tag(to);
+
+        let mut changed = false;
+        let mut seen = std::collections::HashSet::new();
+        let mut updated = Vec::with_capacity(self.tags.len());
+        for tag in &self.tags {
+            let normalized = normalize_tag(tag);
+            let renamed = from.iter().find_map(|source| {
+                if normalized == *source {
+                    Some(to.clone())
+                } else {
+                    normalized.strip_prefix(source.as_str())
+                        .and_then(|rest| rest.strip_prefix(TAG_SEPARATOR))
+                        .map(|rest| format!("{}{}{}", to, TAG_SEPARATOR, rest))
+                }
+            });
+
+            let tag = match renamed {
+                Some(renamed) => {
+                    changed = true;
+                    renamed
+                }
+                None => tag.clone(),
+            };
+            if seen.insert(normalize_tag(&tag)) {
+                updated.push(tag);
+            }
+        }
+
+        if changed {
+            self.tags = updated;
+        }
+        changed
+    }
+
     pub fn with_priority(mut self, priority: u8) -> BookmarkResult<Self> {
         if !(1..=5).contains(&priority) {
             return Err(BookmarkError::InvalidId(format!("Priority must be between 1 and 5, got {}", priority)));
@@ -107,6 +385,30 @@ impl Bookmark {
         Ok(self)
     }
 
+    /// Change the reading status, recording when reading started and finished
+    ///
+    /// Starting clears any earlier completion; going back to unread clears both
+    /// timestamps. Returns true if the status changed.
+    pub fn set_reading_status(&mut self, status: ReadingStatus) -> bool {
+        if self.reading_status == status {
+            return false;
+        }
+        let now = Utc::now();
+        match status {
+            ReadingStatus::Unread => {
+                self.started_at = None;
+                self.completed_at = None;
+            }
+            ReadingStatus::Reading => {
+                self.started_at = Some(now);
+                self.completed_at = None;
+            }
+            ReadingStatus::Completed => self.completed_at = Some(now),
+        }
+        self.reading_status = status;
+        true
+    }
+
     pub fn add_note(&mut self, content: &str) -> String {
         let note = Note::new(content);
         let note_id = note.id.clone();
@@ -161,6 +463,44 @@ mod tests {
         assert_eq!(bookmark.tags, vec!["rust", "programming", "web"]);
     }
 
+    #[test]
+    fn test_normalize_hierarchical_tag() {
+        assert_eq!(normalize_tag(" Work//Projects/ "), "work/projects");
+        assert_eq!(normalize_tag("Rust"), "rust");
+        assert_eq!(normalize_tag(" / "), "");
+    }
+
+    #[test]
+    fn test_rename_and_remove_tags() {
+        let mut bookmark = Bookmark::new("https://example.com", "Test").unwrap()
+            .with_tags(vec!["js".to_string(), "web".to_string(), "javascript".to_string()]);
+
+        // Merge two tags into one, keeping the position of the first match
+        assert!(bookmark.rename_tags(&["JS".to_string(), "javascript".to_string()], "JavaScript"));
+        assert_eq!(bookmark.tags, vec!["javascript", "web"]);
+
+        // Renaming onto an existing tag does not duplicate it
+        assert!(bookmark.rename_tags(&["web".to_string()], "javascript"));
+        assert_eq!(bookmark.tags, vec!["javascript"]);
+
+        // No match leaves tags untouched
+        assert!(!bookmark.rename_tags(&["missing".to_string()], "other"));
+
+        // Removing a tag
+        assert!(bookmark.remove_tags(&["javascript".to_string()]));
+        assert!(bookmark.tags.is_empty());
+        assert!(!bookmark.remove_tags(&["javascript".to_string()]));
+    }
+
+    #[test]
+    fn test_rename_tags_moves_descendants() {
+        let mut bookmark = Bookmark::new("https://example.com", "Test").unwrap()
+            .with_tags(vec!["work".to_string(), "work/projects/automark".to_string(), "workshop".to_string()]);
+
+        assert!(bookmark.rename_tags(&["work".to_string()], "job"));
+        assert_eq!(bookmark.tags, vec!["job", "job/projects/automark", "workshop"]);
+    }
+
     #[test]
     fn test_priority_validation() {
         let bookmark = Bookmark::new("https://example.com", "Test").unwrap();
@@ -214,6 +554,23 @@ mod tests {
         assert_eq!(status, deserialized);
     }
 
+    #[test]
+    fn test_set_reading_status_records_timestamps() {
+        let mut bookmark = Bookmark::new("https://example.com", "Example").unwrap();
+        assert!(!bookmark.set_reading_status(ReadingStatus::Unread));
+
+        assert!(bookmark.set_reading_status(ReadingStatus::Reading));
+        let started_at = bookmark.started_at;
+        assert!(started_at.is_some() && bookmark.completed_at.is_none());
+
+        assert!(bookmark.set_reading_status(ReadingStatus::Completed));
+        assert_eq!(bookmark.started_at, started_at);
+        assert!(bookmark.completed_at.is_some());
+
+        assert!(bookmark.set_reading_status(ReadingStatus::Unread));
+        assert_eq!((bookmark.started_at, bookmark.completed_at), (None, None));
+    }
+
     #[test]
     fn test_bookmark_filters_creation() {
         let filters = BookmarkFilters {
@@ -227,6 +584,9 @@ mod tests {
             published_until: None,
             sort_by: Some(SortBy::BookmarkedDate),
             sort_order: Some(SortDirection::Descending),
+            include_descendant_tags: false,
+            query: None,
+            dead_links: false,
         };
         
         assert_eq!(filters.text_query, Some("rust".to_string()));
@@ -253,6 +613,9 @@ mod tests {
             published_until: Some(now),
             sort_by: Some(SortBy::Title),
             sort_order: Some(SortDirection::Ascending),
+            include_descendant_tags: false,
+            query: None,
+            dead_links: false,
         };
         
         assert_eq!(filters.bookmarked_since, Some(one_day_ago));
@@ -263,6 +626,23 @@ mod tests {
         assert_eq!(filters.sort_order, Some(SortDirection::Ascending));
     }
 
+    #[test]
+    fn test_describe_filters() {
+        let filters = BookmarkFilters {
+            tags: Some(vec!["rust".to_string()]),
+            reading_status: Some(ReadingStatus::Unread),
+            priority_range: Some((4, 5)),
+            sort_by: Some(SortBy::Priority),
+            sort_order: Some(SortDirection::Descending),
+            ..Default::default()
+        };
+        assert_eq!(
+            filters.describe(),
+            "tags: rust | status: Unread | priority: 4-5 | sort: Priority Descending"
+        );
+        assert_eq!(BookmarkFilters::default().describe(), "all bookmarks");
+    }
+
     #[test]
     fn test_sort_enums() {
         // Test SortBy variants
@@ -317,6 +697,7 @@ mod tests {
             title: Some("Test Title".to_string()),
             author: Some("Test Author".to_string()),
             publish_date: Some(Utc::now()),
+            ..Default::default()
         };
         
         assert_eq!(metadata.title, Some("Test Title".to_string()));
@@ -332,6 +713,10 @@ mod tests {
             title: Some("Test Title".to_string()),
             author: None,
             publish_date: None,
+            content: Some("Body text".to_string()),
+            description: Some("A summary".to_string()),
+            site_name: Some("Example".to_string()),
+            ..Default::default()
         };
         
         let json = serde_json::to_string(&metadata).unwrap();
@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use thiserror::Error;
 
+use super::{Rule, RuleSet};
+
 /// Configuration errors
 #[derive(Debug, Error)]
 pub enum ConfigError {
@@ -23,6 +26,13 @@ pub struct Config {
     pub sync: SyncConfig,
     #[serde(default)]
     pub metadata: MetadataConfig,
+    #[serde(default)]
+    pub check: CheckConfig,
+    #[serde(default)]
+    pub cache: CacheConfig,
+    /// Rules that tag and prioritize bookmarks when they are added or refreshed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<Rule>,
 }
 
 /// Storage configuration settings
@@ -50,6 +60,7 @@ pub struct SyncConfig {
 
 /// Metadata extraction configuration settings
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MetadataConfig {
     /// Enable metadata extraction by default
     pub enabled: bool,
@@ -61,6 +72,56 @@ pub struct MetadataConfig {
     pub retry_attempts: u32,
     /// Retry delay in milliseconds
     pub retry_delay_ms: u64,
+    /// Store the readable text of pages so searches can match article bodies
+    pub capture_content: bool,
+    /// Skip pages that robots.txt disallows for `user_agent`; turn off for personal use at your own discretion
+    pub respect_robots_txt: bool,
+    /// Sustained requests per second to a single host (0 disables the limit)
+    pub per_host_requests_per_sec: f64,
+    /// Requests to a host that may be made at once before the rate limit applies
+    pub per_host_burst: u32,
+    /// Largest page body that will be downloaded, in bytes
+    pub max_page_bytes: u64,
+    /// Largest PDF or image that will be downloaded, in bytes
+    pub max_document_bytes: u64,
+    /// Redirects followed before giving up on a page
+    pub max_redirects: usize,
+    /// Refuse to fetch pages on localhost, private networks or cloud metadata addresses
+    pub block_private_addresses: bool,
+    /// Hosts, with their subdomains, fetched even though they are on a private network
+    pub allow_private_hosts: Vec<String>,
+    /// Number of pages `automark refresh` fetches at once
+    pub refresh_concurrency: usize,
+    /// Fill in metadata for bookmarks added offline when the next command runs
+    pub auto_fill_pending: bool,
+    /// HTTP or HTTPS proxy that all requests go through
+    pub proxy: Option<String>,
+    /// PEM file with extra certificate authorities to trust, e.g. a corporate TLS proxy's
+    pub ca_bundle: Option<String>,
+    /// Cookie files in Netscape `cookies.txt` format, sent to the sites they belong to
+    pub cookie_files: Vec<String>,
+    /// Extra request headers by domain; each applies to the domain and its subdomains
+    pub headers: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+/// Link checking configuration settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CheckConfig {
+    /// Number of links checked at once
+    pub concurrency: usize,
+    /// Minimum delay between requests to the same host, in milliseconds
+    pub per_host_delay_ms: u64,
+}
+
+/// HTTP cache configuration settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Keep downloaded pages in the data directory and reuse them while they are fresh
+    pub enabled: bool,
+    /// Largest total size of cached responses, in bytes
+    pub max_bytes: u64,
 }
 
 impl Default for StorageConfig {
@@ -91,6 +152,39 @@ impl Default for MetadataConfig {
             user_agent: "automark/1.0 (https://github.com/evcraddock/automark)".to_string(),
             retry_attempts: 2,
             retry_delay_ms: 1000,
+            capture_content: false,
+            respect_robots_txt: true,
+            per_host_requests_per_sec: 1.0,
+            per_host_burst: 2,
+            max_page_bytes: 5 * 1024 * 1024,
+            max_document_bytes: 50 * 1024 * 1024,
+            max_redirects: 5,
+            block_private_addresses: true,
+            allow_private_hosts: Vec::new(),
+            refresh_concurrency: 4,
+            auto_fill_pending: true,
+            proxy: None,
+            ca_bundle: None,
+            cookie_files: Vec::new(),
+            headers: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for CheckConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: 8,
+            per_host_delay_ms: 1000,
+        }
+    }
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_bytes: 100 * 1024 * 1024,
         }
     }
 }
@@ -113,6 +207,8 @@ impl Config {
             ));
         }
         
+        RuleSet::new(&self.rules).map_err(|e| ConfigError::Validation(e.to_string()))?;
+        
         Ok(())
     }
     
@@ -159,6 +255,85 @@ retry_attempts = 2
 
 # Retry delay in milliseconds
 retry_delay_ms = 1000
+
+# Store the readable text of each page so search can match inside articles
+# Text is kept in the data directory, not in the synced bookmark document
+capture_content = false
+
+# Honor robots.txt rules for the user agent above
+respect_robots_txt = true
+
+# Requests per second allowed to any single site, after an initial burst
+# Set per_host_requests_per_sec to 0 to disable rate limiting
+per_host_requests_per_sec = 1.0
+per_host_burst = 2
+
+# Pages larger than this many bytes are not downloaded
+max_page_bytes = 5242880
+
+# PDFs and images larger than this many bytes are not downloaded
+max_document_bytes = 52428800
+
+# Redirects followed before giving up on a page
+max_redirects = 5
+
+# Refuse to fetch localhost, private network and cloud metadata addresses
+# Turn off to bookmark pages on your own network
+block_private_addresses = true
+
+# Sites on your own network to fetch anyway, with their subdomains
+# allow_private_hosts = ["wiki.internal.example.com", "192.168.1.20"]
+
+# Number of pages `automark refresh` fetches at once
+refresh_concurrency = 4
+
+# Fill in metadata for bookmarks added offline (or with --async-fetch) when the next command runs
+auto_fill_pending = true
+
+# Send every request through an HTTP or HTTPS proxy
+# The proxy looks up host names itself, so block_private_addresses then only
+# refuses addresses written directly in URLs
+# proxy = "http://proxy.example.com:8080"
+
+# Trust extra certificate authorities from a PEM file, e.g. for a corporate TLS proxy
+# ca_bundle = "~/.config/automark/corporate-ca.pem"
+
+# Send cookies exported in Netscape cookies.txt format to the sites they belong to
+# cookie_files = ["~/.config/automark/wiki-cookies.txt"]
+
+# Extra headers for a site and its subdomains
+# [metadata.headers."wiki.example.com"]
+# Authorization = "Bearer your-token"
+
+[check]
+# Number of links `automark check` probes at once
+concurrency = 8
+
+# Minimum delay between requests to the same host, in milliseconds
+per_host_delay_ms = 1000
+
+[cache]
+# Reuse downloaded pages when refreshing, archiving or re-extracting metadata,
+# following the server's ETag, Last-Modified and Cache-Control headers
+# Clear it with `automark cache clear`
+enabled = true
+
+# Largest total size of cached responses, in bytes; the oldest are removed first
+max_bytes = 104857600
+
+# Rules tag and prioritize bookmarks when they are added or refreshed
+# `match` takes a search query; domain:example.com also matches subdomains and
+# values written ~/like this/ are regular expressions. A rule only sets a priority
+# when the bookmark has none. Apply rules to existing bookmarks with
+# `automark rules apply --all` and try them with `automark rules test <url>`
+# [[rules]]
+# match = "domain:github.com"
+# add_tags = ["code"]
+#
+# [[rules]]
+# name = "RFCs"
+# match = 'title:~/RFC \d+/'
+# priority = 4
 "#.to_string()
     }
 }
@@ -295,6 +470,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_config_validate_rules() {
+        let mut config = Config::default();
+        config.rules.push(Rule { name: None, query: "status:maybe".to_string(), add_tags: vec![], priority: None });
+
+        match config.validate() {
+            Err(ConfigError::Validation(msg)) => assert!(msg.contains("Rule 1")),
+            _ => panic!("Expected Validation error"),
+        }
+    }
+
     #[test]
     fn test_config_validate_absolute_path() {
         let mut config = Config::default();
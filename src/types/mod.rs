@@ -1,8 +1,14 @@
 pub mod bookmark;
 pub mod config;
+pub mod query;
+pub mod rule;
+pub mod tag_tree;
 
-pub use bookmark::{Bookmark, Note, ReadingStatus, BookmarkFilters, ExtractedMetadata, SortBy, SortDirection};
-pub use config::{Config, ConfigError, ConfigResult};
+pub use bookmark::{Bookmark, Note, ReadingStatus, BookmarkFilters, ExtractedMetadata, SortBy, SortDirection, TrashedBookmark, BookmarkBatch, SavedSearch, LinkStatus, normalize_tag};
+pub use config::{Config, ConfigError, ConfigResult, MetadataConfig};
+pub use query::QueryExpr;
+pub use rule::{AppliedRule, Rule, RuleSet};
+pub use tag_tree::{TagNode, TagTreeEntry, build_tag_tree, flatten_tag_tree, tag_matches};
 
 use thiserror::Error;
 
@@ -14,6 +20,31 @@ pub enum ExtractorError {
     Timeout,
     #[error("Invalid URL: {0}")]
     InvalidUrl(String),
+    #[error("Blocked by robots.txt: {0}")]
+    BlockedByRobots(String),
+    #[error("Page is larger than {0} bytes")]
+    TooLarge(u64),
+    #[error("Unsupported content type: {0}")]
+    UnsupportedContentType(String),
+    #[error("Too many redirects (more than {0})")]
+    TooManyRedirects(usize),
+    #[error("Refusing to fetch private network address: {0}")]
+    PrivateAddress(String),
+    #[error("Invalid fetch settings: {0}")]
+    InvalidSettings(String),
+    #[error("Server responded with HTTP {0}")]
+    HttpStatus(u16),
+}
+
+impl ExtractorError {
+    /// Whether trying again might succeed; robots.txt, size, address and client error refusals won't change
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::NetworkError(_) | Self::Timeout => true,
+            Self::HttpStatus(status) => *status == 429 || *status >= 500,
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug, Error)]
@@ -30,6 +61,10 @@ pub enum BookmarkError {
     MetadataExtraction(#[from] ExtractorError),
     #[error("Sync failed: {0}")]
     SyncError(String),
+    #[error("Invalid query: {0}")]
+    InvalidQuery(String),
+    #[error("Archive failed: {0}")]
+    ArchiveError(String),
     #[error("Terminal I/O error: {0}")]
     TerminalError(#[from] std::io::Error),
 }
@@ -53,6 +88,9 @@ mod tests {
 
         let invalid_id_error = BookmarkError::InvalidId("ambiguous".to_string());
         assert_eq!(invalid_id_error.to_string(), "Invalid or ambiguous ID: ambiguous");
+
+        let invalid_query_error = BookmarkError::InvalidQuery("Unmatched ')'".to_string());
+        assert_eq!(invalid_query_error.to_string(), "Invalid query: Unmatched ')'");
     }
 
     #[test]
@@ -83,6 +121,11 @@ mod tests {
         let invalid_url_error = ExtractorError::InvalidUrl("bad-url".to_string());
         assert!(matches!(invalid_url_error, ExtractorError::InvalidUrl(_)));
         assert_eq!(invalid_url_error.to_string(), "Invalid URL: bad-url");
+
+        assert!(ExtractorError::HttpStatus(503).is_retryable());
+        assert!(ExtractorError::HttpStatus(429).is_retryable());
+        assert!(!ExtractorError::HttpStatus(404).is_retryable());
+        assert_eq!(ExtractorError::HttpStatus(404).to_string(), "Server responded with HTTP 404");
     }
 
     #[test]
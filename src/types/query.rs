@@ -0,0 +1,708 @@
+use chrono::NaiveDate;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use url::Url;
+
+use super::bookmark::{normalize_tag, Bookmark, ReadingStatus};
+use super::tag_tree::tag_matches;
+use super::{BookmarkError, BookmarkResult};
+
+/// Comparison operator for numeric and date fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Comparison {
+    Eq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl Comparison {
+    fn holds<T: PartialOrd>(self, left: T, right: T) -> bool {
+        match self {
+            Comparison::Eq => left == right,
+            Comparison::Gt => left > right,
+            Comparison::Ge => left >= right,
+            Comparison::Lt => left < right,
+            Comparison::Le => left <= right,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Comparison::Eq => "",
+            Comparison::Gt => ">",
+            Comparison::Ge => ">=",
+            Comparison::Lt => "<",
+            Comparison::Le => "<=",
+        }
+    }
+}
+
+/// Text field that can be matched with a regular expression
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextField {
+    Author,
+    Title,
+    Url,
+    Note,
+}
+
+impl TextField {
+    fn name(self) -> &'static str {
+        match self {
+            TextField::Author => "author",
+            TextField::Title => "title",
+            TextField::Url => "url",
+            TextField::Note => "note",
+        }
+    }
+}
+
+/// Case-insensitive regular expression, written as `~/pattern/` in a query (`title:~/RFC \d+/`)
+///
+/// The `~` keeps values that merely start and end with a slash, like `url:/docs/`, substrings;
+/// a slash-delimited value using regex syntax without the `~` is rejected.
+#[derive(Debug, Clone)]
+pub struct Pattern(Regex);
+
+impl Pattern {
+    pub fn new(pattern: &str) -> BookmarkResult<Self> {
+        RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .map(Pattern)
+            .map_err(|e| BookmarkError::InvalidQuery(format!("Invalid pattern /{}/: {}", pattern, e)))
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    fn is_match(&self, text: &str) -> bool {
+        self.0.is_match(text)
+    }
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Serialize for Pattern {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Pattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        Pattern::new(&pattern).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A single condition in a search query
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum QueryTerm {
+    /// Free text matched against title, URL, author and notes
+    Text(String),
+    /// Tag match; `include_descendants` is set by a trailing `/*` (`tag:work/*`)
+    Tag { tag: String, include_descendants: bool },
+    Status(ReadingStatus),
+    Priority(Comparison, u8),
+    Added(Comparison, NaiveDate),
+    Published(Comparison, NaiveDate),
+    Author(String),
+    Title(String),
+    Url(String),
+    Note(String),
+    /// Site the bookmark is on, including subdomains (`domain:github.com` matches `gist.github.com`)
+    Domain(String),
+    /// Regular expression on a text field
+    Regex(TextField, Pattern),
+}
+
+/// Parsed search query
+///
+/// Terms separated by whitespace are AND-ed; `OR` (or `|`) has lower precedence,
+/// parentheses group, and `-` or `NOT` negates. For example:
+/// `tag:rust (tag:async OR tag:tokio) -status:completed priority>=4 added:>2024-01-01 author:"Jane"`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum QueryExpr {
+    And(Vec<QueryExpr>),
+    Or(Vec<QueryExpr>),
+    Not(Box<QueryExpr>),
+    Term(QueryTerm),
+}
+
+const FIELDS: &[&str] = &["tag", "status", "priority", "added", "published", "author", "title", "url", "note", "domain"];
+
+/// Fields whose values are matched as text and so may be regular expressions
+const TEXT_FIELDS: &[&str] = &["author", "title", "url", "note"];
+
+impl QueryExpr {
+    /// Parse a query string, returning None if it contains no terms
+    ///
+    /// Words that only look like fields, such as `Rust:` in `Rust: the book`, are
+    /// plain text unless the rest of the query uses fields or operators, in which
+    /// case they are reported as mistakes.
+    pub fn parse(input: &str) -> BookmarkResult<Option<QueryExpr>> {
+        let mut tokens = tokenize(input, false)?;
+        if tokens.iter().any(|token| !matches!(token, Token::Term(QueryTerm::Text(_)))) {
+            tokens = tokenize(input, true)?;
+        }
+        if tokens.is_empty() {
+            return Ok(None);
+        }
+
+        let mut parser = Parser { tokens, position: 0 };
+        let expr = parser.parse_or()?;
+        match parser.peek() {
+            None => Ok(Some(expr)),
+            Some(Token::RParen) => Err(BookmarkError::InvalidQuery("Unmatched ')'".to_string())),
+            Some(token) => Err(BookmarkError::InvalidQuery(format!("Unexpected {}", token))),
+        }
+    }
+
+    /// Whether the query is only free text with no fields, operators or grouping
+    pub fn is_plain_text(&self) -> bool {
+        match self {
+            QueryExpr::Term(QueryTerm::Text(_)) => true,
+            QueryExpr::And(children) => children.iter().all(|child| matches!(child, QueryExpr::Term(QueryTerm::Text(_)))),
+            _ => false,
+        }
+    }
+
+    /// Join the words of a plain-text query back into a single search string,
+    /// keeping the quotes around phrases so the search can rank them
+    pub fn to_plain_text(&self) -> String {
+        match self {
+            QueryExpr::Term(QueryTerm::Text(text)) => quote_if_needed(text),
+            QueryExpr::And(children) => children.iter().map(|child| child.to_plain_text()).collect::<Vec<_>>().join(" "),
+            _ => self.to_string(),
+        }
+    }
+
+    /// Evaluate the query against a bookmark
+    pub fn matches(&self, bookmark: &Bookmark) -> bool {
+        match self {
+            QueryExpr::And(children) => children.iter().all(|child| child.matches(bookmark)),
+            QueryExpr::Or(children) => children.iter().any(|child| child.matches(bookmark)),
+            QueryExpr::Not(child) => !child.matches(bookmark),
+            QueryExpr::Term(term) => term.matches(bookmark),
+        }
+    }
+}
+
+impl QueryTerm {
+    fn matches(&self, bookmark: &Bookmark) -> bool {
+        let contains = |haystack: &str, needle: &str| haystack.to_lowercase().contains(&needle.to_lowercase());
+
+        match self {
+            QueryTerm::Text(text) => {
+                contains(&bookmark.title, text)
+                    || contains(&bookmark.url, text)
+                    || bookmark.author.as_deref().is_some_and(|author| contains(author, text))
+                    || bookmark.description.as_deref().is_some_and(|description| contains(description, text))
+                    || bookmark.notes.iter().any(|note| contains(&note.content, text))
+            }
+            QueryTerm::Tag { tag, include_descendants } => bookmark.tags.iter()
+                .any(|bookmark_tag| tag_matches(tag, &normalize_tag(bookmark_tag), *include_descendants)),
+            QueryTerm::Status(status) => bookmark.reading_status == *status,
+            QueryTerm::Priority(comparison, value) => bookmark.priority_rating
+                .is_some_and(|priority| comparison.holds(priority, *value)),
+            QueryTerm::Added(comparison, date) => comparison.holds(bookmark.bookmarked_date.date_naive(), *date),
+            QueryTerm::Published(comparison, date) => bookmark.publish_date
+                .is_some_and(|published| comparison.holds(published.date_naive(), *date)),
+            QueryTerm::Author(author) => bookmark.author.as_deref().is_some_and(|value| contains(value, author)),
+            QueryTerm::Title(title) => contains(&bookmark.title, title),
+            QueryTerm::Url(url) => contains(&bookmark.url, url),
+            QueryTerm::Note(note) => bookmark.notes.iter().any(|value| contains(&value.content, note)),
+            QueryTerm::Domain(domain) => Url::parse(&bookmark.url).ok()
+                .and_then(|url| url.host_str().map(str::to_lowercase))
+                .is_some_and(|host| {
+                    let host = host.trim_start_matches("www.");
+                    host == domain || host.strip_suffix(domain.as_str()).is_some_and(|prefix| prefix.ends_with('.'))
+                }),
+            QueryTerm::Regex(field, pattern) => match field {
+                TextField::Author => bookmark.author.as_deref().is_some_and(|value| pattern.is_match(value)),
+                TextField::Title => pattern.is_match(&bookmark.title),
+                TextField::Url => pattern.is_match(&bookmark.url),
+                TextField::Note => bookmark.notes.iter().any(|note| pattern.is_match(&note.content)),
+            },
+        }
+    }
+}
+
+fn quote_if_needed(value: &str) -> String {
+    if value.is_empty() || value.contains(|c: char| c.is_whitespace() || c == '(' || c == ')') {
+        format!("\"{}\"", value)
+    } else {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for QueryTerm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryTerm::Text(text) => write!(f, "{}", quote_if_needed(text)),
+            QueryTerm::Tag { tag, include_descendants } => {
+                write!(f, "tag:{}{}", quote_if_needed(tag), if *include_descendants { "/*" } else { "" })
+            }
+            QueryTerm::Status(status) => write!(f, "status:{}", format!("{:?}", status).to_lowercase()),
+            QueryTerm::Priority(comparison, value) => write!(f, "priority:{}{}", comparison.symbol(), value),
+            QueryTerm::Added(comparison, date) => write!(f, "added:{}{}", comparison.symbol(), date.format("%Y-%m-%d")),
+            QueryTerm::Published(comparison, date) => write!(f, "published:{}{}", comparison.symbol(), date.format("%Y-%m-%d")),
+            QueryTerm::Author(value) => write!(f, "author:{}", quote_if_needed(value)),
+            QueryTerm::Title(value) => write!(f, "title:{}", quote_if_needed(value)),
+            QueryTerm::Url(value) => write!(f, "url:{}", quote_if_needed(value)),
+            QueryTerm::Note(value) => write!(f, "note:{}", quote_if_needed(value)),
+            QueryTerm::Domain(value) => write!(f, "domain:{}", quote_if_needed(value)),
+            QueryTerm::Regex(field, pattern) => write!(f, "{}:{}", field.name(), quote_if_needed(&format!("~/{}/", pattern.as_str()))),
+        }
+    }
+}
+
+impl fmt::Display for QueryExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |f: &mut fmt::Formatter<'_>, children: &[QueryExpr], separator: &str| -> fmt::Result {
+            for (index, child) in children.iter().enumerate() {
+                if index > 0 {
+                    write!(f, "{}", separator)?;
+                }
+                match child {
+                    QueryExpr::Or(_) | QueryExpr::And(_) => write!(f, "({})", child)?,
+                    _ => write!(f, "{}", child)?,
+                }
+            }
+            Ok(())
+        };
+
+        match self {
+            QueryExpr::And(children) => join(f, children, " "),
+            QueryExpr::Or(children) => join(f, children, " OR "),
+            QueryExpr::Not(child) => match child.as_ref() {
+                QueryExpr::Term(_) => write!(f, "-{}", child),
+                _ => write!(f, "-({})", child),
+            },
+            QueryExpr::Term(term) => write!(f, "{}", term),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    Or,
+    And,
+    Not,
+    Term(QueryTerm),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::LParen => write!(f, "'('"),
+            Token::RParen => write!(f, "')'"),
+            Token::Or => write!(f, "'OR'"),
+            Token::And => write!(f, "'AND'"),
+            Token::Not => write!(f, "'NOT'"),
+            Token::Term(term) => write!(f, "'{}'", term),
+        }
+    }
+}
+
+/// Split a query into tokens; unless `strict`, unknown fields and fields without a value are plain text
+fn tokenize(input: &str, strict: bool) -> BookmarkResult<Vec<Token>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut position = 0;
+
+    while position < chars.len() {
+        let c = chars[position];
+        if c.is_whitespace() {
+            position += 1;
+        } else if c == '(' {
+            tokens.push(Token::LParen);
+            position += 1;
+        } else if c == ')' {
+            tokens.push(Token::RParen);
+            position += 1;
+        } else if c == '|' {
+            tokens.push(Token::Or);
+            position += 1;
+        } else if c == '-' && chars.get(position + 1).is_some_and(|next| !next.is_whitespace()) {
+            tokens.push(Token::Not);
+            position += 1;
+        } else if c == '"' {
+            let (value, next) = read_quoted(&chars, position)?;
+            tokens.push(Token::Term(QueryTerm::Text(value)));
+            position = next;
+        } else {
+            let (word, next) = read_word(&chars, position)?;
+            position = next;
+            match word.as_str() {
+                "OR" => tokens.push(Token::Or),
+                "AND" => tokens.push(Token::And),
+                "NOT" => tokens.push(Token::Not),
+                _ => tokens.push(Token::Term(parse_term(&word, strict)?)),
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Read a double-quoted string starting at `start`, returning its contents and the next position
+fn read_quoted(chars: &[char], start: usize) -> BookmarkResult<(String, usize)> {
+    let mut value = String::new();
+    let mut position = start + 1;
+    while position < chars.len() {
+        if chars[position] == '"' {
+            return Ok((value, position + 1));
+        }
+        value.push(chars[position]);
+        position += 1;
+    }
+    Err(BookmarkError::InvalidQuery("Unterminated quote".to_string()))
+}
+
+/// Read a word up to whitespace or a parenthesis; quoted sections and `~/patterns/`
+/// after a field are kept verbatim so field values can contain spaces. So are
+/// `/patterns/` missing their `~` after a text field, so they can be reported.
+fn read_word(chars: &[char], start: usize) -> BookmarkResult<(String, usize)> {
+    let mut word = String::new();
+    let mut position = start;
+    while position < chars.len() {
+        let c = chars[position];
+        if c.is_whitespace() || c == '(' || c == ')' {
+            break;
+        }
+        let closing_slash = || (position + 1..chars.len()).find(|&end| chars[end] == '/' && chars[end - 1] != '\\');
+        let pattern_end = if c == '/' && word.ends_with(":~") {
+            closing_slash()
+        } else if c == '/' && TEXT_FIELDS.iter().any(|field| word.eq_ignore_ascii_case(&format!("{}:", field))) {
+            closing_slash().filter(|&end| looks_like_regex(&chars[position + 1..end].iter().collect::<String>()))
+        } else {
+            None
+        };
+        if let Some(end) = pattern_end {
+            word.extend(&chars[position..=end]);
+            position = end + 1;
+        } else if c == '"' {
+            let (value, next) = read_quoted(chars, position)?;
+            word.push('"');
+            word.push_str(&value);
+            word.push('"');
+            position = next;
+        } else {
+            word.push(c);
+            position += 1;
+        }
+    }
+    Ok((word, position))
+}
+
+fn unquote(value: &str) -> &str {
+    value.strip_prefix('"').and_then(|v| v.strip_suffix('"')).unwrap_or(value)
+}
+
+/// Parse a single word into a term, recognizing `field:value` and `field>=value` forms
+fn parse_term(word: &str, strict: bool) -> BookmarkResult<QueryTerm> {
+    let field_end = word.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(word.len());
+    let field = word[..field_end].to_lowercase();
+    let rest = &word[field_end..];
+
+    let operators = [(":>=", Comparison::Ge), (":<=", Comparison::Le), (":>", Comparison::Gt), (":<", Comparison::Lt),
+        (":", Comparison::Eq), (">=", Comparison::Ge), ("<=", Comparison::Le), (">", Comparison::Gt), ("<", Comparison::Lt),
+        ("=", Comparison::Eq)];
+    let operator = operators.iter().find(|(symbol, _)| rest.starts_with(symbol));
+
+    let (symbol, comparison) = match operator {
+        Some((symbol, comparison)) if field_end > 0 => (*symbol, *comparison),
+        _ => return Ok(QueryTerm::Text(unquote(word).to_string())),
+    };
+    let value = unquote(&rest[symbol.len()..]);
+
+    if !FIELDS.contains(&field.as_str()) {
+        // Things like https://example.com are plain text, not fields
+        if value.starts_with("//") || !strict {
+            return Ok(QueryTerm::Text(unquote(word).to_string()));
+        }
+        return Err(BookmarkError::InvalidQuery(format!(
+            "Unknown field '{}'. Known fields: {}", field, FIELDS.join(", ")
+        )));
+    }
+    if value.is_empty() {
+        if !strict {
+            return Ok(QueryTerm::Text(word.to_string()));
+        }
+        return Err(BookmarkError::InvalidQuery(format!("Missing value for '{}'", field)));
+    }
+
+    let require_eq = |term: QueryTerm| {
+        if comparison == Comparison::Eq {
+            Ok(term)
+        } else {
+            Err(BookmarkError::InvalidQuery(format!("'{}' does not support comparisons", field)))
+        }
+    };
+
+    match field.as_str() {
+        "tag" => {
+            let (tag, include_descendants) = match value.strip_suffix("/*") {
+                Some(tag) => (tag, true),
+                None => (value, false),
+            };
+            require_eq(QueryTerm::Tag { tag: normalize_tag(tag), include_descendants })
+        }
+        "status" => {
+            let status = match value.to_lowercase().as_str() {
+                "unread" => ReadingStatus::Unread,
+                "reading" => ReadingStatus::Reading,
+                "completed" | "done" => ReadingStatus::Completed,
+                _ => return Err(BookmarkError::InvalidQuery(format!(
+                    "Invalid status '{}'. Use unread, reading or completed", value
+                ))),
+            };
+            require_eq(QueryTerm::Status(status))
+        }
+        "priority" => {
+            let priority: u8 = value.parse()
+                .ok()
+                .filter(|priority| (1..=5).contains(priority))
+                .ok_or_else(|| BookmarkError::InvalidQuery(format!("Priority must be between 1 and 5, got '{}'", value)))?;
+            Ok(QueryTerm::Priority(comparison, priority))
+        }
+        "added" => Ok(QueryTerm::Added(comparison, parse_query_date(value)?)),
+        "published" => Ok(QueryTerm::Published(comparison, parse_query_date(value)?)),
+        "author" => require_eq(text_term(TextField::Author, value, QueryTerm::Author)?),
+        "title" => require_eq(text_term(TextField::Title, value, QueryTerm::Title)?),
+        "url" => require_eq(text_term(TextField::Url, value, QueryTerm::Url)?),
+        "note" => require_eq(text_term(TextField::Note, value, QueryTerm::Note)?),
+        "domain" => require_eq(QueryTerm::Domain(value.to_lowercase().trim_start_matches("www.").to_string())),
+        _ => unreachable!("field list checked above"),
+    }
+}
+
+/// A text field term: a regular expression when the value is written `~/pattern/`, otherwise a substring
+///
+/// A value written `/pattern/` that uses regular expression syntax is an error
+/// rather than a substring that would never match.
+fn text_term(field: TextField, value: &str, substring: fn(String) -> QueryTerm) -> BookmarkResult<QueryTerm> {
+    if let Some(pattern) = value.strip_prefix("~/").and_then(|v| v.strip_suffix('/')).filter(|p| !p.is_empty()) {
+        return Ok(QueryTerm::Regex(field, Pattern::new(pattern)?));
+    }
+    if let Some(pattern) = value.strip_prefix('/').and_then(|v| v.strip_suffix('/')).filter(|p| looks_like_regex(p)) {
+        return Err(BookmarkError::InvalidQuery(format!(
+            "Write regular expressions as {}:~/{}/ (a leading ~ marks a pattern)", field.name(), pattern
+        )));
+    }
+    Ok(substring(value.to_string()))
+}
+
+/// Whether text uses regular expression syntax that never appears in a plain substring search
+fn looks_like_regex(text: &str) -> bool {
+    text.contains(['\\', '^', '$', '*', '+', '?', '[', ']', '{', '}', '|'])
+}
+
+/// Parse a date in YYYY-MM-DD or MM-DD-YYYY format
+fn parse_query_date(value: &str) -> BookmarkResult<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(value, "%m-%d-%Y"))
+        .map_err(|_| BookmarkError::InvalidQuery(format!(
+            "Invalid date '{}'. Use YYYY-MM-DD (e.g., 2024-01-15)", value
+        )))
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> BookmarkResult<QueryExpr> {
+        let mut children = vec![self.parse_and()?];
+        while self.peek() == Some(&Token::Or) {
+            self.position += 1;
+            children.push(self.parse_and()?);
+        }
+        Ok(if children.len() == 1 { children.remove(0) } else { QueryExpr::Or(children) })
+    }
+
+    fn parse_and(&mut self) -> BookmarkResult<QueryExpr> {
+        let mut children = vec![self.parse_unary()?];
+        loop {
+            match self.peek() {
+                Some(Token::And) => {
+                    self.position += 1;
+                    children.push(self.parse_unary()?);
+                }
+                Some(Token::Term(_)) | Some(Token::Not) | Some(Token::LParen) => {
+                    children.push(self.parse_unary()?);
+                }
+                _ => break,
+            }
+        }
+        Ok(if children.len() == 1 { children.remove(0) } else { QueryExpr::And(children) })
+    }
+
+    fn parse_unary(&mut self) -> BookmarkResult<QueryExpr> {
+        match self.next() {
+            Some(Token::Not) => Ok(QueryExpr::Not(Box::new(self.parse_unary()?))),
+            Some(Token::LParen) => {
+                let expr = self.parse_or()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(expr),
+                    _ => Err(BookmarkError::InvalidQuery("Missing closing ')'".to_string())),
+                }
+            }
+            Some(Token::Term(term)) => Ok(QueryExpr::Term(term)),
+            Some(token) => Err(BookmarkError::InvalidQuery(format!("Unexpected {}", token))),
+            None => Err(BookmarkError::InvalidQuery("Query ends unexpectedly".to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn parse(input: &str) -> QueryExpr {
+        QueryExpr::parse(input).unwrap().unwrap()
+    }
+
+    fn tag(name: &str) -> QueryExpr {
+        QueryExpr::Term(QueryTerm::Tag { tag: name.to_string(), include_descendants: false })
+    }
+
+    #[test]
+    fn test_parse_full_example() {
+        let expr = parse(r#"tag:rust (tag:async OR tag:tokio) -status:completed priority>=4 added:>2024-01-01 author:"Jane Doe""#);
+        assert_eq!(expr, QueryExpr::And(vec![
+            tag("rust"),
+            QueryExpr::Or(vec![tag("async"), tag("tokio")]),
+            QueryExpr::Not(Box::new(QueryExpr::Term(QueryTerm::Status(ReadingStatus::Completed)))),
+            QueryExpr::Term(QueryTerm::Priority(Comparison::Ge, 4)),
+            QueryExpr::Term(QueryTerm::Added(Comparison::Gt, NaiveDate::from_ymd_opt(2024, 1, 1).unwrap())),
+            QueryExpr::Term(QueryTerm::Author("Jane Doe".to_string())),
+        ]));
+    }
+
+    #[test]
+    fn test_or_has_lower_precedence_than_and() {
+        let expr = parse("tag:a tag:b | tag:c");
+        assert_eq!(expr, QueryExpr::Or(vec![
+            QueryExpr::And(vec![tag("a"), tag("b")]),
+            tag("c"),
+        ]));
+    }
+
+    #[test]
+    fn test_plain_text() {
+        assert!(parse("rust programming").is_plain_text());
+        assert_eq!(parse("rust  programming").to_plain_text(), "rust programming");
+        assert!(parse("\"exact phrase\"").is_plain_text());
+        assert!(!parse("rust tag:web").is_plain_text());
+        assert!(!parse("-rust").is_plain_text());
+        assert!(parse("https://example.com").is_plain_text());
+        assert!(QueryExpr::parse("   ").unwrap().is_none());
+
+        // Colons in ordinary text aren't fields unless the query is clearly structured
+        assert_eq!(parse("Rust: the book").to_plain_text(), "Rust: the book");
+        assert_eq!(parse("note: todo").to_plain_text(), "note: todo");
+        assert!(parse("foo:bar").is_plain_text());
+        assert_eq!(parse("\"exact phrase\" rust").to_plain_text(), "\"exact phrase\" rust");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for input in ["(tag:rust", "tag:rust)", "foo:bar tag:rust", "-foo:bar", "status:maybe", "priority>=9", "added:yesterday",
+            "author:>x", "tag: status:unread", "\"unterminated", "tag:a OR", "NOT", "title:~/(unclosed/", "domain:>x",
+            r"title:/RFC \d+/", r"url:/^https/"] {
+            assert!(
+                matches!(QueryExpr::parse(input), Err(BookmarkError::InvalidQuery(_))),
+                "expected error for {}", input
+            );
+        }
+    }
+
+    #[test]
+    fn test_matches() {
+        let mut bookmark = Bookmark::new("https://tokio.rs", "Tokio Tutorial").unwrap()
+            .with_tags(vec!["rust".to_string(), "async".to_string(), "work/projects".to_string()])
+            .with_priority(4).unwrap();
+        bookmark.author = Some("Jane Doe".to_string());
+        bookmark.bookmarked_date = Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+        bookmark.add_note("Read the runtime chapter");
+
+        let matching = [
+            r#"tag:rust (tag:async OR tag:tokio) -status:completed priority>=4 added:>2024-01-01 author:"jane""#,
+            "tokio",
+            "RUNTIME",
+            "tag:work/*",
+            "priority:4",
+            "added:2024-03-01",
+            "title:tutorial url:tokio.rs note:chapter",
+            "NOT tag:python",
+        ];
+        for query in matching {
+            assert!(parse(query).matches(&bookmark), "expected match for {}", query);
+        }
+
+        let not_matching = ["tag:work", "status:reading", "priority>4", "added:<2024-01-01", "published:>2020-01-01", "-tokio"];
+        for query in not_matching {
+            assert!(!parse(query).matches(&bookmark), "expected no match for {}", query);
+        }
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let input = r#"tag:rust (tag:async OR tag:tokio) -status:completed priority:>=4 added:>2024-01-01 author:"Jane Doe""#;
+        let expr = parse(input);
+        assert_eq!(expr.to_string(), input);
+        assert_eq!(parse(&expr.to_string()), expr);
+    }
+
+    #[test]
+    fn test_domain_and_patterns() {
+        let bookmark = Bookmark::new("https://www.rfc-editor.org/rfc/rfc9110", "RFC 9110: HTTP Semantics").unwrap();
+        for query in ["domain:rfc-editor.org", "domain:WWW.rfc-editor.org", r"title:~/RFC \d+/", r#"title:"~/^rfc \d+:/""#, "url:~/rfc\\d{4}$/"] {
+            assert!(parse(query).matches(&bookmark), "expected match for {}", query);
+        }
+        for query in ["domain:editor.org", "domain:org.rfc-editor.org", r"title:~/RFC \d{5}/", "author:~/./"] {
+            assert!(!parse(query).matches(&bookmark), "expected no match for {}", query);
+        }
+
+        let subdomain = Bookmark::new("https://gist.github.com/abc", "Gist").unwrap();
+        assert!(parse("domain:github.com").matches(&subdomain));
+
+        // Without the `~`, slashes are part of a substring
+        let docs = Bookmark::new("https://example.com/docs/intro", "Intro").unwrap();
+        assert_eq!(parse("url:/docs/"), QueryExpr::Term(QueryTerm::Url("/docs/".to_string())));
+        assert!(parse("url:/docs/").matches(&docs));
+        let error = QueryExpr::parse(r"tag:rust title:/RFC \d+/").unwrap_err().to_string();
+        assert!(error.contains(r"title:~/RFC \d+/"), "{}", error);
+
+        // Slashes after a field keep a pattern with spaces in one word; URLs are still plain text
+        let expr = parse(r"title:~/RFC \d+/ tag:spec");
+        assert_eq!(expr.to_string(), r#"title:"~/RFC \d+/" tag:spec"#);
+        assert_eq!(parse(&expr.to_string()), expr);
+        assert!(parse("https://example.com/path").is_plain_text());
+    }
+}
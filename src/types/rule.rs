@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+
+use super::bookmark::{normalize_tag, Bookmark};
+use super::{BookmarkError, BookmarkResult, QueryExpr};
+
+/// A `[[rules]]` entry from the config: bookmarks matching a query get tags or a priority
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rule {
+    /// Optional name shown when the rule fires
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Search query selecting the bookmarks, e.g. `domain:github.com` or `title:~/RFC \d+/`
+    #[serde(rename = "match")]
+    pub query: String,
+    /// Tags added to matching bookmarks
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub add_tags: Vec<String>,
+    /// Priority given to matching bookmarks that don't have one yet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u8>,
+}
+
+impl Rule {
+    /// The rule's name, or its query when it has none
+    pub fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.query)
+    }
+}
+
+/// A rule that fired for a bookmark and what it changed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppliedRule {
+    pub rule: String,
+    pub changes: Vec<String>,
+}
+
+/// Rules with their queries parsed, ready to apply
+#[derive(Debug, Clone, Default)]
+pub struct RuleSet {
+    rules: Vec<(Rule, QueryExpr)>,
+}
+
+impl RuleSet {
+    pub fn new(rules: &[Rule]) -> BookmarkResult<Self> {
+        let compiled = rules.iter().enumerate()
+            .map(|(index, rule)| {
+                let invalid = |message: String| BookmarkError::InvalidQuery(format!("Rule {} ({}): {}", index + 1, rule.label(), message));
+                let query = QueryExpr::parse(&rule.query)
+                    .map_err(|e| invalid(e.to_string()))?
+                    .ok_or_else(|| invalid("'match' is empty".to_string()))?;
+                if rule.priority.is_some_and(|priority| !(1..=5).contains(&priority)) {
+                    return Err(invalid("priority must be between 1 and 5".to_string()));
+                }
+                Ok((rule.clone(), query))
+            })
+            .collect::<BookmarkResult<_>>()?;
+        Ok(Self { rules: compiled })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Apply every matching rule in order, returning the ones that fired
+    ///
+    /// Each rule sees the changes of the rules before it, so a rule can match a tag
+    /// another rule added. A priority the bookmark already has is never replaced.
+    pub fn apply(&self, bookmark: &mut Bookmark) -> Vec<AppliedRule> {
+        let mut applied = Vec::new();
+        for (rule, query) in &self.rules {
+            if !query.matches(bookmark) {
+                continue;
+            }
+
+            let mut changes = Vec::new();
+            for tag in rule.add_tags.iter().map(|tag| normalize_tag(tag)).filter(|tag| !tag.is_empty()) {
+                if !bookmark.tags.iter().any(|existing| normalize_tag(existing) == tag) {
+                    changes.push(format!("+tag {}", tag));
+                    bookmark.tags.push(tag);
+                }
+            }
+            if let (Some(priority), None) = (rule.priority, bookmark.priority_rating) {
+                changes.push(format!("priority none -> {}", priority));
+                bookmark.priority_rating = Some(priority);
+            }
+            applied.push(AppliedRule { rule: rule.label().to_string(), changes });
+        }
+        applied
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(query: &str, add_tags: &[&str], priority: Option<u8>) -> Rule {
+        Rule {
+            name: None,
+            query: query.to_string(),
+            add_tags: add_tags.iter().map(|tag| tag.to_string()).collect(),
+            priority,
+        }
+    }
+
+    #[test]
+    fn test_rules_apply_in_order() {
+        let rules = RuleSet::new(&[
+            rule("domain:github.com", &["Code"], None),
+            rule(r"title:~/RFC \d+/", &[], Some(4)),
+            rule("tag:code", &["dev"], Some(2)),
+            rule("domain:example.com", &["never"], None),
+        ]).unwrap();
+
+        let mut bookmark = Bookmark::new("https://github.com/ietf/rfc", "RFC 9110 drafts").unwrap();
+        let applied = rules.apply(&mut bookmark);
+        assert_eq!(applied, vec![
+            AppliedRule { rule: "domain:github.com".to_string(), changes: vec!["+tag code".to_string()] },
+            AppliedRule { rule: r"title:~/RFC \d+/".to_string(), changes: vec!["priority none -> 4".to_string()] },
+            AppliedRule { rule: "tag:code".to_string(), changes: vec!["+tag dev".to_string()] },
+        ]);
+        assert_eq!(bookmark.tags, vec!["code", "dev"]);
+        assert_eq!(bookmark.priority_rating, Some(4));
+
+        // Applying again fires the same rules but changes nothing
+        assert!(rules.apply(&mut bookmark).iter().all(|applied| applied.changes.is_empty()));
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        for invalid in [rule("", &["x"], None), rule("title:~/(/", &[], None), rule("tag:x", &[], Some(7)),
+            rule(r"title:/RFC \d+/", &[], Some(4))] {
+            assert!(matches!(RuleSet::new(&[invalid]), Err(BookmarkError::InvalidQuery(_))));
+        }
+    }
+
+    #[test]
+    fn test_rules_from_toml() {
+        #[derive(Deserialize)]
+        struct File {
+            rules: Vec<Rule>,
+        }
+        let file: File = toml::from_str(r#"
+            [[rules]]
+            match = "domain:github.com"
+            add_tags = ["code"]
+
+            [[rules]]
+            name = "RFCs"
+            match = 'title:~/RFC \d+/'
+            priority = 4
+        "#).unwrap();
+        assert_eq!(file.rules[0], rule("domain:github.com", &["code"], None));
+        assert_eq!(file.rules[1].label(), "RFCs");
+        assert!(RuleSet::new(&file.rules).is_ok());
+    }
+}
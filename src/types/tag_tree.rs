@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+use super::bookmark::{normalize_tag, Bookmark};
+
+/// Separator between levels of a hierarchical tag (e.g. `work/projects/automark`)
+pub const TAG_SEPARATOR: char = '/';
+
+/// A node in the tag hierarchy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagNode {
+    /// Last segment of the tag path
+    pub name: String,
+    /// Full tag path from the root
+    pub path: String,
+    /// Number of bookmarks tagged with exactly this path
+    pub count: usize,
+    /// Number of bookmarks tagged with this path or any descendant
+    pub total_count: usize,
+    pub children: Vec<TagNode>,
+}
+
+/// A tag node flattened for display, with its depth in the tree
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagTreeEntry {
+    pub depth: usize,
+    pub name: String,
+    pub path: String,
+    pub total_count: usize,
+}
+
+/// Check whether a bookmark tag satisfies a filter tag
+///
+/// Both tags are expected to be normalized. With `include_descendants`,
+/// `work` also matches `work/projects` but not `workshop`.
+pub fn tag_matches(filter_tag: &str, tag: &str, include_descendants: bool) -> bool {
+    if tag == filter_tag {
+        return true;
+    }
+    include_descendants
+        && tag.len() > filter_tag.len()
+        && tag.starts_with(filter_tag)
+        && tag[filter_tag.len()..].starts_with(TAG_SEPARATOR)
+}
+
+/// All ancestor paths of a tag, including the tag itself (`a/b/c` -> `a`, `a/b`, `a/b/c`)
+pub fn tag_ancestors(tag: &str) -> Vec<&str> {
+    tag.char_indices()
+        .filter(|(_, c)| *c == TAG_SEPARATOR)
+        .map(|(index, _)| &tag[..index])
+        .chain(std::iter::once(tag))
+        .collect()
+}
+
+#[derive(Default)]
+struct NodeBuilder {
+    count: usize,
+    total_count: usize,
+    children: BTreeMap<String, NodeBuilder>,
+}
+
+impl NodeBuilder {
+    fn child(&mut self, path: &str) -> &mut NodeBuilder {
+        path.split(TAG_SEPARATOR)
+            .fold(self, |node, segment| node.children.entry(segment.to_string()).or_default())
+    }
+
+    fn into_nodes(self, parent_path: &str) -> Vec<TagNode> {
+        self.children
+            .into_iter()
+            .map(|(name, builder)| {
+                let path = if parent_path.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}{}{}", parent_path, TAG_SEPARATOR, name)
+                };
+                TagNode {
+                    count: builder.count,
+                    total_count: builder.total_count,
+                    children: builder.into_nodes(&path),
+                    name,
+                    path,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Build the tag hierarchy for a set of bookmarks, sorted alphabetically at each level
+pub fn build_tag_tree(bookmarks: &[Bookmark]) -> Vec<TagNode> {
+    let mut root = NodeBuilder::default();
+
+    for bookmark in bookmarks {
+        let tags: HashSet<String> = bookmark.tags.iter()
+            .map(|tag| normalize_tag(tag))
+            .filter(|tag| !tag.is_empty())
+            .collect();
+
+        // Count each bookmark once per ancestor, even if it has several descendant tags
+        let mut ancestors = HashSet::new();
+        for tag in &tags {
+            root.child(tag).count += 1;
+            ancestors.extend(tag_ancestors(tag));
+        }
+        for path in ancestors {
+            root.child(path).total_count += 1;
+        }
+    }
+
+    root.into_nodes("")
+}
+
+/// Flatten a tag tree depth-first for list display
+pub fn flatten_tag_tree(nodes: &[TagNode]) -> Vec<TagTreeEntry> {
+    fn visit(nodes: &[TagNode], depth: usize, entries: &mut Vec<TagTreeEntry>) {
+        for node in nodes {
+            entries.push(TagTreeEntry {
+                depth,
+                name: node.name.clone(),
+                path: node.path.clone(),
+                total_count: node.total_count,
+            });
+            visit(&node.children, depth + 1, entries);
+        }
+    }
+
+    let mut entries = Vec::new();
+    visit(nodes, 0, &mut entries);
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bookmark_with_tags(tags: &[&str]) -> Bookmark {
+        Bookmark::new("https://example.com", "Example").unwrap()
+            .with_tags(tags.iter().map(|tag| tag.to_string()).collect())
+    }
+
+    #[test]
+    fn test_tag_matches() {
+        assert!(tag_matches("work", "work", false));
+        assert!(!tag_matches("work", "work/projects", false));
+        assert!(tag_matches("work", "work/projects", true));
+        assert!(tag_matches("work", "work/projects/automark", true));
+        assert!(!tag_matches("work", "workshop", true));
+        assert!(!tag_matches("work/projects", "work", true));
+    }
+
+    #[test]
+    fn test_tag_ancestors() {
+        assert_eq!(tag_ancestors("a/b/c"), vec!["a", "a/b", "a/b/c"]);
+        assert_eq!(tag_ancestors("rust"), vec!["rust"]);
+    }
+
+    #[test]
+    fn test_build_tag_tree() {
+        let bookmarks = vec![
+            bookmark_with_tags(&["work/projects/automark", "work/projects/other"]),
+            bookmark_with_tags(&["work", "rust"]),
+            bookmark_with_tags(&["work/admin"]),
+        ];
+
+        let tree = build_tag_tree(&bookmarks);
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].name, "rust");
+
+        let work = &tree[1];
+        assert_eq!(work.path, "work");
+        assert_eq!(work.count, 1);
+        assert_eq!(work.total_count, 3);
+        assert_eq!(work.children.len(), 2);
+
+        let projects = &work.children[1];
+        assert_eq!(projects.path, "work/projects");
+        assert_eq!(projects.count, 0);
+        // One bookmark with two project tags is counted once
+        assert_eq!(projects.total_count, 1);
+        assert_eq!(projects.children[0].path, "work/projects/automark");
+    }
+
+    #[test]
+    fn test_flatten_tag_tree() {
+        let bookmarks = vec![bookmark_with_tags(&["a/b", "c"])];
+        let entries = flatten_tag_tree(&build_tag_tree(&bookmarks));
+
+        let paths: Vec<(usize, &str)> = entries.iter().map(|e| (e.depth, e.path.as_str())).collect();
+        assert_eq!(paths, vec![(0, "a"), (1, "a/b"), (0, "c")]);
+    }
+}